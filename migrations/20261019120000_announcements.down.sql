@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE announcements;
+DROP TYPE announcement_severity;
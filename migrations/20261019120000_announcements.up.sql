@@ -0,0 +1,14 @@
+-- Add up migration script here
+
+CREATE TYPE announcement_severity AS ENUM ('INFO', 'WARNING', 'CRITICAL');
+
+CREATE TABLE announcements (
+    id SERIAL PRIMARY KEY,
+    content TEXT NOT NULL,
+    severity announcement_severity NOT NULL DEFAULT 'INFO',
+    starts TIMESTAMP WITHOUT TIME ZONE NULL DEFAULT NULL,
+    ends TIMESTAMP WITHOUT TIME ZONE NULL DEFAULT NULL,
+    -- path prefixes of the pages this announcement is displayed on. Empty means every page
+    target_pages TEXT[] NOT NULL DEFAULT '{}',
+    CHECK (starts IS NULL OR ends IS NULL OR starts < ends)
+);
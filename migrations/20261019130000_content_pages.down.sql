@@ -0,0 +1,4 @@
+-- Add down migration script here
+
+DROP TABLE content_page_revisions;
+DROP TABLE content_pages;
@@ -0,0 +1,19 @@
+-- Add up migration script here
+
+CREATE TABLE content_pages (
+    slug TEXT PRIMARY KEY CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    created TIMESTAMP WITHOUT TIME ZONE DEFAULT (NOW() AT TIME ZONE 'utc') NOT NULL
+);
+
+-- Every edit of a content page creates a new revision. The current version of a page is its revision with the highest id
+CREATE TABLE content_page_revisions (
+    id SERIAL PRIMARY KEY,
+    slug TEXT NOT NULL REFERENCES content_pages(slug) ON DELETE CASCADE ON UPDATE CASCADE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    content TEXT NOT NULL,
+    editor INTEGER NULL REFERENCES members(member_id) ON DELETE SET NULL,
+    time TIMESTAMP WITHOUT TIME ZONE DEFAULT (NOW() AT TIME ZONE 'utc') NOT NULL
+);
+
+CREATE INDEX content_page_revisions_slug_idx ON content_page_revisions(slug, id);
@@ -0,0 +1,214 @@
+-- Add up migration script here
+
+CREATE TABLE lists (
+    id SERIAL PRIMARY KEY,
+    slug TEXT NOT NULL UNIQUE CHECK (slug ~ '^[a-z0-9]+(-[a-z0-9]+)*$'),
+    name TEXT NOT NULL
+);
+
+-- All existing demons become part of this list. The default value of the "list" column below is only there for backwards
+-- compatibility with tools that directly insert demons into the database.
+INSERT INTO lists (slug, name) VALUES ('demonlist', 'Demonlist');
+
+ALTER TABLE demons ADD COLUMN list INTEGER NOT NULL DEFAULT 1 REFERENCES lists(id) ON DELETE RESTRICT;
+
+-- Positions are only unique within a list
+ALTER TABLE demons DROP CONSTRAINT unique_position;
+ALTER TABLE demons ADD CONSTRAINT unique_position UNIQUE (list, position) DEFERRABLE INITIALLY IMMEDIATE;
+
+-- Scores are now computed per list, and thus cannot be cached in the players/nationalities/subdivisions tables anymore.
+-- Players/nations/subdivisions without any score on some list simply have no row for that list in the tables below.
+DROP VIEW ranked_players;
+DROP VIEW ranked_nations;
+
+DROP FUNCTION score_of_player(INTEGER);
+DROP FUNCTION score_of_nation(VARCHAR(2));
+DROP FUNCTION score_of_subdivision(VARCHAR(2), VARCHAR(3));
+
+ALTER TABLE players DROP COLUMN score;
+ALTER TABLE nationalities DROP COLUMN score;
+ALTER TABLE subdivisions DROP COLUMN score;
+
+CREATE TABLE player_scores (
+    player INTEGER NOT NULL REFERENCES players(id) ON DELETE CASCADE ON UPDATE CASCADE,
+    list INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+    score DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (player, list)
+);
+
+CREATE TABLE nationality_scores (
+    nation VARCHAR(2) NOT NULL REFERENCES nationalities(iso_country_code) ON DELETE CASCADE,
+    list INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+    score DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (nation, list)
+);
+
+CREATE TABLE subdivision_scores (
+    nation VARCHAR(2) NOT NULL,
+    subdivision VARCHAR(3) NOT NULL,
+    list INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+    score DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (nation, subdivision, list),
+    FOREIGN KEY (nation, subdivision) REFERENCES subdivisions (nation, iso_code) ON DELETE CASCADE
+);
+
+CREATE OR REPLACE VIEW score_giving AS
+    SELECT records.progress, demons.position, demons.requirement, records.player, demons.list
+    FROM records
+    INNER JOIN demons
+    ON demons.id = records.demon
+    WHERE records.status_ = 'APPROVED' AND (demons.position <= 75 OR records.progress = 100)
+
+    UNION
+
+    SELECT 100, demons.position, demons.requirement, demons.verifier, demons.list
+    FROM demons;
+
+-- As suggested when the cached scores were introduced, recomputing all scores is now a "TRUNCATE + INSERT"
+-- Lists cannot be split into sections yet, so all of them have the size of 150 the score formula always assumed so far
+CREATE OR REPLACE FUNCTION recompute_player_scores() RETURNS void AS $$
+    DELETE FROM player_scores;
+
+    INSERT INTO player_scores (player, list, score)
+    SELECT player, list, SUM(record_score(progress, position, 150, requirement))
+    FROM score_giving
+    GROUP BY player, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_nation_scores() RETURNS void AS $$
+    DELETE FROM nationality_scores;
+
+    INSERT INTO nationality_scores (nation, list, score)
+    SELECT nationality, list, SUM(record_score(q.progress, q.position, 150, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position, nationality) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality IS NOT NULL
+        ORDER BY list, players.nationality, position, progress DESC
+    ) q
+    GROUP BY nationality, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_subdivision_scores() RETURNS void AS $$
+    DELETE FROM subdivision_scores;
+
+    INSERT INTO subdivision_scores (nation, subdivision, list, score)
+    SELECT nationality, subdivision, list, SUM(record_score(q.progress, q.position, 150, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position, nationality, subdivision) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality IS NOT NULL
+          AND players.subdivision IS NOT NULL
+        ORDER BY list, players.nationality, players.subdivision, position, progress DESC
+    ) q
+    GROUP BY nationality, subdivision, list;
+$$ LANGUAGE SQL;
+
+-- Counterparts of the above functions that only update the scores of a single player/nation/subdivision (on all lists)
+CREATE FUNCTION recompute_scores_of_player(player_id INTEGER) RETURNS void AS $$
+    DELETE FROM player_scores WHERE player = player_id;
+
+    INSERT INTO player_scores (player, list, score)
+    SELECT player, list, SUM(record_score(progress, position, 150, requirement))
+    FROM score_giving
+    WHERE player = player_id
+    GROUP BY player, list;
+$$ LANGUAGE SQL;
+
+CREATE FUNCTION recompute_scores_of_nation(iso_country_code VARCHAR(2)) RETURNS void AS $$
+    DELETE FROM nationality_scores WHERE nation = iso_country_code;
+
+    INSERT INTO nationality_scores (nation, list, score)
+    SELECT iso_country_code, list, SUM(record_score(q.progress, q.position, 150, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality = iso_country_code
+        ORDER BY list, position, progress DESC
+    ) q
+    GROUP BY list;
+$$ LANGUAGE SQL;
+
+CREATE FUNCTION recompute_scores_of_subdivision(iso_country_code VARCHAR(2), iso_code VARCHAR(3)) RETURNS void AS $$
+    DELETE FROM subdivision_scores WHERE nation = iso_country_code AND subdivision = iso_code;
+
+    INSERT INTO subdivision_scores (nation, subdivision, list, score)
+    SELECT iso_country_code, iso_code, list, SUM(record_score(q.progress, q.position, 150, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality = iso_country_code
+          AND players.subdivision = iso_code
+        ORDER BY list, position, progress DESC
+    ) q
+    GROUP BY list;
+$$ LANGUAGE SQL;
+
+SELECT recompute_player_scores();
+SELECT recompute_nation_scores();
+SELECT recompute_subdivision_scores();
+
+CREATE VIEW ranked_players AS
+    SELECT
+        ROW_NUMBER() OVER(PARTITION BY player_scores.list ORDER BY player_scores.score DESC, id) AS index,
+        RANK() OVER(PARTITION BY player_scores.list ORDER BY player_scores.score DESC) AS rank,
+        player_scores.list,
+        id, name, player_scores.score, subdivision,
+        nationalities.iso_country_code,
+        nationalities.nation,
+        nationalities.continent
+    FROM player_scores
+    INNER JOIN players
+            ON players.id = player_scores.player
+    LEFT OUTER JOIN nationalities
+                 ON players.nationality = nationalities.iso_country_code
+    WHERE NOT players.banned AND player_scores.score > 0.0;
+
+CREATE VIEW ranked_nations AS
+    SELECT
+        ROW_NUMBER() OVER(PARTITION BY nationality_scores.list ORDER BY nationality_scores.score DESC, iso_country_code) AS index,
+        RANK() OVER(PARTITION BY nationality_scores.list ORDER BY nationality_scores.score DESC) AS rank,
+        nationality_scores.list,
+        nationality_scores.score,
+        nationalities.iso_country_code,
+        nationalities.nation,
+        nationalities.continent
+    FROM nationality_scores
+    INNER JOIN nationalities
+            ON nationalities.iso_country_code = nationality_scores.nation
+    WHERE nationality_scores.score > 0.0;
+
+DROP FUNCTION list_at(TIMESTAMP WITHOUT TIME ZONE);
+
+CREATE FUNCTION list_at(TIMESTAMP WITHOUT TIME ZONE)
+    RETURNS TABLE (
+                      name CITEXT,
+                      position_ SMALLINT,
+                      requirement SMALLINT,
+                      video VARCHAR(200),
+                      thumbnail TEXT,
+                      verifier INTEGER,
+                      publisher INTEGER,
+                      id INTEGER,
+                      level_id BIGINT,
+                      current_position SMALLINT,
+                      list INTEGER
+                  )
+AS $$
+SELECT name, CASE WHEN t.position IS NULL THEN demons.position ELSE t.position END, requirement, video, thumbnail, verifier, publisher, demons.id, level_id, demons.position AS current_position, demons.list
+FROM demons
+         LEFT OUTER JOIN (
+    SELECT DISTINCT ON (id) id, position
+    FROM demon_modifications
+    WHERE time >= $1 AND position != -1
+    ORDER BY id, time
+) t
+                         ON demons.id = t.id
+WHERE NOT EXISTS (SELECT 1 FROM demon_additions WHERE demon_additions.id = demons.id AND time >= $1)
+$$
+    LANGUAGE SQL
+    STABLE;
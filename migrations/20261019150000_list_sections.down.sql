@@ -0,0 +1,105 @@
+-- Add down migration script here
+
+CREATE OR REPLACE VIEW score_giving AS
+    SELECT records.progress, demons.position, demons.requirement, records.player, demons.list
+    FROM records
+    INNER JOIN demons
+    ON demons.id = records.demon
+    WHERE records.status_ = 'APPROVED' AND (demons.position <= 75 OR records.progress = 100)
+
+    UNION
+
+    SELECT 100, demons.position, demons.requirement, demons.verifier, demons.list
+    FROM demons;
+
+DROP FUNCTION recompute_scores_of_list(INTEGER);
+
+CREATE OR REPLACE FUNCTION recompute_player_scores() RETURNS void AS $$
+    DELETE FROM player_scores;
+
+    INSERT INTO player_scores (player, list, score)
+    SELECT player, list, SUM(record_score(progress, position, 150, requirement))
+    FROM score_giving
+    GROUP BY player, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_nation_scores() RETURNS void AS $$
+    DELETE FROM nationality_scores;
+
+    INSERT INTO nationality_scores (nation, list, score)
+    SELECT nationality, list, SUM(record_score(q.progress, q.position, 150, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position, nationality) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality IS NOT NULL
+        ORDER BY list, players.nationality, position, progress DESC
+    ) q
+    GROUP BY nationality, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_subdivision_scores() RETURNS void AS $$
+    DELETE FROM subdivision_scores;
+
+    INSERT INTO subdivision_scores (nation, subdivision, list, score)
+    SELECT nationality, subdivision, list, SUM(record_score(q.progress, q.position, 150, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position, nationality, subdivision) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality IS NOT NULL
+          AND players.subdivision IS NOT NULL
+        ORDER BY list, players.nationality, players.subdivision, position, progress DESC
+    ) q
+    GROUP BY nationality, subdivision, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_scores_of_player(player_id INTEGER) RETURNS void AS $$
+    DELETE FROM player_scores WHERE player = player_id;
+
+    INSERT INTO player_scores (player, list, score)
+    SELECT player, list, SUM(record_score(progress, position, 150, requirement))
+    FROM score_giving
+    WHERE player = player_id
+    GROUP BY player, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_scores_of_nation(iso_country_code VARCHAR(2)) RETURNS void AS $$
+    DELETE FROM nationality_scores WHERE nation = iso_country_code;
+
+    INSERT INTO nationality_scores (nation, list, score)
+    SELECT iso_country_code, list, SUM(record_score(q.progress, q.position, 150, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality = iso_country_code
+        ORDER BY list, position, progress DESC
+    ) q
+    GROUP BY list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_scores_of_subdivision(iso_country_code VARCHAR(2), iso_code VARCHAR(3)) RETURNS void AS $$
+    DELETE FROM subdivision_scores WHERE nation = iso_country_code AND subdivision = iso_code;
+
+    INSERT INTO subdivision_scores (nation, subdivision, list, score)
+    SELECT iso_country_code, iso_code, list, SUM(record_score(q.progress, q.position, 150, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality = iso_country_code
+          AND players.subdivision = iso_code
+        ORDER BY list, position, progress DESC
+    ) q
+    GROUP BY list;
+$$ LANGUAGE SQL;
+
+DROP FUNCTION list_size(INTEGER);
+
+DROP TABLE list_sections;
+DROP EXTENSION IF EXISTS btree_gist;
+
+SELECT recompute_player_scores();
+SELECT recompute_nation_scores();
+SELECT recompute_subdivision_scores();
@@ -0,0 +1,206 @@
+-- Add up migration script here
+
+-- Needed for the list equality in the constraint keeping sections from overlapping
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+CREATE TABLE list_sections (
+    id SERIAL PRIMARY KEY,
+    list INTEGER NOT NULL REFERENCES lists(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    first_position SMALLINT NOT NULL CHECK (first_position > 0),
+    -- NULL means that the section extends until the end of the list
+    last_position SMALLINT CHECK (last_position IS NULL OR last_position >= first_position),
+    numbered BOOLEAN NOT NULL DEFAULT TRUE,
+    submissions_open BOOLEAN NOT NULL DEFAULT TRUE,
+    allows_non_100 BOOLEAN NOT NULL DEFAULT FALSE,
+    awards_points BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Checked by the database instead of the application, so that concurrent edits cannot both slip through
+    CONSTRAINT list_sections_no_overlap EXCLUDE USING gist (list WITH =, int4range(first_position, last_position, '[]') WITH &&)
+);
+
+-- Recreate the previously hardcoded main/extended/legacy split (with the default sizes of 75 and 150) on every existing list
+INSERT INTO list_sections (list, name, description, first_position, last_position, numbered, submissions_open, allows_non_100, awards_points)
+SELECT id, 'Main List', 'The main section of the Demonlist. These demons are the hardest rated levels in the game. Records are accepted above a given threshold and award a large amount of points!', 1, 75, TRUE, TRUE, TRUE, TRUE
+FROM lists;
+
+INSERT INTO list_sections (list, name, description, first_position, last_position, numbered, submissions_open, allows_non_100, awards_points)
+SELECT id, 'Extended List', 'These are demons that dont qualify for the main section of the list, but are still of high relevance. Only 100% records are accepted for these demons! Note that non-100% that were submitted/approved before a demon fell off the main list will be retained', 76, 150, TRUE, TRUE, FALSE, TRUE
+FROM lists;
+
+INSERT INTO list_sections (list, name, description, first_position, last_position, numbered, submissions_open, allows_non_100, awards_points)
+SELECT id, 'Legacy List', 'These are demons that used to be on the list, but got pushed off as new demons were added. They are here for nostalgic reasons. This list is in no order whatsoever and will not be maintained any longer at all. This means no new records will be added for these demons.', 151, NULL, FALSE, FALSE, FALSE, TRUE
+FROM lists;
+
+-- A demon only gives points if it is inside a section that awards points, and progress records only count in sections that accept them
+CREATE OR REPLACE VIEW score_giving AS
+    SELECT records.progress, demons.position, demons.requirement, records.player, demons.list
+    FROM records
+    INNER JOIN demons
+    ON demons.id = records.demon
+    INNER JOIN list_sections
+    ON list_sections.list = demons.list
+        AND demons.position >= list_sections.first_position
+        AND (list_sections.last_position IS NULL OR demons.position <= list_sections.last_position)
+    WHERE records.status_ = 'APPROVED' AND list_sections.awards_points AND (list_sections.allows_non_100 OR records.progress = 100)
+
+    UNION
+
+    SELECT 100, demons.position, demons.requirement, demons.verifier, demons.list
+    FROM demons
+    INNER JOIN list_sections
+    ON list_sections.list = demons.list
+        AND demons.position >= list_sections.first_position
+        AND (list_sections.last_position IS NULL OR demons.position <= list_sections.last_position)
+    WHERE list_sections.awards_points;
+
+-- The size of a list, which determines how quickly the points awarded by its demons decrease, is the position at which the
+-- last of its bounded sections ends (150 for the default sections created above). Demons past it award no points.
+-- Lists without any bounded section extend to their last demon. The size is at least 2, as the score formula divides by
+-- the size minus one.
+CREATE FUNCTION list_size(list_id INTEGER) RETURNS INTEGER AS $$
+    SELECT GREATEST(
+        COALESCE(
+            (SELECT MAX(last_position) FROM list_sections WHERE list = list_id),
+            (SELECT MAX(position) FROM demons WHERE list = list_id)
+        ),
+        2
+    )
+$$ LANGUAGE SQL STABLE;
+
+-- Replace the sizes hardcoded into the score functions of the previous migration with the size of each list
+CREATE OR REPLACE FUNCTION recompute_player_scores() RETURNS void AS $$
+    DELETE FROM player_scores;
+
+    INSERT INTO player_scores (player, list, score)
+    SELECT player, list, SUM(record_score(progress, position, list_sizes.size, requirement))
+    FROM score_giving
+    INNER JOIN (SELECT id, list_size(id) AS size FROM lists) list_sizes
+            ON list_sizes.id = score_giving.list
+    GROUP BY player, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_nation_scores() RETURNS void AS $$
+    DELETE FROM nationality_scores;
+
+    INSERT INTO nationality_scores (nation, list, score)
+    SELECT nationality, list, SUM(record_score(q.progress, q.position, list_sizes.size, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position, nationality) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality IS NOT NULL
+        ORDER BY list, players.nationality, position, progress DESC
+    ) q
+    INNER JOIN (SELECT id, list_size(id) AS size FROM lists) list_sizes
+            ON list_sizes.id = q.list
+    GROUP BY nationality, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_subdivision_scores() RETURNS void AS $$
+    DELETE FROM subdivision_scores;
+
+    INSERT INTO subdivision_scores (nation, subdivision, list, score)
+    SELECT nationality, subdivision, list, SUM(record_score(q.progress, q.position, list_sizes.size, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position, nationality, subdivision) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality IS NOT NULL
+          AND players.subdivision IS NOT NULL
+        ORDER BY list, players.nationality, players.subdivision, position, progress DESC
+    ) q
+    INNER JOIN (SELECT id, list_size(id) AS size FROM lists) list_sizes
+            ON list_sizes.id = q.list
+    GROUP BY nationality, subdivision, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_scores_of_player(player_id INTEGER) RETURNS void AS $$
+    DELETE FROM player_scores WHERE player = player_id;
+
+    INSERT INTO player_scores (player, list, score)
+    SELECT player, list, SUM(record_score(progress, position, list_sizes.size, requirement))
+    FROM score_giving
+    INNER JOIN (SELECT id, list_size(id) AS size FROM lists) list_sizes
+            ON list_sizes.id = score_giving.list
+    WHERE player = player_id
+    GROUP BY player, list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_scores_of_nation(iso_country_code VARCHAR(2)) RETURNS void AS $$
+    DELETE FROM nationality_scores WHERE nation = iso_country_code;
+
+    INSERT INTO nationality_scores (nation, list, score)
+    SELECT iso_country_code, list, SUM(record_score(q.progress, q.position, list_sizes.size, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality = iso_country_code
+        ORDER BY list, position, progress DESC
+    ) q
+    INNER JOIN (SELECT id, list_size(id) AS size FROM lists) list_sizes
+            ON list_sizes.id = q.list
+    GROUP BY list;
+$$ LANGUAGE SQL;
+
+CREATE OR REPLACE FUNCTION recompute_scores_of_subdivision(iso_country_code VARCHAR(2), iso_code VARCHAR(3)) RETURNS void AS $$
+    DELETE FROM subdivision_scores WHERE nation = iso_country_code AND subdivision = iso_code;
+
+    INSERT INTO subdivision_scores (nation, subdivision, list, score)
+    SELECT iso_country_code, iso_code, list, SUM(record_score(q.progress, q.position, list_sizes.size, q.requirement))
+    FROM (
+        SELECT DISTINCT ON (list, position) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality = iso_country_code
+          AND players.subdivision = iso_code
+        ORDER BY list, position, progress DESC
+    ) q
+    INNER JOIN (SELECT id, list_size(id) AS size FROM lists) list_sizes
+            ON list_sizes.id = q.list
+    GROUP BY list;
+$$ LANGUAGE SQL;
+
+-- Counterpart of the above functions that only updates the scores of players, nations and subdivisions on a single list,
+-- e.g. after the sections of that list changed
+CREATE FUNCTION recompute_scores_of_list(list_id INTEGER) RETURNS void AS $$
+    DELETE FROM player_scores WHERE list = list_id;
+    DELETE FROM nationality_scores WHERE list = list_id;
+    DELETE FROM subdivision_scores WHERE list = list_id;
+
+    INSERT INTO player_scores (player, list, score)
+    SELECT player, list_id, SUM(record_score(progress, position, list_size(list_id), requirement))
+    FROM score_giving
+    WHERE list = list_id
+    GROUP BY player;
+
+    INSERT INTO nationality_scores (nation, list, score)
+    SELECT nationality, list_id, SUM(record_score(q.progress, q.position, list_size(list_id), q.requirement))
+    FROM (
+        SELECT DISTINCT ON (position, nationality) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality IS NOT NULL
+          AND list = list_id
+        ORDER BY players.nationality, position, progress DESC
+    ) q
+    GROUP BY nationality;
+
+    INSERT INTO subdivision_scores (nation, subdivision, list, score)
+    SELECT nationality, subdivision, list_id, SUM(record_score(q.progress, q.position, list_size(list_id), q.requirement))
+    FROM (
+        SELECT DISTINCT ON (position, nationality, subdivision) * from score_giving
+        INNER JOIN players
+                ON players.id=player
+        WHERE players.nationality IS NOT NULL
+          AND players.subdivision IS NOT NULL
+          AND list = list_id
+        ORDER BY players.nationality, players.subdivision, position, progress DESC
+    ) q
+    GROUP BY nationality, subdivision;
+$$ LANGUAGE SQL;
+
+SELECT recompute_player_scores();
+SELECT recompute_nation_scores();
+SELECT recompute_subdivision_scores();
@@ -0,0 +1,136 @@
+-- Add up migration script here
+
+-- Unlisted demons are no longer part of their list, but are kept around (together with their records) for archival purposes. They
+-- retain the position they had when they were unlisted, which means positions only need to be unique among demons that are still listed.
+ALTER TABLE demons ADD COLUMN unlisted BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE demons DROP CONSTRAINT unique_position;
+ALTER TABLE demons ADD CONSTRAINT unique_position EXCLUDE USING btree (list WITH =, position WITH =) WHERE (NOT unlisted) DEFERRABLE INITIALLY IMMEDIATE;
+
+ALTER TABLE demon_modifications ADD COLUMN unlisted BOOLEAN NULL DEFAULT NULL;
+
+-- Allows the movement log of a demon to look up which demons were removed at the same time it moved
+CREATE INDEX demon_removals ON demon_modifications (time) WHERE unlisted = FALSE;
+
+CREATE OR REPLACE FUNCTION audit_demon_modification() RETURNS trigger AS $demon_modification_trigger$
+DECLARE
+    name_change CITEXT;
+    position_change SMALLINT;
+    requirement_change SMALLINT;
+    video_change VARCHAR(200);
+    thumbnail_change TEXT;
+    verifier_change INT;
+    publisher_change INT;
+    unlisted_change BOOLEAN;
+BEGIN
+    IF (OLD.name <> NEW.name) THEN
+        name_change = OLD.name;
+    END IF;
+
+    IF (OLD.position <> NEW.position) THEN
+        position_change = OLD.position;
+    END IF;
+
+    IF (OLD.requirement <> NEW.requirement) THEN
+        requirement_change = OLD.requirement;
+    END IF;
+
+    IF (OLD.video <> NEW.video) THEN
+        video_change = OLD.video;
+    END IF;
+
+    IF (OLD.thumbnail <> NEW.thumbnail) THEN
+        thumbnail_change = OLD.thumbnail;
+    END IF;
+
+    IF (OLD.verifier <> NEW.verifier) THEN
+        verifier_change = OLD.verifier;
+    END IF;
+
+    IF (OLD.publisher <> NEW.publisher) THEN
+        publisher_change = OLD.publisher;
+    END IF;
+
+    IF (OLD.unlisted <> NEW.unlisted) THEN
+        unlisted_change = OLD.unlisted;
+    END IF;
+
+    INSERT INTO demon_modifications (userid, name, position, requirement, video, verifier, publisher, thumbnail, unlisted, id)
+        (SELECT id, name_change, position_change, requirement_change, video_change, verifier_change, publisher_change, thumbnail_change, unlisted_change, NEW.id
+         FROM active_user LIMIT 1);
+
+    RETURN NEW;
+END;
+$demon_modification_trigger$ LANGUAGE plpgsql;
+
+-- See handling of record_deletions
+CREATE TABLE demon_deletions (
+    id INTEGER NOT NULL -- REFERENCES demons(id)
+) INHERITS (audit_log2);
+
+CREATE FUNCTION audit_demon_deletion() RETURNS trigger AS $demon_deletion_trigger$
+    BEGIN
+        INSERT INTO demon_modifications (userid, id, name, position, requirement, video, verifier, publisher, thumbnail, unlisted)
+            (SELECT id, OLD.id, OLD.name, OLD.position, OLD.requirement, OLD.video, OLD.verifier, OLD.publisher, OLD.thumbnail, OLD.unlisted
+            FROM active_user LIMIT 1);
+
+        INSERT INTO demon_deletions (userid, id)
+            (SELECT id, OLD.id FROM active_user LIMIT 1);
+
+        RETURN NULL;
+    END;
+$demon_deletion_trigger$ LANGUAGE plpgsql;
+
+CREATE TRIGGER demon_deletion_trigger AFTER DELETE ON demons FOR EACH ROW EXECUTE PROCEDURE audit_demon_deletion();
+
+-- Unlisted demons do not give points anymore (neither do records on them)
+CREATE OR REPLACE VIEW score_giving AS
+    SELECT records.progress, demons.position, demons.requirement, records.player, demons.list
+    FROM records
+    INNER JOIN demons
+    ON demons.id = records.demon
+    INNER JOIN list_sections
+    ON list_sections.list = demons.list
+        AND demons.position >= list_sections.first_position
+        AND (list_sections.last_position IS NULL OR demons.position <= list_sections.last_position)
+    WHERE records.status_ = 'APPROVED' AND list_sections.awards_points AND (list_sections.allows_non_100 OR records.progress = 100) AND NOT demons.unlisted
+
+    UNION
+
+    SELECT 100, demons.position, demons.requirement, demons.verifier, demons.list
+    FROM demons
+    INNER JOIN list_sections
+    ON list_sections.list = demons.list
+        AND demons.position >= list_sections.first_position
+        AND (list_sections.last_position IS NULL OR demons.position <= list_sections.last_position)
+    WHERE list_sections.awards_points AND NOT demons.unlisted;
+
+CREATE OR REPLACE FUNCTION list_at(TIMESTAMP WITHOUT TIME ZONE)
+    RETURNS TABLE (
+                      name CITEXT,
+                      position_ SMALLINT,
+                      requirement SMALLINT,
+                      video VARCHAR(200),
+                      thumbnail TEXT,
+                      verifier INTEGER,
+                      publisher INTEGER,
+                      id INTEGER,
+                      level_id BIGINT,
+                      current_position SMALLINT,
+                      list INTEGER
+                  )
+AS $$
+SELECT name, CASE WHEN t.position IS NULL THEN demons.position ELSE t.position END, requirement, video, thumbnail, verifier, publisher, demons.id, level_id, demons.position AS current_position, demons.list
+FROM demons
+         LEFT OUTER JOIN (
+    SELECT DISTINCT ON (id) id, position
+    FROM demon_modifications
+    WHERE time >= $1 AND position != -1
+    ORDER BY id, time
+) t
+                         ON demons.id = t.id
+WHERE NOT EXISTS (SELECT 1 FROM demon_additions WHERE demon_additions.id = demons.id AND time >= $1)
+  AND NOT demons.unlisted
+$$
+    LANGUAGE SQL
+    STABLE;
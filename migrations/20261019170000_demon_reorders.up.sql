@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- Reorders move multiple demons in one go. The individual position changes are still tracked in demon_modifications, this table
+-- only groups them together (all entries of one reorder share the same timestamp), and records which demons were explicitly moved
+-- (as opposed to just shifted around to make room).
+CREATE TABLE demon_reorders (
+    list INTEGER NOT NULL REFERENCES lists(id),
+    moved INTEGER[] NOT NULL
+) INHERITS (audit_log2);
+
+-- Movement logs look up the reorders that happened at the same time as modifications of a demon
+CREATE INDEX demon_reorders_time ON demon_reorders (time);
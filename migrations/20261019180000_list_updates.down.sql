@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP TABLE list_update_requirements;
+DROP TABLE list_update_moves;
+DROP TABLE list_update_additions;
+DROP TABLE list_updates;
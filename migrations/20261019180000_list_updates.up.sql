@@ -0,0 +1,41 @@
+-- Add up migration script here
+
+-- List updates are collections of changes to a list that are prepared in advance and published all at once, either manually or
+-- automatically once their scheduled time has come.
+CREATE TABLE list_updates (
+    id SERIAL PRIMARY KEY,
+    list INTEGER NOT NULL REFERENCES lists(id),
+    title TEXT NOT NULL,
+    author INTEGER NULL REFERENCES members(member_id) ON DELETE SET NULL,
+    -- NULL means this update is a draft that is not scheduled for publication yet
+    scheduled_for TIMESTAMP WITHOUT TIME ZONE NULL,
+    published_at TIMESTAMP WITHOUT TIME ZONE NULL,
+    -- Set if applying the update at its scheduled time failed (e.g. because one of its demons was deleted in the meantime)
+    failure TEXT NULL
+);
+
+CREATE TABLE list_update_additions (
+    id SERIAL PRIMARY KEY,
+    list_update INTEGER NOT NULL REFERENCES list_updates(id) ON DELETE CASCADE,
+    name CITEXT NOT NULL,
+    position SMALLINT NOT NULL,
+    requirement SMALLINT NOT NULL,
+    verifier TEXT NOT NULL,
+    publisher TEXT NOT NULL,
+    creators TEXT[] NOT NULL,
+    video TEXT NULL
+);
+
+CREATE TABLE list_update_moves (
+    list_update INTEGER NOT NULL REFERENCES list_updates(id) ON DELETE CASCADE,
+    demon INTEGER NOT NULL REFERENCES demons(id) ON DELETE CASCADE,
+    position SMALLINT NOT NULL,
+    PRIMARY KEY (list_update, demon)
+);
+
+CREATE TABLE list_update_requirements (
+    list_update INTEGER NOT NULL REFERENCES list_updates(id) ON DELETE CASCADE,
+    demon INTEGER NOT NULL REFERENCES demons(id) ON DELETE CASCADE,
+    requirement SMALLINT NOT NULL,
+    PRIMARY KEY (list_update, demon)
+);
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TABLE placement_opinions;
+DROP TABLE placement_proposals;
+DROP TYPE proposal_status;
@@ -0,0 +1,37 @@
+-- Add up migration script here
+
+CREATE TYPE proposal_status AS ENUM ('OPEN', 'ACCEPTED', 'REJECTED');
+
+CREATE TABLE placement_proposals (
+    id SERIAL PRIMARY KEY,
+    list INTEGER NOT NULL REFERENCES lists(id),
+    -- NULL for proposals to add a new demon that have not been accepted yet. Once such a proposal is accepted, this is set to the
+    -- newly added demon, so that the discussion stays attached to it. Proposals outlive the demon they are about, so that it
+    -- stays traceable how it was placed.
+    demon INTEGER NULL REFERENCES demons(id) ON DELETE SET NULL,
+    position SMALLINT NOT NULL,
+    reason TEXT NOT NULL,
+    -- Only set for proposals to add a new demon
+    name CITEXT NULL,
+    requirement SMALLINT NULL,
+    verifier TEXT NULL,
+    publisher TEXT NULL,
+    creators TEXT[] NULL,
+    video TEXT NULL,
+    author INTEGER NULL REFERENCES members(member_id) ON DELETE SET NULL,
+    status proposal_status NOT NULL DEFAULT 'OPEN',
+    created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc'),
+    resolved_by INTEGER NULL REFERENCES members(member_id) ON DELETE SET NULL,
+    resolved_at TIMESTAMP WITHOUT TIME ZONE NULL,
+    -- Open proposals are about exactly one existing or new demon. Deleting a demon rejects the open proposals about it.
+    CONSTRAINT proposal_for_one_demon CHECK (status <> 'OPEN' OR ((demon IS NULL) <> (name IS NULL)))
+);
+
+-- The position each list team member thinks the proposed demon belongs at. Agreeing with a proposal means giving the proposed position.
+CREATE TABLE placement_opinions (
+    proposal INTEGER NOT NULL REFERENCES placement_proposals(id) ON DELETE CASCADE,
+    member INTEGER NOT NULL REFERENCES members(member_id) ON DELETE CASCADE,
+    position SMALLINT NOT NULL,
+    comment TEXT NOT NULL DEFAULT '',
+    PRIMARY KEY (proposal, member)
+);
@@ -0,0 +1,6 @@
+-- Add down migration script here
+
+DROP TABLE webhook_delivery_attempts;
+DROP TABLE webhook_deliveries;
+DROP TABLE webhooks;
+DROP TYPE webhook_format;
@@ -0,0 +1,38 @@
+-- Add up migration script here
+
+CREATE TYPE webhook_format AS ENUM ('JSON', 'DISCORD');
+
+CREATE TABLE webhooks (
+    id SERIAL PRIMARY KEY,
+    url TEXT NOT NULL,
+    -- Key with which the bodies of all deliveries are signed (HMAC-SHA256), so that receivers can verify they actually come from us
+    secret TEXT NOT NULL,
+    format webhook_format NOT NULL,
+    -- The names of the activities this webhook is notified about
+    events TEXT[] NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_by INTEGER NULL REFERENCES members(member_id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc')
+);
+
+-- The body is rendered when the delivery is created, so that retries send exactly the same (signed) payload
+CREATE TABLE webhook_deliveries (
+    id SERIAL PRIMARY KEY,
+    webhook INTEGER NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    event TEXT NOT NULL,
+    body TEXT NOT NULL,
+    created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc'),
+    delivered_at TIMESTAMP WITHOUT TIME ZONE NULL,
+    -- NULL once the delivery succeeded or we gave up on it
+    next_attempt_at TIMESTAMP WITHOUT TIME ZONE NULL DEFAULT (NOW() AT TIME ZONE 'utc')
+);
+
+CREATE INDEX webhook_deliveries_next_attempt_at ON webhook_deliveries (next_attempt_at) WHERE next_attempt_at IS NOT NULL;
+
+CREATE TABLE webhook_delivery_attempts (
+    delivery INTEGER NOT NULL REFERENCES webhook_deliveries(id) ON DELETE CASCADE,
+    attempted_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc'),
+    -- The HTTP status code the receiver responded with. NULL if no response was received at all
+    status_code SMALLINT NULL,
+    error TEXT NULL
+);
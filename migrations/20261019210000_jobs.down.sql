@@ -0,0 +1,14 @@
+-- Add down migration script here
+
+-- Existing deliveries must not all become due at once, so only those still waiting in the job queue get an attempt scheduled
+ALTER TABLE webhook_deliveries ADD COLUMN next_attempt_at TIMESTAMP WITHOUT TIME ZONE NULL;
+
+UPDATE webhook_deliveries SET next_attempt_at = jobs.run_at
+    FROM jobs WHERE jobs.kind = 'deliver_webhook' AND (jobs.payload::json->>'delivery')::integer = webhook_deliveries.id;
+
+ALTER TABLE webhook_deliveries ALTER COLUMN next_attempt_at SET DEFAULT (NOW() AT TIME ZONE 'utc');
+
+CREATE INDEX webhook_deliveries_next_attempt_at ON webhook_deliveries (next_attempt_at) WHERE next_attempt_at IS NOT NULL;
+
+DROP TABLE dead_jobs;
+DROP TABLE jobs;
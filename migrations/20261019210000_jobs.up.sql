@@ -0,0 +1,33 @@
+-- Add up migration script here
+
+CREATE TABLE jobs (
+    id SERIAL PRIMARY KEY,
+    -- Identifies the handler that runs this job
+    kind TEXT NOT NULL,
+    -- JSON encoded job data, deserialized by the handler
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    max_attempts INTEGER NOT NULL,
+    run_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc'),
+    -- Set while a worker is running this job. If the worker dies (e.g. because the server restarted), the job becomes available again
+    -- once this has passed
+    locked_until TIMESTAMP WITHOUT TIME ZONE NULL,
+    last_error TEXT NULL,
+    created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc')
+);
+
+CREATE INDEX jobs_run_at ON jobs (run_at);
+
+-- Jobs that failed on every attempt end up here, so that administrators can inspect (and possibly retry) them
+CREATE TABLE dead_jobs (
+    id INTEGER PRIMARY KEY,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_error TEXT NOT NULL,
+    created_at TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    failed_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc')
+);
+
+-- Webhook deliveries are now retried by the job queue
+ALTER TABLE webhook_deliveries DROP COLUMN next_attempt_at;
@@ -0,0 +1,15 @@
+-- Add down migration script here
+
+CREATE OR REPLACE FUNCTION set_initial_thumbnail() RETURNS trigger AS '
+BEGIN
+    IF NEW.video IS NOT NULL AND NOT EXISTS(SELECT 1 FROM players WHERE players.id=NEW.verifier AND players.link_banned) THEN
+        NEW.thumbnail := ''https://i.ytimg.com/vi/'' || SUBSTRING(NEW.video FROM ''%v=#"___________#"%'' FOR ''#'') || ''/mqdefault.jpg'';
+    END IF;
+    RETURN NEW;
+END;
+' LANGUAGE plpgsql;
+
+CREATE TRIGGER demons_insert_set_thumbnail BEFORE INSERT ON demons FOR
+EACH ROW EXECUTE PROCEDURE set_initial_thumbnail();
+
+ALTER TABLE demons DROP COLUMN thumbnail_overridden;
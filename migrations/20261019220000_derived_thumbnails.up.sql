@@ -0,0 +1,14 @@
+-- Add up migration script here
+
+-- Thumbnails are now derived from the verification video by whatever video host it is uploaded to, unless they have been set by hand.
+ALTER TABLE demons ADD COLUMN thumbnail_overridden BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Anything that is neither the placeholder nor what the trigger below would have derived was set by hand
+UPDATE demons
+SET thumbnail_overridden = TRUE
+WHERE thumbnail <> 'https://i.ytimg.com/vi/zebrafishes/mqdefault.jpg'
+  AND thumbnail IS DISTINCT FROM 'https://i.ytimg.com/vi/' || SUBSTRING(video FROM '%v=#"___________#"%' FOR '#') || '/mqdefault.jpg';
+
+-- Deriving thumbnails is now done by the application, as only it knows about all supported video hosts
+DROP TRIGGER demons_insert_set_thumbnail ON demons;
+DROP FUNCTION set_initial_thumbnail;
@@ -0,0 +1,12 @@
+-- Add up migration script here
+
+-- Results of checking whether the video of a record can still be reached. Kept out of the records table so that
+-- rechecking videos does not show up in the record audit log.
+CREATE TABLE record_video_checks (
+    record INTEGER PRIMARY KEY REFERENCES records(id) ON DELETE CASCADE,
+    checked_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc'),
+    -- Set while the video is unreachable, to when it was first found to be unreachable
+    unreachable_since TIMESTAMP WITHOUT TIME ZONE
+);
+
+CREATE INDEX record_video_checks_checked_at ON record_video_checks (checked_at);
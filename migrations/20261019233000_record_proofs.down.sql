@@ -0,0 +1,9 @@
+-- Add down migration script here
+
+INSERT INTO record_notes (record, content, is_public)
+SELECT record, 'Raw footage: ' || url, is_public
+FROM record_proofs
+WHERE kind = 'RAW_FOOTAGE';
+
+DROP TABLE record_proofs;
+DROP TYPE record_proof_kind;
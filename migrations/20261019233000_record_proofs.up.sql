@@ -0,0 +1,23 @@
+-- Add up migration script here
+
+CREATE TYPE record_proof_kind AS ENUM ('RAW_FOOTAGE', 'CLIP');
+
+-- Proof attached to a record in addition to its main video (which stays in records.video)
+CREATE TABLE record_proofs (
+    id SERIAL PRIMARY KEY,
+    record INTEGER NOT NULL REFERENCES records(id) ON DELETE CASCADE,
+    kind record_proof_kind NOT NULL,
+    url TEXT NOT NULL,
+    -- Whether everyone can see this proof, or only the list team
+    is_public BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+CREATE INDEX record_proofs_record ON record_proofs (record);
+
+-- Raw footage used to be stored as a note on the record
+INSERT INTO record_proofs (record, kind, url, is_public)
+SELECT record, 'RAW_FOOTAGE', SUBSTRING(content FROM 15), is_public
+FROM record_notes
+WHERE content LIKE 'Raw footage: %';
+
+DELETE FROM record_notes WHERE content LIKE 'Raw footage: %';
@@ -0,0 +1,5 @@
+-- Add down migration script here
+
+DROP TRIGGER release_review_claim_trigger ON records;
+DROP FUNCTION release_review_claim();
+DROP TABLE review_claims;
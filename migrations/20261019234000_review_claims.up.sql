@@ -0,0 +1,20 @@
+-- Add up migration script here
+
+-- Records list team members are currently reviewing. A claim is only valid until it expires.
+CREATE TABLE review_claims (
+    record INTEGER PRIMARY KEY REFERENCES records(id) ON DELETE CASCADE,
+    reviewer INTEGER NOT NULL REFERENCES members(member_id) ON DELETE CASCADE,
+    claimed_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc'),
+    expires_at TIMESTAMP WITHOUT TIME ZONE NOT NULL
+);
+
+-- Once a record's status changes, whoever was reviewing it is done with it
+CREATE FUNCTION release_review_claim() RETURNS trigger AS $release_review_claim$
+    BEGIN
+        DELETE FROM review_claims WHERE record = NEW.id;
+        RETURN NEW;
+    END;
+$release_review_claim$ LANGUAGE plpgsql;
+
+CREATE TRIGGER release_review_claim_trigger AFTER UPDATE OF status_ ON records FOR EACH ROW
+    WHEN (OLD.status_ IS DISTINCT FROM NEW.status_) EXECUTE PROCEDURE release_review_claim();
@@ -0,0 +1,54 @@
+-- Add up migration script here
+
+-- Key of the (deployment configured) reason a record was rejected or put under consideration
+ALTER TABLE records ADD COLUMN status_reason TEXT NULL;
+
+-- Reasons are audited like any other change to a record. As the column is nullable, an empty string records that a record previously
+-- had no reason.
+ALTER TABLE record_modifications ADD COLUMN status_reason TEXT NULL;
+
+CREATE OR REPLACE FUNCTION audit_record_modification() RETURNS trigger AS $record_modification_trigger$
+    DECLARE
+        progress_change SMALLINT;
+        video_change VARCHAR(200);
+        status_change RECORD_STATUS;
+        player_change INT;
+        demon_change INTEGER;
+        status_reason_change TEXT;
+    BEGIN
+        if (OLD.progress <> NEW.progress) THEN
+            progress_change = OLD.progress;
+        END IF;
+
+        IF (OLD.video <> NEW.video) THEN
+            video_change = OLD.video;
+        END IF;
+
+        IF (OLD.status_ <> NEW.status_) THEN
+            status_change = OLD.status_;
+        END IF;
+
+        IF (OLD.player <> NEW.player) THEN
+            player_change = OLD.player;
+        END IF;
+
+        IF (OLD.demon <> NEW.demon) THEN
+            demon_change = OLD.demon;
+        END IF;
+
+        IF (OLD.status_reason IS DISTINCT FROM NEW.status_reason) THEN
+            status_reason_change = COALESCE(OLD.status_reason, '');
+        END IF;
+
+        INSERT INTO record_modifications (userid, id, progress, video, status_, player, demon, status_reason)
+            (SELECT id, NEW.id, progress_change, video_change, status_change, player_change, demon_change, status_reason_change
+            FROM active_user LIMIT 1);
+
+        RETURN NEW;
+    END;
+$record_modification_trigger$ LANGUAGE plpgsql;
+
+-- Hex encoded SHA-256 hash of the secret token handed out to whoever submitted a record, which allows them to look up what became
+-- of their submission. Only the hash is stored, so that the tokens cannot be recovered from the database. Records submitted before
+-- this was introduced do not have one.
+ALTER TABLE records ADD COLUMN status_token_hash TEXT NULL UNIQUE;
@@ -0,0 +1,10 @@
+-- Add down migration script here
+
+DROP TRIGGER track_pending_approval_trigger ON records;
+DROP FUNCTION track_pending_approval();
+DROP TABLE pending_approvals;
+
+-- Postgres cannot remove values from enums, so we just make sure the value is not in use anymore. The audit log is
+-- rewritten last, since moving the records back to SUBMITTED logs their previous status.
+UPDATE records SET status_ = 'SUBMITTED' WHERE status_ = 'PENDING_APPROVAL';
+UPDATE record_modifications SET status_ = 'SUBMITTED' WHERE status_ = 'PENDING_APPROVAL';
@@ -0,0 +1,30 @@
+-- Add up migration script here
+
+-- Records approved by one list team member that still need a second approval
+ALTER TYPE record_status ADD VALUE IF NOT EXISTS 'PENDING_APPROVAL';
+
+-- Who gave the first approval to records that are pending approval
+CREATE TABLE pending_approvals (
+    record INTEGER PRIMARY KEY REFERENCES records(id) ON DELETE CASCADE,
+    approver INTEGER NOT NULL, -- same as the userid column of audit log entries
+    approved_at TIMESTAMP WITHOUT TIME ZONE NOT NULL DEFAULT (NOW() AT TIME ZONE 'utc')
+);
+
+-- Keep track of the first approval whenever a record enters or leaves the pending state. The approver is whoever is
+-- currently active according to the audit log. Comparing as text, since the new enum value cannot be used before this
+-- transaction commits.
+CREATE FUNCTION track_pending_approval() RETURNS trigger AS $track_pending_approval$
+    BEGIN
+        IF (NEW.status_::text = 'PENDING_APPROVAL') THEN
+            INSERT INTO pending_approvals (record, approver) (SELECT NEW.id, id FROM active_user LIMIT 1)
+                ON CONFLICT (record) DO NOTHING;
+        ELSE
+            DELETE FROM pending_approvals WHERE record = NEW.id;
+        END IF;
+
+        RETURN NEW;
+    END;
+$track_pending_approval$ LANGUAGE plpgsql;
+
+CREATE TRIGGER track_pending_approval_trigger AFTER UPDATE OF status_ ON records FOR EACH ROW
+    WHEN (OLD.status_ IS DISTINCT FROM NEW.status_) EXECUTE PROCEDURE track_pending_approval();
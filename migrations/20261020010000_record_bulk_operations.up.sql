@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+-- Bulk operations change many records in one go. The individual changes are still tracked in record_modifications (and
+-- record_deletions), this table only groups them together and records what operation caused them.
+CREATE TABLE record_bulk_operations (
+    operation TEXT NOT NULL,
+    -- The status records were set to, or the name of the player they were reassigned to
+    detail TEXT NULL,
+    records INTEGER[] NOT NULL
+) INHERITS (audit_log2);
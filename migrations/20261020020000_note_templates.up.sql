@@ -0,0 +1,10 @@
+-- Add up migration script here
+
+CREATE TABLE record_note_templates (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    -- May contain the placeholders {player}, {demon} and {progress}, which are filled in with the details of the record a note
+    -- is created on
+    content TEXT NOT NULL,
+    created_by INTEGER NULL REFERENCES members(member_id) ON DELETE SET NULL
+);
@@ -0,0 +1,116 @@
+//! Module providing a fairing that loads the announcements to display on a requested page
+
+use chrono::{NaiveDateTime, Utc};
+use log::error;
+use pointercrate_core::{announcement::Announcement, error::Result, pool::PointercratePool};
+use rocket::{
+    fairing::{Fairing, Info, Kind},
+    http::Method,
+    Data, Request,
+};
+use std::sync::Mutex;
+
+/// The announcements active on the page a request was made for, stored in request-local state.
+///
+/// Populated by the [`AnnouncementFairing`] and rendered by the [`Page`](crate::response::Page)
+/// responder above the navigation bar.
+#[derive(Default)]
+pub struct ActiveAnnouncements(pub Vec<Announcement>);
+
+/// In-memory copy of all announcements that have not expired yet, kept in managed state.
+///
+/// Whether a cached announcement is active is decided anew for each request based on its start and
+/// end time, so the cache only needs to be invalidated (via [`AnnouncementCache::invalidate`])
+/// whenever announcements are created, modified or deleted.
+#[derive(Default)]
+pub struct AnnouncementCache {
+    state: Mutex<CachedAnnouncements>,
+}
+
+#[derive(Default)]
+struct CachedAnnouncements {
+    /// Incremented on every invalidation, so that announcements loaded from the database while
+    /// they were being modified do not end up in the cache
+    generation: u64,
+    announcements: Option<Vec<Announcement>>,
+}
+
+impl AnnouncementCache {
+    /// Discards the cached announcements, causing them to be reloaded on the next page request
+    pub fn invalidate(&self) {
+        let mut state = self.state.lock().unwrap();
+
+        state.generation += 1;
+        state.announcements = None;
+    }
+
+    /// Gets all announcements active at the given point in time (UTC) that target the page with
+    /// the given path, most severe first
+    pub async fn active_on(&self, path: &str, time: NaiveDateTime, pool: &PointercratePool) -> Result<Vec<Announcement>> {
+        let active = |announcements: &[Announcement]| {
+            announcements
+                .iter()
+                .filter(|announcement| announcement.is_active_at(time) && announcement.targets(path))
+                .cloned()
+                .collect()
+        };
+
+        let generation = {
+            let state = self.state.lock().unwrap();
+
+            if let Some(ref announcements) = state.announcements {
+                return Ok(active(announcements));
+            }
+
+            state.generation
+        };
+
+        let announcements = Announcement::unexpired(time, &mut *pool.connection().await?).await?;
+        let active_announcements = active(&announcements);
+
+        let mut state = self.state.lock().unwrap();
+
+        if state.generation == generation {
+            state.announcements = Some(announcements);
+        }
+
+        Ok(active_announcements)
+    }
+}
+
+/// Rocket fairing that, for each `GET` request for an HTML page, retrieves the announcements
+/// currently active on that page from the [`AnnouncementCache`] in managed state.
+#[derive(Default)]
+pub struct AnnouncementFairing;
+
+#[rocket::async_trait]
+impl Fairing for AnnouncementFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Announcements",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        let wants_html = request.accept().map(|accept| accept.preferred().is_html()).unwrap_or(false);
+
+        if request.method() != Method::Get || !wants_html {
+            return;
+        }
+
+        let (Some(pool), Some(cache)) = (
+            request.rocket().state::<PointercratePool>(),
+            request.rocket().state::<AnnouncementCache>(),
+        ) else {
+            return;
+        };
+
+        match cache.active_on(request.uri().path().as_str(), Utc::now().naive_utc(), pool).await {
+            Ok(announcements) => {
+                request.local_cache(|| ActiveAnnouncements(announcements));
+            },
+            Err(err) => error!("Failed to retrieve announcements for {}: {:?}", request.uri(), err),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Module providing the workers running jobs from the [background job queue](pointercrate_core::job)
+//!
+//! Each kind of [`Job`] is run by a [`JobHandler`], which needs to be registered with the
+//! [`JobQueue`] managed by rocket. The [`JobWorkers`] fairing then spawns a number of workers
+//! once the server has launched, which run jobs until the server shuts down.
+
+use log::{error, warn};
+use pointercrate_core::{
+    error::Result,
+    job::{self, QueuedJob},
+    pool::PointercratePool,
+};
+use rocket::{
+    fairing::{Fairing, Info, Kind},
+    tokio, Orbit, Rocket,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::PgConnection;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+/// How long an idle worker waits before checking the queue again
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How many jobs are run concurrently
+const WORKER_COUNT: usize = 4;
+
+/// The outcome of running a job. Any error causes the job to be retried later
+pub type JobResult = std::result::Result<(), Box<dyn std::error::Error + Send + Sync>>;
+
+#[rocket::async_trait]
+pub trait Job: Serialize + DeserializeOwned + Send + Sync {
+    /// Uniquely identifies this kind of job in the queue
+    const KIND: &'static str;
+
+    /// How often running this job is attempted before it is moved to the dead letter table
+    const MAX_ATTEMPTS: i32 = 5;
+
+    /// Adds this job to the queue, returning its id. It is only run once the transaction
+    /// `connection` belongs to (if any) is committed.
+    async fn enqueue(&self, connection: &mut PgConnection) -> Result<i32> {
+        // Serializing our own job structs cannot fail
+        let payload = serde_json::to_string(self).unwrap();
+
+        job::enqueue(Self::KIND, payload, Self::MAX_ATTEMPTS, connection).await
+    }
+}
+
+/// Which attempt at running a job a [`JobHandler`] is making
+#[derive(Debug, Clone, Copy)]
+pub struct Attempt {
+    /// Starts at 1 for the first attempt
+    pub number: i32,
+    pub max_attempts: i32,
+}
+
+impl Attempt {
+    /// Whether the job is moved to the dead letter table if this attempt fails
+    pub fn is_final(&self) -> bool {
+        self.number >= self.max_attempts
+    }
+}
+
+#[rocket::async_trait]
+pub trait JobHandler: Send + Sync + 'static {
+    type Job: Job;
+
+    async fn run(&self, job: Self::Job, attempt: Attempt) -> JobResult;
+}
+
+/// [`JobHandler`] with its job type erased, so that handlers for different jobs can be stored
+/// together
+#[rocket::async_trait]
+trait ErasedJobHandler: Send + Sync {
+    async fn run(&self, payload: &str, attempt: Attempt) -> JobResult;
+}
+
+#[rocket::async_trait]
+impl<H: JobHandler> ErasedJobHandler for H {
+    async fn run(&self, payload: &str, attempt: Attempt) -> JobResult {
+        let job = serde_json::from_str(payload)?;
+
+        JobHandler::run(self, job, attempt).await
+    }
+}
+
+/// The handlers for all kinds of jobs this server knows how to run
+#[derive(Clone, Default)]
+pub struct JobQueue {
+    handlers: Arc<RwLock<HashMap<&'static str, Arc<dyn ErasedJobHandler>>>>,
+}
+
+impl JobQueue {
+    pub fn register<H: JobHandler>(&self, handler: H) {
+        self.handlers.write().unwrap().insert(H::Job::KIND, Arc::new(handler));
+    }
+
+    fn handler(&self, kind: &str) -> Option<Arc<dyn ErasedJobHandler>> {
+        self.handlers.read().unwrap().get(kind).cloned()
+    }
+}
+
+/// Rocket fairing that, once the server has launched, spawns the workers running jobs using the
+/// handlers registered with the managed [`JobQueue`].
+pub struct JobWorkers;
+
+#[rocket::async_trait]
+impl Fairing for JobWorkers {
+    fn info(&self) -> Info {
+        Info {
+            name: "Job Workers",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let Some(queue) = rocket.state::<JobQueue>() else {
+            return warn!("No job queue is managed, background jobs will not be run!");
+        };
+
+        for _ in 0..WORKER_COUNT {
+            let pool = PointercratePool::from(rocket.state::<PointercratePool>().unwrap().clone_inner());
+            let queue = queue.clone();
+
+            tokio::spawn(async move {
+                loop {
+                    match run_next(&queue, &pool).await {
+                        Ok(true) => (),
+                        Ok(false) => tokio::time::sleep(POLL_INTERVAL).await,
+                        Err(err) => {
+                            error!("Failed to run background job: {}", err);
+
+                            tokio::time::sleep(POLL_INTERVAL).await
+                        },
+                    }
+                }
+            });
+        }
+    }
+}
+
+/// Runs the next job in the queue, if there is any. Returns whether a job was run
+async fn run_next(queue: &JobQueue, pool: &PointercratePool) -> Result<bool> {
+    let Some(job) = QueuedJob::claim(&mut *pool.connection().await?).await? else {
+        return Ok(false);
+    };
+
+    let outcome = match queue.handler(&job.kind) {
+        Some(handler) => {
+            let payload = job.payload.clone();
+            let attempt = Attempt {
+                number: job.attempts,
+                max_attempts: job.max_attempts,
+            };
+
+            // Run the handler in its own task, so that a panicking handler only fails the job, not the worker
+            match tokio::spawn(async move { handler.run(&payload, attempt).await }).await {
+                Ok(outcome) => outcome.map_err(|err| err.to_string()),
+                Err(err) => Err(err.to_string()),
+            }
+        },
+        None => Err(format!("No handler is registered for jobs of kind '{}'", job.kind)),
+    };
+
+    let mut connection = pool.connection().await?;
+
+    match outcome {
+        Ok(()) => job.complete(&mut connection).await?,
+        Err(err) => {
+            warn!("Running {} failed: {}", job, err);
+
+            job.fail(err, &mut connection).await?
+        },
+    }
+
+    Ok(true)
+}
@@ -1,5 +1,7 @@
+pub mod announcement;
 pub mod error;
 pub mod etag;
+pub mod job;
 pub mod maintenance;
 pub mod pagination;
 pub mod query;
@@ -10,13 +10,13 @@ use sqlx::PgConnection;
 use crate::response::Response2;
 
 #[derive(Debug)]
-pub struct LinksBuilder {
-    endpoint: &'static str,
+pub struct LinksBuilder<'a> {
+    endpoint: &'a str,
     rels: BTreeMap<&'static str, PaginationParameters>,
 }
 
-impl LinksBuilder {
-    pub fn new(endpoint: &'static str) -> Self {
+impl<'a> LinksBuilder<'a> {
+    pub fn new(endpoint: &'a str) -> Self {
         LinksBuilder {
             endpoint,
             rels: BTreeMap::new(),
@@ -99,7 +99,7 @@ impl LinksBuilder {
 }
 
 pub async fn pagination_response<Q: PaginationQuery, P: Paginatable<Q>>(
-    endpoint: &'static str, query: Q, connection: &mut PgConnection,
+    endpoint: &str, query: Q, connection: &mut PgConnection,
 ) -> Result<Response2<Json<Vec<P>>>, CoreError> {
     let parameters = query.parameters();
 
@@ -1,7 +1,8 @@
-use crate::etag::Tagged;
+use crate::{announcement::ActiveAnnouncements, etag::Tagged};
 use maud::{html, DOCTYPE};
-use pointercrate_core::etag::Taggable;
+use pointercrate_core::{etag::Taggable, permission::PermissionsManager};
 use pointercrate_core_pages::{
+    announcement::announcement_banners,
     head::{Head, HeadLike},
     PageConfiguration, PageFragment,
 };
@@ -16,6 +17,15 @@ use std::{borrow::Cow, io::Cursor};
 
 pub struct Page(PageFragment);
 
+/// The permission bits of the user a request was made by, stored in request-local state.
+///
+/// Authentication request guards put this into [`Request::local_cache`] once they successfully
+/// authenticate a user, which allows the [`Page`] responder to only render the navigation bar
+/// items and footer columns that user is allowed to see. If no user was authenticated, pages are
+/// rendered as if the visitor had no permissions.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AuthenticatedPermissions(pub u16);
+
 impl Page {
     pub fn new(fragment: impl Into<PageFragment>) -> Self {
         Page(fragment.into())
@@ -34,6 +44,11 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for Page {
 
         let fragment = self.0;
 
+        let permissions_we_have = request.local_cache(AuthenticatedPermissions::default).0;
+        let no_permissions = PermissionsManager::new(vec![]);
+        let permissions = request.rocket().state::<PermissionsManager>().unwrap_or(&no_permissions);
+        let announcements = request.local_cache(ActiveAnnouncements::default);
+
         let rendered_fragment = html! {
             (DOCTYPE)
             html lang="en" prefix="og: http://opg.me/ns#" {
@@ -45,13 +60,14 @@ impl<'r, 'o: 'r> Responder<'r, 'o> for Page {
                     // target this element to get background image
                     div style={"width: 100%;height: 100%;position: fixed;top: 0;left: 0;background-size: cover;background-repeat: repeat-y;pointer-events: none; z-index:-1"} {}
 
-                    (page_config.nav_bar)
+                    (announcement_banners(&announcements.0))
+                    (page_config.nav_bar.render_for(permissions_we_have, permissions))
 
                     // enable flex for everything
                     div.grow {
                         (fragment.body)
                     }
-                    (page_config.footer)
+                    (page_config.footer.render_for(permissions_we_have, permissions))
                 }
             }
         }.0;
@@ -0,0 +1,23 @@
+use maud::{html, Markup};
+use pointercrate_core::announcement::{Announcement, Severity};
+
+/// Renders the given announcements as banners, one below the other
+pub fn announcement_banners(announcements: &[Announcement]) -> Markup {
+    html! {
+        @if !announcements.is_empty() {
+            div #announcements {
+                @for announcement in announcements {
+                    @let class = match announcement.severity {
+                        Severity::Info => "info-blue",
+                        Severity::Warning => "info-yellow",
+                        Severity::Critical => "info-red",
+                    };
+
+                    div class = {"announcement " (class)} data-announcement-id = (announcement.id) {
+                        (announcement.content)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,13 @@
 pub fn google_analytics_tag() -> Option<String> {
     std::env::var("ANALYTICS_TAG").ok()
 }
+
+/// The publicly reachable URL of this website (e.g. `https://pointercrate.com`), without a trailing slash
+///
+/// Used wherever absolute URLs are required, such as for `og:image` meta tags. If not set, relative URLs
+/// are used instead.
+pub fn site_url() -> String {
+    std::env::var("SITE_URL")
+        .map(|url| url.trim_end_matches('/').to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,103 @@
+use crate::PageFragment;
+use maud::{html, Markup, PreEscaped};
+use pointercrate_core::content::ContentPage;
+use pulldown_cmark::{html::push_html, Event, Options, Parser, Tag};
+
+impl From<ContentPage> for PageFragment {
+    fn from(page: ContentPage) -> Self {
+        let body = content_page_body(&page);
+
+        PageFragment::new(page.title, page.description).body(body)
+    }
+}
+
+fn content_page_body(page: &ContentPage) -> Markup {
+    html! {
+        div.m-center.flex.container {
+            main.left style = "flex-grow: 1" {
+                section.panel.fade.js-scroll-anim #content-page data-anim = "fade" data-slug = (page.slug) {
+                    h1.underlined.pad {
+                        (page.title)
+                    }
+                    (render_markdown(&page.content))
+                }
+            }
+        }
+    }
+}
+
+/// Renders the given Markdown to HTML
+///
+/// Raw HTML contained in the Markdown source is escaped instead of being passed through. Links and
+/// images pointing to anything but relative, `http(s)` or `mailto` URLs (e.g. `javascript:` URLs)
+/// lose their destination.
+pub fn render_markdown(markdown: &str) -> Markup {
+    let parser = Parser::new_ext(markdown, Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH).map(|event| match event {
+        Event::Html(html) | Event::InlineHtml(html) => Event::Text(html),
+        Event::Start(Tag::Link {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if !is_safe_url(&dest_url) => Event::Start(Tag::Link {
+            link_type,
+            dest_url: "#".into(),
+            title,
+            id,
+        }),
+        Event::Start(Tag::Image {
+            link_type,
+            dest_url,
+            title,
+            id,
+        }) if !is_safe_url(&dest_url) => Event::Start(Tag::Image {
+            link_type,
+            dest_url: "".into(),
+            title,
+            id,
+        }),
+        _ => event,
+    });
+
+    let mut rendered = String::new();
+    push_html(&mut rendered, parser);
+
+    PreEscaped(rendered)
+}
+
+/// Whether the given URL is relative, or uses a scheme that cannot run scripts
+fn is_safe_url(url: &str) -> bool {
+    let url = url.trim_start();
+
+    // Everything before the first ':' is the scheme, unless a path, query or fragment starts earlier
+    match url.find([':', '/', '?', '#']) {
+        Some(index) if url[index..].starts_with(':') => matches!(url[..index].to_ascii_lowercase().as_str(), "http" | "https" | "mailto"),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::render_markdown;
+
+    #[test]
+    fn test_raw_html_is_escaped() {
+        let rendered = render_markdown("Hello <script>alert(1)</script> **World**").into_string();
+
+        assert!(!rendered.contains("<script>"));
+        assert!(rendered.contains("<strong>World</strong>"));
+
+        let rendered = render_markdown(
+            "[x](javascript:alert(1)) [y]( JavaScript&colon;alert(1)) ![z](data:text/html,hi) [a](https://pointercrate.com) [b](/demonlist/) \
+             [c](mailto:a@b.c)",
+        )
+        .into_string();
+
+        assert!(!rendered.to_lowercase().contains("javascript"));
+        assert!(!rendered.contains("data:"));
+        assert!(rendered.contains(r##"<a href="#">x</a>"##));
+        assert!(rendered.contains(r#"href="https://pointercrate.com""#));
+        assert!(rendered.contains(r#"href="/demonlist/""#));
+        assert!(rendered.contains(r#"href="mailto:a@b.c""#));
+    }
+}
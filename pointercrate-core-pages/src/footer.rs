@@ -1,9 +1,10 @@
 use maud::{html, Markup, PreEscaped, Render};
+use pointercrate_core::permission::{Permission, PermissionsManager};
 
 pub struct Footer {
     copyright_notice: Markup,
     terms_of_use: Markup,
-    columns: Vec<FooterColumn>,
+    columns: Vec<(Option<Permission>, FooterColumn)>,
     twitter_links: Vec<Link>,
 }
 
@@ -18,7 +19,13 @@ impl Footer {
     }
 
     pub fn with_column(mut self, column: FooterColumn) -> Self {
-        self.columns.push(column);
+        self.columns.push((None, column));
+        self
+    }
+
+    /// Adds a column that is only displayed to users that have the given permission
+    pub fn with_restricted_column(mut self, permission: Permission, column: FooterColumn) -> Self {
+        self.columns.push((Some(permission), column));
         self
     }
 
@@ -79,8 +86,15 @@ impl Render for &FooterColumn {
     }
 }
 
-impl Render for Footer {
-    fn render(&self) -> Markup {
+impl Footer {
+    /// Renders this footer for a user with the given permission bits, omitting all columns that
+    /// require a permission the user does not have
+    pub fn render_for(&self, permissions_we_have: u16, permissions: &PermissionsManager) -> Markup {
+        let columns = self.columns.iter().filter_map(|(required, column)| match required {
+            Some(required) if permissions.require_permission(permissions_we_have, *required).is_err() => None,
+            _ => Some(column),
+        });
+
         html! {
             footer.center {
                 div.flex.no-stretch {
@@ -92,7 +106,7 @@ impl Render for Footer {
                     }
                 }
                 div.flex.no-stretch {
-                    @for column in &self.columns {
+                    @for column in columns {
                         (column)
                     }
                 }
@@ -5,7 +5,9 @@ use crate::{
 };
 use maud::{html, Markup, PreEscaped, Render, DOCTYPE};
 
+pub mod announcement;
 pub mod config;
+pub mod content;
 pub mod error;
 pub mod footer;
 pub mod head;
@@ -1,4 +1,5 @@
-use maud::{html, Markup, Render};
+use maud::{html, Markup};
+use pointercrate_core::permission::{Permission, PermissionsManager};
 
 pub struct TopLevelNavigationBarItem {
     item: NavigationBarItem,
@@ -8,51 +9,62 @@ pub struct TopLevelNavigationBarItem {
 impl TopLevelNavigationBarItem {
     pub fn new(link: &'static str, content: Markup) -> Self {
         TopLevelNavigationBarItem {
-            item: NavigationBarItem { link, content },
+            item: NavigationBarItem {
+                link,
+                content,
+                required_permission: None,
+            },
             sub_levels: vec![],
         }
     }
 
-    pub fn with_sub_item(mut self, link: &'static str, content: Markup) -> Self {
-        self.sub_levels.push(NavigationBarItem { link, content });
+    /// Only displays this item (and all its sub items) to users that have the given permission
+    pub fn requires(mut self, permission: Permission) -> Self {
+        self.item.required_permission = Some(permission);
         self
     }
-}
-
-struct NavigationBarItem {
-    content: Markup,
-    link: &'static str,
-}
-
-pub struct NavigationBar {
-    logo_path: &'static str,
-    items: Vec<TopLevelNavigationBarItem>,
-}
 
-impl NavigationBar {
-    pub fn new(logo_path: &'static str) -> Self {
-        NavigationBar { logo_path, items: vec![] }
+    pub fn with_sub_item(mut self, link: &'static str, content: Markup) -> Self {
+        self.sub_levels.push(NavigationBarItem {
+            link,
+            content,
+            required_permission: None,
+        });
+        self
     }
 
-    pub fn with_item(mut self, item: TopLevelNavigationBarItem) -> Self {
-        self.items.push(item);
+    /// Adds a sub item that is only displayed to users that have the given permission
+    pub fn with_restricted_sub_item(mut self, permission: Permission, link: &'static str, content: Markup) -> Self {
+        self.sub_levels.push(NavigationBarItem {
+            link,
+            content,
+            required_permission: Some(permission),
+        });
         self
     }
-}
 
-impl Render for &TopLevelNavigationBarItem {
-    fn render(&self) -> Markup {
+    fn render_for(&self, permissions_we_have: u16, permissions: &PermissionsManager) -> Markup {
+        if !self.item.visible_to(permissions_we_have, permissions) {
+            return html! {};
+        }
+
+        let sub_levels = self
+            .sub_levels
+            .iter()
+            .filter(|sub_item| sub_item.visible_to(permissions_we_have, permissions))
+            .collect::<Vec<_>>();
+
         html! {
             div.nav-group {
                 a.nav-item.hover.colorless href = (self.item.link) {
                     (self.item.content)
-                    @if !self.sub_levels.is_empty() {
+                    @if !sub_levels.is_empty() {
                         i.fas.fa-sort-down style = "height: 50%; padding-left: 5px" {}
                     }
                 }
-                @if !self.sub_levels.is_empty() {
+                @if !sub_levels.is_empty() {
                     ul.nav-hover-dropdown {
-                        @for sub_item in &self.sub_levels {
+                        @for sub_item in sub_levels {
                             li {
                                 a.dark-gray.hover href = (sub_item.link) { (sub_item.content)}
                             }
@@ -64,8 +76,42 @@ impl Render for &TopLevelNavigationBarItem {
     }
 }
 
-impl Render for NavigationBar {
-    fn render(&self) -> Markup {
+struct NavigationBarItem {
+    content: Markup,
+    link: &'static str,
+    required_permission: Option<Permission>,
+}
+
+impl NavigationBarItem {
+    fn visible_to(&self, permissions_we_have: u16, permissions: &PermissionsManager) -> bool {
+        match self.required_permission {
+            Some(required) => permissions.require_permission(permissions_we_have, required).is_ok(),
+            None => true,
+        }
+    }
+}
+
+pub struct NavigationBar {
+    logo_path: &'static str,
+    items: Vec<TopLevelNavigationBarItem>,
+}
+
+impl NavigationBar {
+    pub fn new(logo_path: &'static str) -> Self {
+        NavigationBar { logo_path, items: vec![] }
+    }
+
+    pub fn with_item(mut self, item: TopLevelNavigationBarItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Renders this navigation bar for a user with the given permission bits
+    ///
+    /// Items requiring a permission the user does not have (as determined by the given
+    /// [`PermissionsManager`]) are omitted. Pass `0` to render the navigation bar for an
+    /// unauthenticated visitor.
+    pub fn render_for(&self, permissions_we_have: u16, permissions: &PermissionsManager) -> Markup {
         html! {
             header {
                 nav.center.collapse.underlined {
@@ -75,7 +121,7 @@ impl Render for NavigationBar {
                         }
                     }
                     @for item in &self.items {
-                        (item)
+                        (item.render_for(permissions_we_have, permissions))
                     }
                     div.nav-item.collapse-button {
                         div.hamburger.hover {
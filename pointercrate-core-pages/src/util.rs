@@ -1,25 +1,123 @@
 use maud::{html, Markup};
+use pointercrate_core::pagination::{Paginatable, PaginationParameters, PaginationQuery};
+use sqlx::PgConnection;
 use std::fmt::Display;
 
 // FIXME: these should probably be turned into proper structs as well at some point
 
+/// A page of objects rendered server-side, to be displayed in a [`paginator`] to clients that do not
+/// (yet) run JavaScript, such as crawlers.
+///
+/// Once a paginator's JavaScript initializes, it replaces the prerendered entries with the ones it
+/// retrieves from the API, and takes over handling of the "Previous" and "Next" links.
+///
+/// Only publicly reachable pages prerender their paginators. The paginators on the account page
+/// stay empty, as they are only visible to logged in users after switching to their tab, which
+/// requires JavaScript anyway.
+pub struct PrerenderedPage {
+    entries: Vec<Markup>,
+
+    /// Link (relative to the current page) to the previous page, if one exists
+    previous: Option<String>,
+
+    /// Link (relative to the current page) to the next page, if one exists
+    next: Option<String>,
+}
+
+impl PrerenderedPage {
+    /// Retrieves the page described by the given query from the database and renders each object on it
+    /// using the given function.
+    ///
+    /// The generated navigation links preserve all filters of the given query, only updating the
+    /// `before`/`after` parameters, meaning the page on which the paginator is displayed needs to
+    /// accept the query as its own query string.
+    pub async fn render<Q, P, F>(query: &Q, connection: &mut PgConnection, render_entry: F) -> Result<PrerenderedPage, sqlx::Error>
+    where
+        Q: PaginationQuery,
+        P: Paginatable<Q>,
+        F: Fn(&P) -> Markup,
+    {
+        let limit = query.parameters().limit;
+        let (objects, context) = P::page(query, connection).await?;
+
+        let link = |params: PaginationParameters| {
+            serde_urlencoded::to_string(query.with_parameters(params))
+                .ok()
+                .map(|query_string| format!("?{}", query_string))
+        };
+
+        let previous = match objects.first() {
+            Some(first) if context.has_previous() => link(PaginationParameters {
+                before: Some(first.pagination_id()),
+                after: None,
+                limit,
+            }),
+            _ => None,
+        };
+
+        let next = match objects.last() {
+            Some(last) if context.has_next() => link(PaginationParameters {
+                before: None,
+                after: Some(last.pagination_id()),
+                limit,
+            }),
+            _ => None,
+        };
+
+        Ok(PrerenderedPage {
+            entries: objects.iter().map(render_entry).collect(),
+            previous,
+            next,
+        })
+    }
+
+    /// A page holding all of the given entries, for lists that are not paginated
+    pub fn complete(entries: Vec<Markup>) -> PrerenderedPage {
+        PrerenderedPage {
+            entries,
+            previous: None,
+            next: None,
+        }
+    }
+
+    /// Adds the given parameter to the query strings of the navigation links, for pages that accept
+    /// parameters in addition to the paginated query
+    pub fn with_parameter(mut self, name: &str, value: impl Display) -> PrerenderedPage {
+        let parameter = serde_urlencoded::to_string([(name, value.to_string())]).unwrap_or_default();
+
+        for link in [&mut self.previous, &mut self.next].into_iter().flatten() {
+            link.push('&');
+            link.push_str(&parameter);
+        }
+
+        self
+    }
+}
+
 pub fn paginator(id: &str, endpoint: &str) -> Markup {
+    prerendered_paginator(id, endpoint, None)
+}
+
+/// A [`paginator`] whose list is initially filled with the given server-side rendered page
+pub fn prerendered_paginator(id: &str, endpoint: &str, page: Option<&PrerenderedPage>) -> Markup {
     html! {
         div.flex.col.paginator #(id) data-endpoint = (endpoint) {
             p.info-red.output {}
             p.info-green.output {}
             div style="min-height: 450px; position:relative; flex-grow:1" {
-                ul.selection-list style = "position: absolute; top: 0px; bottom:0px; left: 0px; right:0px" {}
-            }
-            div.flex.no-stretch style = "font-variant: small-caps; font-weight: bolder; justify-content: space-around"{
-                div.button.small.prev { "Previous" }
-                div.button.small.next { "Next" }
+                (selection_list(page))
             }
+            (navigation_buttons(page))
         }
     }
 }
 
 pub fn filtered_paginator(id: &str, endpoint: &str) -> Markup {
+    prerendered_filtered_paginator(id, endpoint, None)
+}
+
+/// A [`filtered_paginator`] whose list is initially filled with the given server-side rendered page
+pub fn prerendered_filtered_paginator(id: &str, endpoint: &str, page: Option<&PrerenderedPage>) -> Markup {
     html! {
         div.flex.col.paginator #(id) data-endpoint=(endpoint) {
             div.search.seperated.no-stretch {
@@ -27,11 +125,46 @@ pub fn filtered_paginator(id: &str, endpoint: &str) -> Markup {
             }
             p.info-red.output style = "margin: 5px 0px"{}
             div style="min-height: 400px; position:relative; flex-grow:1" {
-                ul.selection-list style = "position: absolute; top: 0px; bottom:0px; left: 0px; right:0px" {}
+                (selection_list(page))
+            }
+            (navigation_buttons(page))
+        }
+    }
+}
+
+fn selection_list(page: Option<&PrerenderedPage>) -> Markup {
+    html! {
+        ul.selection-list style = "position: absolute; top: 0px; bottom:0px; left: 0px; right:0px" {
+            @if let Some(page) = page {
+                @for entry in &page.entries {
+                    (entry)
+                }
+            }
+        }
+    }
+}
+
+fn navigation_buttons(page: Option<&PrerenderedPage>) -> Markup {
+    let previous = page.and_then(|page| page.previous.as_deref());
+    let next = page.and_then(|page| page.next.as_deref());
+
+    html! {
+        div.flex.no-stretch style = "font-variant: small-caps; font-weight: bolder; justify-content: space-around"{
+            @match previous {
+                Some(href) => {
+                    a.button.small.prev href = (href) rel = "prev" { "Previous" }
+                },
+                None => {
+                    div.button.small.prev { "Previous" }
+                },
             }
-            div.flex.no-stretch style = "font-variant: small-caps; font-weight: bolder; justify-content: space-around"{
-                div.button.small.prev { "Previous" }
-                div.button.small.next { "Next" }
+            @match next {
+                Some(href) => {
+                    a.button.small.next href = (href) rel = "next" { "Next" }
+                },
+                None => {
+                    div.button.small.next { "Next" }
+                },
             }
         }
     }
@@ -538,7 +538,10 @@ export class Paginator extends Output {
       .catch(displayError(this));
   }
 
-  onPreviousClick() {
+  onPreviousClick(event) {
+    // The buttons might be links to server-side rendered pages (for clients without JavaScript)
+    event.preventDefault();
+
     if (this.links.prev) {
       get(this.links.prev)
         .then(this.handleResponse.bind(this))
@@ -546,7 +549,9 @@ export class Paginator extends Output {
     }
   }
 
-  onNextClick() {
+  onNextClick(event) {
+    event.preventDefault();
+
     if (this.links.next) {
       get(this.links.next)
         .then(this.handleResponse.bind(this))
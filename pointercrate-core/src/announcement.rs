@@ -0,0 +1,339 @@
+//! Module for site-wide announcements
+//!
+//! Announcements are short messages (e.g. about guideline changes or upcoming list events) that are
+//! displayed in a banner above the navigation bar of every page they target, for as long as they
+//! are active. They are managed by administrators via the API.
+
+use crate::{
+    error::{CoreError, Result},
+    etag::Taggable,
+    util::{non_nullable, nullable},
+};
+use chrono::NaiveDateTime;
+use log::info;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sqlx::PgConnection;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash, Default)]
+pub enum Severity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+impl Severity {
+    pub fn to_sql(self) -> String {
+        match self {
+            Severity::Info => "INFO",
+            Severity::Warning => "WARNING",
+            Severity::Critical => "CRITICAL",
+        }
+        .to_owned()
+    }
+
+    fn from_sql(sql: &str) -> Self {
+        match sql {
+            "INFO" => Severity::Info,
+            "WARNING" => Severity::Warning,
+            "CRITICAL" => Severity::Critical,
+            _ => panic!("invalid announcement severity: {}", sql),
+        }
+    }
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Critical => write!(f, "critical"),
+        }
+    }
+}
+
+impl Serialize for Severity {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Severity {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let string = String::deserialize(deserializer)?.to_lowercase();
+
+        match &string[..] {
+            "info" => Ok(Severity::Info),
+            "warning" => Ok(Severity::Warning),
+            "critical" => Ok(Severity::Critical),
+            _ => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&string),
+                &"'info', 'warning' or 'critical'",
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Hash, PartialEq, Eq, Clone)]
+pub struct Announcement {
+    pub id: i32,
+    pub content: String,
+    pub severity: Severity,
+
+    /// The point in time (UTC) from which on this announcement is displayed. `None` means it is
+    /// displayed immediately
+    pub starts: Option<NaiveDateTime>,
+
+    /// The point in time (UTC) at which this announcement expires. `None` means it is displayed
+    /// until it is manually expired or deleted
+    pub ends: Option<NaiveDateTime>,
+
+    /// Path prefixes (e.g. `/demonlist/`) of the pages this announcement is displayed on.
+    ///
+    /// If empty, the announcement is displayed on every page
+    pub target_pages: Vec<String>,
+}
+
+impl Taggable for Announcement {}
+
+#[derive(Debug, Deserialize)]
+pub struct PostAnnouncement {
+    content: String,
+
+    #[serde(default)]
+    severity: Severity,
+
+    #[serde(default)]
+    starts: Option<NaiveDateTime>,
+
+    #[serde(default)]
+    ends: Option<NaiveDateTime>,
+
+    #[serde(default)]
+    target_pages: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PatchAnnouncement {
+    #[serde(default, deserialize_with = "non_nullable")]
+    content: Option<String>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    severity: Option<Severity>,
+
+    #[serde(default, deserialize_with = "nullable")]
+    starts: Option<Option<NaiveDateTime>>,
+
+    #[serde(default, deserialize_with = "nullable")]
+    ends: Option<Option<NaiveDateTime>>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    target_pages: Option<Vec<String>>,
+}
+
+// Required until https://github.com/launchbadge/sqlx/pull/108 is merged
+struct FetchedAnnouncement {
+    id: i32,
+    content: String,
+    severity: String,
+    starts: Option<NaiveDateTime>,
+    ends: Option<NaiveDateTime>,
+    target_pages: Vec<String>,
+}
+
+impl From<FetchedAnnouncement> for Announcement {
+    fn from(fetched: FetchedAnnouncement) -> Self {
+        Announcement {
+            id: fetched.id,
+            content: fetched.content,
+            severity: Severity::from_sql(&fetched.severity),
+            starts: fetched.starts,
+            ends: fetched.ends,
+            target_pages: fetched.target_pages,
+        }
+    }
+}
+
+impl Announcement {
+    /// Whether this announcement should be displayed on the page with the given path
+    pub fn targets(&self, path: &str) -> bool {
+        self.target_pages.is_empty() || self.target_pages.iter().any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    /// Whether this announcement is displayed at the given point in time (UTC)
+    pub fn is_active_at(&self, time: NaiveDateTime) -> bool {
+        self.starts.map(|starts| starts <= time).unwrap_or(true) && self.ends.map(|ends| ends > time).unwrap_or(true)
+    }
+
+    fn validate(content: &str, starts: Option<NaiveDateTime>, ends: Option<NaiveDateTime>) -> Result<()> {
+        if content.trim().is_empty() {
+            return Err(CoreError::AnnouncementEmpty);
+        }
+
+        if let (Some(starts), Some(ends)) = (starts, ends) {
+            if ends <= starts {
+                return Err(CoreError::AnnouncementEndsBeforeStart);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<Announcement> {
+        sqlx::query_as!(
+            FetchedAnnouncement,
+            r#"SELECT id, content, severity::TEXT AS "severity!", starts, ends, target_pages FROM announcements WHERE id = $1"#,
+            id
+        )
+        .fetch_one(connection)
+        .await
+        .map(Into::into)
+        .map_err(|err| match err {
+            sqlx::Error::RowNotFound => CoreError::AnnouncementNotFound { id },
+            _ => err.into(),
+        })
+    }
+
+    /// Gets all announcements, including expired ones and those not yet started, newest first
+    pub async fn all(connection: &mut PgConnection) -> Result<Vec<Announcement>> {
+        Ok(sqlx::query_as!(
+            FetchedAnnouncement,
+            r#"SELECT id, content, severity::TEXT AS "severity!", starts, ends, target_pages FROM announcements ORDER BY id DESC"#
+        )
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    /// Gets all announcements that have not expired at the given point in time (UTC), including
+    /// those not yet started, most severe first
+    pub async fn unexpired(time: NaiveDateTime, connection: &mut PgConnection) -> Result<Vec<Announcement>> {
+        Ok(sqlx::query_as!(
+            FetchedAnnouncement,
+            r#"SELECT id, content, severity::TEXT AS "severity!", starts, ends, target_pages FROM announcements
+               WHERE ends IS NULL OR ends > $1
+               ORDER BY severity DESC, id DESC"#,
+            time
+        )
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+    }
+
+    pub async fn create_from(data: PostAnnouncement, connection: &mut PgConnection) -> Result<Announcement> {
+        info!("Creating new announcement from {:?}", data);
+
+        Announcement::validate(&data.content, data.starts, data.ends)?;
+
+        let id = sqlx::query!(
+            "INSERT INTO announcements (content, severity, starts, ends, target_pages) VALUES ($1, cast($2::text as announcement_severity), \
+             $3, $4, $5) RETURNING id",
+            data.content,
+            data.severity.to_sql(),
+            data.starts,
+            data.ends,
+            &data.target_pages
+        )
+        .fetch_one(connection)
+        .await?
+        .id;
+
+        Ok(Announcement {
+            id,
+            content: data.content,
+            severity: data.severity,
+            starts: data.starts,
+            ends: data.ends,
+            target_pages: data.target_pages,
+        })
+    }
+
+    pub async fn apply_patch(mut self, patch: PatchAnnouncement, connection: &mut PgConnection) -> Result<Self> {
+        info!("Patching announcement {} with {:?}", self.id, patch);
+
+        if let Some(content) = patch.content {
+            self.content = content;
+        }
+
+        if let Some(severity) = patch.severity {
+            self.severity = severity;
+        }
+
+        if let Some(starts) = patch.starts {
+            self.starts = starts;
+        }
+
+        if let Some(ends) = patch.ends {
+            self.ends = ends;
+        }
+
+        if let Some(target_pages) = patch.target_pages {
+            self.target_pages = target_pages;
+        }
+
+        Announcement::validate(&self.content, self.starts, self.ends)?;
+
+        sqlx::query!(
+            "UPDATE announcements SET content = $1, severity = cast($2::text as announcement_severity), starts = $3, ends = $4, \
+             target_pages = $5 WHERE id = $6",
+            self.content,
+            self.severity.to_sql(),
+            self.starts,
+            self.ends,
+            &self.target_pages,
+            self.id
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(self)
+    }
+
+    /// Immediately stops displaying this announcement by setting its end time to the given point in time
+    ///
+    /// Announcements that already expired before `now` are left untouched. For announcements that
+    /// have not started yet, the start time is cleared.
+    pub async fn expire(&mut self, now: NaiveDateTime, connection: &mut PgConnection) -> Result<()> {
+        if self.ends.map(|ends| ends <= now).unwrap_or(false) {
+            return Ok(());
+        }
+
+        if self.starts.map(|starts| starts >= now).unwrap_or(false) {
+            self.starts = None;
+        }
+
+        self.ends = Some(now);
+
+        sqlx::query!(
+            "UPDATE announcements SET starts = $1, ends = $2 WHERE id = $3",
+            self.starts,
+            self.ends,
+            self.id
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        info!("Deleting announcement {}", self.id);
+
+        sqlx::query!("DELETE FROM announcements WHERE id = $1", self.id)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
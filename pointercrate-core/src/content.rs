@@ -0,0 +1,258 @@
+//! Module for admin-editable content pages
+//!
+//! Content pages are Markdown documents stored in the database (e.g. a list's guidelines, an FAQ
+//! or a news page). They are identified by a URL slug and keep a full revision history: every edit
+//! creates a new revision, and the most recent revision is what gets displayed.
+
+use crate::{
+    error::{CoreError, Result},
+    etag::Taggable,
+    permission::Permission,
+    util::{is_valid_slug, non_nullable},
+};
+use chrono::NaiveDateTime;
+use log::info;
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+
+/// Configuration of the content pages subsystem, to be managed by rocket
+pub struct ContentPagesConfig {
+    /// The permission a user needs to have to create, edit and delete content pages
+    pub edit_permission: Permission,
+}
+
+impl ContentPagesConfig {
+    pub fn new(edit_permission: Permission) -> Self {
+        ContentPagesConfig { edit_permission }
+    }
+}
+
+/// The current (most recent) revision of a content page
+#[derive(Debug, Serialize, Hash, PartialEq, Eq)]
+pub struct ContentPage {
+    /// The URL slug under which this page is displayed, e.g. `guidelines`
+    pub slug: String,
+    pub title: String,
+
+    /// Short description of the page, used for the `description` meta tags
+    pub description: String,
+
+    /// The content of this page, in Markdown
+    pub content: String,
+
+    /// The id of the revision this object represents
+    pub revision: i32,
+
+    /// The point in time (UTC) of the last edit to this page
+    pub last_edited: NaiveDateTime,
+}
+
+impl Taggable for ContentPage {}
+
+#[derive(Debug, Serialize, Hash, PartialEq, Eq)]
+pub struct ContentPageRevision {
+    pub id: i32,
+    pub title: String,
+    pub description: String,
+    pub content: String,
+
+    /// The id of the user that made this edit. `None` if their account has since been deleted
+    pub editor: Option<i32>,
+    pub time: NaiveDateTime,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PostContentPage {
+    slug: String,
+    title: String,
+
+    #[serde(default)]
+    description: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PatchContentPage {
+    #[serde(default, deserialize_with = "non_nullable")]
+    title: Option<String>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    description: Option<String>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    content: Option<String>,
+}
+
+impl ContentPage {
+    fn validate_slug(slug: &str) -> Result<()> {
+        if !is_valid_slug(slug) {
+            return Err(CoreError::InvalidSlug);
+        }
+
+        Ok(())
+    }
+
+    fn validate_title(title: &str) -> Result<()> {
+        if title.trim().is_empty() {
+            return Err(CoreError::ContentPageTitleEmpty);
+        }
+
+        Ok(())
+    }
+
+    pub async fn by_slug(slug: &str, connection: &mut PgConnection) -> Result<ContentPage> {
+        sqlx::query_as!(
+            ContentPage,
+            r#"SELECT DISTINCT ON (slug) slug, title, description, content, id AS revision, time AS last_edited FROM content_page_revisions
+               WHERE slug = $1 ORDER BY slug, id DESC"#,
+            slug
+        )
+        .fetch_one(connection)
+        .await
+        .map_err(|err| match err {
+            sqlx::Error::RowNotFound => CoreError::ContentPageNotFound { slug: slug.to_string() },
+            _ => err.into(),
+        })
+    }
+
+    /// Gets the current revisions of all content pages, ordered by slug
+    pub async fn all(connection: &mut PgConnection) -> Result<Vec<ContentPage>> {
+        Ok(sqlx::query_as!(
+            ContentPage,
+            r#"SELECT DISTINCT ON (slug) slug, title, description, content, id AS revision, time AS last_edited FROM content_page_revisions
+               ORDER BY slug, id DESC"#
+        )
+        .fetch_all(connection)
+        .await?)
+    }
+
+    /// Gets all revisions of this page, newest first
+    pub async fn revisions(&self, connection: &mut PgConnection) -> Result<Vec<ContentPageRevision>> {
+        Ok(sqlx::query_as!(
+            ContentPageRevision,
+            "SELECT id, title, description, content, editor, time FROM content_page_revisions WHERE slug = $1 ORDER BY id DESC",
+            self.slug
+        )
+        .fetch_all(connection)
+        .await?)
+    }
+
+    pub async fn revision(&self, id: i32, connection: &mut PgConnection) -> Result<ContentPageRevision> {
+        sqlx::query_as!(
+            ContentPageRevision,
+            "SELECT id, title, description, content, editor, time FROM content_page_revisions WHERE slug = $1 AND id = $2",
+            self.slug,
+            id
+        )
+        .fetch_one(connection)
+        .await
+        .map_err(|err| match err {
+            sqlx::Error::RowNotFound => CoreError::ContentPageRevisionNotFound {
+                slug: self.slug.clone(),
+                id,
+            },
+            _ => err.into(),
+        })
+    }
+
+    /// Creates a new content page, with the given data as its first revision
+    pub async fn create_from(data: PostContentPage, editor: i32, connection: &mut PgConnection) -> Result<ContentPage> {
+        info!("Creating new content page from {:?}", data);
+
+        ContentPage::validate_slug(&data.slug)?;
+        ContentPage::validate_title(&data.title)?;
+
+        sqlx::query!("INSERT INTO content_pages (slug) VALUES ($1)", data.slug)
+            .execute(&mut *connection)
+            .await
+            .map_err(|err| match err {
+                sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => {
+                    CoreError::ContentPageExists { slug: data.slug.clone() }
+                },
+                _ => err.into(),
+            })?;
+
+        let mut page = ContentPage {
+            slug: data.slug,
+            title: data.title,
+            description: data.description,
+            content: data.content,
+            revision: 0,
+            last_edited: NaiveDateTime::default(),
+        };
+
+        page.save_revision(editor, connection).await?;
+
+        Ok(page)
+    }
+
+    /// Edits this content page, creating a new revision
+    pub async fn apply_patch(mut self, patch: PatchContentPage, editor: i32, connection: &mut PgConnection) -> Result<Self> {
+        info!("Patching content page {} with {:?}", self.slug, patch);
+
+        if let Some(title) = patch.title {
+            ContentPage::validate_title(&title)?;
+
+            self.title = title;
+        }
+
+        if let Some(description) = patch.description {
+            self.description = description;
+        }
+
+        if let Some(content) = patch.content {
+            self.content = content;
+        }
+
+        self.save_revision(editor, connection).await?;
+
+        Ok(self)
+    }
+
+    /// Reverts this content page to the state of one of its previous revisions
+    ///
+    /// This does not discard any revisions, it instead creates a new one, which is a copy of the
+    /// given revision.
+    pub async fn restore(mut self, revision: i32, editor: i32, connection: &mut PgConnection) -> Result<Self> {
+        info!("Restoring content page {} to revision {}", self.slug, revision);
+
+        let revision = self.revision(revision, &mut *connection).await?;
+
+        self.title = revision.title;
+        self.description = revision.description;
+        self.content = revision.content;
+
+        self.save_revision(editor, connection).await?;
+
+        Ok(self)
+    }
+
+    async fn save_revision(&mut self, editor: i32, connection: &mut PgConnection) -> Result<()> {
+        let row = sqlx::query!(
+            "INSERT INTO content_page_revisions (slug, title, description, content, editor) VALUES ($1, $2, $3, $4, $5) RETURNING id, time",
+            self.slug,
+            self.title,
+            self.description,
+            self.content,
+            editor
+        )
+        .fetch_one(connection)
+        .await?;
+
+        self.revision = row.id;
+        self.last_edited = row.time;
+
+        Ok(())
+    }
+
+    /// Deletes this content page, including all its revisions
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        info!("Deleting content page {}", self.slug);
+
+        sqlx::query!("DELETE FROM content_pages WHERE slug = $1", self.slug)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
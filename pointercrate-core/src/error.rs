@@ -70,6 +70,45 @@ pub enum CoreError {
     )]
     NotFound,
 
+    /// `404 NOT FOUND` error returned if an announcement with a given ID does not exist
+    ///
+    /// Error Code `40401`
+    #[display(fmt = "No announcement with id {} found", id)]
+    AnnouncementNotFound {
+        /// The ID that was requested
+        id: i32,
+    },
+
+    /// `404 NOT FOUND` error returned if a content page with a given slug does not exist
+    ///
+    /// Error Code `40401`
+    #[display(fmt = "No content page '{}' found", slug)]
+    ContentPageNotFound {
+        /// The slug that was requested
+        slug: String,
+    },
+
+    /// `404 NOT FOUND` error returned if a content page does not have a revision with the given ID
+    ///
+    /// Error Code `40401`
+    #[display(fmt = "Content page '{}' has no revision with id {}", slug, id)]
+    ContentPageRevisionNotFound {
+        /// The slug of the content page
+        slug: String,
+
+        /// The revision ID that was requested
+        id: i32,
+    },
+
+    /// `404 NOT FOUND` error returned if no dead job with a given ID exists
+    ///
+    /// Error Code `40401`
+    #[display(fmt = "No dead job with id {} found", id)]
+    JobNotFound {
+        /// The ID that was requested
+        id: i32,
+    },
+
     /// `405 METHOD NOT ALLOWED`
     ///
     /// Error Code `40500`
@@ -87,6 +126,16 @@ pub enum CoreError {
     )]
     Conflict,
 
+    /// `409 CONFLICT` variant returned if a content page is created with a slug that is already in
+    /// use
+    ///
+    /// Error Code `40901`
+    #[display(fmt = "A content page with slug '{}' already exists", slug)]
+    ContentPageExists {
+        /// The slug that is already taken
+        slug: String,
+    },
+
     /// `411 LENGTH REQUIRED`
     ///
     /// Error Code `41100`
@@ -171,6 +220,34 @@ pub enum CoreError {
     #[display(fmt = "Your request contains mutually exclusive fields. Please restrict yourself to one of them")]
     MutuallyExclusive,
 
+    /// `422 UNPROCESSABLE ENTITY` variant returned if an announcement is created with (or patched
+    /// to have) empty content
+    ///
+    /// Error Code `42234`
+    #[display(fmt = "Announcements mustn't be empty!")]
+    AnnouncementEmpty,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if an announcement's end time is not after its
+    /// start time
+    ///
+    /// Error Code `42235`
+    #[display(fmt = "An announcement needs to end after it starts")]
+    AnnouncementEndsBeforeStart,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a content page slug contains anything other
+    /// than lowercase letters, digits and single dashes
+    ///
+    /// Error Code `42236`
+    #[display(fmt = "Invalid slug. Slugs may only consist of lowercase letters and digits, separated by single dashes")]
+    InvalidSlug,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a content page is created with (or patched to
+    /// have) an empty title
+    ///
+    /// Error Code `42237`
+    #[display(fmt = "Content pages must have a title")]
+    ContentPageTitleEmpty,
+
     /// `428 PRECONDITION REQUIRED`
     ///
     /// Error Code `42800`
@@ -245,8 +322,13 @@ impl PointercrateError for CoreError {
             CoreError::Forbidden => 40300,
             CoreError::MissingPermissions { .. } => 40301,
             CoreError::NotFound => 40400,
+            CoreError::AnnouncementNotFound { .. } => 40401,
+            CoreError::ContentPageNotFound { .. } => 40401,
+            CoreError::ContentPageRevisionNotFound { .. } => 40401,
+            CoreError::JobNotFound { .. } => 40401,
             CoreError::MethodNotAllowed => 40500,
             CoreError::Conflict => 40900,
+            CoreError::ContentPageExists { .. } => 40901,
             CoreError::LengthRequired => 41200,
             CoreError::PreconditionFailed => 41200,
             CoreError::PayloadTooLarge => 41300,
@@ -258,6 +340,10 @@ impl PointercrateError for CoreError {
             CoreError::InvalidUrlFormat { .. } => 42225,
             CoreError::AfterSmallerBefore => 42227,
             CoreError::MutuallyExclusive => 42229,
+            CoreError::AnnouncementEmpty => 42234,
+            CoreError::AnnouncementEndsBeforeStart => 42235,
+            CoreError::InvalidSlug => 42236,
+            CoreError::ContentPageTitleEmpty => 42237,
             CoreError::PreconditionRequired => 42800,
             CoreError::Ratelimited { .. } => 42900,
             CoreError::InternalServerError { .. } => 50000,
@@ -0,0 +1,232 @@
+//! Module for the background job queue
+//!
+//! Work that should not hold up a request (checking videos, notifying webhooks, fetching level data
+//! from the Geometry Dash servers, ...) is stored as a job in the database, ideally inside the
+//! transaction of the request that caused it, so that it is only run if the request succeeded. Jobs
+//! are picked up by background workers (see `pointercrate_core_api::job`), and failed jobs are
+//! retried with exponential backoff. Jobs that fail on every attempt are moved to a dead letter
+//! table, where administrators can inspect, retry or discard them.
+//!
+//! Since jobs live in the database, they survive server restarts. A job whose worker died while
+//! running it becomes available again once its lease expires.
+
+use crate::{
+    error::{CoreError, Result},
+    etag::Taggable,
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use derive_more::Display;
+use log::{info, warn};
+use serde::Serialize;
+use sqlx::PgConnection;
+
+/// How long a worker may run a job before it is considered dead and the job is given to another
+/// worker
+const LEASE: Duration = Duration::minutes(10);
+
+/// How long to wait before retrying a job after its first failed attempt. The delay doubles with
+/// every further failure
+const INITIAL_RETRY_DELAY: i64 = 10;
+
+/// A job waiting to be run (or currently running)
+#[derive(Debug, Serialize, Hash, Display, PartialEq, Eq)]
+#[display(fmt = "{} job {}", kind, id)]
+pub struct QueuedJob {
+    pub id: i32,
+
+    /// Identifies the handler that runs this job
+    pub kind: String,
+
+    /// The JSON encoded job data
+    pub payload: String,
+
+    /// How often running this job has been attempted, including the current attempt if it is
+    /// running
+    pub attempts: i32,
+
+    pub max_attempts: i32,
+
+    /// The (UTC) time from which on this job can be run
+    pub run_at: NaiveDateTime,
+
+    /// Set while a worker is running this job
+    pub locked_until: Option<NaiveDateTime>,
+
+    /// Why the previous attempt at running this job failed
+    pub last_error: Option<String>,
+
+    pub created_at: NaiveDateTime,
+}
+
+/// A job that failed on every attempt
+#[derive(Debug, Serialize, Hash, Display, PartialEq, Eq)]
+#[display(fmt = "dead {} job {}", kind, id)]
+pub struct DeadJob {
+    pub id: i32,
+    pub kind: String,
+    pub payload: String,
+    pub attempts: i32,
+
+    /// Why the final attempt at running this job failed
+    pub last_error: String,
+    pub created_at: NaiveDateTime,
+    pub failed_at: NaiveDateTime,
+}
+
+impl Taggable for DeadJob {}
+
+/// Adds a job of the given kind to the queue, returning its id. It is run as soon as a worker is
+/// free, but only once the transaction `connection` belongs to (if any) is committed.
+pub async fn enqueue(kind: &str, payload: String, max_attempts: i32, connection: &mut PgConnection) -> Result<i32> {
+    Ok(sqlx::query!(
+        "INSERT INTO jobs (kind, payload, max_attempts) VALUES ($1, $2, $3) RETURNING id",
+        kind,
+        payload,
+        max_attempts
+    )
+    .fetch_one(connection)
+    .await?
+    .id)
+}
+
+impl QueuedJob {
+    /// Gets all jobs in the queue, in the order they will be run
+    pub async fn all(connection: &mut PgConnection) -> Result<Vec<QueuedJob>> {
+        Ok(sqlx::query_as!(
+            QueuedJob,
+            "SELECT id, kind, payload, attempts, max_attempts, run_at, locked_until, last_error, created_at FROM jobs ORDER BY run_at, id"
+        )
+        .fetch_all(connection)
+        .await?)
+    }
+
+    /// Takes the next job that is due and not currently being run by another worker out of the
+    /// queue for the duration of a lease. Returns `None` if there is no such job.
+    ///
+    /// Should not be called inside a transaction, as other workers need to see that the job is
+    /// taken.
+    pub async fn claim(connection: &mut PgConnection) -> Result<Option<QueuedJob>> {
+        loop {
+            let now = Utc::now().naive_utc();
+
+            let job = sqlx::query_as!(
+                QueuedJob,
+                "UPDATE jobs SET attempts = attempts + 1, locked_until = $1 WHERE id = (SELECT id FROM jobs WHERE run_at <= $2 AND \
+                 (locked_until IS NULL OR locked_until <= $2) ORDER BY run_at, id LIMIT 1 FOR UPDATE SKIP LOCKED) RETURNING id, kind, \
+                 payload, attempts, max_attempts, run_at, locked_until, last_error, created_at",
+                now + LEASE,
+                now
+            )
+            .fetch_optional(&mut *connection)
+            .await?;
+
+            match job {
+                // The worker running the final attempt died
+                Some(job) if job.attempts > job.max_attempts => {
+                    job.fail(
+                        "The worker running this job stopped before finishing it".to_string(),
+                        &mut *connection,
+                    )
+                    .await?
+                },
+                job => return Ok(job),
+            }
+        }
+    }
+
+    /// Removes this job from the queue after it ran successfully
+    pub async fn complete(self, connection: &mut PgConnection) -> Result<()> {
+        sqlx::query!("DELETE FROM jobs WHERE id = $1", self.id).execute(connection).await?;
+
+        Ok(())
+    }
+
+    /// Schedules this job to be retried after a failed attempt, or moves it to the dead letter
+    /// table if this was its final attempt
+    pub async fn fail(self, error: String, connection: &mut PgConnection) -> Result<()> {
+        if self.attempts < self.max_attempts {
+            let delay = INITIAL_RETRY_DELAY << (self.attempts - 1).clamp(0, 20);
+
+            sqlx::query!(
+                "UPDATE jobs SET run_at = $1, locked_until = NULL, last_error = $2 WHERE id = $3",
+                Utc::now().naive_utc() + Duration::seconds(delay),
+                error,
+                self.id
+            )
+            .execute(connection)
+            .await?;
+
+            return Ok(());
+        }
+
+        warn!("Giving up on {} after {} attempts: {}", self, self.attempts, error);
+
+        // A single statement, so that the job can never end up in both tables
+        sqlx::query!(
+            "WITH moved AS (DELETE FROM jobs WHERE id = $1 RETURNING id, kind, payload, LEAST(attempts, max_attempts) AS attempts, \
+             created_at) INSERT INTO dead_jobs (id, kind, payload, attempts, last_error, created_at) SELECT id, kind, payload, attempts, \
+             $2, created_at FROM moved",
+            self.id,
+            error
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+}
+
+impl DeadJob {
+    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<DeadJob> {
+        sqlx::query_as!(
+            DeadJob,
+            "SELECT id, kind, payload, attempts, last_error, created_at, failed_at FROM dead_jobs WHERE id = $1",
+            id
+        )
+        .fetch_one(connection)
+        .await
+        .map_err(|err| match err {
+            sqlx::Error::RowNotFound => CoreError::JobNotFound { id },
+            _ => err.into(),
+        })
+    }
+
+    /// Gets all dead jobs, most recently failed first
+    pub async fn all(connection: &mut PgConnection) -> Result<Vec<DeadJob>> {
+        Ok(sqlx::query_as!(
+            DeadJob,
+            "SELECT id, kind, payload, attempts, last_error, created_at, failed_at FROM dead_jobs ORDER BY failed_at DESC, id DESC"
+        )
+        .fetch_all(connection)
+        .await?)
+    }
+
+    /// Puts this job back into the queue, giving it as many attempts as it had originally
+    ///
+    /// Must run inside a transaction!
+    pub async fn retry(self, connection: &mut PgConnection) -> Result<()> {
+        info!("Retrying {}", self);
+
+        sqlx::query!(
+            "INSERT INTO jobs (id, kind, payload, max_attempts, last_error, created_at) VALUES ($1, $2, $3, $4, $5, $6)",
+            self.id,
+            self.kind,
+            self.payload,
+            self.attempts,
+            self.last_error,
+            self.created_at
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        self.delete(connection).await
+    }
+
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        sqlx::query!("DELETE FROM dead_jobs WHERE id = $1", self.id)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
+pub mod announcement;
 pub mod audit;
 pub mod config;
+pub mod content;
 pub mod error;
 pub mod etag;
+pub mod job;
 pub mod pagination;
 pub mod permission;
 pub mod pool;
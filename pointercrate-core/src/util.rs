@@ -30,3 +30,14 @@ where
         some => Ok(some),
     }
 }
+
+/// Checks whether the given string is a valid URL slug, meaning it consists of groups of lowercase
+/// ASCII letters and digits, separated by single dashes (e.g. `guidelines` or `platformer-list`)
+///
+/// Keep this in sync with the `CHECK` constraints on the `slug` columns in the database.
+pub fn is_valid_slug(slug: &str) -> bool {
+    !slug.is_empty()
+        && slug
+            .split('-')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()))
+}
@@ -0,0 +1,179 @@
+//! Live stream of list activity
+//!
+//! Endpoints and background tasks that change something of interest (records being submitted or
+//! reviewed, demons being added or moved, players being banned or renamed) publish an [`Activity`]
+//! to the [`ActivityStream`] once their changes are committed, which forwards it to everyone
+//! subscribed to `/api/v2/activity` via server-sent events. This saves bots and stream overlays
+//! from having to poll the API. Webhooks are notified separately, before the commit (see [`crate::webhook`]).
+
+use pointercrate_demonlist::{
+    demon::{Demon, MinimalDemon},
+    player::DatabasePlayer,
+    record::{FullRecord, MinimalRecordPD, RecordStatus},
+};
+use rocket::tokio::sync::broadcast::{self, Receiver, Sender};
+use serde::Serialize;
+use std::{collections::HashMap, sync::Arc};
+
+/// How many activities a subscriber can fall behind before it starts missing some
+const BACKLOG: usize = 64;
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum Activity {
+    RecordSubmitted {
+        record: MinimalRecordPD,
+
+        /// The id of the submitter. Only used for Discord webhook messages
+        #[serde(skip)]
+        submitter: Option<i32>,
+    },
+    RecordApproved {
+        record: MinimalRecordPD,
+
+        /// The name of the list team member who reviewed the record. Only used for Discord webhook
+        /// messages
+        #[serde(skip)]
+        reviewer: Option<String>,
+    },
+    RecordRejected {
+        record: MinimalRecordPD,
+
+        /// The name of the list team member who reviewed the record. Only used for Discord webhook
+        /// messages
+        #[serde(skip)]
+        reviewer: Option<String>,
+    },
+    DemonAdded {
+        demon: MinimalDemon,
+        list: i32,
+    },
+    /// The demon was moved to the position given in `demon`
+    DemonMoved {
+        demon: MinimalDemon,
+        list: i32,
+    },
+    PlayerBanned {
+        player: DatabasePlayer,
+    },
+    PlayerRenamed {
+        player: DatabasePlayer,
+        old_name: String,
+    },
+}
+
+impl Activity {
+    /// Turns the given record into a [`MinimalRecordPD`], dropping the submitter information
+    pub(crate) fn record(record: &FullRecord) -> MinimalRecordPD {
+        MinimalRecordPD {
+            id: record.id,
+            progress: record.progress,
+            video: record.video.clone(),
+            status: record.status,
+            demon: record.demon.clone(),
+            player: record.player.clone(),
+        }
+    }
+
+    /// The activity corresponding to the given record having been given its current status by the
+    /// given list team member, if that status is of interest to anyone
+    pub(crate) fn record_reviewed(record: &FullRecord, reviewer: Option<&str>) -> Option<Activity> {
+        let reviewer = reviewer.map(str::to_owned);
+
+        match record.status {
+            RecordStatus::Approved => Some(Activity::RecordApproved {
+                record: Activity::record(record),
+                reviewer,
+            }),
+            RecordStatus::Rejected => Some(Activity::RecordRejected {
+                record: Activity::record(record),
+                reviewer,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The activities describing how a list changed between the two given snapshots of it (as
+    /// returned by [`current_list`](pointercrate_demonlist::demon::current_list))
+    ///
+    /// Used for changes that can shift any number of demons around as a side effect, like
+    /// publishing a list update or deleting a demon. Every demon that was not on the list before
+    /// was added, and every other demon whose position changed was moved.
+    pub(crate) fn list_changes(list: i32, before: &[Demon], after: &[Demon]) -> Vec<Activity> {
+        let positions_before = before
+            .iter()
+            .map(|demon| (demon.base.id, demon.base.position))
+            .collect::<HashMap<_, _>>();
+
+        after
+            .iter()
+            .filter_map(|demon| match positions_before.get(&demon.base.id) {
+                None => Some(Activity::DemonAdded {
+                    demon: demon.base.clone(),
+                    list,
+                }),
+                Some(&position) if position != demon.base.position => Some(Activity::DemonMoved {
+                    demon: demon.base.clone(),
+                    list,
+                }),
+                Some(_) => None,
+            })
+            .collect()
+    }
+
+    /// The name of the server-sent event this activity is sent as
+    pub(crate) fn event_name(&self) -> &'static str {
+        match self {
+            Activity::RecordSubmitted { .. } => "record_submitted",
+            Activity::RecordApproved { .. } => "record_approved",
+            Activity::RecordRejected { .. } => "record_rejected",
+            Activity::DemonAdded { .. } => "demon_added",
+            Activity::DemonMoved { .. } => "demon_moved",
+            Activity::PlayerBanned { .. } => "player_banned",
+            Activity::PlayerRenamed { .. } => "player_renamed",
+        }
+    }
+
+    /// Whether this activity may be seen by everyone, or only by list helpers
+    ///
+    /// Pending and rejected records are not visible to the public via the API either, and neither
+    /// is which players are banned.
+    pub(crate) fn is_public(&self) -> bool {
+        !matches!(
+            self,
+            Activity::RecordSubmitted { .. } | Activity::RecordRejected { .. } | Activity::PlayerBanned { .. }
+        )
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct ActivityStream {
+    sender: Sender<Arc<Activity>>,
+}
+
+impl Default for ActivityStream {
+    fn default() -> Self {
+        ActivityStream {
+            sender: broadcast::channel(BACKLOG).0,
+        }
+    }
+}
+
+impl ActivityStream {
+    /// Sends the given activity to all current subscribers
+    pub(crate) fn publish(&self, activity: Activity) {
+        // Sending only fails if nobody is subscribed, in which case there is nobody to tell anyway
+        let _ = self.sender.send(Arc::new(activity));
+    }
+
+    /// Sends all given activities to all current subscribers, in order
+    pub(crate) fn publish_all(&self, activities: Vec<Activity>) {
+        for activity in activities {
+            self.publish(activity);
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> Receiver<Arc<Activity>> {
+        self.sender.subscribe()
+    }
+}
@@ -0,0 +1,56 @@
+//! Module resolving the list served under the routes that predate support for multiple lists
+//!
+//! Lists cannot be modified once created, so the default list is looked up once at startup and
+//! kept in managed state, instead of being queried by every legacy route.
+
+use log::{error, info};
+use pointercrate_core::pool::PointercratePool;
+use pointercrate_demonlist::{config, list::List};
+use rocket::{
+    fairing::{Fairing, Info, Kind},
+    Build, Rocket,
+};
+
+/// The list served under the routes that predate support for multiple lists, see
+/// [`config::default_list`]
+pub(crate) struct DefaultList(pub List);
+
+/// Rocket fairing that resolves the [`DefaultList`] on ignition, aborting the launch if it does
+/// not exist
+pub(crate) struct DefaultListResolver;
+
+#[rocket::async_trait]
+impl Fairing for DefaultListResolver {
+    fn info(&self) -> Info {
+        Info {
+            name: "Default List",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> rocket::fairing::Result {
+        let Some(pool) = rocket.state::<PointercratePool>() else {
+            error!("PointercratePool not managed, cannot resolve the default list");
+
+            return Err(rocket);
+        };
+
+        let list = match pool.connection().await {
+            Ok(mut connection) => List::default_list(&mut connection).await,
+            Err(err) => Err(err.into()),
+        };
+
+        match list {
+            Ok(list) => {
+                info!("Serving {} under the legacy routes", list);
+
+                Ok(rocket.manage(DefaultList(list)))
+            },
+            Err(err) => {
+                error!("Failed to resolve the default list '{}': {:?}", config::default_list(), err);
+
+                Err(rocket)
+            },
+        }
+    }
+}
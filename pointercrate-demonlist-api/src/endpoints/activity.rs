@@ -0,0 +1,36 @@
+use crate::activity::ActivityStream;
+use pointercrate_demonlist::LIST_HELPER;
+use pointercrate_user_api::auth::TokenAuth;
+use rocket::{
+    response::stream::{Event, EventStream},
+    tokio::{select, sync::broadcast::error::RecvError},
+    Shutdown, State,
+};
+
+/// Stream of [`Activity`](crate::activity::Activity)s as server-sent events
+///
+/// Activities that are not public are only sent to users with `LIST_HELPER` permissions.
+#[rocket::get("/")]
+pub async fn stream(auth: Option<TokenAuth>, activities: &State<ActivityStream>, mut shutdown: Shutdown) -> EventStream![] {
+    // Do not hold onto the database connection of the authentication for the entire lifetime of the stream
+    let is_helper = auth.map(|auth| auth.has_permission(LIST_HELPER)).unwrap_or(false);
+    let mut receiver = activities.subscribe();
+
+    EventStream! {
+        loop {
+            let activity = select! {
+                received = receiver.recv() => match received {
+                    Ok(activity) => activity,
+                    Err(RecvError::Closed) => break,
+                    // Slow subscribers simply miss out on some activities
+                    Err(RecvError::Lagged(_)) => continue,
+                },
+                _ = &mut shutdown => break,
+            };
+
+            if is_helper || activity.is_public() {
+                yield Event::json(&*activity).event(activity.event_name());
+            }
+        }
+    }
+}
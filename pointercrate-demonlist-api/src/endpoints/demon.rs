@@ -1,4 +1,9 @@
-use crate::ratelimits::DemonlistRatelimits;
+use crate::{
+    activity::{Activity, ActivityStream},
+    default_list::DefaultList,
+    ratelimits::DemonlistRatelimits,
+    webhook::notify_webhooks,
+};
 use pointercrate_core::{audit::AuditLogEntry, pool::PointercratePool};
 use pointercrate_core_api::{
     error::Result,
@@ -11,25 +16,41 @@ use pointercrate_demonlist::{
     creator::{Creator, PostCreator},
     demon::{
         audit::{DemonModificationData, MovementLogEntry},
-        Demon, DemonIdPagination, DemonPositionPagination, FullDemon, PatchDemon, PostDemon,
+        current_list, derive_thumbnails, Demon, DemonIdPagination, DemonPositionPagination, FullDemon, MinimalDemon, PatchDemon, PostDemon,
+        Reorder,
     },
     error::DemonlistError,
     player::DatabasePlayer,
-    LIST_ADMINISTRATOR, LIST_MODERATOR,
+    proposal::PlacementProposal,
+    video::VideoHosts,
+    LIST_ADMINISTRATOR, LIST_HELPER, LIST_MODERATOR,
 };
 use pointercrate_user_api::auth::TokenAuth;
 use rocket::{http::Status, serde::json::Json, State};
+use serde_json::{json, Value};
 
 #[rocket::get("/")]
-pub async fn paginate(pool: &State<PointercratePool>, pagination: Query<DemonIdPagination>) -> Result<Response2<Json<Vec<Demon>>>> {
-    Ok(pagination_response("/api/v2/demons/", pagination.0, &mut *pool.connection().await?).await?)
+pub async fn paginate(
+    pool: &State<PointercratePool>, pagination: Query<DemonIdPagination>, default_list: &State<DefaultList>,
+) -> Result<Response2<Json<Vec<Demon>>>> {
+    let mut connection = pool.connection().await?;
+    let mut pagination = pagination.0;
+
+    pagination.list = Some(default_list.0.id);
+
+    Ok(pagination_response("/api/v2/demons/", pagination, &mut connection).await?)
 }
 
 #[rocket::get("/listed")]
 pub async fn paginate_listed(
-    pool: &State<PointercratePool>, pagination: Query<DemonPositionPagination>,
+    pool: &State<PointercratePool>, pagination: Query<DemonPositionPagination>, default_list: &State<DefaultList>,
 ) -> Result<Response2<Json<Vec<Demon>>>> {
-    Ok(pagination_response("/api/v2/demons/listed/", pagination.0, &mut *pool.connection().await?).await?)
+    let mut connection = pool.connection().await?;
+    let mut pagination = pagination.0;
+
+    pagination.list = Some(default_list.0.id);
+
+    Ok(pagination_response("/api/v2/demons/listed/", pagination, &mut connection).await?)
 }
 
 #[rocket::get("/<demon_id>")]
@@ -50,6 +71,18 @@ pub async fn audit(demon_id: i32, mut auth: TokenAuth) -> Result<Json<Vec<AuditL
     Ok(Json(log))
 }
 
+/// All placement proposals concerning this demon, including the discussion that lead to its
+/// current placement
+#[rocket::get("/<demon_id>/proposals")]
+pub async fn proposals(demon_id: i32, mut auth: TokenAuth) -> Result<Json<Vec<PlacementProposal>>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    // Make sure the demon exists, to return a 404 instead of an empty list otherwise
+    Demon::by_id(demon_id, &mut auth.connection).await?;
+
+    Ok(Json(PlacementProposal::of_demon(demon_id, &mut auth.connection).await?))
+}
+
 #[rocket::get("/<demon_id>/audit/movement")]
 pub async fn movement_log(demon_id: i32, pool: &State<PointercratePool>) -> Result<Json<Vec<MovementLogEntry>>> {
     let log = pointercrate_demonlist::demon::audit::movement_log_for_demon(demon_id, &mut *pool.connection().await?).await?;
@@ -63,16 +96,27 @@ pub async fn movement_log(demon_id: i32, pool: &State<PointercratePool>) -> Resu
 
 #[rocket::post("/", data = "<data>")]
 pub async fn post(
-    mut auth: TokenAuth, data: Json<PostDemon>, ratelimits: &State<DemonlistRatelimits>,
+    mut auth: TokenAuth, data: Json<PostDemon>, ratelimits: &State<DemonlistRatelimits>, activities: &State<ActivityStream>,
+    hosts: &State<VideoHosts>, default_list: &State<DefaultList>,
 ) -> Result<Response2<Tagged<FullDemon>>> {
     auth.require_permission(LIST_MODERATOR)?;
 
     ratelimits.add_demon()?;
 
-    let demon = FullDemon::create_from(data.0, &mut auth.connection).await?;
+    let list = default_list.0.clone();
+    let demon = FullDemon::create_from(data.0, list.id, hosts, &mut auth.connection).await?;
+
+    let announced = vec![Activity::DemonAdded {
+        demon: demon.demon.base.clone(),
+        list: list.id,
+    }];
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
 
     auth.commit().await?;
 
+    activities.publish_all(announced);
+
     let demon_id = demon.demon.base.id;
 
     Ok(Response2::tagged(demon)
@@ -81,20 +125,101 @@ pub async fn post(
 }
 
 #[rocket::patch("/<demon_id>", data = "<patch>")]
-pub async fn patch(demon_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchDemon>) -> Result<Tagged<FullDemon>> {
+pub async fn patch(
+    demon_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchDemon>, activities: &State<ActivityStream>,
+    hosts: &State<VideoHosts>,
+) -> Result<Tagged<FullDemon>> {
     auth.require_permission(LIST_MODERATOR)?;
 
     let demon = FullDemon::by_id(demon_id, &mut auth.connection)
         .await?
-        .require_match(precondition)?
-        .apply_patch(patch.0, &mut auth.connection)
-        .await?;
+        .require_match(precondition)?;
+    let list = demon.demon.list;
+
+    // Moving, unlisting or relisting a demon shifts the demons between its old and new position
+    let (demon, announced) = if patch.position.is_some() || patch.unlisted.is_some() {
+        let before = current_list(list, &mut auth.connection).await?;
+        let demon = demon.apply_patch(patch.0, hosts, &mut auth.connection).await?;
+        let after = current_list(list, &mut auth.connection).await?;
+
+        (demon, Activity::list_changes(list, &before, &after))
+    } else {
+        (demon.apply_patch(patch.0, hosts, &mut auth.connection).await?, Vec::new())
+    };
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
 
     auth.commit().await?;
 
+    activities.publish_all(announced);
+
     Ok(Tagged(demon))
 }
 
+#[rocket::post("/reorder", data = "<reorder>")]
+pub async fn reorder(
+    mut auth: TokenAuth, reorder: Json<Reorder>, activities: &State<ActivityStream>, default_list: &State<DefaultList>,
+) -> Result<Json<Vec<MinimalDemon>>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let list = default_list.0.clone();
+    let moved = reorder.0.apply(list.id, &mut auth.connection).await?;
+
+    let announced = moved
+        .iter()
+        .map(|demon| Activity::DemonMoved {
+            demon: demon.clone(),
+            list: list.id,
+        })
+        .collect::<Vec<_>>();
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    activities.publish_all(announced);
+
+    Ok(Json(moved))
+}
+
+/// Derives the thumbnails of all demons whose thumbnail was not set manually from their
+/// verification videos
+#[rocket::post("/thumbnails")]
+pub async fn backfill_thumbnails(mut auth: TokenAuth, hosts: &State<VideoHosts>) -> Result<Json<Value>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    let changed = derive_thumbnails(hosts, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    Ok(Json(json! {{"changed": changed}}))
+}
+
+#[rocket::delete("/<demon_id>")]
+pub async fn delete(demon_id: i32, mut auth: TokenAuth, precondition: Precondition, activities: &State<ActivityStream>) -> Result<Status> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let demon = FullDemon::by_id(demon_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?;
+    let list = demon.demon.list;
+
+    // Deleting a demon shifts all demons below it up by one
+    let before = current_list(list, &mut auth.connection).await?;
+    demon.delete(&mut auth.connection).await?;
+    let after = current_list(list, &mut auth.connection).await?;
+
+    let announced = Activity::list_changes(list, &before, &after);
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    activities.publish_all(announced);
+
+    Ok(Status::NoContent)
+}
+
 #[rocket::post("/<demon_id>/creators", data = "<creator>")]
 pub async fn post_creator(demon_id: i32, mut auth: TokenAuth, creator: Json<PostCreator>) -> Result<Response2<Json<()>>> {
     auth.require_permission(LIST_MODERATOR)?;
@@ -0,0 +1,386 @@
+use crate::{
+    activity::{Activity, ActivityStream},
+    ratelimits::DemonlistRatelimits,
+    webhook::notify_webhooks,
+};
+use pointercrate_core::pool::PointercratePool;
+use pointercrate_core_api::{
+    error::Result,
+    etag::{Precondition, TaggableExt, Tagged},
+    pagination::pagination_response,
+    query::Query,
+    response::Response2,
+};
+use pointercrate_demonlist::{
+    demon::{current_list, Demon, DemonIdPagination, DemonPositionPagination, FullDemon, MinimalDemon, PostDemon, Reorder},
+    error::DemonlistError,
+    list::{
+        section::{ListSection, PatchListSection, PostListSection},
+        update::{ListUpdate, PatchListUpdate, PostListUpdate},
+        List, PostList,
+    },
+    nationality::{Nationality, NationalityRankingPagination, NationalityRecord, RankedNation},
+    player::{FullPlayer, Player, RankedPlayer, RankingPagination},
+    proposal::{PlacementProposal, PostPlacementProposal},
+    video::VideoHosts,
+    LIST_ADMINISTRATOR, LIST_HELPER, LIST_MODERATOR,
+};
+use pointercrate_user_api::auth::TokenAuth;
+use rocket::{http::Status, serde::json::Json, State};
+use sqlx::PgConnection;
+
+#[rocket::get("/")]
+pub async fn get_all(pool: &State<PointercratePool>) -> Result<Json<Vec<List>>> {
+    Ok(Json(List::all(&mut *pool.connection().await?).await?))
+}
+
+#[rocket::post("/", data = "<data>")]
+pub async fn post(mut auth: TokenAuth, data: Json<PostList>) -> Result<Response2<Tagged<List>>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    let list = List::create_from(data.0, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    let location = format!("/api/v2/lists/{}/", list.slug);
+
+    Ok(Response2::tagged(list).status(Status::Created).with_header("Location", location))
+}
+
+#[rocket::get("/<slug>")]
+pub async fn get(slug: &str, pool: &State<PointercratePool>) -> Result<Tagged<List>> {
+    Ok(Tagged(List::by_slug(slug, &mut *pool.connection().await?).await?))
+}
+
+#[rocket::get("/<slug>/demons")]
+pub async fn paginate_demons(
+    slug: &str, pool: &State<PointercratePool>, pagination: Query<DemonIdPagination>,
+) -> Result<Response2<Json<Vec<Demon>>>> {
+    let mut connection = pool.connection().await?;
+    let mut pagination = pagination.0;
+
+    pagination.list = Some(List::by_slug(slug, &mut connection).await?.id);
+
+    Ok(pagination_response(&format!("/api/v2/lists/{}/demons/", slug), pagination, &mut connection).await?)
+}
+
+#[rocket::get("/<slug>/demons/listed")]
+pub async fn paginate_listed_demons(
+    slug: &str, pool: &State<PointercratePool>, pagination: Query<DemonPositionPagination>,
+) -> Result<Response2<Json<Vec<Demon>>>> {
+    let mut connection = pool.connection().await?;
+    let mut pagination = pagination.0;
+
+    pagination.list = Some(List::by_slug(slug, &mut connection).await?.id);
+
+    Ok(pagination_response(&format!("/api/v2/lists/{}/demons/listed/", slug), pagination, &mut connection).await?)
+}
+
+#[rocket::post("/<slug>/demons", data = "<data>")]
+pub async fn post_demon(
+    slug: &str, mut auth: TokenAuth, data: Json<PostDemon>, ratelimits: &State<DemonlistRatelimits>, activities: &State<ActivityStream>,
+    hosts: &State<VideoHosts>,
+) -> Result<Response2<Tagged<FullDemon>>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    ratelimits.add_demon()?;
+
+    let list = List::by_slug(slug, &mut auth.connection).await?;
+    let demon = FullDemon::create_from(data.0, list.id, hosts, &mut auth.connection).await?;
+
+    let announced = vec![Activity::DemonAdded {
+        demon: demon.demon.base.clone(),
+        list: list.id,
+    }];
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    activities.publish_all(announced);
+
+    let demon_id = demon.demon.base.id;
+
+    Ok(Response2::tagged(demon)
+        .status(Status::Created)
+        .with_header("Location", format!("/api/v2/demons/{}/", demon_id)))
+}
+
+#[rocket::post("/<slug>/demons/reorder", data = "<reorder>")]
+pub async fn reorder_demons(
+    slug: &str, mut auth: TokenAuth, reorder: Json<Reorder>, activities: &State<ActivityStream>,
+) -> Result<Json<Vec<MinimalDemon>>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let list = List::by_slug(slug, &mut auth.connection).await?;
+    let moved = reorder.0.apply(list.id, &mut auth.connection).await?;
+
+    let announced = moved
+        .iter()
+        .map(|demon| Activity::DemonMoved {
+            demon: demon.clone(),
+            list: list.id,
+        })
+        .collect::<Vec<_>>();
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    activities.publish_all(announced);
+
+    Ok(Json(moved))
+}
+
+#[rocket::get("/<slug>/players/ranking")]
+pub async fn player_ranking(
+    slug: &str, pool: &State<PointercratePool>, query: Query<RankingPagination>,
+) -> Result<Response2<Json<Vec<RankedPlayer>>>> {
+    let mut connection = pool.connection().await?;
+    let mut pagination = query.0;
+
+    pagination.list = Some(List::by_slug(slug, &mut connection).await?.id);
+
+    Ok(pagination_response(&format!("/api/v2/lists/{}/players/ranking/", slug), pagination, &mut connection).await?)
+}
+
+#[rocket::get("/<slug>/players/<player_id>", rank = 1)]
+pub async fn get_player(slug: &str, player_id: i32, pool: &State<PointercratePool>) -> Result<Tagged<FullPlayer>> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    Ok(Tagged(
+        Player::by_id(player_id, list.id, &mut connection)
+            .await?
+            .upgrade(list.id, &mut connection)
+            .await?,
+    ))
+}
+
+#[rocket::get("/<slug>/nationalities/ranking")]
+pub async fn nation_ranking(
+    slug: &str, pool: &State<PointercratePool>, pagination: Query<NationalityRankingPagination>,
+) -> Result<Json<Vec<RankedNation>>> {
+    let mut connection = pool.connection().await?;
+    let mut pagination = pagination.0;
+
+    pagination.list = Some(List::by_slug(slug, &mut connection).await?.id);
+
+    Ok(Json(pagination.page(&mut connection).await?))
+}
+
+#[rocket::get("/<slug>/nationalities/<iso_code>", rank = 1)]
+pub async fn nation(slug: &str, iso_code: String, pool: &State<PointercratePool>) -> Result<Tagged<NationalityRecord>> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    // good code
+    let nationality = Nationality::by_country_code_or_name(iso_code.to_uppercase().as_ref(), &mut connection).await?;
+
+    Ok(Tagged(nationality.upgrade(list.id, &mut connection).await?))
+}
+
+#[rocket::get("/<slug>/sections")]
+pub async fn sections(slug: &str, pool: &State<PointercratePool>) -> Result<Json<Vec<ListSection>>> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    Ok(Json(ListSection::of_list(list.id, &mut connection).await?))
+}
+
+#[rocket::post("/<slug>/sections", data = "<data>")]
+pub async fn post_section(slug: &str, mut auth: TokenAuth, data: Json<PostListSection>) -> Result<Response2<Tagged<ListSection>>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    let list = List::by_slug(slug, &mut auth.connection).await?;
+    let section = ListSection::create_from(data.0, list.id, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    let location = format!("/api/v2/lists/{}/sections/{}/", slug, section.id);
+
+    Ok(Response2::tagged(section).status(Status::Created).with_header("Location", location))
+}
+
+/// Retrieves the section with the given id, making sure it actually belongs to the list with the
+/// given slug
+async fn section_on(slug: &str, section_id: i32, connection: &mut PgConnection) -> Result<ListSection> {
+    let list = List::by_slug(slug, &mut *connection).await?;
+    let section = ListSection::by_id(section_id, connection).await?;
+
+    if section.list != list.id {
+        return Err(DemonlistError::SectionNotFound { id: section_id }.into());
+    }
+
+    Ok(section)
+}
+
+#[rocket::get("/<slug>/sections/<section_id>")]
+pub async fn get_section(slug: &str, section_id: i32, pool: &State<PointercratePool>) -> Result<Tagged<ListSection>> {
+    Ok(Tagged(section_on(slug, section_id, &mut *pool.connection().await?).await?))
+}
+
+#[rocket::patch("/<slug>/sections/<section_id>", data = "<patch>")]
+pub async fn patch_section(
+    slug: &str, section_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchListSection>,
+) -> Result<Tagged<ListSection>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    let section = section_on(slug, section_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .apply_patch(patch.0, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(section))
+}
+
+#[rocket::delete("/<slug>/sections/<section_id>")]
+pub async fn delete_section(slug: &str, section_id: i32, mut auth: TokenAuth, precondition: Precondition) -> Result<Status> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    let section = section_on(slug, section_id, &mut auth.connection).await?;
+
+    precondition.require_etag_match(&section)?;
+
+    section.delete(&mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+#[rocket::get("/<slug>/updates")]
+pub async fn updates(slug: &str, mut auth: TokenAuth) -> Result<Json<Vec<ListUpdate>>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let list = List::by_slug(slug, &mut auth.connection).await?;
+
+    Ok(Json(ListUpdate::of_list(list.id, &mut auth.connection).await?))
+}
+
+#[rocket::post("/<slug>/updates", data = "<data>")]
+pub async fn post_update(
+    slug: &str, mut auth: TokenAuth, data: Json<PostListUpdate>, hosts: &State<VideoHosts>,
+) -> Result<Response2<Tagged<ListUpdate>>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let list = List::by_slug(slug, &mut auth.connection).await?;
+    let author = auth.user.inner().id;
+    let update = ListUpdate::create_from(data.0, list.id, author, hosts, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    let location = format!("/api/v2/lists/{}/updates/{}/", slug, update.id);
+
+    Ok(Response2::tagged(update).status(Status::Created).with_header("Location", location))
+}
+
+/// Retrieves the list update with the given id, making sure it actually belongs to the list with
+/// the given slug
+async fn update_on(slug: &str, update_id: i32, connection: &mut PgConnection) -> Result<ListUpdate> {
+    let list = List::by_slug(slug, &mut *connection).await?;
+    let update = ListUpdate::by_id(update_id, connection).await?;
+
+    if update.list != list.id {
+        return Err(DemonlistError::ListUpdateNotFound { id: update_id }.into());
+    }
+
+    Ok(update)
+}
+
+#[rocket::get("/<slug>/updates/<update_id>")]
+pub async fn get_update(slug: &str, update_id: i32, mut auth: TokenAuth) -> Result<Tagged<ListUpdate>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    Ok(Tagged(update_on(slug, update_id, &mut auth.connection).await?))
+}
+
+#[rocket::patch("/<slug>/updates/<update_id>", data = "<patch>")]
+pub async fn patch_update(
+    slug: &str, update_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchListUpdate>,
+) -> Result<Tagged<ListUpdate>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let update = update_on(slug, update_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .apply_patch(patch.0, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(update))
+}
+
+#[rocket::delete("/<slug>/updates/<update_id>")]
+pub async fn delete_update(slug: &str, update_id: i32, mut auth: TokenAuth, precondition: Precondition) -> Result<Status> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    update_on(slug, update_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .delete(&mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+#[rocket::post("/<slug>/updates/<update_id>/publish")]
+pub async fn publish_update(
+    slug: &str, update_id: i32, mut auth: TokenAuth, precondition: Precondition, hosts: &State<VideoHosts>,
+    activities: &State<ActivityStream>,
+) -> Result<Tagged<ListUpdate>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let update = update_on(slug, update_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?;
+    let before = current_list(update.list, &mut auth.connection).await?;
+    let update = update.publish(hosts, &mut auth.connection).await?;
+    let after = current_list(update.list, &mut auth.connection).await?;
+
+    let announced = Activity::list_changes(update.list, &before, &after);
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    activities.publish_all(announced);
+
+    Ok(Tagged(update))
+}
+
+#[rocket::get("/<slug>/proposals?<open>")]
+pub async fn proposals(slug: &str, open: Option<bool>, mut auth: TokenAuth) -> Result<Json<Vec<PlacementProposal>>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let list = List::by_slug(slug, &mut auth.connection).await?;
+
+    Ok(Json(
+        PlacementProposal::of_list(list.id, open.unwrap_or(false), &mut auth.connection).await?,
+    ))
+}
+
+#[rocket::post("/<slug>/proposals", data = "<data>")]
+pub async fn post_proposal(
+    slug: &str, mut auth: TokenAuth, data: Json<PostPlacementProposal>, hosts: &State<VideoHosts>,
+) -> Result<Response2<Tagged<PlacementProposal>>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let list = List::by_slug(slug, &mut auth.connection).await?;
+    let author = auth.user.inner().id;
+    let proposal = PlacementProposal::create_from(data.0, list.id, author, hosts, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    let location = format!("/api/v2/proposals/{}/", proposal.id);
+
+    Ok(Response2::tagged(proposal)
+        .status(Status::Created)
+        .with_header("Location", location))
+}
@@ -1,15 +1,20 @@
-use pointercrate_demonlist::config;
-use rocket::serde::json::Json;
+use crate::default_list::DefaultList;
+use pointercrate_core::pool::PointercratePool;
+use pointercrate_core_api::error::Result;
+use pointercrate_demonlist::list::section::ListSection;
+use rocket::{serde::json::Json, State};
 use serde_json::{json, Value};
 
 #[rocket::get("/")]
-pub fn list_information() -> Json<Value> {
+pub async fn list_information(pool: &State<PointercratePool>, default_list: &State<DefaultList>) -> Result<Json<Value>> {
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+
     let data = json! {
         {
-            "list_size": config::list_size(),
-            "extended_list_size": config::extended_list_size()
+            "sections": ListSection::of_list(list.id, &mut connection).await?
         }
     };
 
-    Json(data)
+    Ok(Json(data))
 }
@@ -1,6 +1,10 @@
+pub(crate) mod activity;
 pub(crate) mod demon;
+pub(crate) mod list;
 pub(crate) mod misc;
 pub(crate) mod nationality;
 pub(crate) mod player;
+pub(crate) mod proposal;
 pub(crate) mod record;
 pub(crate) mod submitter;
+pub(crate) mod webhook;
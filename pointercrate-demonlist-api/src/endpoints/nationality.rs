@@ -1,3 +1,4 @@
+use crate::default_list::DefaultList;
 use pointercrate_core::pool::PointercratePool;
 use pointercrate_core_api::{error::Result, etag::Tagged, query::Query};
 use pointercrate_demonlist::nationality::{Nationality, NationalityRankingPagination, NationalityRecord, RankedNation, Subdivision};
@@ -14,16 +15,26 @@ pub async fn subdivisions(pool: &State<PointercratePool>, iso_code: String) -> R
 }
 
 #[rocket::get("/ranking")]
-pub async fn ranking(pool: &State<PointercratePool>, pagination: Query<NationalityRankingPagination>) -> Result<Json<Vec<RankedNation>>> {
-    Ok(Json(pagination.0.page(&mut *pool.connection().await?).await?))
+pub async fn ranking(
+    pool: &State<PointercratePool>, pagination: Query<NationalityRankingPagination>, default_list: &State<DefaultList>,
+) -> Result<Json<Vec<RankedNation>>> {
+    let mut connection = pool.connection().await?;
+    let mut pagination = pagination.0;
+
+    pagination.list = Some(default_list.0.id);
+
+    Ok(Json(pagination.page(&mut connection).await?))
 }
 
 #[rocket::get("/<iso_code>")]
-pub async fn nation(pool: &State<PointercratePool>, iso_code: String) -> Result<Tagged<NationalityRecord>> {
+pub async fn nation(
+    pool: &State<PointercratePool>, iso_code: String, default_list: &State<DefaultList>,
+) -> Result<Tagged<NationalityRecord>> {
     let mut connection = pool.connection().await?;
 
     // good code
-    let nationality = Nationality::by_country_code_or_name(iso_code.to_uppercase().as_ref(), &mut *connection).await?;
+    let nationality = Nationality::by_country_code_or_name(iso_code.to_uppercase().as_ref(), &mut connection).await?;
+    let list = default_list.0.clone();
 
-    Ok(Tagged(nationality.upgrade(&mut *connection).await?))
+    Ok(Tagged(nationality.upgrade(list.id, &mut connection).await?))
 }
@@ -1,4 +1,10 @@
-use crate::{config, ratelimits::DemonlistRatelimits};
+use crate::{
+    activity::{Activity, ActivityStream},
+    config,
+    default_list::DefaultList,
+    ratelimits::DemonlistRatelimits,
+    webhook::notify_webhooks,
+};
 use log::warn;
 use pointercrate_core::{error::CoreError, pool::PointercratePool};
 use pointercrate_core_api::{
@@ -24,7 +30,7 @@ use std::net::IpAddr;
 
 #[rocket::get("/")]
 pub async fn paginate(
-    pool: &State<PointercratePool>, query: Query<PlayerPagination>, auth: Option<TokenAuth>,
+    pool: &State<PointercratePool>, query: Query<PlayerPagination>, auth: Option<TokenAuth>, default_list: &State<DefaultList>,
 ) -> Result<Response2<Json<Vec<Player>>>> {
     let mut pagination = query.0;
 
@@ -36,37 +42,72 @@ pub async fn paginate(
         pagination.banned = Some(false);
     }
 
-    Ok(pagination_response("/api/v1/players/", pagination, &mut *pool.connection().await?).await?)
+    let mut connection = pool.connection().await?;
+
+    pagination.list = Some(default_list.0.id);
+
+    Ok(pagination_response("/api/v1/players/", pagination, &mut connection).await?)
 }
 
 #[rocket::get("/ranking")]
-pub async fn ranking(pool: &State<PointercratePool>, query: Query<RankingPagination>) -> Result<Response2<Json<Vec<RankedPlayer>>>> {
-    Ok(pagination_response("/api/v1/players/ranking/", query.0, &mut *pool.connection().await?).await?)
+pub async fn ranking(
+    pool: &State<PointercratePool>, query: Query<RankingPagination>, default_list: &State<DefaultList>,
+) -> Result<Response2<Json<Vec<RankedPlayer>>>> {
+    let mut connection = pool.connection().await?;
+    let mut pagination = query.0;
+
+    pagination.list = Some(default_list.0.id);
+
+    Ok(pagination_response("/api/v1/players/ranking/", pagination, &mut connection).await?)
 }
 
 #[rocket::get("/<player_id>")]
-pub async fn get(player_id: i32, pool: &State<PointercratePool>) -> Result<Tagged<FullPlayer>> {
+pub async fn get(player_id: i32, pool: &State<PointercratePool>, default_list: &State<DefaultList>) -> Result<Tagged<FullPlayer>> {
     let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
 
     Ok(Tagged(
-        Player::by_id(player_id, &mut *connection).await?.upgrade(&mut *connection).await?,
+        Player::by_id(player_id, list.id, &mut connection)
+            .await?
+            .upgrade(list.id, &mut connection)
+            .await?,
     ))
 }
 
 #[rocket::patch("/<player_id>", data = "<patch>")]
 pub async fn patch(
-    player_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchPlayer>,
+    player_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchPlayer>, activities: &State<ActivityStream>,
+    default_list: &State<DefaultList>,
 ) -> Result<Tagged<FullPlayer>> {
-    let player = Player::by_id(player_id, &mut auth.connection)
+    let list = default_list.0.clone();
+    let player = Player::by_id(player_id, list.id, &mut auth.connection)
         .await?
-        .upgrade(&mut auth.connection)
+        .upgrade(list.id, &mut auth.connection)
         .await?
-        .require_match(precondition)?
-        .apply_patch(patch.0, &mut auth.connection)
-        .await?;
+        .require_match(precondition)?;
+    let old = player.player.base.clone();
+    let player = player.apply_patch(patch.0, &mut auth.connection).await?;
+
+    let new = &player.player.base;
+    let mut announced = Vec::new();
+
+    if new.banned && !old.banned {
+        announced.push(Activity::PlayerBanned { player: new.clone() });
+    }
+
+    if new.name != old.name {
+        announced.push(Activity::PlayerRenamed {
+            player: new.clone(),
+            old_name: old.name,
+        });
+    }
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
 
     auth.commit().await?;
 
+    activities.publish_all(announced);
+
     Ok(Tagged(player))
 }
 
@@ -160,9 +201,10 @@ struct GeolocationResponse {
 
 #[rocket::post("/<player_id>/geolocate")]
 pub async fn geolocate_nationality(
-    player_id: i32, ip: IpAddr, mut auth: TokenAuth, ratelimits: &State<DemonlistRatelimits>,
+    player_id: i32, ip: IpAddr, mut auth: TokenAuth, ratelimits: &State<DemonlistRatelimits>, default_list: &State<DefaultList>,
 ) -> Result<Json<Nationality>> {
-    let mut player = Player::by_id(player_id, &mut auth.connection).await?;
+    let list = default_list.0.clone();
+    let mut player = Player::by_id(player_id, list.id, &mut auth.connection).await?;
     let claim = PlayerClaim::get(auth.user.inner().id, player_id, &mut auth.connection).await?;
 
     if !claim.verified {
@@ -0,0 +1,94 @@
+use crate::{
+    activity::{Activity, ActivityStream},
+    webhook::notify_webhooks,
+};
+use pointercrate_core_api::{
+    error::Result,
+    etag::{Precondition, TaggableExt, Tagged},
+};
+use pointercrate_demonlist::{
+    demon::current_list,
+    proposal::{PlacementProposal, PutOpinion},
+    video::VideoHosts,
+    LIST_HELPER, LIST_MODERATOR,
+};
+use pointercrate_user_api::auth::TokenAuth;
+use rocket::{serde::json::Json, State};
+
+#[rocket::get("/<proposal_id>")]
+pub async fn get(proposal_id: i32, mut auth: TokenAuth) -> Result<Tagged<PlacementProposal>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    Ok(Tagged(PlacementProposal::by_id(proposal_id, &mut auth.connection).await?))
+}
+
+#[rocket::put("/<proposal_id>/opinion", data = "<opinion>")]
+pub async fn put_opinion(proposal_id: i32, mut auth: TokenAuth, opinion: Json<PutOpinion>) -> Result<Tagged<PlacementProposal>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let user_id = auth.user.inner().id;
+    let proposal = PlacementProposal::by_id(proposal_id, &mut auth.connection)
+        .await?
+        .put_opinion(user_id, opinion.0, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(proposal))
+}
+
+#[rocket::delete("/<proposal_id>/opinion")]
+pub async fn delete_opinion(proposal_id: i32, mut auth: TokenAuth) -> Result<Tagged<PlacementProposal>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let user_id = auth.user.inner().id;
+    let proposal = PlacementProposal::by_id(proposal_id, &mut auth.connection)
+        .await?
+        .delete_opinion(user_id, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(proposal))
+}
+
+#[rocket::post("/<proposal_id>/accept")]
+pub async fn accept(
+    proposal_id: i32, mut auth: TokenAuth, precondition: Precondition, hosts: &State<VideoHosts>, activities: &State<ActivityStream>,
+) -> Result<Tagged<PlacementProposal>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let moderator = auth.user.inner().id;
+    let proposal = PlacementProposal::by_id(proposal_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?;
+    let before = current_list(proposal.list, &mut auth.connection).await?;
+    let proposal = proposal.accept(moderator, hosts, &mut auth.connection).await?;
+    let after = current_list(proposal.list, &mut auth.connection).await?;
+
+    let announced = Activity::list_changes(proposal.list, &before, &after);
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    activities.publish_all(announced);
+
+    Ok(Tagged(proposal))
+}
+
+#[rocket::post("/<proposal_id>/reject")]
+pub async fn reject(proposal_id: i32, mut auth: TokenAuth, precondition: Precondition) -> Result<Tagged<PlacementProposal>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let moderator = auth.user.inner().id;
+    let proposal = PlacementProposal::by_id(proposal_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .reject(moderator, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(proposal))
+}
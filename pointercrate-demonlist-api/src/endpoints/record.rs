@@ -1,33 +1,52 @@
-use crate::ratelimits::DemonlistRatelimits;
-use log::{debug, error, warn};
+use crate::{
+    activity::{Activity, ActivityStream},
+    jobs::ValidateSubmissionVideo,
+    ratelimits::DemonlistRatelimits,
+    webhook::notify_webhooks,
+};
 use pointercrate_core::{audit::AuditLogEntry, error::CoreError, pool::PointercratePool};
 use pointercrate_core_api::{
     error::Result,
     etag::{Precondition, TaggableExt, Tagged},
+    job::Job,
     pagination::pagination_response,
     query::Query,
     response::Response2,
 };
 use pointercrate_demonlist::{
     error::DemonlistError,
+    list::section::ListSection,
     player::claim::PlayerClaim,
     record::{
+        approval::ApprovalPolicy,
         audit::RecordModificationData,
-        note::{notes_on, NewNote, Note, PatchNote},
+        bulk::{BulkOperation, BulkOutcome},
+        lookup::SubmissionStatus,
+        note::{
+            notes_on,
+            template::{NewNoteTemplate, NoteTemplate, PatchNoteTemplate},
+            NewNote, Note, PatchNote,
+        },
+        proof::{NewProof, PatchProof, Proof},
+        reason::{StatusReason, StatusReasons},
+        review::ReviewClaim,
+        rules::SubmissionRules,
         FullRecord, MinimalRecordPD, PatchRecord, RecordPagination, RecordStatus, Submission,
     },
     submitter::Submitter,
+    video::VideoHosts,
     LIST_ADMINISTRATOR, LIST_HELPER, LIST_MODERATOR,
 };
 use pointercrate_user_api::auth::TokenAuth;
-use rocket::{http::Status, serde::json::Json, tokio, State};
-use sqlx::{pool::PoolConnection, Postgres};
+use rocket::{http::Status, serde::json::Json, State};
 use std::net::IpAddr;
 
 /// Pagination endpoint for records in case authentication is provided
 ///
 /// Subject to the following constraints
 /// + Only users with `LIST_MODERATOR` permissions can filter by submitter.
+/// + Only users with `LIST_HELPER` permissions can filter by whether a record's video is unreachable.
+/// + Only users with `LIST_HELPER` permissions can filter by whether a record has raw footage.
 /// + Only users with `LIST_HELPER` permissions can filter by record status. For all other users,
 /// the `status` property defaults to `APPROVED` (although explicitly setting the status to
 /// `APPROVED` is allowed, UNLESS we also filter by player and the player we filter by match a
@@ -41,6 +60,10 @@ pub async fn paginate(mut auth: TokenAuth, query: Query<RecordPagination>) -> Re
         auth.require_permission(LIST_MODERATOR)?;
     }
 
+    if pagination.video_unreachable.is_some() || pagination.has_raw_footage.is_some() {
+        auth.require_permission(LIST_HELPER)?;
+    }
+
     let claim = PlayerClaim::by_user(auth.user.inner().id, &mut auth.connection)
         .await?
         .filter(|c| c.verified);
@@ -63,7 +86,7 @@ pub async fn unauthed_pagination(
     let mut connection = pool.connection().await?;
     let mut pagination = query.0;
 
-    if pagination.submitter.is_some() {
+    if pagination.submitter.is_some() || pagination.video_unreachable.is_some() || pagination.has_raw_footage.is_some() {
         return Err(CoreError::Unauthorized.into());
     }
 
@@ -79,12 +102,18 @@ pub async fn unauthed_pagination(
 #[rocket::post("/", data = "<submission>")]
 pub async fn submit(
     ip: IpAddr, auth: Option<TokenAuth>, submission: Json<Submission>, pool: &State<PointercratePool>,
-    ratelimits: &State<DemonlistRatelimits>,
+    ratelimits: &State<DemonlistRatelimits>, rules: &State<SubmissionRules>, hosts: &State<VideoHosts>, approvals: &State<ApprovalPolicy>,
+    activities: &State<ActivityStream>,
 ) -> Result<Tagged<FullRecord>> {
     let submission = submission.0;
-    let (is_team_member, user_id) = match auth {
-        Some(ref auth) => (auth.has_permission(LIST_HELPER), Some(auth.user.inner().id)),
-        None => (false, None),
+    let (is_team_member, is_moderator, user_id, user_name) = match auth {
+        Some(ref auth) => (
+            auth.has_permission(LIST_HELPER),
+            auth.has_permission(LIST_MODERATOR),
+            Some(auth.user.inner().id),
+            Some(auth.user.inner().name.clone()),
+        ),
+        None => (false, false, None, None),
     };
 
     if submission.status() != RecordStatus::Submitted || !submission.has_video() {
@@ -113,7 +142,9 @@ pub async fn submit(
         return Err(DemonlistError::BannedFromSubmissions.into());
     }
 
-    let normalized = submission.normalize(&mut *connection).await?;
+    let mut normalized = submission.normalize(hosts, &mut *connection).await?;
+
+    normalized.apply_approval_policy(approvals, is_moderator)?;
 
     // check if the player is claimed with submissions locked
     if let Some(claim) = normalized.verified_player_claim(&mut *connection).await? {
@@ -125,7 +156,7 @@ pub async fn submit(
         }
     }
 
-    let validated = normalized.validate(&mut *connection).await?;
+    let validated = normalized.validate(&submitter, rules, &mut *connection).await?;
 
     if !is_team_member {
         // Check ratelimits before any change is made to the database so that the transaction rollback is
@@ -138,19 +169,18 @@ pub async fn submit(
 
     let mut record = validated.create(submitter, &mut *connection).await?;
 
+    // New submissions are only announced once their video has been checked
+    if record.status == RecordStatus::Submitted && record.video.is_some() {
+        ValidateSubmissionVideo { record_id: record.id }.enqueue(&mut connection).await?;
+    }
+
+    let announced: Vec<Activity> = Activity::record_reviewed(&record, user_name.as_deref()).into_iter().collect();
+
+    notify_webhooks(&announced, &mut connection).await?;
+
     connection.commit().await.map_err(DemonlistError::from)?;
 
-    // FIXME: This is fucking stupid
-    if record.status == RecordStatus::Submitted {
-        if let Some(ref video) = record.video {
-            tokio::spawn(validate(
-                record.id,
-                video.to_string(),
-                webhook_embed(&record),
-                pool.connection().await?,
-            ));
-        }
-    }
+    activities.publish_all(announced);
 
     if !is_team_member {
         record.submitter = None;
@@ -179,6 +209,7 @@ pub async fn get(record_id: i32, auth: Option<TokenAuth>, pool: &State<Pointercr
             return Err(DemonlistError::RecordNotFound { record_id }.into());
         }
         record.submitter = None;
+        record.proofs.retain(|proof| proof.is_public);
     }
 
     Ok(Tagged(record))
@@ -199,25 +230,56 @@ pub async fn audit(record_id: i32, mut auth: TokenAuth) -> Result<Json<Vec<Audit
 
 #[rocket::patch("/<record_id>", data = "<patch>")]
 pub async fn patch(
-    record_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchRecord>,
+    record_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchRecord>, activities: &State<ActivityStream>,
+    hosts: &State<VideoHosts>, reasons: &State<StatusReasons>, approvals: &State<ApprovalPolicy>,
 ) -> Result<Tagged<FullRecord>> {
+    let mut patch = patch.0;
     let record = FullRecord::by_id(record_id, &mut auth.connection).await?;
+    let old_status = record.status;
 
-    if record.demon.position > pointercrate_demonlist::config::extended_list_size() {
+    // Helpers can only manage records on demons in sections that are still open for submissions
+    let section = ListSection::of_demon(record.demon.id, &mut auth.connection).await?;
+
+    if !section.map(|section| section.submissions_open).unwrap_or(false) {
         auth.require_permission(LIST_MODERATOR)?;
     } else {
         auth.require_permission(LIST_HELPER)?;
     }
 
-    let record = record
-        .require_match(precondition)?
-        .apply_patch(patch.0, &mut auth.connection)
+    let record = record.require_match(precondition)?;
+
+    // Only whoever claimed a record for review gets to decide on it, unless a moderator steps in
+    if patch.changes_status() && !auth.has_permission(LIST_MODERATOR) {
+        ReviewClaim::require_unclaimed_or_held_by(record.id, auth.user.inner().id, &mut auth.connection).await?;
+    }
+
+    // Approvals of records on highly placed demons might need to be signed off by a second team member
+    patch
+        .apply_approval_policy(
+            &record,
+            approvals,
+            auth.user.inner().id,
+            auth.has_permission(LIST_MODERATOR),
+            &mut auth.connection,
+        )
         .await?;
 
-    tokio::spawn(execute_webhook(webhook_status_embed(&record, &auth.user.inner().name)));
+    let record = record.apply_patch(patch, hosts, reasons, &mut auth.connection).await?;
+
+    let announced: Vec<Activity> = if record.status != old_status {
+        Activity::record_reviewed(&record, Some(&auth.user.inner().name))
+            .into_iter()
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
 
     auth.commit().await?;
 
+    activities.publish_all(announced);
+
     Ok(Tagged(record))
 }
 
@@ -239,6 +301,34 @@ pub async fn delete(record_id: i32, mut auth: TokenAuth, precondition: Precondit
     Ok(Status::NoContent)
 }
 
+/// Changes the status of, deletes, or reassigns a whole set of records at once
+///
+/// The records are selected either by id or by a filter using the same fields as record pagination.
+/// If `dry_run` is set, nothing is changed, and only the records that would have been affected are
+/// returned.
+#[rocket::post("/bulk", data = "<operation>")]
+pub async fn bulk(
+    mut auth: TokenAuth, operation: Json<BulkOperation>, reasons: &State<StatusReasons>, activities: &State<ActivityStream>,
+) -> Result<Json<BulkOutcome>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let outcome = operation.0.apply(reasons, &mut auth.connection).await?;
+
+    let announced = outcome
+        .reviewed
+        .iter()
+        .filter_map(|record| Activity::record_reviewed(record, Some(&auth.user.inner().name)))
+        .collect::<Vec<_>>();
+
+    notify_webhooks(&announced, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    activities.publish_all(announced);
+
+    Ok(Json(outcome))
+}
+
 #[rocket::get("/<record_id>/notes")]
 pub async fn get_notes(record_id: i32, mut auth: TokenAuth) -> Result<Response2<Json<Vec<Note>>>> {
     let record_holder_id = sqlx::query!("SELECT player FROM records WHERE id = $1", record_id)
@@ -319,109 +409,207 @@ pub async fn delete_note(record_id: i32, note_id: i32, mut auth: TokenAuth) -> R
     Ok(Status::NoContent)
 }
 
-async fn validate(record_id: i32, video: String, body: serde_json::Value, mut connection: PoolConnection<Postgres>) {
-    debug!("Verifying that submission {} with video {} actually is valid", record_id, video);
+#[rocket::get("/note-templates")]
+pub async fn note_templates(mut auth: TokenAuth) -> Result<Json<Vec<NoteTemplate>>> {
+    auth.require_permission(LIST_HELPER)?;
 
-    match reqwest::get(&video).await {
-        Ok(response) => {
-            let status = response.status().as_u16();
+    Ok(Json(NoteTemplate::all(&mut auth.connection).await?))
+}
 
-            if (200..400).contains(&status) {
-                debug!("GET request yielded some sort of successful response, executing webhook");
+#[rocket::post("/note-templates", data = "<data>")]
+pub async fn add_note_template(mut auth: TokenAuth, data: Json<NewNoteTemplate>) -> Result<Response2<Tagged<NoteTemplate>>> {
+    auth.require_permission(LIST_MODERATOR)?;
 
-                execute_webhook(body).await;
-            } else {
-                warn!("Server response to 'GET {}' was {:?}, deleting submission!", video, response);
+    let created_by = auth.user.inner().id;
+    let template = NoteTemplate::create_from(data.0, created_by, &mut auth.connection).await?;
 
-                match FullRecord::delete_by_id(record_id, &mut *connection).await {
-                    Ok(_) => (),
-                    Err(error) => error!("INTERNAL SERVER ERROR: Failure to delete record - {:?}!", error),
-                }
-            }
-        },
-        Err(error) => {
-            error!(
-                "INTERNAL SERVER ERROR: GET request to verify video failed: {:?}. Deleting submission",
-                error
-            );
-
-            match FullRecord::delete_by_id(record_id, &mut *connection).await {
-                Ok(_) => (),
-                Err(error) => error!("INTERNAL SERVER ERROR: Failure to delete record - {:?}!", error),
-            }
-        },
-    }
+    auth.commit().await?;
+
+    let location = format!("/api/v1/records/note-templates/{}/", template.id);
+
+    Ok(Response2::tagged(template)
+        .status(Status::Created)
+        .with_header("Location", location))
+}
+
+#[rocket::patch("/note-templates/<template_id>", data = "<patch>")]
+pub async fn patch_note_template(
+    template_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchNoteTemplate>,
+) -> Result<Tagged<NoteTemplate>> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    let template = NoteTemplate::by_id(template_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .apply_patch(patch.0, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(template))
+}
+
+// Explicitly ranked, as it would otherwise collide with `DELETE /<record_id>/review`
+#[rocket::delete("/note-templates/<template_id>", rank = 1)]
+pub async fn delete_note_template(template_id: i32, mut auth: TokenAuth, precondition: Precondition) -> Result<Status> {
+    auth.require_permission(LIST_MODERATOR)?;
+
+    NoteTemplate::by_id(template_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .delete(&mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+#[rocket::post("/<record_id>/proofs", data = "<data>")]
+pub async fn add_proof(
+    record_id: i32, mut auth: TokenAuth, data: Json<NewProof>, hosts: &State<VideoHosts>,
+) -> Result<Response2<Tagged<Proof>>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let record = FullRecord::by_id(record_id, &mut auth.connection).await?;
+
+    let proof = Proof::create_on(&record, data.0, hosts, &mut auth.connection).await?;
+    let proof_id = proof.id;
+
+    auth.commit().await?;
+
+    Ok(Response2::tagged(proof)
+        .status(Status::Created)
+        .with_header("Location", format!("/api/v1/records/{}/proofs/{}/", record.id, proof_id)))
 }
 
-async fn execute_webhook(body: serde_json::Value) {
-    if let Some(ref webhook_url) = crate::config::submission_webhook() {
-        match reqwest::Client::new()
-            .post(webhook_url)
-            .header("Content-Type", "application/json")
-            .body(body.to_string())
-            .send()
-            .await
-        {
-            Err(error) => error!("INTERNAL SERVER ERROR: Failure to execute discord webhook: {:?}", error),
-            Ok(_) => debug!("Successfully executed discord webhook"),
+#[rocket::get("/<record_id>/proofs/<proof_id>")]
+pub async fn get_proof(record_id: i32, proof_id: i32, auth: Option<TokenAuth>, pool: &State<PointercratePool>) -> Result<Tagged<Proof>> {
+    let is_helper = match auth {
+        Some(ref auth) => auth.has_permission(LIST_HELPER),
+        _ => false,
+    };
+
+    let mut connection = match auth {
+        Some(auth) => auth.connection,
+        None => pool.transaction().await?,
+    };
+
+    let proof = Proof::by_id(record_id, proof_id, &mut *connection).await?;
+
+    // Same visibility rules as for the proof embedded in the record itself
+    if !is_helper {
+        let record = FullRecord::by_id(record_id, &mut *connection).await?;
+
+        if record.status != RecordStatus::Approved || !proof.is_public {
+            return Err(DemonlistError::ProofNotFound { proof_id, record_id }.into());
         }
-    } else {
-        warn!("Trying to execute webhook, though no link was configured!");
     }
+
+    Ok(Tagged(proof))
 }
 
-fn webhook_embed(record: &FullRecord) -> serde_json::Value {
-    let mut payload = serde_json::json!({
-        "content": format!("**New record submitted! ID: {}**", record.id),
-        "embeds": [
-            {
-                "type": "rich",
-                "title": format!("{}% on {}", record.progress, record.demon.name),
-                "description": format!("{} just got {}% on {}! Go add their record!", record.player.name, record.progress, record.demon.name),
-                "footer": {
-                    "text": format!("This record has been submitted by submitter #{}", record.submitter.map(|s|s.id).unwrap_or(1))
-                },
-                "author": {
-                    "name": format!("{} (ID: {})", record.player.name, record.player.id),
-                    "url": record.video
-                },
-                "thumbnail": {
-                    "url": "https://cdn.discordapp.com/emojis/561867333476286464.png?size=1024"
-                },
-            }
-        ]
-    });
-
-    if let Some(ref video) = record.video {
-        payload["embeds"][0]["fields"] = serde_json::json! {
-            [{
-                "name": "Video Proof:",
-                "value": video
-            }]
-        };
+#[rocket::patch("/<record_id>/proofs/<proof_id>", data = "<patch>")]
+pub async fn patch_proof(
+    record_id: i32, proof_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchProof>,
+) -> Result<Tagged<Proof>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let proof = Proof::by_id(record_id, proof_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .apply_patch(patch.0, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(proof))
+}
+
+#[rocket::delete("/<record_id>/proofs/<proof_id>")]
+pub async fn delete_proof(record_id: i32, proof_id: i32, mut auth: TokenAuth, precondition: Precondition) -> Result<Status> {
+    auth.require_permission(LIST_HELPER)?;
+
+    Proof::by_id(record_id, proof_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .delete(&mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+/// Lists which list team member is currently reviewing which record
+#[rocket::get("/reviews")]
+pub async fn reviews(mut auth: TokenAuth) -> Result<Response2<Json<Vec<ReviewClaim>>>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    Ok(Response2::json(ReviewClaim::all(&mut auth.connection).await?))
+}
+
+/// Claims the submission that should be reviewed next, optionally restricted to the list with the
+/// given id
+#[rocket::post("/reviews/next?<list>")]
+pub async fn claim_next_review(list: Option<i32>, mut auth: TokenAuth) -> Result<Response2<Tagged<ReviewClaim>>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let claim = ReviewClaim::claim_next(auth.user.inner().id, list, &mut auth.connection).await?;
+    let record_id = claim.record;
+
+    auth.commit().await?;
+
+    Ok(Response2::tagged(claim)
+        .status(Status::Created)
+        .with_header("Location", format!("/api/v1/records/{}/", record_id)))
+}
+
+/// Claims the given record for review, or renews the claim if it is already held by the requesting
+/// user
+#[rocket::put("/<record_id>/review")]
+pub async fn claim_review(record_id: i32, mut auth: TokenAuth) -> Result<Tagged<ReviewClaim>> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let record = FullRecord::by_id(record_id, &mut auth.connection).await?;
+    let claim = ReviewClaim::claim(&record, auth.user.inner().id, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(claim))
+}
+
+#[rocket::delete("/<record_id>/review")]
+pub async fn release_review(record_id: i32, mut auth: TokenAuth) -> Result<Status> {
+    let claim = ReviewClaim::by_record(record_id, &mut auth.connection).await?;
+
+    // Moderators can release claims of others, e.g. if someone claimed a record and then went offline
+    if claim.reviewer != auth.user.inner().id {
+        auth.require_permission(LIST_MODERATOR)?;
+    } else {
+        auth.require_permission(LIST_HELPER)?;
     }
 
-    payload
+    claim.release(&mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
 }
 
-fn webhook_status_embed(record: &FullRecord, edited_by: &str) -> serde_json::Value {
-    let payload = serde_json::json!({
-        "content": format!("**Record edited! ID: {}**", record.id),
-        "embeds": [
-            {
-                "type": "rich",
-                "title": format!("{}% on {}", record.progress, record.demon.name),
-                "description": format!("The status of {}'s record has been set to `{}`!", record.player.name, record.status),
-                "author": {
-                    "name": format!("Owner: {} (ID: {})", record.player.name, record.player.id),
-                    "url": record.video
-                },
-                "footer": {
-                    "text": format!("Edited by {}", edited_by),
-                },
-            }
-        ]
-    });
+/// Lists the reasons records can be rejected or put under consideration for
+#[rocket::get("/reasons")]
+pub fn reasons(reasons: &State<StatusReasons>) -> Json<Vec<StatusReason>> {
+    Json(reasons.all().to_vec())
+}
+
+/// Allows submitters to look up the status of their submission via the token they were given when
+/// submitting it
+#[rocket::get("/status?<token>")]
+pub async fn submission_status(
+    token: &str, pool: &State<PointercratePool>, reasons: &State<StatusReasons>,
+) -> Result<Response2<Json<SubmissionStatus>>> {
+    let mut connection = pool.connection().await?;
 
-    payload
+    Ok(Response2::json(SubmissionStatus::by_token(token, reasons, &mut connection).await?))
 }
@@ -0,0 +1,87 @@
+use pointercrate_core_api::{
+    error::Result,
+    etag::{Precondition, TaggableExt, Tagged},
+    response::Response2,
+};
+use pointercrate_demonlist::{
+    webhook::{PatchWebhook, PostWebhook, RegisteredWebhook, Webhook, WebhookDelivery},
+    LIST_ADMINISTRATOR,
+};
+use pointercrate_user_api::auth::TokenAuth;
+use rocket::{http::Status, serde::json::Json};
+
+/// How many deliveries the delivery log returns if no limit is given
+const DEFAULT_LOG_LENGTH: i64 = 50;
+
+#[rocket::get("/")]
+pub async fn get_all(mut auth: TokenAuth) -> Result<Json<Vec<Webhook>>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    Ok(Json(Webhook::all(&mut auth.connection).await?))
+}
+
+/// Registers a new webhook. This is the only response that contains the webhook's secret
+#[rocket::post("/", data = "<data>")]
+pub async fn post(mut auth: TokenAuth, data: Json<PostWebhook>) -> Result<Response2<Tagged<RegisteredWebhook>>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    let created_by = auth.user.inner().id;
+    let webhook = Webhook::create_from(data.0, created_by, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    let location = format!("/api/v2/webhooks/{}/", webhook.id);
+
+    Ok(Response2::tagged(RegisteredWebhook::from(webhook))
+        .status(Status::Created)
+        .with_header("Location", location))
+}
+
+#[rocket::get("/<webhook_id>")]
+pub async fn get(webhook_id: i32, mut auth: TokenAuth) -> Result<Tagged<Webhook>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    Ok(Tagged(Webhook::by_id(webhook_id, &mut auth.connection).await?))
+}
+
+#[rocket::patch("/<webhook_id>", data = "<patch>")]
+pub async fn patch(webhook_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchWebhook>) -> Result<Tagged<Webhook>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    let webhook = Webhook::by_id(webhook_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .apply_patch(patch.0, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(webhook))
+}
+
+#[rocket::delete("/<webhook_id>")]
+pub async fn delete(webhook_id: i32, mut auth: TokenAuth, precondition: Precondition) -> Result<Status> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    Webhook::by_id(webhook_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .delete(&mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+/// The most recent deliveries to the given webhook, including all attempts at sending them
+#[rocket::get("/<webhook_id>/deliveries?<limit>")]
+pub async fn deliveries(webhook_id: i32, limit: Option<i64>, mut auth: TokenAuth) -> Result<Json<Vec<WebhookDelivery>>> {
+    auth.require_permission(LIST_ADMINISTRATOR)?;
+
+    // Make sure we 404 for webhooks that do not exist
+    let webhook = Webhook::by_id(webhook_id, &mut auth.connection).await?;
+    let limit = limit.unwrap_or(DEFAULT_LOG_LENGTH).clamp(1, 100);
+
+    Ok(Json(WebhookDelivery::of_webhook(webhook.id, limit, &mut auth.connection).await?))
+}
@@ -0,0 +1,58 @@
+//! Caching for rendered Atom feeds
+//!
+//! Feed readers poll feeds periodically, and building a feed means digging through the audit logs.
+//! Rendered feeds are therefore kept in memory for a few minutes, delaying new entries by at most
+//! that long.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// How long a rendered feed is served before it is rendered again
+const FEED_CACHE_DURATION: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub(crate) enum FeedKey {
+    /// The feed of changes to the list with the given id
+    Changes(i32),
+
+    /// The feed of approved records on the list with the given id, optionally restricted to a
+    /// player (by id) or to the players of a nation (by ISO country code)
+    Records(i32, Option<i32>, Option<String>),
+}
+
+struct CachedFeed {
+    rendered_at: Instant,
+    xml: String,
+}
+
+#[derive(Default)]
+pub(crate) struct FeedCache {
+    feeds: Mutex<HashMap<FeedKey, CachedFeed>>,
+}
+
+impl FeedCache {
+    /// Gets the cached feed for the given key, unless it has expired
+    pub(crate) fn get(&self, key: &FeedKey) -> Option<String> {
+        match self.feeds.lock().unwrap().get(key) {
+            Some(cached) if cached.rendered_at.elapsed() < FEED_CACHE_DURATION => Some(cached.xml.clone()),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, key: FeedKey, xml: String) {
+        let mut feeds = self.feeds.lock().unwrap();
+
+        // There is a feed per player, so get rid of the ones nobody is polling
+        feeds.retain(|_, cached| cached.rendered_at.elapsed() < FEED_CACHE_DURATION);
+        feeds.insert(
+            key,
+            CachedFeed {
+                rendered_at: Instant::now(),
+                xml,
+            },
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Background jobs of the demonlist, run via the [job queue](pointercrate_core_api::job)
+
+use crate::{
+    activity::{Activity, ActivityStream},
+    webhook::notify_webhooks,
+};
+use log::{debug, warn};
+use pointercrate_core::pool::PointercratePool;
+use pointercrate_core_api::job::{Attempt, Job, JobHandler, JobResult};
+use pointercrate_demonlist::{
+    error::DemonlistError,
+    record::{FullRecord, RecordStatus},
+};
+use pointercrate_integrate::gd::GeometryDashConnector;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+/// Requests the given video, returning why it cannot be reached if the video host says that it
+/// does not exist (anymore)
+///
+/// Failures that might be temporary (network errors, server errors, being rate limited) are
+/// returned as errors instead, so that the job doing the check is retried later with backoff. Only
+/// a definitive answer from the video host gets a video flagged as unreachable.
+async fn check_video(video: &str) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let response = reqwest::get(video).await?;
+    let status = response.status();
+
+    if status.is_success() || status.is_redirection() {
+        return Ok(None);
+    }
+
+    if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+        return Err(format!("'GET {}' failed with status {}, retrying later", video, status).into());
+    }
+
+    warn!("Server response to 'GET {}' was {:?}", video, response);
+
+    Ok(Some(format!("'GET {}' returned status {}", video, status)))
+}
+
+/// Checks that the video of a newly submitted record actually exists, flagging the submission if
+/// it does not. Only once the video has been checked is the submission announced to the list team,
+/// or once the last attempt at checking it was inconclusive.
+#[derive(Serialize, Deserialize)]
+pub struct ValidateSubmissionVideo {
+    pub record_id: i32,
+}
+
+impl Job for ValidateSubmissionVideo {
+    const KIND: &'static str = "validate_submission_video";
+}
+
+pub struct SubmissionVideoValidator {
+    pub pool: PointercratePool,
+    pub activities: ActivityStream,
+}
+
+#[rocket::async_trait]
+impl JobHandler for SubmissionVideoValidator {
+    type Job = ValidateSubmissionVideo;
+
+    async fn run(&self, job: ValidateSubmissionVideo, attempt: Attempt) -> JobResult {
+        let mut connection = self.pool.transaction().await?;
+
+        let mut record = match FullRecord::by_id(job.record_id, &mut connection).await {
+            Ok(record) => record,
+            Err(DemonlistError::RecordNotFound { .. }) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        // The list team got to the submission before we did
+        if record.status != RecordStatus::Submitted {
+            return Ok(());
+        }
+
+        let Some(video) = record.video.clone() else { return Ok(()) };
+
+        debug!("Verifying that submission {} with video {} actually is valid", record.id, video);
+
+        match check_video(&video).await {
+            Ok(None) => record.mark_video_reachable(&mut connection).await?,
+            Ok(Some(reason)) => record.flag_video_unreachable(&reason, &mut connection).await?,
+            // Rather announce the submission without a verdict on its video than not at all
+            Err(err) if attempt.is_final() => warn!("Giving up on checking the video of submission {}: {}", record.id, err),
+            Err(err) => return Err(err),
+        }
+
+        let announced = vec![Activity::RecordSubmitted {
+            record: Activity::record(&record),
+            submitter: record.submitter.as_ref().map(|submitter| submitter.id),
+        }];
+
+        notify_webhooks(&announced, &mut connection).await?;
+
+        connection.commit().await.map_err(DemonlistError::from)?;
+
+        self.activities.publish_all(announced);
+
+        Ok(())
+    }
+}
+
+/// Checks that the video of an approved record can still be reached, flagging it for the list team
+/// if it cannot. Queued periodically by the [`VideoRecheckScheduler`](crate::scheduler::VideoRecheckScheduler).
+#[derive(Serialize, Deserialize)]
+pub struct RecheckRecordVideo {
+    pub record_id: i32,
+}
+
+impl Job for RecheckRecordVideo {
+    const KIND: &'static str = "recheck_record_video";
+}
+
+pub struct RecordVideoChecker(pub PointercratePool);
+
+#[rocket::async_trait]
+impl JobHandler for RecordVideoChecker {
+    type Job = RecheckRecordVideo;
+
+    async fn run(&self, job: RecheckRecordVideo, _: Attempt) -> JobResult {
+        let mut connection = self.0.connection().await?;
+
+        let mut record = match FullRecord::by_id(job.record_id, &mut connection).await {
+            Ok(record) => record,
+            Err(DemonlistError::RecordNotFound { .. }) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+
+        // The video might have been removed since the check was queued
+        let Some(video) = record.video.clone() else { return Ok(()) };
+
+        match check_video(&video).await? {
+            None => record.mark_video_reachable(&mut connection).await?,
+            Some(reason) => record.flag_video_unreachable(&reason, &mut connection).await?,
+        }
+
+        Ok(())
+    }
+}
+
+/// Fetches updated level data for a demon from the Geometry Dash servers
+#[derive(Serialize, Deserialize)]
+pub struct RefreshLevelData {
+    pub demon_id: i32,
+    pub name: String,
+}
+
+impl Job for RefreshLevelData {
+    const KIND: &'static str = "refresh_level_data";
+}
+
+pub struct LevelDataRefresher(pub GeometryDashConnector);
+
+#[rocket::async_trait]
+impl JobHandler for LevelDataRefresher {
+    type Job = RefreshLevelData;
+
+    async fn run(&self, job: RefreshLevelData, _: Attempt) -> JobResult {
+        Ok(self.0.refresh_demon_data(&job.name, job.demon_id).await?)
+    }
+}
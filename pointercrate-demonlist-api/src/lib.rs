@@ -1,21 +1,86 @@
-use crate::{endpoints::misc, ratelimits::DemonlistRatelimits};
+use crate::{
+    activity::ActivityStream,
+    default_list::DefaultListResolver,
+    endpoints::misc,
+    feed::FeedCache,
+    jobs::{LevelDataRefresher, RecordVideoChecker, SubmissionVideoValidator},
+    preview::PreviewCache,
+    ratelimits::DemonlistRatelimits,
+    scheduler::{ListUpdateScheduler, VideoRecheckScheduler},
+    webhook::{ConfiguredWebhookRegistrar, WebhookSender},
+};
 use pointercrate_core::pool::PointercratePool;
+use pointercrate_core_api::job::{JobQueue, JobWorkers};
+use pointercrate_demonlist::{
+    record::{approval::ApprovalPolicy, reason::StatusReasons, rules::SubmissionRules},
+    video::VideoHosts,
+};
 use pointercrate_integrate::gd::GeometryDashConnector;
 use rocket::{Build, Rocket};
 
+pub(crate) mod activity;
 pub(crate) mod config;
+pub(crate) mod default_list;
 mod endpoints;
+pub(crate) mod feed;
+pub(crate) mod jobs;
 pub(crate) mod pages;
+pub(crate) mod preview;
 pub(crate) mod ratelimits;
+pub(crate) mod scheduler;
+pub(crate) mod webhook;
+
+pub use scheduler::publish_due_list_updates;
 
 pub fn setup(rocket: Rocket<Build>) -> Rocket<Build> {
     let ratelimits = DemonlistRatelimits::new();
-    let dash_rs = GeometryDashConnector::new(rocket.state::<PointercratePool>().unwrap().clone_inner());
+    let pool = rocket.state::<PointercratePool>().unwrap().clone_inner();
+    let dash_rs = GeometryDashConnector::new(pool.clone());
+    let activities = ActivityStream::default();
+
+    // Deployments can register their own submission rules by managing a `SubmissionRules` instance before calling this function
+    let rocket = manage_default::<SubmissionRules>(rocket);
+
+    // Same for the video hosts videos can be uploaded to
+    let rocket = manage_default::<VideoHosts>(rocket);
+
+    // And for the reasons records can be rejected or put under consideration for
+    let rocket = manage_default::<StatusReasons>(rocket);
+
+    // And for how many list team members need to approve records
+    let rocket = manage_default::<ApprovalPolicy>(rocket);
+
+    // Other components might have already set up the job queue
+    let rocket = match rocket.state::<JobQueue>() {
+        Some(_) => rocket,
+        None => rocket.manage(JobQueue::default()).attach(JobWorkers),
+    };
+
+    let jobs = rocket.state::<JobQueue>().unwrap();
+
+    jobs.register(SubmissionVideoValidator {
+        pool: PointercratePool::from(pool.clone()),
+        activities: activities.clone(),
+    });
+    jobs.register(RecordVideoChecker(PointercratePool::from(pool.clone())));
+    jobs.register(LevelDataRefresher(dash_rs.clone()));
+    jobs.register(WebhookSender {
+        pool: PointercratePool::from(pool),
+        client: reqwest::Client::new(),
+    });
 
     rocket
         .manage(ratelimits)
         .manage(dash_rs)
+        .manage(PreviewCache::default())
+        .manage(FeedCache::default())
+        .manage(activities)
+        .attach(DefaultListResolver)
+        .attach(ListUpdateScheduler)
+        .attach(VideoRecheckScheduler)
+        .attach(ConfiguredWebhookRegistrar)
         .mount("/api/v1/list_information/", rocket::routes![misc::list_information])
+        .mount("/api/v2/activity/", rocket::routes![endpoints::activity::stream])
         .mount(
             "/api/v1/submitters/",
             rocket::routes![
@@ -31,12 +96,27 @@ pub fn setup(rocket: Rocket<Build>) -> Rocket<Build> {
                 endpoints::record::add_note,
                 endpoints::record::audit,
                 endpoints::record::delete,
+                endpoints::record::bulk,
                 endpoints::record::delete_note,
                 endpoints::record::get,
                 endpoints::record::paginate,
                 endpoints::record::unauthed_pagination,
                 endpoints::record::patch,
                 endpoints::record::patch_note,
+                endpoints::record::note_templates,
+                endpoints::record::add_note_template,
+                endpoints::record::patch_note_template,
+                endpoints::record::delete_note_template,
+                endpoints::record::add_proof,
+                endpoints::record::get_proof,
+                endpoints::record::patch_proof,
+                endpoints::record::delete_proof,
+                endpoints::record::reviews,
+                endpoints::record::claim_next_review,
+                endpoints::record::claim_review,
+                endpoints::record::release_review,
+                endpoints::record::reasons,
+                endpoints::record::submission_status,
                 endpoints::record::submit
             ],
         )
@@ -62,6 +142,35 @@ pub fn setup(rocket: Rocket<Build>) -> Rocket<Build> {
                 endpoints::nationality::nation
             ],
         )
+        .mount(
+            "/api/v2/lists/",
+            rocket::routes![
+                endpoints::list::get_all,
+                endpoints::list::post,
+                endpoints::list::get,
+                endpoints::list::paginate_demons,
+                endpoints::list::paginate_listed_demons,
+                endpoints::list::post_demon,
+                endpoints::list::reorder_demons,
+                endpoints::list::player_ranking,
+                endpoints::list::get_player,
+                endpoints::list::nation_ranking,
+                endpoints::list::nation,
+                endpoints::list::sections,
+                endpoints::list::post_section,
+                endpoints::list::get_section,
+                endpoints::list::patch_section,
+                endpoints::list::delete_section,
+                endpoints::list::updates,
+                endpoints::list::post_update,
+                endpoints::list::get_update,
+                endpoints::list::patch_update,
+                endpoints::list::delete_update,
+                endpoints::list::publish_update,
+                endpoints::list::proposals,
+                endpoints::list::post_proposal
+            ],
+        )
         .mount(
             "/api/v2/demons/",
             rocket::routes![
@@ -70,12 +179,37 @@ pub fn setup(rocket: Rocket<Build>) -> Rocket<Build> {
                 endpoints::demon::paginate_listed,
                 endpoints::demon::audit,
                 endpoints::demon::movement_log,
+                endpoints::demon::proposals,
                 endpoints::demon::patch,
+                endpoints::demon::reorder,
+                endpoints::demon::backfill_thumbnails,
+                endpoints::demon::delete,
                 endpoints::demon::post,
                 endpoints::demon::post_creator,
                 endpoints::demon::delete_creator
             ],
         )
+        .mount(
+            "/api/v2/proposals/",
+            rocket::routes![
+                endpoints::proposal::get,
+                endpoints::proposal::put_opinion,
+                endpoints::proposal::delete_opinion,
+                endpoints::proposal::accept,
+                endpoints::proposal::reject
+            ],
+        )
+        .mount(
+            "/api/v2/webhooks/",
+            rocket::routes![
+                endpoints::webhook::get_all,
+                endpoints::webhook::post,
+                endpoints::webhook::get,
+                endpoints::webhook::patch,
+                endpoints::webhook::delete,
+                endpoints::webhook::deliveries
+            ],
+        )
         .mount(
             "/demonlist/",
             rocket::routes![
@@ -85,7 +219,39 @@ pub fn setup(rocket: Rocket<Build>) -> Rocket<Build> {
                 pages::nation_stats_viewer,
                 pages::demon_page,
                 pages::demon_permalink,
-                pages::heatmap_css
+                pages::list_update_preview,
+                pages::submission_status,
+                pages::demon_card,
+                pages::player_card,
+                pages::heatmap_css,
+                pages::changes_feed,
+                pages::records_feed,
+                pages::player_records_feed,
+                pages::nation_records_feed
             ],
         )
+        .mount(
+            "/lists/",
+            rocket::routes![
+                pages::list_overview,
+                pages::list_stats_viewer,
+                pages::list_nation_stats_viewer,
+                pages::list_demon_page,
+                pages::list_player_card,
+                pages::list_heatmap_css,
+                pages::list_changes_feed,
+                pages::list_records_feed,
+                pages::list_player_records_feed,
+                pages::list_nation_records_feed
+            ],
+        )
+}
+
+/// Manages the default instance of `T`, unless the deployment already put its own one under
+/// rocket's management
+fn manage_default<T: Default + Send + Sync + 'static>(rocket: Rocket<Build>) -> Rocket<Build> {
+    match rocket.state::<T>() {
+        Some(_) => rocket,
+        None => rocket.manage(T::default()),
+    }
 }
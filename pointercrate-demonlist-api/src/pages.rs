@@ -1,47 +1,87 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use rocket::{response::Redirect, State};
+use rocket::{response::Redirect, tokio, State};
 
+use crate::{
+    default_list::DefaultList,
+    feed::{FeedCache, FeedKey},
+    jobs::RefreshLevelData,
+    preview::{CardKey, PreviewCache},
+};
 use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
-use pointercrate_core::{audit::AuditLogEntryType, pool::PointercratePool};
+use log::warn;
+use pointercrate_core::{audit::AuditLogEntryType, error::CoreError, pool::PointercratePool};
 use pointercrate_core_api::{
     error::Result,
+    job::Job,
+    query::Query,
     response::{Page, Response2},
 };
-use pointercrate_core_pages::head::HeadLike;
+use pointercrate_core_pages::{head::HeadLike, util::PrerenderedPage};
 use pointercrate_demonlist::{
-    demon::{audit::audit_log_for_demon, current_list, list_at, FullDemon, MinimalDemon},
+    demon::{
+        audit::{audit_log_for_demon, recent_list_changes},
+        current_list, list_at, FullDemon, MinimalDemon,
+    },
     error::DemonlistError,
-    nationality::Nationality,
+    list::{section::ListSection, update::ListUpdate, List},
+    nationality::{Nationality, NationalityRankingPagination},
+    player::{DatabasePlayer, Player, RankingPagination},
+    record::{audit::recently_approved_records, lookup::SubmissionStatus, reason::StatusReasons},
+    video::VideoHosts,
     LIST_ADMINISTRATOR, LIST_HELPER, LIST_MODERATOR,
 };
 use pointercrate_demonlist_pages::{
     components::{team::Team, time_machine::Tardis},
     demon_page::{DemonMovement, DemonPage},
+    feed::{AtomFeed, ATOM_CONTENT_TYPE},
     overview::OverviewPage,
+    preview::{DemonCard, PlayerCard, PreviewCard},
     statsviewer::individual::IndividualStatsViewer,
+    submission_status::SubmissionStatusPage,
 };
 use pointercrate_integrate::gd::GeometryDashConnector;
 use pointercrate_user::User;
 use pointercrate_user_api::auth::TokenAuth;
 use rand::Rng;
 use rocket::{futures::StreamExt, http::CookieJar};
+use sqlx::PgConnection;
 
 #[rocket::get("/?statsviewer=true")]
 pub fn stats_viewer_redirect() -> Redirect {
-    Redirect::to(rocket::uri!(stats_viewer))
+    Redirect::to(rocket::uri!(stats_viewer(player = _)))
 }
 
 #[rocket::get("/?<timemachine>&<submitter>")]
 pub async fn overview(
     pool: &State<PointercratePool>, timemachine: Option<bool>, submitter: Option<bool>, cookies: &CookieJar<'_>, auth: Option<TokenAuth>,
+    default_list: &State<DefaultList>,
 ) -> Result<Page> {
-    // A few months before pointercrate first went live - definitely the oldest data we have
-    let beginning_of_time = NaiveDate::from_ymd_opt(2019, 4, 19).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+
+    list_overview_page(list, &mut connection, timemachine, submitter, cookies, auth).await
+}
 
+#[rocket::get("/<slug>?<timemachine>&<submitter>")]
+pub async fn list_overview(
+    slug: &str, pool: &State<PointercratePool>, timemachine: Option<bool>, submitter: Option<bool>, cookies: &CookieJar<'_>,
+    auth: Option<TokenAuth>,
+) -> Result<Page> {
     let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
 
-    let demonlist = current_list(&mut *connection).await?;
+    list_overview_page(list, &mut connection, timemachine, submitter, cookies, auth).await
+}
+
+async fn list_overview_page(
+    list: List, connection: &mut PgConnection, timemachine: Option<bool>, submitter: Option<bool>, cookies: &CookieJar<'_>,
+    auth: Option<TokenAuth>,
+) -> Result<Page> {
+    // A few months before pointercrate first went live - definitely the oldest data we have
+    let beginning_of_time = NaiveDate::from_ymd_opt(2019, 4, 19).unwrap().and_hms_opt(0, 0, 0).unwrap();
+
+    let demonlist = current_list(list.id, &mut *connection).await?;
 
     let mut specified_when = cookies
         .get("when")
@@ -72,11 +112,13 @@ pub async fn overview(
     let mut tardis = Tardis::new(timemachine.unwrap_or(false));
 
     if let Some(destination) = specified_when {
-        let demons_then = list_at(&mut *connection, destination.naive_utc()).await?;
+        let demons_then = list_at(list.id, &mut *connection, destination.naive_utc()).await?;
         tardis.activate(destination, demons_then, !is_april_1st)
     }
 
     let mut page = Page::new(OverviewPage {
+        sections: ListSection::of_list(list.id, &mut *connection).await?,
+        list,
         team: Team {
             admins: User::by_permission(LIST_ADMINISTRATOR, &mut *connection).await?,
             moderators: User::by_permission(LIST_MODERATOR, &mut *connection).await?,
@@ -94,13 +136,53 @@ pub async fn overview(
     Ok(page)
 }
 
+#[rocket::get("/updates/<update_id>/preview")]
+pub async fn list_update_preview(update_id: i32, mut auth: TokenAuth, hosts: &State<VideoHosts>) -> Result<Page> {
+    auth.require_permission(LIST_HELPER)?;
+
+    let connection = &mut *auth.connection;
+
+    let update = ListUpdate::by_id(update_id, connection).await?;
+    let list = List::by_id(update.list, connection).await?;
+    let demons_after = update.preview(hosts, connection).await?;
+
+    let page = Page::new(OverviewPage {
+        sections: ListSection::of_list(list.id, connection).await?,
+        team: Team {
+            admins: User::by_permission(LIST_ADMINISTRATOR, connection).await?,
+            moderators: User::by_permission(LIST_MODERATOR, connection).await?,
+            helpers: User::by_permission(LIST_HELPER, connection).await?,
+        },
+        demonlist: current_list(list.id, connection).await?,
+        list,
+        time_machine: Tardis::Preview {
+            title: update.title,
+            scheduled_for: update.scheduled_for,
+            demons: demons_after,
+        },
+        submitter_initially_visible: false,
+    });
+
+    Ok(page.meta("csrf_token", auth.user.generate_csrf_token()))
+}
+
+#[rocket::get("/submission?<token>")]
+pub async fn submission_status(token: &str, pool: &State<PointercratePool>, reasons: &State<StatusReasons>) -> Result<Page> {
+    let mut connection = pool.connection().await?;
+
+    let submission = SubmissionStatus::by_token(token, reasons, &mut connection).await?;
+
+    Ok(Page::new(SubmissionStatusPage { submission }))
+}
+
 #[rocket::get("/permalink/<demon_id>")]
 pub async fn demon_permalink(
-    demon_id: i32, pool: &State<PointercratePool>, gd: &State<GeometryDashConnector>, auth: Option<TokenAuth>,
+    demon_id: i32, pool: &State<PointercratePool>, gd: &State<GeometryDashConnector>, hosts: &State<VideoHosts>, auth: Option<TokenAuth>,
 ) -> Result<Page> {
     let mut connection = pool.connection().await?;
 
     let full_demon = FullDemon::by_id(demon_id, &mut connection).await?;
+    let list = List::by_id(full_demon.demon.list, &mut connection).await?;
 
     let audit_log = audit_log_for_demon(full_demon.demon.base.id, &mut *connection).await?;
 
@@ -138,16 +220,28 @@ pub async fn demon_permalink(
         );
     }
 
+    if gd.refresh_due(&full_demon.demon) {
+        RefreshLevelData {
+            demon_id: full_demon.demon.base.id,
+            name: full_demon.demon.base.name.clone(),
+        }
+        .enqueue(&mut connection)
+        .await?;
+    }
+
     let mut page = Page::new(DemonPage {
         team: Team {
             admins: User::by_permission(LIST_ADMINISTRATOR, &mut *connection).await?,
             moderators: User::by_permission(LIST_MODERATOR, &mut *connection).await?,
             helpers: User::by_permission(LIST_HELPER, &mut *connection).await?,
         },
-        demonlist: current_list(&mut *connection).await?,
+        demonlist: current_list(list.id, &mut connection).await?,
+        sections: ListSection::of_list(list.id, &mut connection).await?,
+        list,
         movements: modifications,
         integration: gd.load_level_for_demon(&full_demon.demon).await,
         data: full_demon,
+        video_hosts: hosts.inner().clone(),
     });
 
     if let Some(token_auth) = auth {
@@ -158,40 +252,248 @@ pub async fn demon_permalink(
 }
 
 #[rocket::get("/<position>")]
-pub async fn demon_page(position: i16, pool: &State<PointercratePool>) -> Result<Redirect> {
+pub async fn demon_page(position: i16, pool: &State<PointercratePool>, default_list: &State<DefaultList>) -> Result<Redirect> {
     let mut connection = pool.connection().await?;
 
-    let id = MinimalDemon::by_position(position, &mut connection).await?.id;
+    let list = default_list.0.clone();
+    let id = MinimalDemon::by_position(position, list.id, &mut connection).await?.id;
 
     Ok(Redirect::to(rocket::uri!("/demonlist", demon_permalink(id))))
 }
 
-#[rocket::get("/statsviewer")]
-pub async fn stats_viewer(pool: &State<PointercratePool>) -> Result<Page> {
+#[rocket::get("/<slug>/<position>")]
+pub async fn list_demon_page(slug: &str, position: i16, pool: &State<PointercratePool>) -> Result<Redirect> {
+    let mut connection = pool.connection().await?;
+
+    let list = List::by_slug(slug, &mut connection).await?;
+    let id = MinimalDemon::by_position(position, list.id, &mut connection).await?.id;
+
+    Ok(Redirect::to(rocket::uri!("/demonlist", demon_permalink(id))))
+}
+
+#[rocket::get("/permalink/<demon_id>/card.png")]
+pub async fn demon_card(demon_id: i32, pool: &State<PointercratePool>, cache: &State<PreviewCache>) -> Result<Response2<Vec<u8>>> {
+    let mut connection = pool.connection().await?;
+
+    let demon = FullDemon::by_id(demon_id, &mut connection).await?.demon;
+    let list = List::by_id(demon.list, &mut connection).await?;
+
+    let key = CardKey::Demon(demon.base.id);
+    let fingerprint = PreviewCache::fingerprint(&(&demon, &list));
+
+    let png = match cache.get(key, fingerprint) {
+        Some(png) => png,
+        None => {
+            let thumbnail = fetch_thumbnail(&demon.thumbnail).await;
+            let has_thumbnail = thumbnail.is_some();
+
+            let png = tokio::task::spawn_blocking(move || {
+                DemonCard {
+                    demon: &demon,
+                    list: &list,
+                    thumbnail: thumbnail.as_deref(),
+                }
+                .render_png()
+            })
+            .await
+            .ok()
+            .flatten()
+            .ok_or(CoreError::InternalServerError)?;
+
+            let png = Arc::new(png);
+
+            // Failing to download the thumbnail might be temporary, so try again on the next request
+            if has_thumbnail {
+                cache.insert(key, fingerprint, Arc::clone(&png));
+            }
+
+            png
+        },
+    };
+
+    Ok(png_response(&png))
+}
+
+#[rocket::get("/statsviewer/players/<player_id>/card.png")]
+pub async fn player_card(
+    player_id: i32, pool: &State<PointercratePool>, cache: &State<PreviewCache>, default_list: &State<DefaultList>,
+) -> Result<Response2<Vec<u8>>> {
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+
+    player_card_png(player_id, list, &mut connection, cache).await
+}
+
+#[rocket::get("/<slug>/statsviewer/players/<player_id>/card.png")]
+pub async fn list_player_card(
+    slug: &str, player_id: i32, pool: &State<PointercratePool>, cache: &State<PreviewCache>,
+) -> Result<Response2<Vec<u8>>> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    player_card_png(player_id, list, &mut connection, cache).await
+}
+
+async fn player_card_png(player_id: i32, list: List, connection: &mut PgConnection, cache: &PreviewCache) -> Result<Response2<Vec<u8>>> {
+    let player = Player::by_id(player_id, list.id, &mut *connection).await?;
+    let rank = player.rank(list.id, &mut *connection).await?;
+    let player = player.upgrade(list.id, connection).await?;
+
+    let key = CardKey::Player(player.player.base.id, player.list);
+    let fingerprint = PreviewCache::fingerprint(&(&player, rank, &list));
+
+    let png = match cache.get(key, fingerprint) {
+        Some(png) => png,
+        None => {
+            let png = tokio::task::spawn_blocking(move || {
+                PlayerCard {
+                    player: &player,
+                    list: &list,
+                    rank,
+                }
+                .render_png()
+            })
+            .await
+            .ok()
+            .flatten()
+            .ok_or(CoreError::InternalServerError)?;
+
+            let png = Arc::new(png);
+            cache.insert(key, fingerprint, Arc::clone(&png));
+            png
+        },
+    };
+
+    Ok(png_response(&png))
+}
+
+/// Downloads a demon's thumbnail for embedding into its preview card. Failure to do so is not
+/// fatal, the card is simply rendered without an image.
+async fn fetch_thumbnail(url: &str) -> Option<Vec<u8>> {
+    let client = reqwest::Client::builder().timeout(Duration::from_secs(5)).build().ok()?;
+
+    match client.get(url).send().await.and_then(|response| response.error_for_status()) {
+        Ok(response) => response.bytes().await.ok().map(|bytes| bytes.to_vec()),
+        Err(err) => {
+            warn!("Failed to retrieve thumbnail {} for preview card: {:?}", url, err);
+
+            None
+        },
+    }
+}
+
+fn png_response(png: &[u8]) -> Response2<Vec<u8>> {
+    Response2::new(png.to_vec())
+        .with_header("Content-Type", "image/png")
+        .with_header("Cache-Control", "public, max-age=3600")
+}
+
+#[rocket::get("/statsviewer?<player>")]
+pub async fn stats_viewer(
+    pool: &State<PointercratePool>, query: Query<RankingPagination>, player: Option<i32>, default_list: &State<DefaultList>,
+) -> Result<Page> {
     let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+
+    stats_viewer_page(list, &mut connection, query.0, player).await
+}
+
+#[rocket::get("/<slug>/statsviewer?<player>")]
+pub async fn list_stats_viewer(
+    slug: &str, pool: &State<PointercratePool>, query: Query<RankingPagination>, player: Option<i32>,
+) -> Result<Page> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    stats_viewer_page(list, &mut connection, query.0, player).await
+}
+
+async fn stats_viewer_page(list: List, connection: &mut PgConnection, mut query: RankingPagination, player: Option<i32>) -> Result<Page> {
+    query.params.validate()?;
+    query.list = Some(list.id);
+
+    let player = match player {
+        Some(id) => Some(DatabasePlayer::by_id(id, &mut *connection).await?),
+        None => None,
+    };
+
+    let mut ranking = PrerenderedPage::render(&query, &mut *connection, IndividualStatsViewer::ranking_entry)
+        .await
+        .map_err(DemonlistError::from)?;
+
+    // Keep the selected player when navigating the ranking
+    if let Some(ref player) = player {
+        ranking = ranking.with_parameter("player", player.id);
+    }
 
     Ok(Page::new(IndividualStatsViewer {
         nationalities_in_use: Nationality::used(&mut *connection).await?,
+        ranking,
+        player,
+        list,
     }))
 }
 
 #[rocket::get("/statsviewer/nations")]
-pub async fn nation_stats_viewer() -> Page {
-    Page::new(pointercrate_demonlist_pages::statsviewer::national::nation_based_stats_viewer())
+pub async fn nation_stats_viewer(
+    pool: &State<PointercratePool>, query: Query<NationalityRankingPagination>, default_list: &State<DefaultList>,
+) -> Result<Page> {
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+
+    nation_stats_viewer_page(list, &mut connection, query.0).await
+}
+
+#[rocket::get("/<slug>/statsviewer/nations")]
+pub async fn list_nation_stats_viewer(
+    slug: &str, pool: &State<PointercratePool>, query: Query<NationalityRankingPagination>,
+) -> Result<Page> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    nation_stats_viewer_page(list, &mut connection, query.0).await
+}
+
+async fn nation_stats_viewer_page(list: List, connection: &mut PgConnection, mut query: NationalityRankingPagination) -> Result<Page> {
+    query.list = Some(list.id);
+
+    let ranking = query.page(connection).await?;
+
+    Ok(Page::new(
+        pointercrate_demonlist_pages::statsviewer::national::nation_based_stats_viewer(&list, &ranking),
+    ))
 }
 
 #[rocket::get("/statsviewer/heatmap.css")]
-pub async fn heatmap_css(pool: &State<PointercratePool>) -> Result<Response2<String>> {
+pub async fn heatmap_css(pool: &State<PointercratePool>, default_list: &State<DefaultList>) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+
+    heatmap_css_for(list, &mut connection).await
+}
+
+#[rocket::get("/<slug>/statsviewer/heatmap.css")]
+pub async fn list_heatmap_css(slug: &str, pool: &State<PointercratePool>) -> Result<Response2<String>> {
     let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    heatmap_css_for(list, &mut connection).await
+}
+
+async fn heatmap_css_for(list: List, connection: &mut PgConnection) -> Result<Response2<String>> {
     let mut css = String::new();
 
     let mut nation_scores = HashMap::new();
-    let mut nations_stream = sqlx::query!("SELECT iso_country_code, score FROM nationalities WHERE score > 0.0").fetch(&mut *connection);
+    let mut nations_stream = sqlx::query!(
+        "SELECT nation, score FROM nationality_scores WHERE list = $1 AND score > 0.0",
+        list.id
+    )
+    .fetch(&mut *connection);
 
     while let Some(row) = nations_stream.next().await {
         let row = row.map_err(DemonlistError::from)?;
 
-        nation_scores.insert(row.iso_country_code, row.score);
+        nation_scores.insert(row.nation, row.score);
     }
 
     let Some(&max_nation_score) = nation_scores.values().max_by(|a, b| a.total_cmp(b)) else {
@@ -206,14 +508,17 @@ pub async fn heatmap_css(pool: &State<PointercratePool>) -> Result<Response2<Str
     // un-borrow `connection`
     drop(nations_stream);
 
-    let mut subdivisions_stream =
-        sqlx::query!("SELECT nation, iso_code, score FROM subdivisions WHERE score > 0.0").fetch(&mut *connection);
+    let mut subdivisions_stream = sqlx::query!(
+        "SELECT nation, subdivision, score FROM subdivision_scores WHERE list = $1 AND score > 0.0",
+        list.id
+    )
+    .fetch(&mut *connection);
 
     while let Some(row) = subdivisions_stream.next().await {
         let row = row.map_err(DemonlistError::from)?;
 
         css.push_str(&make_css_rule(
-            &format!("{}-{}", row.nation, row.iso_code),
+            &format!("{}-{}", row.nation, row.subdivision),
             row.score,
             *nation_scores.get(&row.nation).unwrap_or(&f64::INFINITY),
         ))
@@ -235,3 +540,146 @@ fn make_css_rule(code: &str, score: f64, highest_score: f64) -> String {
         0xe0 as f64 + (0xc6 - 0xe0) as f64 * (score / highest_score),
     )
 }
+
+/// The number of entries in each Atom feed
+const FEED_LENGTH: i64 = 50;
+
+#[rocket::get("/feed.atom")]
+pub async fn changes_feed(
+    pool: &State<PointercratePool>, cache: &State<FeedCache>, default_list: &State<DefaultList>,
+) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+
+    changes_feed_for(list, &mut connection, cache).await
+}
+
+#[rocket::get("/<slug>/feed.atom")]
+pub async fn list_changes_feed(slug: &str, pool: &State<PointercratePool>, cache: &State<FeedCache>) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    changes_feed_for(list, &mut connection, cache).await
+}
+
+async fn changes_feed_for(list: List, connection: &mut PgConnection, cache: &FeedCache) -> Result<Response2<String>> {
+    let key = FeedKey::Changes(list.id);
+
+    if let Some(xml) = cache.get(&key) {
+        return Ok(atom_response(xml));
+    }
+
+    let changes = recent_list_changes(list.id, FEED_LENGTH, connection).await?;
+    let xml = AtomFeed::list_changes(&list, &changes).render();
+
+    cache.insert(key, xml.clone());
+
+    Ok(atom_response(xml))
+}
+
+#[rocket::get("/records.atom")]
+pub async fn records_feed(
+    pool: &State<PointercratePool>, cache: &State<FeedCache>, default_list: &State<DefaultList>,
+) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+
+    records_feed_for(list, None, None, &mut connection, cache).await
+}
+
+#[rocket::get("/<slug>/records.atom")]
+pub async fn list_records_feed(slug: &str, pool: &State<PointercratePool>, cache: &State<FeedCache>) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+
+    records_feed_for(list, None, None, &mut connection, cache).await
+}
+
+#[rocket::get("/players/<player_id>/records.atom")]
+pub async fn player_records_feed(
+    player_id: i32, pool: &State<PointercratePool>, cache: &State<FeedCache>, default_list: &State<DefaultList>,
+) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+    let player = DatabasePlayer::by_id(player_id, &mut connection).await?;
+
+    records_feed_for(list, Some(player), None, &mut connection, cache).await
+}
+
+#[rocket::get("/<slug>/players/<player_id>/records.atom")]
+pub async fn list_player_records_feed(
+    slug: &str, player_id: i32, pool: &State<PointercratePool>, cache: &State<FeedCache>,
+) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+    let player = DatabasePlayer::by_id(player_id, &mut connection).await?;
+
+    records_feed_for(list, Some(player), None, &mut connection, cache).await
+}
+
+#[rocket::get("/nations/<nation>/records.atom")]
+pub async fn nation_records_feed(
+    nation: &str, pool: &State<PointercratePool>, cache: &State<FeedCache>, default_list: &State<DefaultList>,
+) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = default_list.0.clone();
+    let nation = Nationality::by_country_code_or_name(&nation.to_uppercase(), &mut connection).await?;
+
+    records_feed_for(list, None, Some(nation), &mut connection, cache).await
+}
+
+#[rocket::get("/<slug>/nations/<nation>/records.atom")]
+pub async fn list_nation_records_feed(
+    slug: &str, nation: &str, pool: &State<PointercratePool>, cache: &State<FeedCache>,
+) -> Result<Response2<String>> {
+    let mut connection = pool.connection().await?;
+    let list = List::by_slug(slug, &mut connection).await?;
+    let nation = Nationality::by_country_code_or_name(&nation.to_uppercase(), &mut connection).await?;
+
+    records_feed_for(list, None, Some(nation), &mut connection, cache).await
+}
+
+async fn records_feed_for(
+    list: List, player: Option<DatabasePlayer>, nation: Option<Nationality>, connection: &mut PgConnection, cache: &FeedCache,
+) -> Result<Response2<String>> {
+    let key = FeedKey::Records(
+        list.id,
+        player.as_ref().map(|player| player.id),
+        nation.as_ref().map(|nation| nation.iso_country_code.clone()),
+    );
+
+    if let Some(xml) = cache.get(&key) {
+        return Ok(atom_response(xml));
+    }
+
+    let records = recently_approved_records(
+        list.id,
+        player.as_ref().map(|player| player.id),
+        nation.as_ref().map(|nation| nation.iso_country_code.as_str()),
+        FEED_LENGTH,
+        connection,
+    )
+    .await?;
+
+    let feed = match (player, nation) {
+        (Some(player), _) => {
+            AtomFeed::approved_records(&list, Some(&player.name), &format!("players/{}/records.atom", player.id), &records)
+        },
+        (None, Some(nation)) => AtomFeed::approved_records(
+            &list,
+            Some(&format!("players from {}", nation.nation)),
+            &format!("nations/{}/records.atom", nation.iso_country_code),
+            &records,
+        ),
+        (None, None) => AtomFeed::approved_records(&list, None, "records.atom", &records),
+    };
+    let xml = feed.render();
+
+    cache.insert(key, xml.clone());
+
+    Ok(atom_response(xml))
+}
+
+fn atom_response(xml: String) -> Response2<String> {
+    Response2::new(xml).with_header("Content-Type", ATOM_CONTENT_TYPE)
+}
@@ -0,0 +1,49 @@
+//! Caching for rendered Open Graph preview cards
+//!
+//! Rasterizing a card is comparatively expensive, and crawlers tend to request the same card
+//! many times, so rendered PNGs are kept in memory until the data they were rendered from changes.
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+    sync::{Arc, Mutex},
+};
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum CardKey {
+    Demon(i32),
+    /// A player's card on a specific list, as (player id, list id)
+    Player(i32, i32),
+}
+
+struct CachedCard {
+    /// Fingerprint of the data this card was rendered from
+    fingerprint: u64,
+    png: Arc<Vec<u8>>,
+}
+
+#[derive(Default)]
+pub(crate) struct PreviewCache {
+    cards: Mutex<HashMap<CardKey, CachedCard>>,
+}
+
+impl PreviewCache {
+    /// Computes the fingerprint of the data a card is rendered from
+    pub(crate) fn fingerprint(data: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        data.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Gets the cached card for the given key, unless it is outdated
+    pub(crate) fn get(&self, key: CardKey, fingerprint: u64) -> Option<Arc<Vec<u8>>> {
+        match self.cards.lock().unwrap().get(&key) {
+            Some(cached) if cached.fingerprint == fingerprint => Some(Arc::clone(&cached.png)),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn insert(&self, key: CardKey, fingerprint: u64, png: Arc<Vec<u8>>) {
+        self.cards.lock().unwrap().insert(key, CachedCard { fingerprint, png });
+    }
+}
@@ -0,0 +1,164 @@
+//! Module providing the background tasks that publish scheduled list updates and periodically
+//! recheck record videos
+
+use crate::{
+    activity::{Activity, ActivityStream},
+    jobs::RecheckRecordVideo,
+    webhook::notify_webhooks,
+};
+use log::{error, info, warn};
+use pointercrate_core::{error::PointercrateError, pool::PointercratePool};
+use pointercrate_core_api::job::Job;
+use pointercrate_demonlist::{
+    demon::current_list,
+    error::{DemonlistError, Result},
+    list::update::{ListUpdate, PUBLICATION_CHECK_INTERVAL},
+    record::{claim_due_video_checks, VIDEO_RECHECK_INTERVAL},
+    video::VideoHosts,
+};
+use rocket::{
+    fairing::{Fairing, Info, Kind},
+    tokio, Orbit, Rocket,
+};
+
+/// Rocket fairing that, once the server has launched, spawns a task periodically publishing all
+/// [`ListUpdate`](pointercrate_demonlist::list::update::ListUpdate)s whose scheduled time has come.
+pub struct ListUpdateScheduler;
+
+/// Publishes all list updates whose scheduled time has come
+///
+/// Each update is published in its own transaction, on behalf of its author (see
+/// [`ListUpdate::publish_as_author`]). If publishing an update fails because of the update itself,
+/// the reason is stored in [`ListUpdate::failure`] and it is not retried until it is rescheduled.
+/// Updates that failed due to internal errors (such as the database being unreachable) stay due
+/// and are retried on the next run.
+pub(crate) async fn publish_due_updates(pool: &PointercratePool, hosts: &VideoHosts, activities: &ActivityStream) -> Result<()> {
+    let due = ListUpdate::due(&mut *pool.connection().await?).await?;
+
+    for update_id in due {
+        let mut transaction = pool.transaction().await?;
+        let update = ListUpdate::by_id(update_id, &mut transaction).await?;
+        let before = current_list(update.list, &mut transaction).await?;
+
+        match update.publish_as_author(hosts, &mut transaction).await {
+            Ok(update) => {
+                let after = current_list(update.list, &mut transaction).await?;
+                let announced = Activity::list_changes(update.list, &before, &after);
+
+                notify_webhooks(&announced, &mut transaction).await?;
+
+                transaction.commit().await?;
+
+                activities.publish_all(announced);
+
+                info!("Published scheduled list update {}", update);
+            },
+            // Someone published it manually while we were getting to it
+            Err(DemonlistError::ListUpdatePublished) => transaction.rollback().await?,
+            Err(err) if err.status_code() == 500 => {
+                transaction.rollback().await?;
+
+                warn!("Failed to publish scheduled list update {}, retrying later: {}", update_id, err);
+            },
+            Err(err) => {
+                transaction.rollback().await?;
+
+                warn!("Failed to publish scheduled list update {}: {}", update_id, err);
+
+                if let Err(err) = ListUpdate::mark_failed(update_id, err.to_string(), &mut *pool.connection().await?).await {
+                    error!("Failed to mark list update {} as failed: {}", update_id, err);
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// Immediately publishes all list updates whose scheduled time has come, instead of waiting for
+/// the [`ListUpdateScheduler`] to get to them
+pub async fn publish_due_list_updates(rocket: &Rocket<Orbit>) -> Result<()> {
+    publish_due_updates(
+        rocket.state::<PointercratePool>().unwrap(),
+        rocket.state::<VideoHosts>().unwrap(),
+        rocket.state::<ActivityStream>().unwrap(),
+    )
+    .await
+}
+
+#[rocket::async_trait]
+impl Fairing for ListUpdateScheduler {
+    fn info(&self) -> Info {
+        Info {
+            name: "List Update Scheduler",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let pool = PointercratePool::from(rocket.state::<PointercratePool>().unwrap().clone_inner());
+        let hosts = rocket.state::<VideoHosts>().unwrap().clone();
+        let activities = rocket.state::<ActivityStream>().unwrap().clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PUBLICATION_CHECK_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                if let Err(err) = publish_due_updates(&pool, &hosts, &activities).await {
+                    error!("Failed to publish scheduled list updates: {}", err);
+                }
+            }
+        });
+    }
+}
+
+/// Rocket fairing that, once the server has launched, spawns a task periodically queuing
+/// [`RecheckRecordVideo`] jobs for the approved records whose videos are most overdue for a check,
+/// so that dead links get flagged for the list team.
+pub struct VideoRecheckScheduler;
+
+/// Queues video rechecks for the next batch of records that are due for one
+async fn queue_due_video_checks(pool: &PointercratePool) -> Result<()> {
+    let mut transaction = pool.transaction().await?;
+    let due = claim_due_video_checks(&mut transaction).await?;
+
+    for &record_id in &due {
+        RecheckRecordVideo { record_id }.enqueue(&mut transaction).await?;
+    }
+
+    transaction.commit().await?;
+
+    if !due.is_empty() {
+        info!("Queued video rechecks for {} records", due.len());
+    }
+
+    Ok(())
+}
+
+#[rocket::async_trait]
+impl Fairing for VideoRecheckScheduler {
+    fn info(&self) -> Info {
+        Info {
+            name: "Video Recheck Scheduler",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let pool = PointercratePool::from(rocket.state::<PointercratePool>().unwrap().clone_inner());
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(VIDEO_RECHECK_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                if let Err(err) = queue_due_video_checks(&pool).await {
+                    error!("Failed to queue video rechecks: {}", err);
+                }
+            }
+        });
+    }
+}
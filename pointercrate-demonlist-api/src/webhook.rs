@@ -0,0 +1,240 @@
+//! Module notifying registered [`Webhook`](pointercrate_demonlist::webhook::Webhook)s about list
+//! activity
+//!
+//! Every [`Activity`] caused by a request is turned into a [`WebhookDelivery`] for each webhook
+//! subscribed to it, inside the request's transaction. The deliveries are then sent (and retried if
+//! necessary) via the job queue.
+//!
+//! For compatibility with older deployments, a Discord webhook configured via the `DISCORD_WEBHOOK`
+//! environment variable is registered at startup (see [`ConfiguredWebhookRegistrar`]).
+
+use crate::{activity::Activity, config};
+use hmac::{Hmac, Mac};
+use log::{error, info};
+use pointercrate_core::pool::PointercratePool;
+use pointercrate_core_api::job::{Attempt, Job, JobHandler, JobResult};
+use pointercrate_demonlist::{
+    error::{DemonlistError, Result},
+    webhook::{Webhook, WebhookDelivery, WebhookFormat, MAX_DELIVERY_ATTEMPTS},
+};
+use rocket::{
+    fairing::{Fairing, Info, Kind},
+    Orbit, Rocket,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use sqlx::{Connection, PgConnection};
+use std::time::Duration;
+
+/// The header containing the signature of a delivery's body, in the form `sha256=<hex digest>`
+const SIGNATURE_HEADER: &str = "X-Pointercrate-Signature";
+
+/// How long a receiver has to respond before an attempt is considered failed
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The activities the webhook configured via `DISCORD_WEBHOOK` is notified about, which are the ones
+/// it was notified about before webhooks could be registered via the API
+const CONFIGURED_WEBHOOK_EVENTS: &[&str] = &["record_submitted", "record_approved", "record_rejected"];
+
+/// Rocket fairing that, once the server has launched, registers the Discord webhook configured via
+/// the `DISCORD_WEBHOOK` environment variable, unless a webhook to that url already exists
+pub struct ConfiguredWebhookRegistrar;
+
+#[rocket::async_trait]
+impl Fairing for ConfiguredWebhookRegistrar {
+    fn info(&self) -> Info {
+        Info {
+            name: "Configured Webhook Registrar",
+            kind: Kind::Liftoff,
+        }
+    }
+
+    async fn on_liftoff(&self, rocket: &Rocket<Orbit>) {
+        let Some(url) = config::submission_webhook() else { return };
+        let pool = rocket.state::<PointercratePool>().unwrap();
+
+        let registered = match pool.connection().await {
+            Ok(mut connection) => {
+                Webhook::register_if_missing(&url, WebhookFormat::Discord, CONFIGURED_WEBHOOK_EVENTS, &mut connection).await
+            },
+            Err(err) => Err(err.into()),
+        };
+
+        match registered {
+            Ok(Some(webhook)) => info!("Registered {} from DISCORD_WEBHOOK", webhook),
+            Ok(None) => (),
+            Err(err) => error!("Failed to register webhook from DISCORD_WEBHOOK: {}", err),
+        }
+    }
+}
+
+/// Creates a delivery of each of the given activities to every webhook subscribed to it
+///
+/// Must be called on the transaction making the changes the activities describe, so that webhooks
+/// are only notified about changes that actually got committed, and so that no notification is
+/// lost if the server goes down right after the commit.
+pub(crate) async fn notify_webhooks(activities: &[Activity], connection: &mut PgConnection) -> Result<()> {
+    for activity in activities {
+        for webhook in Webhook::subscribed_to(activity.event_name(), &mut *connection).await? {
+            let body = match webhook.format {
+                WebhookFormat::Json => serde_json::to_string(activity).unwrap(),
+                WebhookFormat::Discord => discord_message(activity).to_string(),
+            };
+
+            let delivery = webhook.enqueue(activity.event_name(), body, &mut *connection).await?;
+
+            DeliverWebhook { delivery: delivery.id }.enqueue(&mut *connection).await?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Sends a [`WebhookDelivery`] to its webhook
+#[derive(Serialize, Deserialize)]
+pub struct DeliverWebhook {
+    pub delivery: i32,
+}
+
+impl Job for DeliverWebhook {
+    const KIND: &'static str = "deliver_webhook";
+    const MAX_ATTEMPTS: i32 = MAX_DELIVERY_ATTEMPTS;
+}
+
+pub struct WebhookSender {
+    pub pool: PointercratePool,
+    pub client: reqwest::Client,
+}
+
+#[rocket::async_trait]
+impl JobHandler for WebhookSender {
+    type Job = DeliverWebhook;
+
+    async fn run(&self, job: DeliverWebhook, _: Attempt) -> JobResult {
+        let mut connection = self.pool.connection().await?;
+
+        let delivery = match WebhookDelivery::by_id(job.delivery, &mut connection).await {
+            Ok(delivery) => delivery,
+            // Deleting a webhook deletes its deliveries
+            Err(DemonlistError::WebhookDeliveryNotFound { .. }) => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let webhook = Webhook::by_id(delivery.webhook, &mut connection).await?;
+
+        if !webhook.active || delivery.delivered_at.is_some() {
+            return Ok(());
+        }
+
+        let outcome = send(&self.client, &webhook, &delivery).await;
+
+        // Only start the transaction once the request is done, so that it is not held open while waiting for the receiver
+        let mut transaction = connection.begin().await.map_err(DemonlistError::from)?;
+        let delivery = delivery.record_attempt(outcome, &mut transaction).await?;
+
+        transaction.commit().await.map_err(DemonlistError::from)?;
+
+        match delivery.attempts.last().and_then(|attempt| attempt.error.clone()) {
+            Some(error) => Err(error.into()),
+            None => Ok(()),
+        }
+    }
+}
+
+async fn send(client: &reqwest::Client, webhook: &Webhook, delivery: &WebhookDelivery) -> std::result::Result<u16, String> {
+    client
+        .post(&webhook.url)
+        .header("Content-Type", "application/json")
+        .header("X-Pointercrate-Event", &delivery.event)
+        .header("X-Pointercrate-Delivery", delivery.id)
+        .header(SIGNATURE_HEADER, format!("sha256={}", sign(&webhook.secret, &delivery.body)))
+        .body(delivery.body.clone())
+        .timeout(DELIVERY_TIMEOUT)
+        .send()
+        .await
+        .map(|response| response.status().as_u16())
+        .map_err(|err| err.to_string())
+}
+
+/// Computes the hex encoded HMAC-SHA256 of the given body
+fn sign(secret: &str, body: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take keys of any size");
+
+    mac.update(body.as_bytes());
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Describes the given activity as a message for a Discord webhook
+fn discord_message(activity: &Activity) -> serde_json::Value {
+    match activity {
+        Activity::RecordSubmitted { record, submitter } => {
+            let mut payload = serde_json::json!({
+                "content": format!("**New record submitted! ID: {}**", record.id),
+                "embeds": [
+                    {
+                        "type": "rich",
+                        "title": format!("{}% on {}", record.progress, record.demon.name),
+                        "description": format!("{} just got {}% on {}! Go add their record!", record.player.name, record.progress, record.demon.name),
+                        "footer": {
+                            "text": format!("This record has been submitted by submitter #{}", submitter.unwrap_or(1))
+                        },
+                        "author": {
+                            "name": format!("{} (ID: {})", record.player.name, record.player.id),
+                            "url": record.video
+                        },
+                        "thumbnail": {
+                            "url": "https://cdn.discordapp.com/emojis/561867333476286464.png?size=1024"
+                        },
+                    }
+                ]
+            });
+
+            if let Some(ref video) = record.video {
+                payload["embeds"][0]["fields"] = serde_json::json! {
+                    [{
+                        "name": "Video Proof:",
+                        "value": video
+                    }]
+                };
+            }
+
+            payload
+        },
+        Activity::RecordApproved { record, reviewer } | Activity::RecordRejected { record, reviewer } => {
+            let mut payload = serde_json::json!({
+                "content": format!("**Record {}! ID: {}**", record.status, record.id),
+                "embeds": [
+                    {
+                        "type": "rich",
+                        "title": format!("{}% on {}", record.progress, record.demon.name),
+                        "description": format!("The status of {}'s record has been set to `{}`!", record.player.name, record.status),
+                        "author": {
+                            "name": format!("Owner: {} (ID: {})", record.player.name, record.player.id),
+                            "url": record.video
+                        },
+                    }
+                ]
+            });
+
+            if let Some(reviewer) = reviewer {
+                payload["embeds"][0]["footer"] = serde_json::json! {{
+                    "text": format!("Edited by {}", reviewer)
+                }};
+            }
+
+            payload
+        },
+        Activity::DemonAdded { demon, .. } => serde_json::json!({
+            "content": format!("**{} has been added to the list at #{}!**", demon.name, demon.position)
+        }),
+        Activity::DemonMoved { demon, .. } => serde_json::json!({
+            "content": format!("**{} has been moved to #{}!**", demon.name, demon.position)
+        }),
+        Activity::PlayerBanned { player } => serde_json::json!({
+            "content": format!("**{} (ID: {}) has been banned!**", player.name, player.id)
+        }),
+        Activity::PlayerRenamed { player, old_name } => serde_json::json!({
+            "content": format!("**{} (ID: {}) has been renamed to {}!**", old_name, player.id, player.name)
+        }),
+    }
+}
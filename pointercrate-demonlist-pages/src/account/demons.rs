@@ -128,6 +128,10 @@ impl AccountPageTab for DemonsTab {
 
                                     }
                                 }
+                                div.flex.space {
+                                    span.button.purple.hover #demon-unlist style = "margin: 15px auto 0px" {"Unlist Demon"};
+                                    span.button.red.hover #demon-delete style = "margin: 15px auto 0px" {"Delete Demon"};
+                                }
                             }
                         }
                     }
@@ -277,18 +281,15 @@ fn change_thumbnail_dialog() -> Markup {
                     "Change thumbnail link:"
                 }
                 p style = "max-width: 400px"{
-                    "Change the thumbnail link for this record. To link it to the thumbnail of a youtube video, set it to "
-                    i {
-                        "https://i.ytimg.com/vi/" b{"VIDEO_ID"} "/mqdefault.jpg"
-                    }
-                    "."
+                    "Change the thumbnail link for this demon. By default, the thumbnail is derived from the verification video (if its host \
+                     provides thumbnails), and setting a link here overrides that. Leave empty to derive it from the verification video again."
                 }
                 form.flex.col novalidate = "" {
                     p.info-red.output {}
                     p.info-green.output {}
                     span.form-input #demon-thumbnail-edit {
                         label for = "thumbnail" {"Thumbnail link:"}
-                        input required="" name = "thumbnail" type = "url";
+                        input name = "thumbnail" type = "url";
                         p.error {}
                     }
                     input.button.purple.hover type = "submit" style = "margin: 15px auto 0px;" value = "Edit";
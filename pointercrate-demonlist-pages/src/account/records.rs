@@ -10,6 +10,7 @@ use pointercrate_core_pages::{
 };
 use pointercrate_demonlist::{
     demon::{current_list, Demon},
+    list::{section::ListSection, List},
     LIST_HELPER,
 };
 use pointercrate_user::{sqlx::PgConnection, AuthenticatedUser};
@@ -52,8 +53,17 @@ impl AccountPageTab for RecordsPage {
     }
 
     async fn content(&self, _user: &AuthenticatedUser, _permissions: &PermissionsManager, connection: &mut PgConnection) -> Markup {
-        let demons = match current_list(connection).await {
-            Ok(demons) => demons,
+        // The record manager operates on the default list for now
+        let demons_and_sections = match List::default_list(&mut *connection).await {
+            Ok(list) => match current_list(list.id, &mut *connection).await {
+                Ok(demons) => ListSection::of_list(list.id, connection).await.map(|sections| (demons, sections)),
+                Err(err) => Err(err),
+            },
+            Err(err) => Err(err),
+        };
+
+        let (demons, sections) = match demons_and_sections {
+            Ok(demons_and_sections) => demons_and_sections,
             Err(err) => {
                 return ErrorFragment {
                     status: err.status_code(),
@@ -66,7 +76,7 @@ impl AccountPageTab for RecordsPage {
 
         html! {
             div.left {
-                (RecordSubmitter::new(false, &demons[..]))
+                (RecordSubmitter::new(false, &demons[..], &sections[..]))
                 (record_manager(&demons[..]))
                 (note_adder())
                 div.panel.fade #record-notes-container style = "display:none" {
@@ -78,6 +88,7 @@ impl AccountPageTab for RecordsPage {
                 (manager_help())
             }
             div.right {
+                (review_queue())
                 (status_selector())
                 (record_selector())
                 (player_selector())
@@ -122,6 +133,7 @@ fn record_manager(demons: &[Demon]) -> Markup {
                                         li.colorless.hover data-value="approved" {"Approved"}
                                         li.colorless.hover data-value="rejected" {"Rejected"}
                                         li.colorless.hover data-value="under consideration" {"Under Consideration"}
+                                        li.colorless.hover data-value="pending approval" {"Pending Approval"}
                                         li.colorless.hover data-value="submitted" {"Submitted"}
                                     }
                                 }
@@ -140,6 +152,34 @@ fn record_manager(demons: &[Demon]) -> Markup {
                                 a.link #record-video-link target = "_blank" {}
                             }
                         }
+                        div.stats-container.flex.space  {
+                            span {
+                                b {
+                                    "Status Reason:"
+                                }
+                                br;
+                                div.dropdown-menu #edit-record-reason style = "max-width: 300px" data-default = "none" {
+                                    div{
+                                        input type="text" style = "font-weight: bold;";
+                                    }
+                                    div.menu {
+                                        ul {
+                                            li.colorless.hover.underlined data-value="none" {"None"}
+                                            // populated by javascript with the reasons configured for this pointercrate instance
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        div.stats-container.flex.space  {
+                            span {
+                                b {
+                                    "Additional Proof:"
+                                }
+                                br;
+                                span #record-proofs {} // populated by javascript when a record is clicked
+                            }
+                        }
                         div.stats-container.flex.space {
                             span {
                                 b {
@@ -191,7 +231,7 @@ fn manager_help() -> Markup {
                 "Use the list on the left to select records for editing/viewing. Use the panel on the right to filter the record list by status, player, etc.. Clicking the 'All Demons' field at the top allows to filter by demon."
             }
             p {
-                "There are four possible record states a record can be in: " i { "'rejected', 'approved', 'submitted'" } " and " i { "'under consideration'" } ". For simplicity of explanation we will assume that 'Bob' is a player and 'Cataclysm' is a demon he has a record on."
+                "There are five possible record states a record can be in: " i { "'rejected', 'approved', 'submitted', 'under consideration'" } " and " i { "'pending approval'" } ". For simplicity of explanation we will assume that 'Bob' is a player and 'Cataclysm' is a demon he has a record on."
                 ul {
                     li {
                         b{"Rejected: "} "If the record is 'rejected', it means that Bob has no other record in other states on Cataclysm and no submissions for Bob on Cataclysm are possible. Conversely, this means if Bob has a record on Catalysm that's not rejected, we immediately know that no rejected record for Bob on Cataclysm exists. "
@@ -209,6 +249,9 @@ fn manager_help() -> Markup {
                     li {
                         b {"Under Consideration: "} "If the record is 'under consideration' it is conceptually still a submission. The only difference is, that no more submissions for Bob on Cataclysm are allowed now."
                     }
+                    li {
+                        b {"Pending Approval: "} "If Cataclysm is placed high enough, approving Bob's record needs to be signed off by two different list team members (or a single list moderator). The first approval puts the record into the 'pending approval' state, which otherwise behaves like 'under consideration'. Once a different list team member approves it, it becomes 'approved'."
+                    }
                 }
             }
             p {
@@ -223,6 +266,21 @@ fn manager_help() -> Markup {
     }
 }
 
+fn review_queue() -> Markup {
+    html! {
+        div.panel.fade #review-queue-panel {
+            h2.underlined.pad {
+                "Review Queue"
+            }
+            p {
+                "Claim the submission that should be reviewed next, so that nobody else on the list team reviews it at the same time. Submissions for higher placed demons come first. Your claim expires after 30 minutes and is released as soon as you change the record's status."
+            }
+            p.info-red.output {}
+            span.button.purple.hover #review-next style = "margin: 15px auto 0px" {"Review next submission"};
+        }
+    }
+}
+
 fn status_selector() -> Markup {
     // FIXME: no vec
     let dropdown_items = vec![
@@ -238,6 +296,9 @@ fn status_selector() -> Markup {
         html! {
             li.colorless.hover data-value = "under consideration" {"Under Consideration"}
         },
+        html! {
+            li.colorless.hover data-value = "pending approval" {"Pending Approval"}
+        },
     ];
 
     html! {
@@ -323,8 +384,20 @@ fn note_adder() -> Markup {
                     input #add-note-is-public-checkbox type = "checkbox" name = "is_public";
                 }
             }
+            div.flex.no-stretch style="justify-content: space-between; align-items: center; margin-bottom: 0.75em" {
+                b {
+                    "Template:"
+                }
+                // populated by javascript with the note templates set up by the list moderators
+                (dropdown("none", html! {
+                    li.colorless.hover.underlined data-value = "none" {"No template"}
+                }, std::iter::empty()))
+            }
             p.info-red.output {}
             textarea style = "width: 100%" placeholder = "Add note here. Click 'Add' above when done!"{}
+            p {
+                "Templates can contain the placeholders " i {"{player}"} ", " i {"{demon}"} " and " i {"{progress}"} ", which are filled in with the details of the record."
+            }
         }
     }
 }
@@ -1,19 +1,28 @@
 use crate::components::{demon_dropdown, player_selection_dialog};
 use maud::{html, Markup, Render};
-use pointercrate_demonlist::{config, demon::Demon};
+use pointercrate_demonlist::{demon::Demon, list::section::ListSection};
 
 pub struct RecordSubmitter<'a> {
     initially_visible: bool,
     demons: &'a [Demon],
+    sections: &'a [ListSection],
 }
 
-impl RecordSubmitter<'_> {
-    pub fn new(visible: bool, demons: &[Demon]) -> RecordSubmitter {
+impl<'a> RecordSubmitter<'a> {
+    pub fn new(visible: bool, demons: &'a [Demon], sections: &'a [ListSection]) -> RecordSubmitter<'a> {
         RecordSubmitter {
             initially_visible: visible,
             demons,
+            sections,
         }
     }
+
+    /// Whether records for the given demon can be submitted, based on the section it is in
+    fn accepts_submissions_for(&self, demon: &Demon) -> bool {
+        ListSection::containing(self.sections, demon.base.position)
+            .map(|section| section.submissions_open)
+            .unwrap_or(false)
+    }
 }
 
 impl Render for RecordSubmitter<'_> {
@@ -31,10 +40,10 @@ impl Render for RecordSubmitter<'_> {
                         "Demon:"
                     }
                     p {
-                        "The demon the record was made on. Only demons in the top " (config::extended_list_size()) " are accepted. This excludes legacy demons!"
+                        "The demon the record was made on. Only demons in sections of the list that are open for submissions are accepted. This excludes legacy demons!"
                     }
                     span.form-input data-type = "dropdown" {
-                        (demon_dropdown("id_demon", self.demons.iter().filter(|demon| demon.base.position <= config::extended_list_size())))
+                        (demon_dropdown("id_demon", self.demons.iter().filter(|demon| self.accepts_submissions_for(demon))))
                         p.error {}
                     }
                     h3 {
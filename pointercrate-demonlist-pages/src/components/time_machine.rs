@@ -1,4 +1,4 @@
-use chrono::{DateTime, Datelike, FixedOffset};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime};
 use maud::{html, Markup, Render};
 use pointercrate_demonlist::demon::TimeShiftedDemon;
 
@@ -15,6 +15,13 @@ pub enum Tardis {
         /// Whether the time selection panel should be visible.
         show_selector: bool,
     },
+    /// Instead of the past, shows a possible future: the list as it will look after a
+    /// [`ListUpdate`](pointercrate_demonlist::list::update::ListUpdate) has been published
+    Preview {
+        title: String,
+        scheduled_for: Option<NaiveDateTime>,
+        demons: Vec<TimeShiftedDemon>,
+    },
 }
 
 impl Tardis {
@@ -37,6 +44,7 @@ impl Tardis {
                 show_selector: visible, ..
             } => *visible,
             Tardis::Deactivated { show_selector: visible } => *visible,
+            Tardis::Preview { .. } => false,
         }
     }
 }
@@ -62,7 +70,17 @@ impl Render for Tardis {
                                 }
                             }
                         }
-                        a.purple.button href = "/demonlist/" onclick=r#"document.cookie = "when=""# style = "margin-left: 15px"{ b{"Go to present" }}
+                        a.purple.button href = "?" onclick=r#"document.cookie = "when=""# style = "margin-left: 15px"{ b{"Go to present" }}
+                    }
+                },
+                Tardis::Preview { title, scheduled_for, .. } => {
+                    div.panel.fade.medium-gray style="align-items: center;" {
+                        "You are currently looking at a preview of the list update "
+                        b { (title) }
+                        @if let Some(scheduled_for) = scheduled_for {
+                            br;
+                            "It is scheduled to be published on " (scheduled_for.format("%A, %B %e %Y at %l:%M%P UTC"))
+                        }
                     }
                 },
                 _ => {}
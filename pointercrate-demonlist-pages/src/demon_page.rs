@@ -7,13 +7,13 @@ use crate::{
 };
 use chrono::NaiveDateTime;
 use maud::{html, Markup, PreEscaped};
-use pointercrate_core_pages::{head::HeadLike, PageFragment};
+use pointercrate_core_pages::{config::site_url, head::HeadLike, PageFragment};
 use pointercrate_demonlist::{
-    config::{self as list_config, extended_list_size},
     demon::{Demon, FullDemon},
+    list::{section::ListSection, List},
+    video::VideoHosts,
 };
 use pointercrate_integrate::gd::{IntegrationLevel, Thunk};
-use url::Url;
 
 #[derive(Debug)]
 pub struct DemonMovement {
@@ -23,10 +23,13 @@ pub struct DemonMovement {
 
 pub struct DemonPage {
     pub team: Team,
+    pub list: List,
+    pub sections: Vec<ListSection>,
     pub demonlist: Vec<Demon>,
     pub data: FullDemon,
     pub movements: Vec<DemonMovement>,
     pub integration: Option<IntegrationLevel>,
+    pub video_hosts: VideoHosts,
 }
 
 impl From<DemonPage> for PageFragment {
@@ -34,6 +37,11 @@ impl From<DemonPage> for PageFragment {
         use pointercrate_core_pages::{versioned_import, with_version_string};
 
         PageFragment::new(page.title(), page.description())
+            .meta(
+                "og:image",
+                format!("{}/demonlist/permalink/{}/card.png", site_url(), page.data.demon.base.id),
+            )
+            .meta("twitter:card", "summary_large_image")
             .module(with_version_string!("/static/core/js/modules/form.js"))
             .module(with_version_string!("/static/demonlist/js/modules/demonlist.js"))
             .module(with_version_string!("/static/demonlist/js/demonlist.js"))
@@ -47,13 +55,23 @@ impl From<DemonPage> for PageFragment {
 }
 
 impl DemonPage {
+    /// The section this demon is currently in
+    fn section(&self) -> Option<&ListSection> {
+        // Unlisted demons are not part of any section anymore, even though they still have a position
+        if self.data.demon.unlisted {
+            return None;
+        }
+
+        ListSection::containing(&self.sections, self.data.demon.base.position)
+    }
+
     fn title(&self) -> String {
         let mut title = format!(
             "{} - Geometry Dash Demonlist",
             self.data.demon.base.name // FIXME: flatten the structs, holy shit
         );
 
-        if self.data.demon.base.position <= extended_list_size() {
+        if self.section().map(|section| section.numbered).unwrap_or(false) {
             title = format!("#{} - {}", self.data.demon.base.position, title);
         }
 
@@ -110,16 +128,19 @@ impl DemonPage {
             "##, self.data.demon.base.id, self.data.name(), self.description(), self.data.position())))
             (PreEscaped(format!("
                 <script>
-                    window.list_length = {0};
-                    window.extended_list_length = {1};
-                    window.demon_id = {2};
-                </script>", list_config::list_size(), list_config::extended_list_size(), self.data.demon.base.id
+                    window.numbered_sections = {0};
+                    window.demon_id = {1};
+                </script>", super::numbered_sections_js(&self.sections), self.data.demon.base.id
             )))
         }
     }
 
     fn body(&self) -> Markup {
-        let dropdowns = super::dropdowns(&self.demonlist.iter().collect::<Vec<_>>()[..], Some(&self.data.demon));
+        let dropdowns = super::dropdowns(
+            &self.sections,
+            &self.demonlist.iter().collect::<Vec<_>>()[..],
+            Some(&self.data.demon),
+        );
 
         let mut labels = Vec::new();
 
@@ -151,7 +172,7 @@ impl DemonPage {
 
             div.flex.m-center.container {
                 main.left {
-                    (RecordSubmitter::new(false, &self.demonlist))
+                    (RecordSubmitter::new(false, &self.demonlist, &self.sections))
                     (self.demon_panel())
                     (self.level_info_panel())
                     div.panel.fade.js-scroll-anim.js-collapse data-anim = "fade" {
@@ -187,7 +208,7 @@ impl DemonPage {
                 aside.right {
                     (self.team)
                     (submit_panel())
-                    (stats_viewer_panel())
+                    (stats_viewer_panel(&self.list))
                     (super::discord_panel())
                 }
             }
@@ -198,6 +219,10 @@ impl DemonPage {
         let position = self.data.demon.base.position;
         let name = &self.data.demon.base.name;
 
+        let section = self.section();
+        let awards_points = section.map(|section| section.awards_points).unwrap_or(false);
+        let allows_non_100 = section.map(|section| section.allows_non_100).unwrap_or(false);
+
         let score100 = self.data.demon.score(100);
         let score_requirement = self.data.demon.score(self.data.demon.requirement);
 
@@ -206,13 +231,13 @@ impl DemonPage {
                 div.underlined {
                     h1 #demon-heading style = "overflow: hidden"{
                         @if self.data.demon.base.position != 1 {
-                            a href=(format!("/demonlist/{:?}", self.data.demon.base.position - 1)) {
+                            a href=(format!("{}/{:?}", crate::list_url(&self.list), self.data.demon.base.position - 1)) {
                                 i class="fa fa-chevron-left" style="padding-right: 5%" {}
                             }
                         }
                         (name)
                         @if position as usize != self.demonlist.len() {
-                            a href=(format!("/demonlist/{:?}", position + 1)) {
+                            a href=(format!("{}/{:?}", crate::list_url(&self.list), position + 1)) {
                                 i class="fa fa-chevron-right" style="padding-left: 5%" {}
                             }
                         }
@@ -243,12 +268,12 @@ impl DemonPage {
                     }
                 }
                 @if let Some(ref video) = self.data.demon.video {
-                    @if let Some(embedded_video) = embed(video) {
+                    @if let Some(embedded_video) = self.video_hosts.embed_url(video) {
                         iframe."ratio-16-9"."js-delay-attr" style="width:90%; margin: 15px 5%" allowfullscreen="" data-attr = "src" data-attr-value = (embedded_video) referrerpolicy="strict-origin-when-cross-origin" {"Verification Video"}
                     }
                 }
                 div.underlined.pad.flex.wrap #level-info {
-                    @if position <= list_config::extended_list_size() {
+                    @if awards_points {
                         span {
                             b {
                                 "Demonlist score (100%): "
@@ -257,7 +282,7 @@ impl DemonPage {
                             (format!("{:.2}", score100))
                         }
                     }
-                    @if position <= list_config::list_size(){
+                    @if awards_points && allows_non_100 {
                         span {
                             b {
                                 "Demonlist score (" (self.data.demon.requirement) "%): "
@@ -340,22 +365,24 @@ impl DemonPage {
     }
 
     fn records_panel(&self) -> Markup {
-        let position = self.data.demon.base.position;
         let _name = &self.data.demon.base.name;
+        let section = self.section();
+        let submissions_open = section.map(|section| section.submissions_open).unwrap_or(false);
+        let allows_non_100 = section.map(|section| section.allows_non_100).unwrap_or(false);
 
         html! {
-            @if !self.data.records.is_empty() || position <= list_config::extended_list_size() {
+            @if !self.data.records.is_empty() || submissions_open {
                 section.records.panel.fade.js-scroll-anim data-anim = "fade" {
                     div.underlined.pad {
                         h2 {
                             "Records"
                         }
-                        @if position <= list_config::list_size() {
+                        @if submissions_open && allows_non_100 {
                             h3 {
                                 (self.data.demon.requirement) "% or better required to qualify"
                             }
                         }
-                        @else if position <= list_config::extended_list_size() {
+                        @else if submissions_open {
                             h3 {
                                 "100% required to qualify"
                             }
@@ -373,7 +400,7 @@ impl DemonPage {
                     }
                     @if self.data.records.is_empty() {
                         h3 {
-                            @if position > list_config::extended_list_size() {
+                            @if !submissions_open {
                                 "No records!"
                             }
                             @else {
@@ -419,7 +446,7 @@ impl DemonPage {
                                         td.video-link {
                                             @if let Some(ref video) = record.video {
                                                  a.link href = (video) target = "_blank"{
-                                                     (host(video))
+                                                     (self.video_hosts.name_of(video))
                                                  }
                                             }
                                         }
@@ -433,38 +460,3 @@ impl DemonPage {
         }
     }
 }
-
-fn host(video: &str) -> &str {
-    match Url::parse(video).unwrap().domain().unwrap() {
-        "www.youtube.com" => "YouTube",
-        "www.twitch.tv" => "Twitch",
-        "everyplay.com" => "Everyplay",
-        "www.bilibili.com" => "Bilibili",
-        "vimeo.com" => "Vimeo",
-        host => panic!("{}", host),
-    }
-}
-
-fn embed(video: &str) -> Option<String> {
-    // Video URLs need to be wellformed once we get here!
-    let url = Url::parse(video).unwrap();
-
-    match url.domain()? {
-        "www.youtube.com" => {
-            let video_id = url
-                .query_pairs()
-                .find_map(|(key, value)| if key == "v" { Some(value) } else { None })?;
-
-            Some(format!("https://www.youtube.com/embed/{}", video_id))
-        },
-        "www.twitch.tv" => {
-            // per validation always of the form 'https://www.twitch.tv/videos/[video id]/'
-            let mut url_segment = url.path_segments()?;
-            url_segment.next()?;
-            let video_id = url_segment.next()?;
-
-            Some(format!("https://player.twitch.tv/?video={}&autoplay=false", video_id))
-        },
-        _ => None,
-    }
-}
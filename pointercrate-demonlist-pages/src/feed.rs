@@ -0,0 +1,158 @@
+//! Atom feeds of list changes and newly approved records, so that the list can be followed in feed
+//! readers
+//!
+//! Feeds are described using [`maud`], just like the preview cards, but rendered as XML according
+//! to [RFC 4287](https://datatracker.ietf.org/doc/html/rfc4287).
+
+use crate::list_url;
+use chrono::{NaiveDateTime, Utc};
+use maud::{html, Markup, PreEscaped};
+use pointercrate_core_pages::config::site_url;
+use pointercrate_demonlist::{demon::audit::ListChange, list::List, record::audit::ApprovedRecord};
+
+/// The content type with which feeds should be served
+pub const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+
+pub struct AtomFeed {
+    /// The (relative) URL this feed is served at
+    pub path: String,
+
+    pub title: String,
+
+    /// The (relative) URL of the page whose content this feed mirrors
+    pub alternate: String,
+
+    /// The name of whoever publishes this feed
+    pub author: String,
+
+    /// The entries of this feed, most recent first
+    pub entries: Vec<FeedEntry>,
+}
+
+pub struct FeedEntry {
+    /// A URI uniquely and permanently identifying this entry
+    pub id: String,
+
+    pub title: String,
+
+    /// The (relative) URL this entry links to
+    pub link: String,
+
+    pub updated: NaiveDateTime,
+
+    pub summary: String,
+}
+
+impl AtomFeed {
+    /// A feed of demons being added to or moved on the given list
+    pub fn list_changes(list: &List, changes: &[ListChange]) -> AtomFeed {
+        AtomFeed {
+            path: format!("{}/feed.atom", list_url(list)),
+            title: format!("{} - Changes", list.name),
+            alternate: format!("{}/", list_url(list)),
+            author: list.name.clone(),
+            entries: changes
+                .iter()
+                .map(|change| {
+                    let name = &change.demon_name;
+                    let (verb, kind) = if change.added {
+                        ("added", "addition")
+                    } else {
+                        ("moved", "movement")
+                    };
+
+                    let title = format!("{} {} to #{}", name, verb, change.new_position);
+
+                    FeedEntry {
+                        id: format!(
+                            "urn:pointercrate:demon:{}:{}:{}",
+                            change.demon_id,
+                            kind,
+                            change.time.and_utc().timestamp()
+                        ),
+                        summary: format!("{} was {} on the {}", name, verb, list.name),
+                        title,
+                        link: format!("/demonlist/permalink/{}/", change.demon_id),
+                        updated: change.time,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// A feed of newly approved records. `scope` describes whose records are included (e.g. a
+    /// player's name), and `path` is the path of the feed relative to the list's URL prefix
+    pub fn approved_records(list: &List, scope: Option<&str>, path: &str, records: &[ApprovedRecord]) -> AtomFeed {
+        AtomFeed {
+            path: format!("{}/{}", list_url(list), path),
+            title: match scope {
+                Some(scope) => format!("{} - Records by {}", list.name, scope),
+                None => format!("{} - Records", list.name),
+            },
+            alternate: format!("{}/statsviewer/", list_url(list)),
+            author: list.name.clone(),
+            entries: records
+                .iter()
+                .map(|approved| {
+                    let record = &approved.record;
+
+                    let title = if record.progress == 100 {
+                        format!("{} beat {}", record.player.name, record.demon.name)
+                    } else {
+                        format!("{} got {}% on {}", record.player.name, record.progress, record.demon.name)
+                    };
+
+                    FeedEntry {
+                        id: format!("urn:pointercrate:record:{}", record.id),
+                        summary: format!("{} (#{} on the {})", title, record.demon.position, list.name),
+                        title,
+                        link: format!("{}/statsviewer?player={}", list_url(list), record.player.id),
+                        updated: approved.approved_at,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let site = site_url();
+
+        // Feeds without entries still need an update time
+        let updated = self
+            .entries
+            .iter()
+            .map(|entry| entry.updated)
+            .max()
+            .unwrap_or_else(|| Utc::now().naive_utc());
+
+        let markup: Markup = html! {
+            (PreEscaped(r#"<?xml version="1.0" encoding="utf-8"?>"#))
+            feed xmlns="http://www.w3.org/2005/Atom" {
+                id { (site) (self.path) }
+                title { (self.title) }
+                updated { (timestamp(updated)) }
+                link rel="self" type="application/atom+xml" href={(site) (self.path)} {}
+                link rel="alternate" type="text/html" href={(site) (self.alternate)} {}
+                author {
+                    name { (self.author) }
+                }
+                @for entry in &self.entries {
+                    entry {
+                        id { (entry.id) }
+                        title { (entry.title) }
+                        updated { (timestamp(entry.updated)) }
+                        link rel="alternate" href={(site) (entry.link)} {}
+                        summary { (entry.summary) }
+                    }
+                }
+            }
+        };
+
+        markup.into_string()
+    }
+}
+
+/// Formats the given UTC time as an RFC 3339 timestamp, as required by Atom
+fn timestamp(time: NaiveDateTime) -> String {
+    time.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
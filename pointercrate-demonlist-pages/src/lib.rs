@@ -1,70 +1,67 @@
 use maud::{html, Markup};
 
-use pointercrate_demonlist::{config, demon::Demon};
+use pointercrate_demonlist::{
+    demon::Demon,
+    list::{section::ListSection, List},
+};
 
 pub mod account;
 pub mod components;
 pub mod demon_page;
+pub mod feed;
 pub mod overview;
+pub mod preview;
 pub mod statsviewer;
+pub mod submission_status;
 
-struct ListSection {
-    name: &'static str,
-    description: &'static str,
-    id: &'static str,
-    numbered: bool,
+/// The URL prefix under which the pages of the given list are served
+///
+/// The default list keeps living under `/demonlist`, all other lists are served under
+/// `/lists/<slug>`.
+pub fn list_url(list: &List) -> String {
+    if list.is_default() {
+        "/demonlist".to_string()
+    } else {
+        format!("/lists/{}", list.slug)
+    }
 }
 
-static MAIN_SECTION: ListSection = ListSection {
-    name: "Main List",
-    description: "The main section of the Demonlist. These demons are the hardest rated levels in the game. Records are accepted above a \
-                  given threshold and award a large amount of points!",
-    id: "mainlist",
-    numbered: true,
-};
-
-static EXTENDED_SECTION: ListSection = ListSection {
-    name: "Extended List",
-    description: "These are demons that dont qualify for the main section of the list, but are still of high relevance. Only 100% records \
-                  are accepted for these demons! Note that non-100% that were submitted/approved before a demon fell off the main list \
-                  will be retained",
-    id: "extended",
-    numbered: true,
-};
-
-static LEGACY_SECTION: ListSection = ListSection {
-    name: "Legacy List",
-    description: "These are demons that used to be on the list, but got pushed off as new demons were added. They are here for nostalgic \
-                  reasons. This list is in no order whatsoever and will not be maintained any longer at all. This means no new records \
-                  will be added for these demons.",
-    id: "legacy",
-    numbered: false,
-};
-
-fn dropdowns(all_demons: &[&Demon], current: Option<&Demon>) -> Markup {
-    let (main, extended, legacy) = if all_demons.len() < config::list_size() as usize {
-        (all_demons, Default::default(), Default::default())
-    } else {
-        let (extended, legacy) = if all_demons.len() < config::extended_list_size() as usize {
-            (&all_demons[config::list_size() as usize..], Default::default())
-        } else {
-            (
-                &all_demons[config::list_size() as usize..config::extended_list_size() as usize],
-                &all_demons[config::extended_list_size() as usize..],
+/// Renders the positions covered by the numbered sections among the given ones as a javascript
+/// array of `[first, last]` pairs (with `last` being `null` for sections extending until the end of
+/// the list)
+fn numbered_sections_js(sections: &[ListSection]) -> String {
+    let ranges = sections
+        .iter()
+        .filter(|section| section.numbered)
+        .map(|section| {
+            format!(
+                "[{}, {}]",
+                section.first_position,
+                section
+                    .last_position
+                    .map(|last| last.to_string())
+                    .unwrap_or_else(|| "null".to_string())
             )
-        };
+        })
+        .collect::<Vec<_>>();
 
-        (&all_demons[..config::list_size() as usize], extended, legacy)
-    };
+    format!("[{}]", ranges.join(", "))
+}
 
+/// The URL prefix of the API endpoints scoped to the given list
+pub fn list_api_url(list: &List) -> String {
+    format!("/api/v2/lists/{}", list.slug)
+}
+
+fn dropdowns(sections: &[ListSection], all_demons: &[&Demon], current: Option<&Demon>) -> Markup {
     html! {
         nav.flex.wrap.m-center.fade #lists style="text-align: center;" {
-            // The drop down for the main list:
-            (dropdown(&MAIN_SECTION, main, current))
-            // The drop down for the extended list:
-            (dropdown(&EXTENDED_SECTION, extended, current))
-            // The drop down for the legacy list:
-            (dropdown(&LEGACY_SECTION, legacy, current))
+            // One drop down for each section of the list
+            @for section in sections {
+                @let demons = all_demons.iter().copied().filter(|demon| section.contains(demon.base.position)).collect::<Vec<_>>();
+
+                (dropdown(section, &demons, current))
+            }
         }
     }
 }
@@ -93,11 +90,11 @@ fn dropdown(section: &ListSection, demons: &[&Demon], current: Option<&Demon>) -
 
     html! {
         div {
-            div.button.dark-gray.hover.no-shadow.js-toggle data-toggle-group="0" onclick={"javascript:void(DropDown.toggleDropDown('" (section.id) "'))"} {
+            div.button.dark-gray.hover.no-shadow.js-toggle data-toggle-group="0" onclick={"javascript:void(DropDown.toggleDropDown('section-" (section.id) "'))"} {
                 (section.name)
             }
 
-            div.see-through.fade.dropdown #(section.id) {
+            div.see-through.fade.dropdown id = {"section-" (section.id)} {
                 div.search.js-search.seperated style = "margin: 10px" {
                     input placeholder = "Filter..." type = "text" {}
                 }
@@ -4,23 +4,26 @@ use crate::{
         team::Team,
         time_machine::Tardis,
     },
+    list_url,
     statsviewer::stats_viewer_panel,
 };
 use maud::{html, Markup, PreEscaped};
 use pointercrate_core_pages::{head::HeadLike, PageFragment};
 use pointercrate_demonlist::{
-    config as list_config,
     demon::{Demon, TimeShiftedDemon},
+    list::{section::ListSection, List},
 };
 
 pub struct OverviewPage {
     pub team: Team,
+    pub list: List,
+    pub sections: Vec<ListSection>,
     pub demonlist: Vec<Demon>,
     pub time_machine: Tardis,
     pub submitter_initially_visible: bool,
 }
 
-fn demon_panel(demon: &Demon, current_position: Option<i16>) -> Markup {
+fn demon_panel(demon: &Demon, current_position: Option<i16>, sections: &[ListSection]) -> Markup {
     html! {
          section.panel.fade style="overflow:hidden" {
              div.flex style = "align-items: center" {
@@ -44,11 +47,13 @@ fn demon_panel(demon: &Demon, current_position: Option<i16>) -> Markup {
                          }
                          @if let Some(current_position) = current_position {
                              br;
-                             @if current_position > list_config::extended_list_size() {
-                                 "Currently Legacy"
-                             }
-                             @else {
-                                 "Currently #"(current_position)
+                             @match ListSection::containing(sections, current_position) {
+                                 Some(section) if !section.numbered => {
+                                     "Currently in the " (section.name)
+                                 },
+                                 _ => {
+                                     "Currently #"(current_position)
+                                 }
                              }
                          }
                      }
@@ -74,7 +79,13 @@ impl From<OverviewPage> for PageFragment {
     fn from(page: OverviewPage) -> Self {
         use pointercrate_core_pages::{versioned_import, with_version_string};
 
-        PageFragment::new("1.9 GDPS Demonlist", "The official 1.9 GDPS Demonlist!")
+        let (title, description) = if page.list.is_default() {
+            ("1.9 GDPS Demonlist".to_string(), "The official 1.9 GDPS Demonlist!".to_string())
+        } else {
+            (page.list.name.clone(), format!("The official {}!", page.list.name))
+        };
+
+        PageFragment::new(title, description)
             .module(with_version_string!("/static/core/js/modules/form.js"))
             .module(with_version_string!("/static/demonlist/js/modules/demonlist.js"))
             .module(with_version_string!("/static/demonlist/js/demonlist.js"))
@@ -90,6 +101,7 @@ impl From<OverviewPage> for PageFragment {
 impl OverviewPage {
     fn head(&self) -> Markup {
         html! {
+            @if self.list.is_default() {
             (PreEscaped(r#"
                 <script type="application/ld+json">
                 {
@@ -122,24 +134,35 @@ impl OverviewPage {
                 }
                 </script>
             "#))
+            // FIXME: abstract away
+            link ref = "canonical" href = "https://pointercrate.xyze.dev/demonlist/";
+            }
+            link rel="alternate" type="application/atom+xml" title={(self.list.name) " - Changes"} href={(list_url(&self.list)) "/feed.atom"};
+            link rel="alternate" type="application/atom+xml" title={(self.list.name) " - Records"} href={(list_url(&self.list)) "/records.atom"};
             (PreEscaped(format!("
                 <script>
-                    window.list_length = {0};
-                    window.extended_list_length = {1}
-                </script>", list_config::list_size(), list_config::extended_list_size())
+                    window.numbered_sections = {0};
+                </script>", super::numbered_sections_js(&self.sections))
             ))
-            // FIXME: abstract away
-            link ref = "canonical" href = "https://pointercrate.xyze.dev/demonlist/";
         }
     }
 
     fn body(&self) -> Markup {
         let demons_for_dropdown: Vec<&Demon> = match self.time_machine {
-            Tardis::Activated { ref demons, .. } => demons.iter().map(|demon| &demon.current_demon).collect(),
+            Tardis::Activated { ref demons, .. } | Tardis::Preview { ref demons, .. } => {
+                demons.iter().map(|demon| &demon.current_demon).collect()
+            },
             _ => self.demonlist.iter().collect(),
         };
 
-        let dropdowns = super::dropdowns(&demons_for_dropdown[..], None);
+        let dropdowns = super::dropdowns(&self.sections, &demons_for_dropdown[..], None);
+
+        // Only demons in numbered sections get a panel of their own, the others are only shown in the dropdowns
+        let is_numbered = |demon: &Demon| {
+            ListSection::containing(&self.sections, demon.base.position)
+                .map(|section| section.numbered)
+                .unwrap_or(false)
+        };
 
         html! {
             (dropdowns)
@@ -149,20 +172,20 @@ impl OverviewPage {
                     (self.time_machine)
                     (migration_panel())
 
-                    (RecordSubmitter::new(self.submitter_initially_visible, &self.demonlist))
+                    (RecordSubmitter::new(self.submitter_initially_visible, &self.demonlist, &self.sections))
 
                     @match &self.time_machine {
-                        Tardis::Activated { demons, ..} => {
+                        Tardis::Activated { demons, ..} | Tardis::Preview { demons, .. } => {
                             @for TimeShiftedDemon {current_demon, position_now} in demons {
-                                @if current_demon.base.position <= list_config::extended_list_size() {
-                                    (demon_panel(current_demon, Some(*position_now)))
+                                @if is_numbered(current_demon) {
+                                    (demon_panel(current_demon, *position_now, &self.sections))
                                 }
                             }
                         },
                         _ => {
                             @for demon in &self.demonlist {
-                                @if demon.base.position <= list_config::extended_list_size() {
-                                    (demon_panel(demon, None))
+                                @if is_numbered(demon) {
+                                    (demon_panel(demon, None, &self.sections))
                                 }
                             }
                         }
@@ -172,7 +195,7 @@ impl OverviewPage {
                 aside.right {
                     (self.team)
                     (submit_panel())
-                    (stats_viewer_panel())
+                    (stats_viewer_panel(&self.list))
                     (super::discord_panel())
                 }
             }
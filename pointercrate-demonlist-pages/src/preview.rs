@@ -0,0 +1,165 @@
+//! Open Graph preview images ("share cards") for demon pages and player profiles
+//!
+//! Cards are described as SVG and rasterized to PNG on the CPU via [`resvg`], so that platforms
+//! such as Discord or Twitter can display a rich preview when a link is shared.
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use log::error;
+use maud::{html, Markup};
+use pointercrate_demonlist::{demon::Demon, list::List, player::FullPlayer};
+use resvg::{
+    tiny_skia::{Pixmap, Transform},
+    usvg::{fontdb::Database, Options, Tree},
+};
+use std::sync::{Arc, OnceLock};
+
+/// Width of a preview card in pixels, as recommended for `og:image`
+pub const CARD_WIDTH: u32 = 1200;
+
+/// Height of a preview card in pixels, as recommended for `og:image`
+pub const CARD_HEIGHT: u32 = 630;
+
+const FONT_FAMILY: &str = "Montserrat, 'DejaVu Sans', sans-serif";
+
+pub trait PreviewCard {
+    /// The SVG source of this card, which must have a size of [`CARD_WIDTH`]x[`CARD_HEIGHT`]
+    fn svg(&self) -> Markup;
+
+    /// Rasterizes this card into a PNG image
+    ///
+    /// Returns `None` (and logs the reason) if rendering failed.
+    fn render_png(&self) -> Option<Vec<u8>> {
+        let options = Options {
+            fontdb: fonts(),
+            ..Options::default()
+        };
+
+        let tree = match Tree::from_str(&self.svg().into_string(), &options) {
+            Ok(tree) => tree,
+            Err(err) => {
+                error!("Failed to parse preview card SVG: {:?}", err);
+
+                return None;
+            },
+        };
+
+        let mut pixmap = Pixmap::new(CARD_WIDTH, CARD_HEIGHT)?;
+
+        resvg::render(&tree, Transform::default(), &mut pixmap.as_mut());
+
+        pixmap
+            .encode_png()
+            .map_err(|err| error!("Failed to encode preview card as PNG: {:?}", err))
+            .ok()
+    }
+}
+
+/// The fonts available for rendering text on preview cards. Loading the system fonts is expensive,
+/// so it is only done once.
+fn fonts() -> Arc<Database> {
+    static FONTS: OnceLock<Arc<Database>> = OnceLock::new();
+
+    FONTS
+        .get_or_init(|| {
+            let mut database = Database::new();
+            database.load_system_fonts();
+            Arc::new(database)
+        })
+        .clone()
+}
+
+/// Common layout of all cards: A dark background with an optional image on the right half, a small
+/// caption at the top and up to two lines of details below a large headline
+fn card(image: Option<String>, caption: &str, headline: &str, details: &[String]) -> Markup {
+    html! {
+        svg xmlns="http://www.w3.org/2000/svg" width=(CARD_WIDTH) height=(CARD_HEIGHT) viewBox={"0 0 " (CARD_WIDTH) " " (CARD_HEIGHT)} {
+            defs {
+                linearGradient #fade x1="0" y1="0" x2="1" y2="0" {
+                    stop offset="0.4" stop-color="#1b1b1b" stop-opacity="1" {}
+                    stop offset="1" stop-color="#1b1b1b" stop-opacity="0.3" {}
+                }
+            }
+            rect width="100%" height="100%" fill="#1b1b1b" {}
+            @if let Some(image) = image {
+                image href=(image) x="480" y="0" width="720" height=(CARD_HEIGHT) preserveAspectRatio="xMidYMid slice" {}
+            }
+            rect width="100%" height="100%" fill="url(#fade)" {}
+            rect x="0" y="0" width="16" height=(CARD_HEIGHT) fill="#0881c6" {}
+            text x="80" y="150" font-family=(FONT_FAMILY) font-size="40" fill="#9a9a9a" { (caption) }
+            text x="80" y="290" font-family=(FONT_FAMILY) font-size="96" font-weight="bold" fill="#ffffff" { (headline) }
+            @for (index, detail) in details.iter().enumerate() {
+                text x="80" y=(400 + 70 * index) font-family=(FONT_FAMILY) font-size="44" fill="#dddddd" { (detail) }
+            }
+        }
+    }
+}
+
+/// Guesses the MIME type of an image from its first few bytes
+fn image_mime_type(data: &[u8]) -> Option<&'static str> {
+    match data {
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [b'G', b'I', b'F', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        _ => None,
+    }
+}
+
+pub struct DemonCard<'a> {
+    pub demon: &'a Demon,
+
+    /// The list the demon is on
+    pub list: &'a List,
+
+    /// The raw image data of the demon's thumbnail, if it could be retrieved
+    pub thumbnail: Option<&'a [u8]>,
+}
+
+impl PreviewCard for DemonCard<'_> {
+    fn svg(&self) -> Markup {
+        let image = self
+            .thumbnail
+            .and_then(|data| image_mime_type(data).map(|mime| format!("data:{};base64,{}", mime, STANDARD.encode(data))));
+
+        card(
+            image,
+            &format!("#{} on the {}", self.demon.base.position, self.list.name),
+            &self.demon.base.name,
+            &[
+                format!("verified by {}", self.demon.verifier.name),
+                format!("published by {}", self.demon.publisher.name),
+            ],
+        )
+    }
+}
+
+pub struct PlayerCard<'a> {
+    pub player: &'a FullPlayer,
+
+    /// The list whose stats viewer the player is ranked on
+    pub list: &'a List,
+
+    /// The player's rank on the stats viewer, `None` if they are unranked
+    pub rank: Option<i64>,
+}
+
+impl PreviewCard for PlayerCard<'_> {
+    fn svg(&self) -> Markup {
+        let caption = match self.rank {
+            Some(rank) => format!("#{} on the {} Stats Viewer", rank, self.list.name),
+            None => format!("Unranked on the {} Stats Viewer", self.list.name),
+        };
+
+        let hardest = match self.player.hardest_demon() {
+            Some(demon) => format!("Hardest: {} (#{})", demon.name, demon.position),
+            None => "Hardest: None".to_string(),
+        };
+
+        card(
+            None,
+            &caption,
+            &self.player.player.base.name,
+            &[format!("Score: {:.2}", self.player.player.score), hardest],
+        )
+    }
+}
@@ -1,47 +1,85 @@
 use crate::statsviewer::stats_viewer_html;
 use maud::{html, Markup};
-use pointercrate_core_pages::{head::HeadLike, PageFragment};
-use pointercrate_demonlist::nationality::Nationality;
+use pointercrate_core_pages::{config::site_url, head::HeadLike, util::PrerenderedPage, PageFragment};
+use pointercrate_demonlist::{
+    list::List,
+    nationality::Nationality,
+    player::{DatabasePlayer, RankedPlayer},
+};
 
-#[derive(Debug)]
 pub struct IndividualStatsViewer {
+    pub list: List,
+
     pub nationalities_in_use: Vec<Nationality>,
+
+    /// The first page of the player ranking, for clients without JavaScript
+    pub ranking: PrerenderedPage,
+
+    /// The player whose profile should initially be displayed, if any
+    pub player: Option<DatabasePlayer>,
 }
 
 impl From<IndividualStatsViewer> for PageFragment {
     fn from(stats_viewer: IndividualStatsViewer) -> Self {
         use pointercrate_core_pages::{versioned_import, with_version_string};
 
-        PageFragment::new(
-            "Individual Stats Viewer",
-            "The pointercrate individual stats viewer, a ranking of the worlds best Geometry Dash players. Now more local than ever, \
-             allowing you to see who's the best in your state!",
-        )
-        .module(with_version_string!("/static/demonlist/js/modules/statsviewer.js"))
-        .module(with_version_string!("/static/demonlist/js/statsviewer/individual.js"))
-        .import(versioned_import!("/static/core/js/modules/form.js"))
-        .import(versioned_import!("/static/demonlist/js/modules/demonlist.js"))
-        .import(versioned_import!("/static/demonlist/js/modules/statsviewer.js"))
-        .stylesheet(with_version_string!("/static/demonlist/css/statsviewer.css"))
-        .stylesheet(with_version_string!("/static/core/css/sidebar.css"))
-        .body(stats_viewer.body())
+        let fragment = match stats_viewer.player {
+            Some(ref player) => PageFragment::new(
+                format!("{} - Individual Stats Viewer", player.name),
+                format!("{}'s profile on the pointercrate individual stats viewer", player.name),
+            )
+            .meta(
+                "og:image",
+                format!(
+                    "{}{}/statsviewer/players/{}/card.png",
+                    site_url(),
+                    crate::list_url(&stats_viewer.list),
+                    player.id
+                ),
+            )
+            .meta("twitter:card", "summary_large_image"),
+            None => PageFragment::new(
+                "Individual Stats Viewer",
+                "The pointercrate individual stats viewer, a ranking of the worlds best Geometry Dash players. Now more local than ever, \
+                 allowing you to see who's the best in your state!",
+            ),
+        };
+
+        fragment
+            .module(with_version_string!("/static/demonlist/js/modules/statsviewer.js"))
+            .module(with_version_string!("/static/demonlist/js/statsviewer/individual.js"))
+            .import(versioned_import!("/static/core/js/modules/form.js"))
+            .import(versioned_import!("/static/demonlist/js/modules/demonlist.js"))
+            .import(versioned_import!("/static/demonlist/js/modules/statsviewer.js"))
+            .stylesheet(with_version_string!("/static/demonlist/css/statsviewer.css"))
+            .stylesheet(with_version_string!("/static/core/css/sidebar.css"))
+            .body(stats_viewer.body())
     }
 }
 
 impl IndividualStatsViewer {
-    fn body(&self) -> Markup {
+    /// Renders an entry of the player ranking the same way `generateStatsViewerPlayer` in
+    /// `statsviewer/individual.js` does
+    pub fn ranking_entry(ranked: &RankedPlayer) -> Markup {
         html! {
-            nav.flex.wrap.m-center.fade #statsviewers style="text-align: center;" {
-                a.button.dark-gray.hover.no-shadow href="/demonlist/statsviewer/"{
-                    b {"Individual"}
-                }
-                a.button.dark-gray.hover.no-shadow href="/demonlist/statsviewer/nations/" {
-                    b {"Nations"}
+            li.dark-gray.hover data-id = (ranked.player.base.id) data-rank = (ranked.rank) {
+                @if let Some(ref nationality) = ranked.player.nationality {
+                    span.flag-icon title = (nationality.nation) style = {"background-image: url(/static/demonlist/images/flags/" (nationality.iso_country_code.to_lowercase()) ".svg"} {}
+                    " "
                 }
+                b { "#" (ranked.rank) " " }
+                (ranked.player.base.name)
+                i { (format!("{:.2}", ranked.player.score)) }
             }
+        }
+    }
+
+    fn body(&self) -> Markup {
+        html! {
+            (super::stats_viewer_nav(&self.list))
             div.flex.m-center.container {
                 main.left {
-                    (stats_viewer_html(Some(&self.nationalities_in_use), super::standard_stats_viewer_rows()))
+                    (stats_viewer_html(&self.list, Some(&self.nationalities_in_use), super::standard_stats_viewer_rows(), Some(&self.ranking)))
                 }
                 aside.right {
                     (super::continent_panel())
@@ -1,11 +1,11 @@
 use maud::{html, Markup, PreEscaped};
-use pointercrate_core_pages::util::{dropdown, filtered_paginator, simple_dropdown};
-use pointercrate_demonlist::nationality::Nationality;
+use pointercrate_core_pages::util::{dropdown, prerendered_filtered_paginator, simple_dropdown, PrerenderedPage};
+use pointercrate_demonlist::{list::List, nationality::Nationality};
 
 pub mod individual;
 pub mod national;
 
-pub(crate) fn stats_viewer_panel() -> Markup {
+pub(crate) fn stats_viewer_panel(list: &List) -> Markup {
     html! {
         section #stats.panel.fade.js-scroll-anim data-anim = "fade" {
             div.underlined {
@@ -16,7 +16,7 @@ pub(crate) fn stats_viewer_panel() -> Markup {
             p {
                 "Get a detailed overview of who completed the most, created the most demons or beat the hardest demons! There is even a leaderboard to compare yourself to the very best!"
             }
-            a.purple.hover.button #show-stats-viewer href = "/demonlist/statsviewer/ "{
+            a.purple.hover.button #show-stats-viewer href = {(crate::list_url(list)) "/statsviewer/"} {
                 "Open the stats viewer!"
             }
         }
@@ -71,9 +71,24 @@ fn standard_stats_viewer_rows() -> Vec<StatsViewerRow> {
     ]
 }
 
-fn stats_viewer_html(nations: Option<&[Nationality]>, rows: Vec<StatsViewerRow>) -> Markup {
+fn stats_viewer_nav(list: &List) -> Markup {
     html! {
-        section.panel.fade #statsviewer style="overflow:initial" {
+        nav.flex.wrap.m-center.fade #statsviewers style="text-align: center;" {
+            a.button.dark-gray.hover.no-shadow href={(crate::list_url(list)) "/statsviewer/"} {
+                b {"Individual"}
+            }
+            a.button.dark-gray.hover.no-shadow href={(crate::list_url(list)) "/statsviewer/nations/"} {
+                b {"Nations"}
+            }
+        }
+    }
+}
+
+fn stats_viewer_html(list: &List, nations: Option<&[Nationality]>, rows: Vec<StatsViewerRow>, ranking: Option<&PrerenderedPage>) -> Markup {
+    let ranking_endpoint = format!("{}/players/ranking/", crate::list_api_url(list));
+
+    html! {
+        section.panel.fade #statsviewer style="overflow:initial" data-list-api = (crate::list_api_url(list)) {
             h2.underlined.pad {
                 "Stats Viewer"
                 @if let Some(nations) = nations {
@@ -101,7 +116,7 @@ fn stats_viewer_html(nations: Option<&[Nationality]>, rows: Vec<StatsViewerRow>)
                 }
             }
             div.flex.viewer {
-                (filtered_paginator("stats-viewer-pagination", "/api/v1/players/ranking/"))
+                (prerendered_filtered_paginator("stats-viewer-pagination", &ranking_endpoint, ranking))
                 p.viewer-welcome {
                     "Click on a player's name on the left to get started!"
                 }
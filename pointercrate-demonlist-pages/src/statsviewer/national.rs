@@ -1,8 +1,11 @@
 use crate::statsviewer::{stats_viewer_html, StatsViewerRow};
 use maud::{html, Markup};
-use pointercrate_core_pages::{head::HeadLike, PageFragment};
+use pointercrate_core_pages::{head::HeadLike, util::PrerenderedPage, PageFragment};
+use pointercrate_demonlist::{list::List, nationality::RankedNation};
 
-pub fn nation_based_stats_viewer() -> PageFragment {
+/// Renders the nation stats viewer, with the given ranking already filled in for clients without
+/// JavaScript
+pub fn nation_based_stats_viewer(list: &List, ranking: &[RankedNation]) -> PageFragment {
     use pointercrate_core_pages::{versioned_import, with_version_string};
 
     PageFragment::new(
@@ -17,27 +20,37 @@ pub fn nation_based_stats_viewer() -> PageFragment {
     .import(versioned_import!("/static/demonlist/js/modules/statsviewer.js"))
     .stylesheet(with_version_string!("/static/demonlist/css/statsviewer.css"))
     .stylesheet(with_version_string!("/static/core/css/sidebar.css"))
-    .body(nation_based_stats_viewer_html())
+    .body(nation_based_stats_viewer_html(list, ranking))
 }
 
-fn nation_based_stats_viewer_html() -> Markup {
+/// Renders an entry of the nation ranking the same way `generateStatsViewerNation` in
+/// `statsviewer/nation.js` does
+fn ranking_entry(ranked: &RankedNation) -> Markup {
+    html! {
+        li.dark-gray.hover data-id = (ranked.nationality.iso_country_code) data-rank = (ranked.rank) {
+            span.flag-icon title = (ranked.nationality.nation) style = {"background-image: url(/static/demonlist/images/flags/" (ranked.nationality.iso_country_code.to_lowercase()) ".svg"} {}
+            " "
+            b { "#" (ranked.rank) " " }
+            (ranked.nationality.nation)
+            i { (format!("{:.2}", ranked.score)) }
+        }
+    }
+}
+
+fn nation_based_stats_viewer_html(list: &List, ranking: &[RankedNation]) -> Markup {
     let mut rows = super::standard_stats_viewer_rows();
 
     rows[0].0.insert(1, ("Players", "players"));
     rows.push(StatsViewerRow(vec![("Unbeaten demons", "unbeaten")]));
 
+    // The nation ranking is not paginated, the API always returns it in full
+    let ranking = PrerenderedPage::complete(ranking.iter().map(ranking_entry).collect());
+
     html! {
-        nav.flex.wrap.m-center.fade #statsviewers style="text-align: center;" {
-            a.button.dark-gray.hover.no-shadow href="/demonlist/statsviewer/"{
-                b {"Individual"}
-            }
-            a.button.dark-gray.hover.no-shadow href="/demonlist/statsviewer/nations/" {
-                b {"Nations"}
-            }
-        }
+        (super::stats_viewer_nav(list))
         div.flex.m-center.container {
             main.left {
-                (stats_viewer_html(None, rows))
+                (stats_viewer_html(list, None, rows, Some(&ranking)))
             }
             aside.right {
                 (super::continent_panel())
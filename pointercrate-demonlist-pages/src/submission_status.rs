@@ -0,0 +1,89 @@
+use maud::{html, Markup};
+use pointercrate_core_pages::{head::HeadLike, PageFragment};
+use pointercrate_demonlist::record::{lookup::SubmissionStatus, RecordStatus};
+
+/// The page on which submitters can look up what became of their submission
+pub struct SubmissionStatusPage {
+    pub submission: SubmissionStatus,
+}
+
+impl From<SubmissionStatusPage> for PageFragment {
+    fn from(page: SubmissionStatusPage) -> Self {
+        use pointercrate_core_pages::with_version_string;
+
+        PageFragment::new(
+            format!("Submission #{} - Geometry Dash Demonlist", page.submission.id),
+            "The current status of your record submission to the pointercrate demonlist",
+        )
+        .meta("robots", "noindex")
+        .stylesheet(with_version_string!("/static/demonlist/css/demonlist.css"))
+        .body(page.body())
+    }
+}
+
+impl SubmissionStatusPage {
+    fn body(&self) -> Markup {
+        let submission = &self.submission;
+
+        html! {
+            div.flex.m-center.container {
+                main.left {
+                    section.panel.fade {
+                        h1.underlined.pad {
+                            "Submission #" (submission.id)
+                        }
+                        p {
+                            b { (submission.player.name) }
+                            " - " (submission.progress) "% on "
+                            a.link href = {"/demonlist/permalink/" (submission.demon.id) "/"} { (submission.demon.name) }
+                        }
+                        @if let Some(ref video) = submission.video {
+                            p {
+                                "Video: "
+                                a.link href = (video) target = "_blank" rel = "noreferrer noopener" { (video) }
+                            }
+                        }
+                        div.stats-container.flex.space {
+                            span {
+                                b { "Status:" }
+                                br;
+                                (submission.status)
+                            }
+                            @if let Some(ref reason) = submission.reason {
+                                span {
+                                    b { "Reason:" }
+                                    br;
+                                    (reason.description)
+                                }
+                            }
+                        }
+                        p {
+                            @match submission.status {
+                                RecordStatus::Submitted => "Your submission has not been looked at by the list team yet. Please be patient!",
+                                RecordStatus::UnderConsideration => "The list team has looked at your submission, but needs more time (or information) to make a decision on it.",
+                                RecordStatus::PendingApproval => "Your submission has been approved by a member of the list team, and now needs to be signed off on by a second one.",
+                                RecordStatus::Approved => "Your record has been approved and is now visible on the demonlist. Congratulations!",
+                                RecordStatus::Rejected => "Your submission has been rejected. Please do not resubmit it unless asked to by the list team.",
+                            }
+                        }
+                    }
+                    @if !submission.notes.is_empty() {
+                        section.panel.fade {
+                            h2.underlined.pad {
+                                "Notes from the list team"
+                            }
+                            @for note in &submission.notes {
+                                div.dark-gray style = "padding: 10px; margin: 10px 0px" {
+                                    p style = "white-space: pre-wrap; margin: 0px" { (note.content) }
+                                    @if let Some(ref author) = note.author {
+                                        i { "- " (author) }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -17,7 +17,7 @@ import {
   del,
   displayError,
   Form,
-  post, setupEditorDialog, FormDialog,
+  post, patch, setupEditorDialog, FormDialog,
 } from "/static/core/js/modules/form.js";
 
 export let demonManager;
@@ -49,6 +49,8 @@ export class DemonManager extends FilteredPaginator {
 
     this._creators = document.getElementById("demon-creators");
 
+    this._unlist = document.getElementById("demon-unlist");
+
     let videoForm = setupFormDialogEditor(
       new PaginatorEditorBackend(this, false),
       "demon-video-dialog",
@@ -66,7 +68,6 @@ export class DemonManager extends FilteredPaginator {
     thumbnailForm.addValidators({
       "demon-thumbnail-edit": {
         "Please enter a valid URL": typeMismatch,
-        "Please enter a URL": valueMissing,
       },
     });
 
@@ -139,6 +140,13 @@ export class DemonManager extends FilteredPaginator {
     this._id.innerText = this.currentObject.id;
     this._name.innerText = this.currentObject.name;
     this._position.innerText = this.currentObject.position;
+
+    if (this.currentObject.unlisted) {
+      this._position.innerText += " (unlisted)";
+      this._unlist.innerText = "Relist Demon";
+    } else {
+      this._unlist.innerText = "Unlist Demon";
+    }
     this._requirement.innerText = this.currentObject.requirement;
 
     var embeddedVideo = embedVideo(this.currentObject.video);
@@ -240,6 +248,47 @@ function createCreatorHtml(creator) {
   return span;
 }
 
+function setupEditDemonForm() {
+  document.getElementById("demon-unlist").addEventListener("click", () => {
+    let unlisted = !demonManager.currentObject.unlisted;
+
+    if (
+      !unlisted ||
+      confirm(
+        "Are you sure? This will remove this demon from the list. Its records will be kept, but will not give any points anymore!"
+      )
+    ) {
+      patch(
+        "/api/v2/demons/" + demonManager.currentObject.id + "/",
+        { "If-Match": demonManager.currentEtag },
+        { unlisted: unlisted }
+      )
+        .then((response) => {
+          demonManager.onReceive(response);
+          demonManager.refresh();
+        })
+        .catch(displayError(demonManager.output));
+    }
+  });
+
+  document.getElementById("demon-delete").addEventListener("click", () => {
+    if (
+      confirm(
+        "Are you sure? This will irrevocably delete this demon and all records on it! If you want to keep the records around, unlist the demon instead."
+      )
+    ) {
+      del("/api/v2/demons/" + demonManager.currentObject.id + "/", {
+        "If-Match": demonManager.currentEtag,
+      })
+        .then(() => {
+          demonManager.output.hideContent();
+          demonManager.refresh();
+        })
+        .catch(displayError(demonManager.output));
+    }
+  });
+}
+
 function setupDemonAdditionForm() {
   let form = new Form(document.getElementById("demon-submission-form"));
 
@@ -293,6 +342,8 @@ export function initialize() {
   demonManager = new DemonManager();
   demonManager.initialize();
 
+  setupEditDemonForm();
+
   let addDemonForm = setupDemonAdditionForm();
 
   let creatorFormDialog = new PlayerSelectionDialog("demon-add-creator-dialog");
@@ -45,6 +45,7 @@ class RecordManager extends Paginator {
     this._progress = document.getElementById("record-progress");
     this._submitter = document.getElementById("record-submitter");
     this._notes = document.getElementById("record-notes");
+    this._proofs = document.getElementById("record-proofs");
 
     this.dropdown = new Dropdown(
       document
@@ -70,6 +71,26 @@ class RecordManager extends Paginator {
       this.output
     );
 
+    this._reason = setupDropdownEditor(
+      new PaginatorEditorBackend(this, true),
+      "edit-record-reason",
+      "reason",
+      this.output,
+      { none: null }
+    );
+
+    get("/api/v1/records/reasons").then((response) => {
+      for (let reason of response.data) {
+        let li = document.createElement("li");
+
+        li.classList.add("colorless", "hover");
+        li.dataset.value = reason.key;
+        li.innerText = reason.description;
+
+        this._reason.addLI(li);
+      }
+    });
+
     this.initProgressDialog();
     this.initVideoDialog();
 
@@ -157,6 +178,18 @@ class RecordManager extends Paginator {
       this._video_link.style.display = "none";
     }
 
+    while (this._proofs.firstChild) {
+      this._proofs.removeChild(this._proofs.firstChild);
+    }
+
+    for (let proof of this.currentObject.proofs) {
+      this._proofs.appendChild(createProofHtml(proof));
+    }
+
+    if (this.currentObject.proofs.length === 0) {
+      this._proofs.innerText = "None";
+    }
+
     this._id.innerHTML = this.currentObject.id;
     this._demon.innerHTML =
       this.currentObject.demon.name + " (" + this.currentObject.demon.id + ")";
@@ -166,6 +199,7 @@ class RecordManager extends Paginator {
       this.currentObject.player.id +
       ")";
     this._status.selectSilently(this.currentObject.status);
+    this._reason.selectSilently(this.currentObject.reason || "none");
     this._progress.innerHTML = this.currentObject.progress + "%";
     this._submitter.innerHTML = this.currentObject.submitter.id;
 
@@ -185,6 +219,21 @@ class RecordManager extends Paginator {
   }
 }
 
+function createProofHtml(proof) {
+  let proofDiv = document.createElement("div");
+  let link = document.createElement("a");
+
+  link.classList.add("link");
+  link.target = "_blank";
+  link.href = proof.url;
+  link.innerText = proof.url;
+
+  proofDiv.appendChild(document.createTextNode((proof.kind === "RawFootage" ? "Raw footage" : "Clip") + (proof.is_public ? "" : " (list team only)") + ": "));
+  proofDiv.appendChild(link);
+
+  return proofDiv;
+}
+
 function createNoteHtml(note) {
   let noteDiv = document.createElement("div");
 
@@ -262,19 +311,52 @@ function setupAddNote() {
   let textArea = adder.getElementsByTagName("textarea")[0];
   let add = adder.getElementsByClassName("button")[0];
   let isPublic = document.getElementById("add-note-is-public-checkbox");
+  let templateDropdown = new Dropdown(
+    adder.getElementsByClassName("dropdown-menu")[0]
+  );
+  let templates = {};
+
+  get("/api/v1/records/note-templates").then((response) => {
+    for (let template of response.data) {
+      let li = document.createElement("li");
+
+      li.classList.add("colorless", "hover");
+      li.dataset.value = template.id;
+      li.innerText = template.name;
+
+      templates[template.id] = template;
+      templateDropdown.addLI(li);
+    }
+  });
+
+  // While a template is selected, the textarea only previews it. The placeholders are filled in by the server.
+  templateDropdown.addEventListener((selected) => {
+    if (selected === "none") {
+      textArea.disabled = false;
+      textArea.value = "";
+    } else {
+      textArea.disabled = true;
+      textArea.value = templates[selected].content;
+    }
+  });
 
   add.addEventListener("click", () => {
+    let data = { is_public: isPublic.checked };
+
+    if (templateDropdown.selected === "none") data.content = textArea.value;
+    else data.template = parseInt(templateDropdown.selected);
+
     post(
       "/api/v1/records/" + recordManager.currentObject.id + "/notes/",
       {},
-      { content: textArea.value, is_public: isPublic.checked }
+      data
     )
       .then((noteResponse) => {
         let newNote = createNoteHtml(noteResponse.data.data);
         recordManager._notes.appendChild(newNote);
 
         $(adder).hide(100);
-        textArea.value = "";
+        templateDropdown.select("none");
       })
       .catch(displayError(output));
   });
@@ -337,6 +419,16 @@ function setupRecordFilterPlayerNameForm() {
   });
 }
 
+function setupReviewQueue() {
+  let output = new Output(document.getElementById("review-queue-panel"));
+
+  document.getElementById("review-next").addEventListener("click", () => {
+    post("/api/v1/records/reviews/next")
+      .then((response) => recordManager.selectArbitrary(response.data.data.record))
+      .catch(displayError(output));
+  });
+}
+
 function setupEditRecordForm() {
   document.getElementById("record-delete").addEventListener("click", () => {
     if (
@@ -360,6 +452,7 @@ export function initialize() {
   setupAddNote();
   setupEditRecordForm();
   setupRecordSearchRecordIdForm();
+  setupReviewQueue();
 
   initializeRecordSubmitter(true);
 
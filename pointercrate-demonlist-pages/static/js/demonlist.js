@@ -12,6 +12,20 @@ $(document).ready(function () {
   initializeTimeMachine();
 });
 
+/**
+ * Whether the given position lies outside of all numbered sections of the list (e.g. on the legacy list)
+ *
+ * @param {Number|null|undefined} position
+ * @returns {boolean}
+ */
+function isUnnumbered(position) {
+  if (position === null || position === undefined) {
+    return false;
+  }
+
+  return !window.numbered_sections.some(([first, last]) => position >= first && (last === null || position <= last));
+}
+
 function initializeHistoryTable() {
   if (!window.demon_id) {
     return;
@@ -27,7 +41,7 @@ function initializeHistoryTable() {
       let newRow = document.createElement("tr");
       let cells = [1, 2, 3, 4].map(() => document.createElement("td"));
 
-      if (entry["new_position"] > window.extended_list_length && lastPosition > window.extended_list_length) {
+      if (isUnnumbered(entry["new_position"]) && isUnnumbered(lastPosition)) {
         // skip movements that happen completely on the legacy list
         continue;
       }
@@ -48,7 +62,7 @@ function initializeHistoryTable() {
           newRow.classList.add("moved-down");
         }
 
-        if(entry["new_position"] > window.extended_list_length || lastPosition > window.extended_list_length) {
+        if(isUnnumbered(entry["new_position"]) || isUnnumbered(lastPosition)) {
           cells[3].appendChild(document.createTextNode("Legacy"));
         } else {
           cells[3].appendChild(arrow);
@@ -59,7 +73,7 @@ function initializeHistoryTable() {
       }
 
       if (entry["new_position"] !== undefined) {
-        if(entry["new_position"] > window.extended_list_length)
+        if(isUnnumbered(entry["new_position"]))
           cells[2].innerText = "-";
         else
         cells[2].innerText = entry["new_position"];
@@ -88,6 +102,25 @@ function initializeHistoryTable() {
           name = name.length > 24 ? `${name.substring(0, 24)}...` : name;
 
           reason = name + " was moved " + verb + " past this demon"
+        } else if (entry["reason"]["OtherRemoved"] !== undefined) {
+          let other = entry["reason"]["OtherRemoved"]["other"];
+          let name = other.name === null ? "A demon" : other["name"];
+
+          name = name.length > 24 ? `${name.substring(0, 24)}...` : name;
+
+          reason = name + " was removed from the list";
+        } else if (entry["reason"]["Reordered"] !== undefined) {
+          let moved = entry["reason"]["Reordered"]["moved"];
+
+          if (moved.length === 1) {
+            let name = moved[0].name === null ? "A demon" : moved[0]["name"];
+
+            name = name.length > 24 ? `${name.substring(0, 24)}...` : name;
+
+            reason = name + " was moved as part of a list update";
+          } else {
+            reason = moved.length + " demons were moved as part of a list update";
+          }
         }
       }
 
@@ -58,7 +58,7 @@ export function initializeTimeMachine() {
       gtag('event', 'time-machine-usage', {'event-category': 'demonlist', 'label': when});
     }
 
-    window.location = "/demonlist/";
+    window.location = window.location.pathname;
   })
 }
 
@@ -112,8 +112,10 @@ export function initializeRecordSubmitter(submitApproved = false) {
       data.status = "approved";
     }
     post("/api/v1/records/", headers, data)
-      .then(() => {
-        submissionForm.setSuccess("Record successfully submitted");
+      .then((response) => {
+        let statusUrl = "/demonlist/submission?token=" + response.data.data.status_token;
+
+        submissionForm.setSuccess("Record successfully submitted! You can check its status <a class='link' href='" + statusUrl + "'>here</a> (keep this link, it is the only way to look up your submission).");
         submissionForm.clear();
 
         if (typeof gtag === "function") {
@@ -298,6 +300,9 @@ export function generateRecord(record) {
     case "under consideration":
       li.style.borderLeftColor = "rgba(142, 230, 230, .8)";
       break;
+    case "pending approval":
+      li.style.borderLeftColor = "rgba(106, 180, 253, .8)";
+      break;
     default:
       break;
   }
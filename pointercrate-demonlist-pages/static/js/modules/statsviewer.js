@@ -54,14 +54,42 @@ export class StatsViewer extends FilteredPaginator {
     }
 
     initialize() {
-        return get("/api/v1/list_information/").then(data => {
-            this.list_size = data.data['list_size'];
-            this.extended_list_size = data.data['extended_list_size'];
+        return get(this.html.dataset.listApi + "/sections/").then(response => {
+            this.sections = response.data;
 
             super.initialize()
         });
     }
 
+    /**
+     * Gets the index of the list section containing the given position, or -1 if no section contains it
+     *
+     * @param {Number} position
+     * @returns {Number}
+     */
+    sectionIndexOf(position) {
+        return this.sections.findIndex(section => position >= section.first_position && (section.last_position === null || position <= section.last_position));
+    }
+
+    /**
+     * Counts how many of the given demons lie in each section of the list
+     *
+     * @param demons An array of objects with a `position` field
+     * @returns {Number[]} The amount of demons per section, in the same order as `this.sections`
+     */
+    countPerSection(demons) {
+        let counts = this.sections.map(() => 0);
+
+        for (let demon of demons) {
+            let index = this.sectionIndexOf(demon.position);
+
+            if (index !== -1)
+                ++counts[index];
+        }
+
+        return counts;
+    }
+
     setName(name, nationality) {
         if(nationality === null) {
             this._name.textContent = name;
@@ -92,8 +120,8 @@ export class StatsViewer extends FilteredPaginator {
         this._hardest.appendChild(hardest === undefined ? document.createTextNode("None") : this.formatDemon(hardest, "/demonlist/permalink/" + hardest.id + "/"));
     }
 
-    setCompletionNumber(main, extended, legacy) {
-        this._amountBeaten.textContent = main + " Main, " + extended + " Extended, " + legacy + " Legacy ";
+    setCompletionNumber(counts) {
+        this._amountBeaten.textContent = this.sections.map((section, index) => counts[index] + " " + section.name).join(", ");
     }
 
     onReceive(response) {
@@ -109,9 +137,11 @@ export class StatsViewer extends FilteredPaginator {
     formatDemon(demon, link) {
         var element;
 
-        if (demon.position <= this.list_size) {
+        let index = this.sectionIndexOf(demon.position);
+
+        if (index === 0) {
             element = document.createElement("b");
-        } else if (demon.position <= this.extended_list_size) {
+        } else if (index !== -1 && this.sections[index].numbered) {
             element = document.createElement("span");
         } else {
             element = document.createElement("i");
@@ -1,12 +1,12 @@
-import {Dropdown} from "/static/core/js/modules/form.js";
+import {displayError, Dropdown} from "/static/core/js/modules/form.js";
 import {getCountryFlag, populateSubdivisionDropdown} from "/static/demonlist/js/modules/demonlist.js";
 import {formatInto, InteractiveWorldMap, StatsViewer} from "/static/demonlist/js/modules/statsviewer.js";
 
 class IndividualStatsViewer extends StatsViewer {
     constructor(html) {
         super(html, {
-            retrievalEndpoint: "/api/v1/players/",
-            rankingEndpoint: "/api/v1/players/ranking/",
+            retrievalEndpoint: html.dataset.listApi + "/players/",
+            rankingEndpoint: html.dataset.listApi + "/players/ranking/",
             entryGenerator: generateStatsViewerPlayer
         });
     }
@@ -26,20 +26,8 @@ class IndividualStatsViewer extends StatsViewer {
 
         beaten.sort((r1, r2) => r1.demon.name.localeCompare(r2.demon.name));
 
-        let legacy = beaten.filter(
-            (record) => record.demon.position > this.extended_list_size
-        ).length;
-        let extended = beaten.filter(
-            (record) =>
-                record.demon.position > this.list_size &&
-                record.demon.position <= this.extended_list_size
-        ).length;
-
-        let verifiedExtended = playerData.verified.filter(demon => demon.position <= this.extended_list_size && demon.position > this.list_size).length;
-        let verifiedLegacy = playerData.verified.filter(demon => demon.position > this.extended_list_size).length;
-
         this.formatRecordsInto(this._beaten, beaten);
-        this.setCompletionNumber(beaten.length - legacy - extended + playerData.verified.length - verifiedExtended - verifiedLegacy, extended + verifiedExtended, legacy + verifiedLegacy);
+        this.setCompletionNumber(this.countPerSection(beaten.map((record) => record.demon).concat(playerData.verified)));
 
         let hardest = playerData.verified
             .concat(beaten.map((record) => record.demon))
@@ -76,7 +64,14 @@ $(window).on("load", function () {
     map.showSubdivisions();
 
     window.statsViewer = new IndividualStatsViewer(document.getElementById("statsviewer"));
-    window.statsViewer.initialize();
+    window.statsViewer.initialize().then(() => {
+        // Links to player profiles are of the form /demonlist/statsviewer/?player=<id> (or /lists/<slug>/statsviewer/?player=<id>)
+        let player = new URLSearchParams(window.location.search).get("player");
+
+        if (player !== null) {
+            window.statsViewer.selectArbitrary(player).catch(displayError(window.statsViewer));
+        }
+    });
 
     new Dropdown(
         document
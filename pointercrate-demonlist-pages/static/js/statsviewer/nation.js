@@ -5,8 +5,8 @@ import {getCountryFlag} from "/static/demonlist/js/modules/demonlist.js";
 class NationStatsViewer extends StatsViewer {
     constructor(html) {
         super(html, {
-            retrievalEndpoint: "/api/v1/nationalities/",
-            rankingEndpoint: "/api/v1/nationalities/ranking/",
+            retrievalEndpoint: html.dataset.listApi + "/nationalities/",
+            rankingEndpoint: html.dataset.listApi + "/nationalities/ranking/",
             entryGenerator: generateStatsViewerNation
         });
 
@@ -25,9 +25,6 @@ class NationStatsViewer extends StatsViewer {
         let beaten = [];
         let progress = [];
 
-        let legacy = 0;
-        let extended = 0;
-
         let hardest = undefined;
 
         let players = new Set();
@@ -45,16 +42,11 @@ class NationStatsViewer extends StatsViewer {
                 if(hardest === undefined || record.position < hardest.position) {
                     hardest = {name: record.demon, position: record.position, id: record.id};
                 }
-
-                if(record.position > this.list_size)
-                    if(record.position <= this.extended_list_size)
-                        ++extended;
-                    else
-                        ++legacy;
             }
         }
 
-        let amountBeaten = beaten.length - extended - legacy;
+        // Verifications count as completions, but demons which were also beaten by someone from this nation shouldn't be counted twice
+        let completed = beaten.slice();
 
         for(let record of nationData.verified) {
             players.add(record.player);
@@ -64,19 +56,13 @@ class NationStatsViewer extends StatsViewer {
             }
 
             if(!beaten.some(d => d.id === record.id))
-                if(record.position > this.list_size)
-                    if(record.position <= this.extended_list_size)
-                        ++extended;
-                    else
-                        ++legacy;
-                else
-                    ++amountBeaten;
+                completed.push(record);
         }
 
         this._players.innerText = players.size.toString();
 
         this.setHardest(hardest);
-        this.setCompletionNumber(amountBeaten, extended, legacy);
+        this.setCompletionNumber(this.countPerSection(completed));
 
         nationData.unbeaten.sort((r1, r2) => r1.name.localeCompare(r2.name));
         beaten.sort((r1, r2) => r1.demon.localeCompare(r2.demon));
@@ -1,5 +1,6 @@
-SELECT id, name::TEXT, banned, nation::TEXT, iso_country_code::TEXT, players.score
+SELECT id, name::TEXT, banned, nation::TEXT, iso_country_code::TEXT, COALESCE(player_scores.score, 0) AS score
 FROM players
+LEFT OUTER JOIN player_scores ON player_scores.player = players.id AND player_scores.list = $9
 LEFT OUTER JOIN nationalities ON nationality = iso_country_code
 WHERE (id < $1 OR $1 IS NULL)
   AND (id > $2 OR $2 IS NULL)
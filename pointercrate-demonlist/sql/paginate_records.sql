@@ -18,5 +18,8 @@ WHERE (records.id < $1 OR $1 IS NULL)
   AND (records.video = $12 OR (records.video IS NULL AND $13) OR ($12 IS NULL AND NOT $13))
   AND (players.id = $14 OR $14 IS NULL)
   AND (records.submitter = $15 OR $15 IS NULL)
+  AND (demons.list = $17 OR $17 IS NULL)
+  AND (EXISTS(SELECT 1 FROM record_video_checks WHERE record = records.id AND unreachable_since IS NOT NULL) = $18 OR $18 IS NULL)
+  AND (EXISTS(SELECT 1 FROM record_proofs WHERE record = records.id AND kind = 'RAW_FOOTAGE') = $19 OR $19 IS NULL)
 ORDER BY id {}
 LIMIT $16
\ No newline at end of file
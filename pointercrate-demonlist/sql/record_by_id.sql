@@ -1,9 +1,11 @@
-SELECT progress, CASE WHEN players.link_banned THEN NULL ELSE records.video::text END, status_::text AS "status!: String" ,
+SELECT progress, CASE WHEN players.link_banned THEN NULL ELSE records.video::text END, status_::text AS "status!: String", status_reason,
        players.id AS player_id, players.name AS "player_name: String", players.banned AS player_banned,
        demons.id AS demon_id, demons.name AS "demon_name: String", demons.position,
-       submitters.submitter_id AS submitter_id, submitters.banned AS submitter_banned
+       submitters.submitter_id AS submitter_id, submitters.banned AS submitter_banned,
+       record_video_checks.unreachable_since AS "video_unreachable_since?"
 FROM records
 INNER JOIN players ON records.player = players.id
 INNER JOIN demons ON records.demon = demons.id
 INNER JOIN submitters ON records.submitter = submitters.submitter_id
+LEFT OUTER JOIN record_video_checks ON records.id = record_video_checks.record
 WHERE records.id = $1
\ No newline at end of file
@@ -1,9 +1,7 @@
 use pointercrate_core::util::from_env_or_default;
 
-pub fn list_size() -> i16 {
-    from_env_or_default("LIST_SIZE", 75)
-}
-
-pub fn extended_list_size() -> i16 {
-    from_env_or_default("EXTENDED_LIST_SIZE", 150)
+/// The slug of the list served under the routes that predate support for multiple lists (such as
+/// `/demonlist/` or `/api/v2/demons/`)
+pub fn default_list() -> String {
+    from_env_or_default("DEFAULT_LIST", "demonlist".to_string())
 }
@@ -54,11 +54,12 @@ pub async fn creators_of(demon: &MinimalDemon, connection: &mut PgConnection) ->
     Ok(players)
 }
 
-pub async fn created_by(player_id: i32, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
+pub async fn created_by(player_id: i32, list: i32, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
     query_many_demons!(
         connection,
         r#"SELECT demons.id, demons.name, demons.position FROM demons INNER JOIN creators ON demons.id = creators.demon WHERE
-         creators.creator=$1"#,
-        player_id
+         creators.creator=$1 AND demons.list=$2 AND NOT demons.unlisted"#,
+        player_id,
+        list
     )
 }
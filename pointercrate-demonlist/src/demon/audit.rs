@@ -16,14 +16,26 @@ pub struct DemonModificationData {
     pub video: Option<String>,
     pub verifier: Option<NamedId>,
     pub publisher: Option<NamedId>,
+    pub unlisted: Option<bool>,
 }
 
 #[derive(Serialize, Debug)]
 pub enum MovementReason {
     Added,
     Moved,
-    OtherAddedAbove { other: NamedId },
-    OtherMoved { other: NamedId },
+    OtherAddedAbove {
+        other: NamedId,
+    },
+    OtherMoved {
+        other: NamedId,
+    },
+    OtherRemoved {
+        other: NamedId,
+    },
+    /// The demon was shifted around as part of a reorder that explicitly moved the given demons
+    Reordered {
+        moved: Vec<NamedId>,
+    },
     Unknown,
 }
 
@@ -44,6 +56,10 @@ pub async fn movement_log_for_demon(demon_id: i32, connection: &mut PgConnection
     let mut additions = HashMap::new();
     // map time -> NamedId keeping track when movements to -1 happened
     let mut all_moves = HashMap::new();
+    // map time -> NamedId keeping track of all demons that got unlisted or deleted
+    let mut removals = HashMap::new();
+    // map time -> Vec<NamedId> keeping track of the demons explicitly moved by each reorder
+    let mut reorders: HashMap<_, Vec<_>> = HashMap::new();
 
     {
         // non-lexical lifetimes working amazingly I see >.>
@@ -84,6 +100,50 @@ pub async fn movement_log_for_demon(demon_id: i32, connection: &mut PgConnection
         }
     }
 
+    {
+        // The audit trigger for deletions also sets the "unlisted" column to the (old) value `false` if a listed demon gets deleted.
+        // Only removals that happened at the same time as a modification of the demon we're looking at can explain its movements.
+        let mut removal_stream = sqlx::query!(
+            r#"SELECT removals.time, removals.id, COALESCE(demons.name, removals.name)::TEXT AS name
+               FROM demon_modifications AS own
+               INNER JOIN demon_modifications AS removals ON removals.time = own.time AND removals.unlisted = FALSE
+               LEFT OUTER JOIN demons ON demons.id = removals.id
+               WHERE own.id = $1"#,
+            demon_id
+        )
+        .fetch(&mut *connection);
+
+        while let Some(row) = removal_stream.next().await {
+            let row = row?;
+            removals.insert(
+                row.time,
+                NamedId {
+                    id: row.id,
+                    name: row.name,
+                },
+            );
+        }
+    }
+
+    {
+        // Same as above, only reorders that modified the demon we're looking at are relevant
+        let mut reorder_stream = sqlx::query!(
+            r#"SELECT time, moved.id AS "id!", demons.name::TEXT FROM demon_reorders CROSS JOIN LATERAL UNNEST(demon_reorders.moved) AS moved(id)
+               LEFT OUTER JOIN demons ON demons.id = moved.id
+               WHERE EXISTS (SELECT 1 FROM demon_modifications AS own WHERE own.id = $1 AND own.time = demon_reorders.time)"#,
+            demon_id
+        )
+        .fetch(&mut *connection);
+
+        while let Some(row) = reorder_stream.next().await {
+            let row = row?;
+            reorders.entry(row.time).or_default().push(NamedId {
+                id: row.id,
+                name: row.name,
+            });
+        }
+    }
+
     for log_entry in audit_log {
         let time = log_entry.time;
 
@@ -118,6 +178,21 @@ pub async fn movement_log_for_demon(demon_id: i32, connection: &mut PgConnection
                         continue;
                     }
 
+                    // all position changes done by a reorder happen in the same transaction and thus share a timestamp
+                    if let Some(moved) = reorders.get(&time) {
+                        movement_log.push(MovementLogEntry {
+                            reason: if moved.iter().any(|other| other.id == demon_id) {
+                                MovementReason::Moved
+                            } else {
+                                MovementReason::Reordered { moved: moved.clone() }
+                            },
+                            new_position: None,
+                            time,
+                        });
+
+                        continue;
+                    }
+
                     let moved = all_moves.get(&time);
 
                     match moved {
@@ -142,11 +217,20 @@ pub async fn movement_log_for_demon(demon_id: i32, connection: &mut PgConnection
                                     new_position: None,
                                     time,
                                 }),
-                                None => movement_log.push(MovementLogEntry {
-                                    reason: MovementReason::Unknown,
-                                    new_position: None,
-                                    time,
-                                }),
+                                None => match removals.get(&time) {
+                                    Some(removed_demon) => movement_log.push(MovementLogEntry {
+                                        reason: MovementReason::OtherRemoved {
+                                            other: removed_demon.clone(),
+                                        },
+                                        new_position: None,
+                                        time,
+                                    }),
+                                    None => movement_log.push(MovementLogEntry {
+                                        reason: MovementReason::Unknown,
+                                        new_position: None,
+                                        time,
+                                    }),
+                                },
                             }
                         },
                     }
@@ -159,11 +243,13 @@ pub async fn movement_log_for_demon(demon_id: i32, connection: &mut PgConnection
                     // movement is the shift induced by that other demon being moved
                     // if there exists an addition entry for another demon with the same timestamp,
                     // then this movement is the shift induced by that addition
+                    // same for unlistings and deletions of other demons
                     // otherwise, we do not know (the log entry is from before we kept track of
                     // audit logs accurately) :(
                 }
             },
-            AuditLogEntryType::Deletion => unreachable!(),
+            // deleted demons have no current position, see below
+            AuditLogEntryType::Deletion => (),
         }
     }
 
@@ -177,6 +263,71 @@ pub async fn movement_log_for_demon(demon_id: i32, connection: &mut PgConnection
     Ok(movement_log)
 }
 
+/// A demon being added to a list, or being explicitly moved on it
+#[derive(Serialize, Debug)]
+pub struct ListChange {
+    pub demon_id: i32,
+    pub demon_name: String,
+    pub time: NaiveDateTime,
+
+    /// Whether the demon was newly added (as opposed to being moved)
+    pub added: bool,
+
+    /// The position the demon was placed at by this change
+    pub new_position: i16,
+}
+
+/// Gets the most recent additions and (explicit) movements of demons on the given list, most recent
+/// first
+///
+/// Demons shifted around as a consequence of another demon being added or moved are not included.
+/// Changes to demons that have since been deleted are skipped.
+pub async fn recent_list_changes(list: i32, limit: i64, connection: &mut PgConnection) -> Result<Vec<ListChange>> {
+    // The audit log stores the old values of modified columns, so the position a demon ended up at after a change is the old
+    // position logged by the next change of its position (skipping the temporary -1 of moves), or its current position if there
+    // was none. Only looked up for the changes that make it into the result.
+    let rows = sqlx::query!(
+        r#"SELECT changes.time AS "time!", changes.id AS "id!", changes.name AS "name!", changes.added AS "added!",
+                  COALESCE(next_position.position, demons.position) AS "new_position!"
+           FROM (
+               SELECT * FROM (
+                   SELECT demon_additions.time, demons.id, demons.name::TEXT, TRUE AS added FROM demon_additions
+                   INNER JOIN demons ON demons.id = demon_additions.id WHERE demons.list = $1
+                 UNION ALL
+                   SELECT demon_modifications.time, demons.id, demons.name::TEXT, FALSE FROM demon_modifications
+                   INNER JOIN demons ON demons.id = demon_modifications.id WHERE demon_modifications.position = -1 AND demons.list = $1
+                 UNION ALL
+                   SELECT demon_reorders.time, demons.id, demons.name::TEXT, FALSE FROM demon_reorders
+                   CROSS JOIN LATERAL UNNEST(demon_reorders.moved) AS moved(id) INNER JOIN demons ON demons.id = moved.id
+                   WHERE demon_reorders.list = $1
+               ) AS all_changes ORDER BY time DESC LIMIT $2
+           ) AS changes
+           INNER JOIN demons ON demons.id = changes.id
+           LEFT JOIN LATERAL (
+               SELECT position FROM demon_modifications
+               WHERE demon_modifications.id = changes.id AND demon_modifications.time > changes.time
+                 AND demon_modifications.position IS NOT NULL AND demon_modifications.position <> -1
+               ORDER BY demon_modifications.time LIMIT 1
+           ) AS next_position ON TRUE
+           ORDER BY changes.time DESC"#,
+        list,
+        limit
+    )
+    .fetch_all(connection)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ListChange {
+            demon_id: row.id,
+            demon_name: row.name,
+            time: row.time,
+            added: row.added,
+            new_position: row.new_position,
+        })
+        .collect())
+}
+
 pub async fn audit_log_for_demon(demon_id: i32, connection: &mut PgConnection) -> Result<Vec<AuditLogEntry<DemonModificationData>>> {
     let mut entries = Vec::new();
 
@@ -203,6 +354,14 @@ pub async fn audit_log_for_demon(demon_id: i32, connection: &mut PgConnection) -
         });
     }
 
+    let deletion_row = sqlx::query!(
+        r#"SELECT time, audit_id, userid, members.name AS "name?" FROM demon_deletions LEFT OUTER JOIN members ON members.member_id = userid
+           WHERE id = $1"#,
+        demon_id
+    )
+    .fetch_optional(&mut *connection)
+    .await?;
+
     let mut modification_stream = sqlx::query!(
         r#"SELECT time,
                 audit_id,
@@ -215,7 +374,8 @@ pub async fn audit_log_for_demon(demon_id: i32, connection: &mut PgConnection) -
                 verifier,
                 verifiers.name::text as verifier_name,
                 publisher,
-                publishers.name::text as publisher_name
+                publishers.name::text as publisher_name,
+                unlisted
            FROM demon_modifications
            LEFT OUTER JOIN members ON members.member_id = userid
            LEFT OUTER JOIN players AS verifiers ON verifier=verifiers.id
@@ -253,6 +413,7 @@ pub async fn audit_log_for_demon(demon_id: i32, connection: &mut PgConnection) -
                     }),
                     None => None,
                 },
+                unlisted: row.unlisted,
             }),
             user: NamedId {
                 name: row.username,
@@ -261,5 +422,18 @@ pub async fn audit_log_for_demon(demon_id: i32, connection: &mut PgConnection) -
         })
     }
 
+    if let Some(deletion) = deletion_row {
+        entries.push(AuditLogEntry {
+            time: deletion.time,
+            entry_id: deletion.audit_id,
+            id: demon_id,
+            user: NamedId {
+                name: deletion.name,
+                id: deletion.userid,
+            },
+            r#type: AuditLogEntryType::Deletion,
+        });
+    }
+
     Ok(entries)
 }
@@ -0,0 +1,51 @@
+use crate::{
+    demon::{Demon, FullDemon},
+    error::Result,
+    player::recompute_scores_of_list,
+};
+use log::info;
+use sqlx::PgConnection;
+
+impl FullDemon {
+    /// Deletes this demon, together with all its records and creators, closing the gap it leaves
+    /// behind on its list. Open placement proposals about it are rejected.
+    ///
+    /// If you want to keep the demon's records around, [unlist](Demon::unlist) it instead.
+    ///
+    /// Must be run within a transaction!
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        info!("Deleting demon {}", self);
+
+        // Associated notes get deleted due to the ON DELETE CASCADE on record_notes.record
+        sqlx::query!("DELETE FROM records WHERE demon = $1", self.demon.base.id)
+            .execute(&mut *connection)
+            .await?;
+
+        sqlx::query!("DELETE FROM creators WHERE demon = $1", self.demon.base.id)
+            .execute(&mut *connection)
+            .await?;
+
+        // Proposals about this demon are kept (see the ON DELETE SET NULL on placement_proposals.demon), but there is nothing left to
+        // decide for the open ones
+        sqlx::query!(
+            "UPDATE placement_proposals SET status = 'REJECTED', resolved_at = (NOW() AT TIME ZONE 'utc') WHERE demon = $1 AND status = \
+             'OPEN'",
+            self.demon.base.id
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        sqlx::query!("DELETE FROM demons WHERE id = $1", self.demon.base.id)
+            .execute(&mut *connection)
+            .await?;
+
+        // Unlisted demons have already left a gap that got closed when they were unlisted
+        if !self.demon.unlisted {
+            Demon::shift_up(self.demon.base.position, self.demon.list, connection).await?;
+        }
+
+        recompute_scores_of_list(self.demon.list, connection).await?;
+
+        Ok(())
+    }
+}
@@ -20,13 +20,18 @@ impl MinimalDemon {
             })
     }
 
-    pub async fn by_position(position: i16, connection: &mut PgConnection) -> Result<MinimalDemon> {
+    pub async fn by_position(position: i16, list: i32, connection: &mut PgConnection) -> Result<MinimalDemon> {
         let row = sqlx::query!(
-            r#"SELECT id, name as "name: String", position FROM demons WHERE position = $1"#,
-            position
+            r#"SELECT id, name as "name: String", position FROM demons WHERE position = $1 AND list = $2 AND NOT unlisted"#,
+            position,
+            list
         )
         .fetch_one(connection)
-        .await?;
+        .await
+        .map_err(|err| match err {
+            Error::RowNotFound => DemonlistError::DemonNotFoundPosition { demon_position: position },
+            _ => err.into(),
+        })?;
 
         Ok(MinimalDemon {
             id: row.id,
@@ -77,8 +82,8 @@ impl FullDemon {
         Demon::by_id(id, connection).await?.upgrade(connection).await
     }
 
-    pub async fn by_position(position: i16, connection: &mut PgConnection) -> Result<FullDemon> {
-        Demon::by_position(position, connection).await?.upgrade(connection).await
+    pub async fn by_position(position: i16, list: i32, connection: &mut PgConnection) -> Result<FullDemon> {
+        Demon::by_position(position, list, connection).await?.upgrade(connection).await
     }
 }
 
@@ -106,8 +111,8 @@ impl Demon {
             })
     }
 
-    pub async fn by_position(position: i16, connection: &mut PgConnection) -> Result<Demon> {
-        sqlx::query_file_as!(FetchedDemon, "sql/demon_by_position.sql", position)
+    pub async fn by_position(position: i16, list: i32, connection: &mut PgConnection) -> Result<Demon> {
+        sqlx::query_file_as!(FetchedDemon, "sql/demon_by_position.sql", position, list)
             .fetch_one(connection)
             .await
             .map(Into::into)
@@ -119,8 +124,8 @@ impl Demon {
 }
 
 macro_rules! query_many_demons {
-    ($connection:expr, $query:expr, $id:expr) => {{
-        let mut stream = sqlx::query_as!(MinimalDemon, $query, $id).fetch($connection);
+    ($connection:expr, $query:expr, $($arg:expr),*) => {{
+        let mut stream = sqlx::query_as!(MinimalDemon, $query, $($arg),*).fetch($connection);
         let mut demons = Vec::new();
 
         while let Some(row) = stream.next().await {
@@ -131,19 +136,21 @@ macro_rules! query_many_demons {
     }};
 }
 
-pub async fn published_by(player: &DatabasePlayer, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
+pub async fn published_by(player: &DatabasePlayer, list: i32, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
     query_many_demons!(
         connection,
-        r#"SELECT id, name, position FROM demons WHERE publisher = $1"#,
-        player.id
+        r#"SELECT id, name, position FROM demons WHERE publisher = $1 AND list = $2 AND NOT unlisted"#,
+        player.id,
+        list
     )
 }
 
-pub async fn verified_by(player: &DatabasePlayer, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
+pub async fn verified_by(player: &DatabasePlayer, list: i32, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
     query_many_demons!(
         connection,
-        r#"SELECT id, name, position FROM demons WHERE verifier = $1"#,
-        player.id
+        r#"SELECT id, name, position FROM demons WHERE verifier = $1 AND list = $2 AND NOT unlisted"#,
+        player.id,
+        list
     )
 }
 
@@ -161,6 +168,8 @@ struct FetchedDemon {
     verifier_name: String,
     verifier_banned: bool,
     level_id: Option<i64>,
+    list: i32,
+    unlisted: bool,
 }
 
 impl From<FetchedDemon> for Demon {
@@ -185,12 +194,14 @@ impl From<FetchedDemon> for Demon {
                 banned: fetched.verifier_banned,
             },
             level_id: fetched.level_id.map(|id| id as u64),
+            list: fetched.list,
+            unlisted: fetched.unlisted,
         }
     }
 }
 
-pub async fn current_list(connection: &mut PgConnection) -> Result<Vec<Demon>> {
-    Ok(sqlx::query_file_as!(FetchedDemon, "sql/all_demons.sql")
+pub async fn current_list(list: i32, connection: &mut PgConnection) -> Result<Vec<Demon>> {
+    Ok(sqlx::query_file_as!(FetchedDemon, "sql/all_demons.sql", list)
         .fetch_all(connection)
         .await?
         .into_iter()
@@ -198,8 +209,8 @@ pub async fn current_list(connection: &mut PgConnection) -> Result<Vec<Demon>> {
         .collect())
 }
 
-pub async fn list_at(connection: &mut PgConnection, at: NaiveDateTime) -> Result<Vec<TimeShiftedDemon>> {
-    let mut stream = sqlx::query_file!("sql/all_demons_at.sql", at).fetch(connection);
+pub async fn list_at(list: i32, connection: &mut PgConnection, at: NaiveDateTime) -> Result<Vec<TimeShiftedDemon>> {
+    let mut stream = sqlx::query_file!("sql/all_demons_at.sql", at, list).fetch(connection);
     let mut demons = Vec::new();
 
     while let Some(row) = stream.next().await {
@@ -226,8 +237,10 @@ pub async fn list_at(connection: &mut PgConnection, at: NaiveDateTime) -> Result
                     banned: row.verifier_banned,
                 },
                 level_id: row.level_id.map(|i| i as u64),
+                list: row.list,
+                unlisted: false,
             },
-            position_now: row.current_position,
+            position_now: Some(row.current_position),
         })
     }
 
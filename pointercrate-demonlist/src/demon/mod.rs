@@ -1,8 +1,9 @@
 pub use self::{
     get::{current_list, list_at, published_by, verified_by},
     paginate::{DemonIdPagination, DemonPositionPagination},
-    patch::PatchDemon,
+    patch::{derive_thumbnails, PatchDemon},
     post::PostDemon,
+    reorder::{DemonMove, Reorder},
 };
 use crate::{
     error::{DemonlistError, Result},
@@ -22,13 +23,19 @@ use std::{
 #[macro_use]
 mod get;
 pub mod audit;
+mod delete;
 mod paginate;
 mod patch;
 mod post;
+mod reorder;
 
 pub struct TimeShiftedDemon {
     pub current_demon: Demon,
-    pub position_now: i16,
+
+    /// The position this demon is at right now, or `None` if it is not on the list at the moment
+    /// (which happens when previewing a [`ListUpdate`](crate::list::update::ListUpdate) that adds
+    /// it)
+    pub position_now: Option<i16>,
 }
 
 /// Struct modelling a demon. These objects are returned from the paginating `/demons/` endpoint
@@ -57,6 +64,15 @@ pub struct Demon {
     /// This is automatically queried based on the level name, but can be manually overridden by a
     /// list mod.
     pub level_id: Option<u64>,
+
+    /// The id of the [`List`](crate::list::List) this [`Demon`] is on
+    pub list: i32,
+
+    /// Whether this [`Demon`] has been removed from its list
+    ///
+    /// Unlisted demons keep the position they were at when they got unlisted, but that position
+    /// might now be taken by another demon. Neither they nor their records give any points.
+    pub unlisted: bool,
 }
 
 /// Absolutely minimal representation of a demon to be sent when a demon is part of another object
@@ -66,9 +82,9 @@ pub struct MinimalDemon {
     /// The [`Demon`]'s unique internal pointercrate ID
     pub id: i32,
 
-    /// The [`Demon`]'s position on the demonlist
+    /// The [`Demon`]'s position on its list
     ///
-    /// Positions for consecutive demons are always consecutive positive integers
+    /// Positions for consecutive demons on the same list are always consecutive positive integers
     pub position: i16,
 
     /// The [`Demon`]'s Geometry Dash level name
@@ -175,10 +191,10 @@ impl Demon {
         Ok(())
     }
 
-    pub async fn validate_position(position: i16, connection: &mut PgConnection) -> Result<()> {
+    pub async fn validate_position(position: i16, list: i32, connection: &mut PgConnection) -> Result<()> {
         // To prevent holes from being created in the list, the new position must lie between 1 and (current
         // last position + 1), inclusive
-        let maximal_position = Demon::max_position(connection).await? + 1;
+        let maximal_position = Demon::max_position(list, connection).await? + 1;
 
         if position > maximal_position || position < 1 {
             return Err(DemonlistError::InvalidPosition { maximal: maximal_position });
@@ -187,26 +203,64 @@ impl Demon {
         Ok(())
     }
 
-    /// Increments the position of all demons with positions equal to or greater than the given one,
-    /// by one.
-    async fn shift_down(starting_at: i16, connection: &mut PgConnection) -> Result<()> {
-        info!("Shifting down all demons, starting at {}", starting_at);
+    /// Ensures that the demon with the given id exists and is currently listed on the given list
+    pub(crate) async fn validate_on_list(demon_id: i32, list: i32, connection: &mut PgConnection) -> Result<()> {
+        let demon = Demon::by_id(demon_id, connection).await?;
+
+        if demon.unlisted {
+            return Err(DemonlistError::DemonUnlisted);
+        }
 
-        sqlx::query!("UPDATE demons SET position = position + 1 WHERE position >= $1", starting_at)
-            .execute(connection)
-            .await?;
+        if demon.list != list {
+            return Err(DemonlistError::DemonNotOnList { demon_id });
+        }
 
         Ok(())
     }
 
-    /// Gets the current max position a demon has, or `0` if there are no demons
-    /// in the database
-    pub async fn max_position(connection: &mut PgConnection) -> Result<i16> {
-        Ok(sqlx::query!("SELECT MAX(position) as max_position FROM demons")
-            .fetch_one(connection)
-            .await?
-            .max_position
-            .unwrap_or(0))
+    /// Increments the position of all demons on the given list with positions equal to or greater
+    /// than the given one, by one.
+    async fn shift_down(starting_at: i16, list: i32, connection: &mut PgConnection) -> Result<()> {
+        info!("Shifting down all demons on list {}, starting at {}", list, starting_at);
+
+        sqlx::query!(
+            "UPDATE demons SET position = position + 1 WHERE position >= $1 AND list = $2 AND NOT unlisted",
+            starting_at,
+            list
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Decrements the position of all demons on the given list with positions greater than the
+    /// given one, by one. Used to close the gap left behind by a demon that got removed from the list
+    async fn shift_up(starting_after: i16, list: i32, connection: &mut PgConnection) -> Result<()> {
+        info!("Shifting up all demons on list {}, starting after {}", list, starting_after);
+
+        sqlx::query!(
+            "UPDATE demons SET position = position - 1 WHERE position > $1 AND list = $2 AND NOT unlisted",
+            starting_after,
+            list
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Gets the current max position a demon on the given list has, or `0` if there are no demons
+    /// on that list
+    pub async fn max_position(list: i32, connection: &mut PgConnection) -> Result<i16> {
+        Ok(sqlx::query!(
+            "SELECT MAX(position) as max_position FROM demons WHERE list = $1 AND NOT unlisted",
+            list
+        )
+        .fetch_one(connection)
+        .await?
+        .max_position
+        .unwrap_or(0))
     }
 
     pub fn score(&self, progress: i16) -> f64 {
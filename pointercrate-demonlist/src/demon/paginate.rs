@@ -42,6 +42,14 @@ pub struct DemonIdPagination {
     #[serde(default, deserialize_with = "non_nullable")]
     #[serde(rename = "requirement__lt")]
     requirement_lt: Option<i16>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    unlisted: Option<bool>,
+
+    /// The id of the list to restrict the pagination to. Not part of the query string, but set
+    /// based on the list the request was made for.
+    #[serde(skip)]
+    pub list: Option<i32>,
 }
 
 impl PaginationQuery for DemonIdPagination {
@@ -79,6 +87,8 @@ impl Paginatable<DemonIdPagination> for Demon {
             .bind(query.publisher_name.as_deref())
             .bind(query.name_contains.as_deref())
             .bind(query.params.limit + 1)
+            .bind(query.list)
+            .bind(query.unlisted)
             .fetch(connection);
 
         let mut demons = Vec::new();
@@ -108,6 +118,8 @@ impl Paginatable<DemonIdPagination> for Demon {
                     banned: row.get("verifier_banned"),
                 },
                 level_id: row.get::<Option<i64>, _>("level_id").map(|id| id as u64),
+                list: row.get("list"),
+                unlisted: row.get("unlisted"),
             })
         }
 
@@ -150,6 +162,11 @@ pub struct DemonPositionPagination {
     #[serde(default, deserialize_with = "non_nullable")]
     #[serde(rename = "requirement__lt")]
     pub requirement_lt: Option<i16>,
+
+    /// The id of the list to restrict the pagination to. Not part of the query string, but set
+    /// based on the list the request was made for.
+    #[serde(skip)]
+    pub list: Option<i32>,
 }
 
 impl PaginationQuery for DemonPositionPagination {
@@ -187,6 +204,7 @@ impl Paginatable<DemonPositionPagination> for Demon {
             .bind(query.publisher_name.as_deref())
             .bind(query.name_contains.as_deref())
             .bind(query.params.limit + 1)
+            .bind(query.list)
             .fetch(connection);
 
         let mut demons = Vec::new();
@@ -216,6 +234,8 @@ impl Paginatable<DemonPositionPagination> for Demon {
                     banned: row.get("verifier_banned"),
                 },
                 level_id: row.get::<Option<i64>, _>("level_id").map(|id| id as u64),
+                list: row.get("list"),
+                unlisted: row.get("unlisted"),
             })
         }
 
@@ -1,7 +1,8 @@
 use crate::{
     demon::{Demon, FullDemon, MinimalDemon},
     error::{DemonlistError, Result},
-    player::{recompute_scores, DatabasePlayer},
+    player::{recompute_scores_of_list, DatabasePlayer},
+    video::VideoHosts,
 };
 use log::{debug, info, warn};
 use pointercrate_core::util::{non_nullable, nullable};
@@ -19,8 +20,10 @@ pub struct PatchDemon {
     #[serde(default, deserialize_with = "nullable")]
     pub video: Option<Option<String>>,
 
-    #[serde(default, deserialize_with = "non_nullable")]
-    pub thumbnail: Option<String>,
+    /// Setting the thumbnail overrides the one derived from the demon's video. Setting it to `null`
+    /// removes the override again.
+    #[serde(default, deserialize_with = "nullable")]
+    pub thumbnail: Option<Option<String>>,
 
     #[serde(default, deserialize_with = "non_nullable")]
     pub requirement: Option<i16>,
@@ -30,13 +33,16 @@ pub struct PatchDemon {
 
     #[serde(default, deserialize_with = "non_nullable")]
     pub publisher: Option<String>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    pub unlisted: Option<bool>,
 }
 
 impl FullDemon {
-    pub async fn apply_patch(mut self, patch: PatchDemon, connection: &mut PgConnection) -> Result<Self> {
+    pub async fn apply_patch(mut self, patch: PatchDemon, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<Self> {
         let changes_requirement = patch.requirement.is_some();
 
-        let updated_demon = self.demon.apply_patch(patch, connection).await?;
+        let updated_demon = self.demon.apply_patch(patch, hosts, connection).await?;
 
         if changes_requirement {
             self.records.retain(|record| record.progress >= updated_demon.requirement);
@@ -51,10 +57,23 @@ impl FullDemon {
 
 impl Demon {
     /// Must run inside a transaction!
-    pub async fn apply_patch(mut self, patch: PatchDemon, connection: &mut PgConnection) -> Result<Self> {
+    pub async fn apply_patch(mut self, patch: PatchDemon, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<Self> {
         // duplicate names are OK nowadays
 
+        // This needs to happen before the move, so that relisting a demon and moving it to its new position can be done in one request
+        if let Some(unlisted) = patch.unlisted {
+            if unlisted {
+                self.unlist(connection).await?;
+            } else {
+                self.relist(connection).await?;
+            }
+        }
+
         if let Some(position) = patch.position {
+            if self.unlisted {
+                return Err(DemonlistError::DemonUnlisted);
+            }
+
             self.base.mv(position, connection).await?;
         }
 
@@ -65,12 +84,15 @@ impl Demon {
         if let Some(video) = patch.video {
             match video {
                 None => self.remove_video(connection).await?,
-                Some(video) => self.set_video(video, connection).await?,
+                Some(video) => self.set_video(video, hosts, connection).await?,
             }
         }
 
         if let Some(thumbnail) = patch.thumbnail {
-            self.set_thumbnail(thumbnail, connection).await?;
+            match thumbnail {
+                None => self.reset_thumbnail(hosts, connection).await?,
+                Some(thumbnail) => self.set_thumbnail(thumbnail, connection).await?,
+            }
         }
 
         if let Some(verifier) = patch.verifier {
@@ -92,6 +114,62 @@ impl Demon {
         Ok(self)
     }
 
+    /// Removes this demon from its list, closing the gap it leaves behind by shifting up all demons
+    /// below it
+    ///
+    /// The demon's records are kept for archival purposes, but neither they nor the demon itself give
+    /// points anymore.
+    pub async fn unlist(&mut self, connection: &mut PgConnection) -> Result<()> {
+        if self.unlisted {
+            warn!("No-op unlisting of demon {}", self);
+
+            return Ok(());
+        }
+
+        sqlx::query!("UPDATE demons SET unlisted = TRUE WHERE id = $1", self.base.id)
+            .execute(&mut *connection)
+            .await?;
+
+        Demon::shift_up(self.base.position, self.list, connection).await?;
+
+        info!("Unlisted demon {} from position {}", self, self.base.position);
+
+        self.unlisted = true;
+
+        recompute_scores_of_list(self.list, connection).await?;
+
+        Ok(())
+    }
+
+    /// Puts a previously unlisted demon back onto its list. It is placed at the very end of the list
+    /// and has to be moved to its actual position afterwards.
+    pub async fn relist(&mut self, connection: &mut PgConnection) -> Result<()> {
+        if !self.unlisted {
+            warn!("No-op relisting of demon {}", self);
+
+            return Ok(());
+        }
+
+        let position = Demon::max_position(self.list, connection).await? + 1;
+
+        sqlx::query!(
+            "UPDATE demons SET unlisted = FALSE, position = $2 WHERE id = $1",
+            self.base.id,
+            position
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        info!("Relisted demon {} at position {}", self, position);
+
+        self.base.position = position;
+        self.unlisted = false;
+
+        recompute_scores_of_list(self.list, connection).await?;
+
+        Ok(())
+    }
+
     pub async fn set_verifier(&mut self, verifier: DatabasePlayer, connection: &mut PgConnection) -> Result<()> {
         if verifier.id != self.verifier.id {
             sqlx::query!("UPDATE demons SET verifier = $1 WHERE id = $2", verifier.id, self.base.id)
@@ -138,37 +216,133 @@ impl Demon {
         Ok(())
     }
 
-    pub async fn set_video(&mut self, video: String, connection: &mut PgConnection) -> Result<()> {
-        let video = crate::video::validate(&video)?;
+    pub async fn set_video(&mut self, video: String, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<()> {
+        let video = hosts.validate(&video)?;
 
         sqlx::query!("UPDATE demons SET video = $1::text WHERE id = $2", video, self.base.id)
-            .execute(connection)
+            .execute(&mut *connection)
             .await?;
 
         self.video = Some(video);
 
-        Ok(())
+        self.derive_thumbnail(hosts, connection).await
     }
 
+    /// Removes this demon's video, resetting its thumbnail to the default one unless it has been
+    /// overridden manually
     pub async fn remove_video(&mut self, connection: &mut PgConnection) -> Result<()> {
         sqlx::query!("UPDATE demons SET video = NULL WHERE id = $1", self.base.id)
-            .execute(connection)
+            .execute(&mut *connection)
             .await?;
 
         self.video = None;
 
-        Ok(())
+        self.reset_derived_thumbnail(connection).await
     }
 
+    /// Manually sets this demon's thumbnail, which stops it from being derived from the demon's
+    /// video
     pub async fn set_thumbnail(&mut self, thumbnail: String, connection: &mut PgConnection) -> Result<()> {
-        sqlx::query!("UPDATE demons SET thumbnail = $1::text WHERE id = $2", thumbnail, self.base.id)
-            .execute(connection)
-            .await?;
+        sqlx::query!(
+            "UPDATE demons SET thumbnail = $1::text, thumbnail_overridden = TRUE WHERE id = $2",
+            thumbnail,
+            self.base.id
+        )
+        .execute(connection)
+        .await?;
 
         self.thumbnail = thumbnail;
 
         Ok(())
     }
+
+    /// Removes a manual override of this demon's thumbnail, deriving it from the demon's video again
+    pub async fn reset_thumbnail(&mut self, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<()> {
+        sqlx::query!("UPDATE demons SET thumbnail_overridden = FALSE WHERE id = $1", self.base.id)
+            .execute(&mut *connection)
+            .await?;
+
+        match self.video {
+            Some(_) => self.derive_thumbnail(hosts, connection).await,
+            None => self.reset_derived_thumbnail(connection).await,
+        }
+    }
+
+    /// Resets this demon's thumbnail to the default one, unless it has been overridden manually
+    async fn reset_derived_thumbnail(&mut self, connection: &mut PgConnection) -> Result<()> {
+        let reset = sqlx::query!(
+            "UPDATE demons SET thumbnail = DEFAULT WHERE id = $1 AND NOT thumbnail_overridden RETURNING thumbnail",
+            self.base.id
+        )
+        .fetch_optional(connection)
+        .await?;
+
+        if let Some(row) = reset {
+            self.thumbnail = row.thumbnail;
+        }
+
+        Ok(())
+    }
+
+    /// Sets this demon's thumbnail to the one derived from its video by the video's host, unless
+    /// it has been overridden manually (or the host does not provide thumbnails)
+    ///
+    /// Demons verified by link banned players keep their current thumbnail.
+    pub(crate) async fn derive_thumbnail(&mut self, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<()> {
+        let Some(thumbnail) = self.video.as_deref().and_then(|video| hosts.thumbnail_url(video)) else {
+            return Ok(());
+        };
+
+        let updated = sqlx::query!(
+            "UPDATE demons SET thumbnail = $1::text WHERE id = $2 AND NOT thumbnail_overridden AND NOT EXISTS (SELECT 1 FROM players \
+             WHERE players.id = demons.verifier AND players.link_banned)",
+            thumbnail,
+            self.base.id
+        )
+        .execute(connection)
+        .await?;
+
+        if updated.rows_affected() > 0 {
+            debug!("Derived thumbnail {} for demon {}", thumbnail, self);
+
+            self.thumbnail = thumbnail;
+        }
+
+        Ok(())
+    }
+}
+
+/// Derives the thumbnails of all demons whose thumbnail has not been overridden manually from
+/// their videos, returning how many thumbnails changed
+///
+/// Used to backfill thumbnails for demons added before their video host supported thumbnails (or
+/// before thumbnails were derived automatically at all).
+pub async fn derive_thumbnails(hosts: &VideoHosts, connection: &mut PgConnection) -> Result<u64> {
+    let demons = sqlx::query!(
+        r#"SELECT demons.id, demons.video::text AS "video!" FROM demons INNER JOIN players ON demons.verifier = players.id
+           WHERE demons.video IS NOT NULL AND NOT demons.thumbnail_overridden AND NOT players.link_banned"#
+    )
+    .fetch_all(&mut *connection)
+    .await?;
+
+    let (ids, thumbnails): (Vec<_>, Vec<_>) = demons
+        .into_iter()
+        .filter_map(|demon| hosts.thumbnail_url(&demon.video).map(|thumbnail| (demon.id, thumbnail)))
+        .unzip();
+
+    let changed = sqlx::query!(
+        "UPDATE demons SET thumbnail = derived.thumbnail FROM UNNEST($1::INTEGER[], $2::TEXT[]) AS derived(id, thumbnail) WHERE demons.id = \
+         derived.id AND demons.thumbnail <> derived.thumbnail",
+        &ids,
+        &thumbnails
+    )
+    .execute(connection)
+    .await?
+    .rows_affected();
+
+    info!("Derived {} demon thumbnails from verification videos", changed);
+
+    Ok(changed)
 }
 
 impl MinimalDemon {
@@ -184,13 +358,18 @@ impl MinimalDemon {
         Ok(())
     }
 
-    /// Moves this demon to the specified position
+    /// Moves this demon to the specified position on the list it is on
     ///
     /// Validates that `to` is `> 0` and less than or equal to the currently highest position on the
     /// list (to preven "holes")
     pub async fn mv(&mut self, to: i16, connection: &mut PgConnection) -> Result<()> {
+        let list = sqlx::query!("SELECT list FROM demons WHERE id = $1", self.id)
+            .fetch_one(&mut *connection)
+            .await?
+            .list;
+
         // This returns 0 if the list is empty, but if the list is empty then there is no demon for us to do a move with, so we will never get here anyway.
-        let maximal_position = Demon::max_position(connection).await?;
+        let maximal_position = Demon::max_position(list, connection).await?;
 
         if to > maximal_position || to < 1 {
             return Err(DemonlistError::InvalidPosition { maximal: maximal_position });
@@ -216,9 +395,10 @@ impl MinimalDemon {
             );
 
             sqlx::query!(
-                "UPDATE demons SET position = position - 1 WHERE position > $1 AND position <= $2",
+                "UPDATE demons SET position = position - 1 WHERE position > $1 AND position <= $2 AND list = $3 AND NOT unlisted",
                 self.position,
-                to
+                to,
+                list
             )
             .execute(&mut *connection)
             .await?;
@@ -229,9 +409,10 @@ impl MinimalDemon {
             );
 
             sqlx::query!(
-                "UPDATE demons SET position = position + 1 WHERE position >= $1 AND position < $2",
+                "UPDATE demons SET position = position + 1 WHERE position >= $1 AND position < $2 AND list = $3 AND NOT unlisted",
                 to,
-                self.position
+                self.position,
+                list
             )
             .execute(&mut *connection)
             .await?;
@@ -247,7 +428,7 @@ impl MinimalDemon {
 
         self.position = to;
 
-        recompute_scores(connection).await?;
+        recompute_scores_of_list(list, connection).await?;
 
         Ok(())
     }
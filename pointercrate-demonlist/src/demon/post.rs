@@ -3,55 +3,70 @@ use crate::{
     demon::{Demon, FullDemon, MinimalDemon},
     error::Result,
     player::{recompute_scores, DatabasePlayer},
+    video::VideoHosts,
 };
 use log::info;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use sqlx::PgConnection;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
 pub struct PostDemon {
-    name: String,
-    position: i16,
-    requirement: i16,
-    verifier: String,
-    publisher: String,
-    creators: Vec<String>,
-    video: Option<String>,
+    pub(crate) name: String,
+    pub(crate) position: i16,
+    pub(crate) requirement: i16,
+    pub(crate) verifier: String,
+    pub(crate) publisher: String,
+    pub(crate) creators: Vec<String>,
+    pub(crate) video: Option<String>,
 }
 
 impl FullDemon {
+    /// Creates a new demon on the list with the given id
+    ///
     /// Must be run within a transaction!
-    pub async fn create_from(data: PostDemon, connection: &mut PgConnection) -> Result<FullDemon> {
+    pub async fn create_from(mut data: PostDemon, list: i32, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<FullDemon> {
+        if let Some(ref video) = data.video {
+            data.video = Some(hosts.validate(video)?);
+        }
+
+        FullDemon::create_prevalidated(data, list, hosts, connection).await
+    }
+
+    /// Creates a new demon on the list with the given id from data whose video has already been
+    /// validated (for example because it is part of a list update or placement proposal)
+    ///
+    /// Must be run within a transaction!
+    pub(crate) async fn create_prevalidated(
+        data: PostDemon, list: i32, hosts: &VideoHosts, connection: &mut PgConnection,
+    ) -> Result<FullDemon> {
         info!("Creating new demon from {:?}", data);
 
         Demon::validate_requirement(data.requirement)?;
 
-        let video = match data.video {
-            Some(ref video) => Some(crate::video::validate(video)?),
-            None => None,
-        };
+        let video = data.video;
 
-        Demon::validate_position(data.position, connection).await?;
+        Demon::validate_position(data.position, list, connection).await?;
 
         let publisher = DatabasePlayer::by_name_or_create(data.publisher.as_ref(), connection).await?;
         let verifier = DatabasePlayer::by_name_or_create(data.verifier.as_ref(), connection).await?;
 
-        Demon::shift_down(data.position, connection).await?;
+        Demon::shift_down(data.position, list, connection).await?;
 
         let created = sqlx::query!(
-            "INSERT INTO demons (name, position, requirement, video, verifier, publisher) VALUES ($1::text,$2,$3,$4::text,$5,$6) \
-             RETURNING id, thumbnail",
+            "INSERT INTO demons (name, position, requirement, video, verifier, publisher, list) VALUES \
+             ($1::text,$2,$3,$4::text,$5,$6,$7) RETURNING id, thumbnail",
             data.name.to_string(),
             data.position,
             data.requirement,
             video.as_ref(),
             verifier.id,
-            publisher.id
+            publisher.id,
+            list
         )
         .fetch_one(&mut *connection)
         .await?;
 
-        let demon = Demon {
+        let mut demon = Demon {
             base: MinimalDemon {
                 id: created.id,
                 position: data.position,
@@ -63,8 +78,12 @@ impl FullDemon {
             publisher,
             verifier,
             level_id: None,
+            list,
+            unlisted: false,
         };
 
+        demon.derive_thumbnail(hosts, &mut *connection).await?;
+
         let mut creators = Vec::new();
 
         for creator in data.creators {
@@ -0,0 +1,130 @@
+use crate::{
+    demon::{Demon, MinimalDemon},
+    error::{DemonlistError, Result},
+    player::recompute_scores_of_list,
+};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use std::collections::{HashMap, HashSet};
+
+#[derive(Deserialize, Serialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct DemonMove {
+    pub demon: i32,
+    pub position: i16,
+}
+
+/// A set of moves to be applied to a list all at once
+///
+/// Demons not mentioned in any move keep their relative order and fill up the positions that no
+/// demon was explicitly moved to. A complete target ordering can be given by specifying a move for
+/// every demon on the list.
+#[derive(Deserialize, Debug)]
+pub struct Reorder {
+    pub moves: Vec<DemonMove>,
+}
+
+impl Reorder {
+    /// Applies this reorder to the given list, returning all demons whose position changed
+    ///
+    /// Unlike doing the moves one by one via [`MinimalDemon::mv`], all positions are updated by a
+    /// single query, scores are only recomputed once and the movement log shows the reorder as one
+    /// change.
+    ///
+    /// Must be run within a transaction!
+    pub async fn apply(self, list: i32, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
+        let current = sqlx::query!(
+            r#"SELECT id, name::text AS "name!", position FROM demons WHERE list = $1 AND NOT unlisted ORDER BY position"#,
+            list
+        )
+        .fetch_all(&mut *connection)
+        .await?;
+
+        let maximal = current.len() as i16;
+
+        // targets[i] is the demon explicitly moved to position i + 1, if any
+        let mut targets = vec![None; current.len()];
+        let mut moved = HashSet::new();
+
+        for DemonMove { demon, position } in &self.moves {
+            if !current.iter().any(|row| row.id == *demon) {
+                // Figure out why the demon isn't on the list (this errors out if it doesn't exist at all)
+                if Demon::by_id(*demon, connection).await?.unlisted {
+                    return Err(DemonlistError::DemonUnlisted);
+                }
+
+                return Err(DemonlistError::DemonNotOnList { demon_id: *demon });
+            }
+
+            if *position < 1 || *position > maximal {
+                return Err(DemonlistError::InvalidPosition { maximal });
+            }
+
+            let target = &mut targets[(*position - 1) as usize];
+
+            if !moved.insert(*demon) || target.is_some() {
+                return Err(DemonlistError::ConflictingMoves);
+            }
+
+            *target = Some(*demon);
+        }
+
+        let mut remaining = current.iter().filter(|row| !moved.contains(&row.id)).map(|row| row.id);
+        let names = current
+            .iter()
+            .map(|row| (row.id, (row.position, &row.name)))
+            .collect::<HashMap<_, _>>();
+
+        let mut ids = Vec::new();
+        let mut positions = Vec::new();
+        let mut changed = Vec::new();
+
+        for (index, target) in targets.into_iter().enumerate() {
+            let position = index as i16 + 1;
+            // Every position not targeted by a move is filled by exactly one of the remaining demons
+            let demon = target.or_else(|| remaining.next()).unwrap();
+            let (old_position, name) = names[&demon];
+
+            if old_position != position {
+                debug!("Reorder moves demon {} from {} to {}", name, old_position, position);
+
+                ids.push(demon);
+                positions.push(position);
+                changed.push(MinimalDemon {
+                    id: demon,
+                    position,
+                    name: name.clone(),
+                });
+            }
+        }
+
+        if changed.is_empty() {
+            info!("No-op reorder of list {}", list);
+
+            return Ok(changed);
+        }
+
+        // Since the position uniqueness constraint is deferrable, it is only checked at the end of this statement
+        sqlx::query!(
+            "UPDATE demons SET position = moves.position FROM UNNEST($1::INTEGER[], $2::SMALLINT[]) AS moves(id, position) WHERE demons.id = \
+             moves.id",
+            &ids,
+            &positions
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        // Not checked at compile time, since active_user is a temporary table that only exists on connections with an active user
+        sqlx::query("INSERT INTO demon_reorders (userid, list, moved) SELECT id, $1, $2 FROM active_user LIMIT 1")
+            .bind(list)
+            .bind(moved.into_iter().collect::<Vec<_>>())
+            .execute(&mut *connection)
+            .await?;
+
+        info!("Reordered list {}, changing the positions of {} demons", list, changed.len());
+
+        recompute_scores_of_list(list, connection).await?;
+
+        Ok(changed)
+    }
+}
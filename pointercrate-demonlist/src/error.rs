@@ -44,6 +44,12 @@ pub enum DemonlistError {
     #[display(fmt = "No note with id {} found on record with id {}", note_id, record_id)]
     NoteNotFound { note_id: i32, record_id: i32 },
 
+    #[display(fmt = "No proof with id {} found on record with id {}", proof_id, record_id)]
+    ProofNotFound { proof_id: i32, record_id: i32 },
+
+    #[display(fmt = "No note template with id {} found", template_id)]
+    NoteTemplateNotFound { template_id: i32 },
+
     #[display(fmt = "Player with id {} is no creator of demon with id {}", player_id, demon_id)]
     CreatorNotFound { demon_id: i32, player_id: i32 },
 
@@ -74,6 +80,36 @@ pub enum DemonlistError {
     #[display(fmt = "No claim by user {} on player {} found", member_id, player_id)]
     ClaimNotFound { member_id: i32, player_id: i32 },
 
+    #[display(fmt = "No list with slug '{}' found", slug)]
+    ListNotFound { slug: String },
+
+    #[display(fmt = "No list with id {} found", id)]
+    ListNotFoundId { id: i32 },
+
+    #[display(fmt = "No section with id {} found on this list", id)]
+    SectionNotFound { id: i32 },
+
+    #[display(fmt = "No list update with id {} found on this list", id)]
+    ListUpdateNotFound { id: i32 },
+
+    #[display(fmt = "No placement proposal with id {} found", id)]
+    ProposalNotFound { id: i32 },
+
+    #[display(fmt = "No webhook with id {} found", id)]
+    WebhookNotFound { id: i32 },
+
+    #[display(fmt = "No webhook delivery with id {} found", id)]
+    WebhookDeliveryNotFound { id: i32 },
+
+    #[display(fmt = "Record with id {} is not being reviewed by anyone", record_id)]
+    ReviewClaimNotFound { record_id: i32 },
+
+    #[display(fmt = "There are no unclaimed submissions left to review")]
+    ReviewQueueEmpty,
+
+    #[display(fmt = "No submission with the given status token found")]
+    SubmissionNotFound,
+
     #[display(fmt = "This player is already registered as a creator on this demon")]
     CreatorExists,
 
@@ -96,6 +132,68 @@ pub enum DemonlistError {
     )]
     ConflictingClaims { player1: String, player2: String },
 
+    /// `409 CONFLICT` variant
+    ///
+    /// Error Code `40909`
+    #[display(fmt = "A list with slug '{}' already exists", slug)]
+    ListExists { slug: String },
+
+    /// `409 CONFLICT` variant returned if attempted to create or move a section such that its
+    /// positions overlap those of another section of the same list
+    ///
+    /// Error Code `40910`
+    #[display(fmt = "This section would overlap with the '{}' section", other)]
+    SectionsOverlap { other: String },
+
+    /// `409 CONFLICT` variant returned if attempted to move a demon that is not on its list
+    /// anymore
+    ///
+    /// Error Code `40911`
+    #[display(fmt = "This demon is unlisted and needs to be relisted before it can be moved")]
+    DemonUnlisted,
+
+    /// `409 CONFLICT` variant returned if attempted to modify, delete or publish a list update that
+    /// has already been published
+    ///
+    /// Error Code `40912`
+    #[display(fmt = "This list update has already been published")]
+    ListUpdatePublished,
+
+    /// `409 CONFLICT` variant returned if attempted to give an opinion on, accept or reject a
+    /// placement proposal that has already been accepted or rejected
+    ///
+    /// Error Code `40913`
+    #[display(fmt = "This proposal has already been resolved")]
+    ProposalResolved,
+
+    /// `409 CONFLICT` variant returned if attempted to claim a record for review that another list
+    /// team member is already reviewing
+    ///
+    /// Error Code `40914`
+    #[display(fmt = "This record is already being reviewed by {}", reviewer)]
+    RecordClaimed { reviewer: String },
+
+    /// `409 CONFLICT` variant returned if attempted to claim a record for review that is neither
+    /// submitted, under consideration nor pending approval
+    ///
+    /// Error Code `40915`
+    #[display(fmt = "Only submitted records, records under consideration and records pending approval can be claimed for review")]
+    RecordNotReviewable,
+
+    /// `409 CONFLICT` variant returned if a list team member tries to give the second approval to a
+    /// record they already gave the first approval to
+    ///
+    /// Error Code `40916`
+    #[display(fmt = "You already approved this record. It needs to be approved by a different list team member")]
+    SecondApprovalRequired,
+
+    /// `409 CONFLICT` variant returned if a scheduled list update is due but the user that created
+    /// it has been deleted, meaning there is no one to attribute its changes to
+    ///
+    /// Error Code `40917`
+    #[display(fmt = "The author of this list update no longer exists. It needs to be published manually")]
+    ListUpdateAuthorDeleted,
+
     /// `422 UNPROCESSABLE ENTITY` variant returned if attempted to create a demon with a record
     /// requirements outside of [0, 100]
     ///
@@ -145,20 +243,20 @@ pub enum DemonlistError {
     /// `422 UNPROCESSABLE ENTITY` variant
     ///
     /// Error Code 42219
-    #[display(fmt = "You cannot submit records for legacy demons")]
-    SubmitLegacy,
+    #[display(fmt = "You cannot submit records for demons in the {}", section)]
+    SubmissionsClosed { section: String },
 
     /// `422 UNPROCESSABLE ENTITY` variant
     ///
     /// Error Code 42220
-    #[display(fmt = "Only 100% records can be submitted for the extended section of the list")]
-    Non100Extended,
+    #[display(fmt = "Only 100% records can be submitted for demons in the {}", section)]
+    Non100NotAccepted { section: String },
 
     /// `422 UNPROCESSABLE ENTITY` variant
     ///
     /// Error Code `42224`
-    #[display(fmt = "The given video host is not supported. Supported are 'youtube', 'vimeo', 'everyplay', 'twitch' and 'bilibili'")]
-    UnsupportedVideoHost,
+    #[display(fmt = "The given video host is not supported. Supported are '{}'", "supported.join(\"', '\")")]
+    UnsupportedVideoHost { supported: Vec<&'static str> },
 
     /// `422 UNPROCESSABLE ENTITY` variant
     ///
@@ -189,6 +287,124 @@ pub enum DemonlistError {
     /// Error Code `42233`
     #[display(fmt = "Raw footage needs to be a valid URL")]
     MalformedRawUrl,
+
+    /// `422 UNPROCESSABLE ENTITY` variant
+    ///
+    /// Error Code `42238`
+    #[display(fmt = "The name of a list mustn't be empty")]
+    ListNameEmpty,
+
+    /// `422 UNPROCESSABLE ENTITY` variant
+    ///
+    /// Error Code `42239`
+    #[display(fmt = "The name of a section mustn't be empty")]
+    SectionNameEmpty,
+
+    /// `422 UNPROCESSABLE ENTITY` variant
+    ///
+    /// Error Code `42240`
+    #[display(fmt = "A section needs to start at a position greater than or equal to 1 and cannot end before it starts")]
+    InvalidSectionRange,
+
+    /// `422 UNPROCESSABLE ENTITY` variant, returned by custom
+    /// [`SubmissionRule`](crate::record::rules::SubmissionRule)s
+    ///
+    /// Error Code `42241`
+    #[display(fmt = "This submission violates the submission rules: {}", reason)]
+    SubmissionRuleViolated { reason: String },
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a reorder moves the same demon twice, or
+    /// two demons to the same position
+    ///
+    /// Error Code `42242`
+    #[display(fmt = "A reorder cannot move a demon more than once or multiple demons to the same position")]
+    ConflictingMoves,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a reorder tries to move a demon that is on a
+    /// different list
+    ///
+    /// Error Code `42243`
+    #[display(fmt = "The demon with id {} is not on this list", demon_id)]
+    DemonNotOnList { demon_id: i32 },
+
+    /// `422 UNPROCESSABLE ENTITY` variant
+    ///
+    /// Error Code `42244`
+    #[display(fmt = "The title of a list update mustn't be empty")]
+    ListUpdateTitleEmpty,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a placement proposal is neither for an
+    /// existing demon nor for a new one, or for both at once
+    ///
+    /// Error Code `42245`
+    #[display(fmt = "A placement proposal needs to be for exactly one existing or new demon")]
+    InvalidProposal,
+
+    /// `422 UNPROCESSABLE ENTITY` variant
+    ///
+    /// Error Code `42246`
+    #[display(fmt = "The given webhook URL is malformed")]
+    MalformedWebhookUrl,
+
+    /// `422 UNPROCESSABLE ENTITY` variant
+    ///
+    /// Error Code `42247`
+    #[display(fmt = "A webhook needs to be notified about at least one event")]
+    NoWebhookEvents,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a webhook is to be notified about an event
+    /// that does not exist
+    ///
+    /// Error Code `42248`
+    #[display(fmt = "Webhooks cannot be notified about '{}' events", event)]
+    UnknownWebhookEvent { event: String },
+
+    /// `422 UNPROCESSABLE ENTITY` variant
+    ///
+    /// Error Code `42249`
+    #[display(fmt = "The secret of a webhook mustn't be empty")]
+    WebhookSecretEmpty,
+
+    /// `422 UNPROCESSABLE ENTITY` variant
+    ///
+    /// Error Code `42250`
+    #[display(fmt = "No status reason with key '{}' exists", key)]
+    UnknownStatusReason { key: String },
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a record is given a status reason that cannot
+    /// explain its status (e.g. a rejection reason for an approved record)
+    ///
+    /// Error Code `42251`
+    #[display(fmt = "The status reason '{}' cannot be given to {} records", key, status)]
+    StatusReasonNotApplicable { key: String, status: RecordStatus },
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a record is explicitly given the 'pending
+    /// approval' status, instead of being approved
+    ///
+    /// Error Code `42252`
+    #[display(fmt = "Records can only become pending approval by being approved")]
+    PendingApprovalNotSettable,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a bulk operation on records specifies
+    /// neither a list of record ids nor a filter selecting the records to operate on, or a filter
+    /// that does not filter anything
+    ///
+    /// Error Code `42253`
+    #[display(fmt = "A bulk operation needs to select the records to operate on, either by id or by a non-empty filter")]
+    NoRecordsSelected,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a note template is given an empty name
+    ///
+    /// Error Code `42254`
+    #[display(fmt = "Note templates need a name")]
+    NoteTemplateNameEmpty,
+
+    /// `422 UNPROCESSABLE ENTITY` variant returned if a note template contains a placeholder that
+    /// cannot be filled in
+    ///
+    /// Error Code `42255`
+    #[display(fmt = "Unknown placeholder '{{{}}}' in note template", placeholder)]
+    UnknownNotePlaceholder { placeholder: String },
 }
 
 impl std::error::Error for DemonlistError {}
@@ -201,6 +417,8 @@ impl PointercrateError for DemonlistError {
             Core(core) => core.error_code(),
             SubmitterNotFound { .. } => 40401,
             NoteNotFound { .. } => 40401,
+            ProofNotFound { .. } => 40401,
+            NoteTemplateNotFound { .. } => 40401,
             CreatorNotFound { .. } => 40401,
             CreatorExists => 40905,
             InvalidRequirement => 42212,
@@ -220,19 +438,56 @@ impl PointercrateError for DemonlistError {
             DemonNotFoundPosition { .. } => 40401,
             RecordNotFound { .. } => 40401,
             ClaimNotFound { .. } => 40401,
+            ListNotFound { .. } => 40401,
+            ListNotFoundId { .. } => 40401,
+            SectionNotFound { .. } => 40401,
+            ListUpdateNotFound { .. } => 40401,
+            ProposalNotFound { .. } => 40401,
+            WebhookNotFound { .. } => 40401,
+            WebhookDeliveryNotFound { .. } => 40401,
+            ReviewClaimNotFound { .. } => 40401,
+            ReviewQueueEmpty => 40401,
+            SubmissionNotFound => 40401,
             DuplicateVideo { .. } => 40906,
             NoNationSet => 40907,
             ConflictingClaims { .. } => 40908,
+            ListExists { .. } => 40909,
+            SectionsOverlap { .. } => 40910,
+            DemonUnlisted => 40911,
+            ListUpdatePublished => 40912,
+            ProposalResolved => 40913,
+            RecordClaimed { .. } => 40914,
+            RecordNotReviewable => 40915,
+            SecondApprovalRequired => 40916,
+            ListUpdateAuthorDeleted => 40917,
             InvalidProgress { .. } => 42215,
             SubmissionExists { .. } => 42217,
             PlayerBanned => 42218,
-            SubmitLegacy => 42219,
-            Non100Extended => 42220,
-            UnsupportedVideoHost => 42224,
+            SubmissionsClosed { .. } => 42219,
+            Non100NotAccepted { .. } => 42220,
+            UnsupportedVideoHost { .. } => 42224,
             DemonNameNotUnique { .. } => 42228,
             AlreadyClaimed => 42231,
             RawRequired => 42232,
             MalformedRawUrl => 42233,
+            ListNameEmpty => 42238,
+            SectionNameEmpty => 42239,
+            InvalidSectionRange => 42240,
+            SubmissionRuleViolated { .. } => 42241,
+            ConflictingMoves => 42242,
+            DemonNotOnList { .. } => 42243,
+            ListUpdateTitleEmpty => 42244,
+            InvalidProposal => 42245,
+            MalformedWebhookUrl => 42246,
+            NoWebhookEvents => 42247,
+            UnknownWebhookEvent { .. } => 42248,
+            WebhookSecretEmpty => 42249,
+            UnknownStatusReason { .. } => 42250,
+            StatusReasonNotApplicable { .. } => 42251,
+            PendingApprovalNotSettable => 42252,
+            NoRecordsSelected => 42253,
+            NoteTemplateNameEmpty => 42254,
+            UnknownNotePlaceholder { .. } => 42255,
         }
     }
 }
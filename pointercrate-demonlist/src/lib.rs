@@ -6,11 +6,14 @@ pub mod demon;
 pub mod config;
 pub mod creator;
 pub mod error;
+pub mod list;
 pub mod nationality;
 pub mod player;
+pub mod proposal;
 pub mod record;
 pub mod submitter;
-mod video;
+pub mod video;
+pub mod webhook;
 
 pub const LIST_HELPER: Permission = Permission::new("List Helper", 0x2);
 pub const LIST_MODERATOR: Permission = Permission::new("List Moderator", 0x4);
@@ -0,0 +1,40 @@
+use crate::{
+    error::{DemonlistError, Result},
+    list::List,
+};
+use sqlx::{Error, PgConnection};
+
+impl List {
+    pub async fn by_slug(slug: &str, connection: &mut PgConnection) -> Result<List> {
+        sqlx::query_as!(List, "SELECT id, slug, name FROM lists WHERE slug = $1", slug)
+            .fetch_one(connection)
+            .await
+            .map_err(|err| match err {
+                Error::RowNotFound => DemonlistError::ListNotFound { slug: slug.to_string() },
+                _ => err.into(),
+            })
+    }
+
+    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<List> {
+        sqlx::query_as!(List, "SELECT id, slug, name FROM lists WHERE id = $1", id)
+            .fetch_one(connection)
+            .await
+            .map_err(|err| match err {
+                Error::RowNotFound => DemonlistError::ListNotFoundId { id },
+                _ => err.into(),
+            })
+    }
+
+    /// Gets the list served under the routes that predate support for multiple lists, see
+    /// [`crate::config::default_list`]
+    pub async fn default_list(connection: &mut PgConnection) -> Result<List> {
+        List::by_slug(&crate::config::default_list(), connection).await
+    }
+
+    /// Gets all lists, in the order in which they were created
+    pub async fn all(connection: &mut PgConnection) -> Result<Vec<List>> {
+        Ok(sqlx::query_as!(List, "SELECT id, slug, name FROM lists ORDER BY id")
+            .fetch_all(connection)
+            .await?)
+    }
+}
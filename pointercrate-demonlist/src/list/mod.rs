@@ -0,0 +1,39 @@
+//! Module for lists
+//!
+//! A single pointercrate deployment can host multiple independent lists (say, a main list, a
+//! platformer list and a challenge list). Each demon belongs to exactly one list, and positions,
+//! scores and rankings are all scoped to a list. Players and users are shared between all lists.
+//! Each list is further split into [`section`]s, and larger changes to it can be prepared in advance
+//! as [`update`]s.
+
+pub use self::post::PostList;
+use derive_more::Display;
+use pointercrate_core::etag::Taggable;
+use serde::{Deserialize, Serialize};
+
+mod get;
+mod post;
+pub mod section;
+pub mod update;
+
+#[derive(Debug, Deserialize, Serialize, Hash, Display, Clone, PartialEq, Eq)]
+#[display(fmt = "{} (slug: {})", name, slug)]
+pub struct List {
+    pub id: i32,
+
+    /// The URL slug identifying this list, e.g. `platformer`
+    pub slug: String,
+
+    /// The display name of this list, e.g. `Platformer Demonlist`
+    pub name: String,
+}
+
+impl Taggable for List {}
+
+impl List {
+    /// Whether this list is the one served under the routes that predate support for multiple
+    /// lists
+    pub fn is_default(&self) -> bool {
+        self.slug == crate::config::default_list()
+    }
+}
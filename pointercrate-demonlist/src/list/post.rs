@@ -0,0 +1,51 @@
+use crate::{
+    error::{DemonlistError, Result},
+    list::List,
+};
+use log::info;
+use pointercrate_core::{error::CoreError, util::is_valid_slug};
+use serde::Deserialize;
+use sqlx::PgConnection;
+
+#[derive(Debug, Deserialize)]
+pub struct PostList {
+    slug: String,
+    name: String,
+}
+
+impl List {
+    pub async fn create_from(data: PostList, connection: &mut PgConnection) -> Result<List> {
+        info!("Creating new list from {:?}", data);
+
+        if !is_valid_slug(&data.slug) {
+            return Err(CoreError::InvalidSlug.into());
+        }
+
+        if data.name.trim().is_empty() {
+            return Err(DemonlistError::ListNameEmpty);
+        }
+
+        let id = sqlx::query!("INSERT INTO lists (slug, name) VALUES ($1, $2) RETURNING id", data.slug, data.name)
+            .fetch_one(&mut *connection)
+            .await
+            .map_err(|err| match err {
+                sqlx::Error::Database(ref db_err) if db_err.is_unique_violation() => DemonlistError::ListExists { slug: data.slug.clone() },
+                _ => err.into(),
+            })?
+            .id;
+
+        // New lists start out with a single section spanning the entire list. It can be split up later on.
+        sqlx::query!(
+            "INSERT INTO list_sections (list, name, first_position, allows_non_100) VALUES ($1, 'Main List', 1, TRUE)",
+            id
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(List {
+            id,
+            slug: data.slug,
+            name: data.name,
+        })
+    }
+}
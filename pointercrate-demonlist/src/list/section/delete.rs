@@ -0,0 +1,19 @@
+use crate::{error::Result, list::section::ListSection, player::recompute_scores_of_list};
+use log::info;
+use sqlx::PgConnection;
+
+impl ListSection {
+    /// Must run inside a transaction!
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        info!("Deleting section {}", self);
+
+        sqlx::query!("DELETE FROM list_sections WHERE id = $1", self.id)
+            .execute(&mut *connection)
+            .await?;
+
+        // Demons previously in this section no longer award any points
+        recompute_scores_of_list(self.list, connection).await?;
+
+        Ok(())
+    }
+}
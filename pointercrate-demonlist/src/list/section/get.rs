@@ -0,0 +1,53 @@
+use crate::{
+    error::{DemonlistError, Result},
+    list::section::ListSection,
+};
+use sqlx::{Error, PgConnection};
+
+impl ListSection {
+    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<ListSection> {
+        sqlx::query_as!(
+            ListSection,
+            "SELECT id, list, name, description, first_position, last_position, numbered, submissions_open, allows_non_100, \
+             awards_points FROM list_sections WHERE id = $1",
+            id
+        )
+        .fetch_one(connection)
+        .await
+        .map_err(|err| match err {
+            Error::RowNotFound => DemonlistError::SectionNotFound { id },
+            _ => err.into(),
+        })
+    }
+
+    /// Gets all sections of the given list, ordered by the positions they cover
+    pub async fn of_list(list: i32, connection: &mut PgConnection) -> Result<Vec<ListSection>> {
+        Ok(sqlx::query_as!(
+            ListSection,
+            "SELECT id, list, name, description, first_position, last_position, numbered, submissions_open, allows_non_100, \
+             awards_points FROM list_sections WHERE list = $1 ORDER BY first_position",
+            list
+        )
+        .fetch_all(connection)
+        .await?)
+    }
+
+    /// Gets the section the demon with the given id currently is in, if any
+    pub async fn of_demon(demon_id: i32, connection: &mut PgConnection) -> Result<Option<ListSection>> {
+        Ok(sqlx::query_as!(
+            ListSection,
+            r#"SELECT list_sections.id, list_sections.list, list_sections.name, list_sections.description, list_sections.first_position,
+                      list_sections.last_position, list_sections.numbered, list_sections.submissions_open, list_sections.allows_non_100,
+                      list_sections.awards_points
+               FROM list_sections
+               INNER JOIN demons ON demons.list = list_sections.list
+               WHERE demons.id = $1
+                 AND NOT demons.unlisted
+                 AND demons.position >= list_sections.first_position
+                 AND (list_sections.last_position IS NULL OR demons.position <= list_sections.last_position)"#,
+            demon_id
+        )
+        .fetch_optional(connection)
+        .await?)
+    }
+}
@@ -0,0 +1,118 @@
+//! Module for list sections
+//!
+//! Each list is split into sections (classically the "main", "extended" and "legacy" list), each
+//! covering a range of positions. Sections determine how demons inside them are displayed, whether
+//! records can be submitted for them, whether non-100% records are accepted and whether they award
+//! points at all.
+
+pub use self::{patch::PatchListSection, post::PostListSection};
+use crate::error::{DemonlistError, Result};
+use derive_more::Display;
+use pointercrate_core::etag::Taggable;
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+
+mod delete;
+mod get;
+mod patch;
+mod post;
+
+#[derive(Debug, Deserialize, Serialize, Hash, Display, Clone, PartialEq, Eq)]
+#[display(fmt = "{} (positions {} to {:?})", name, first_position, last_position)]
+pub struct ListSection {
+    pub id: i32,
+
+    /// The id of the [`List`](crate::list::List) this section is part of
+    pub list: i32,
+
+    pub name: String,
+    pub description: String,
+
+    /// The first position (inclusive) covered by this section
+    pub first_position: i16,
+
+    /// The last position (inclusive) covered by this section. `None` means this section extends
+    /// until the end of the list
+    pub last_position: Option<i16>,
+
+    /// Whether the positions of demons in this section are displayed. If not, the demons are
+    /// presented as an unordered collection
+    pub numbered: bool,
+
+    /// Whether records can be submitted for demons in this section. List moderators can still add
+    /// records directly
+    pub submissions_open: bool,
+
+    /// Whether non-100% records can be submitted for, and award points on, demons in this section
+    pub allows_non_100: bool,
+
+    /// Whether records on (and verifications of) demons in this section award points
+    pub awards_points: bool,
+}
+
+impl Taggable for ListSection {}
+
+impl ListSection {
+    /// Whether the given position lies inside this section
+    pub fn contains(&self, position: i16) -> bool {
+        position >= self.first_position && self.last_position.map(|last| position <= last).unwrap_or(true)
+    }
+
+    /// Finds the section containing the given position, if any
+    pub fn containing(sections: &[ListSection], position: i16) -> Option<&ListSection> {
+        sections.iter().find(|section| section.contains(position))
+    }
+
+    fn validate(name: &str, first_position: i16, last_position: Option<i16>) -> Result<()> {
+        if name.trim().is_empty() {
+            return Err(DemonlistError::SectionNameEmpty);
+        }
+
+        if first_position < 1 || last_position.map(|last| last < first_position).unwrap_or(false) {
+            return Err(DemonlistError::InvalidSectionRange);
+        }
+
+        Ok(())
+    }
+
+    /// Turns a violation of the `list_sections_no_overlap` constraint, raised when inserting or
+    /// updating a section covering the given range, into [`DemonlistError::SectionsOverlap`]
+    ///
+    /// The statement must have run inside a savepoint that has been rolled back already, as the
+    /// overlapping section is looked up afterwards
+    async fn overlap_error(
+        err: sqlx::Error, list: i32, ignored: Option<i32>, first_position: i16, last_position: Option<i16>, connection: &mut PgConnection,
+    ) -> DemonlistError {
+        match err {
+            sqlx::Error::Database(ref db_err) if db_err.constraint() == Some("list_sections_no_overlap") => {
+                match ListSection::ensure_no_overlap(list, ignored, first_position, last_position, connection).await {
+                    Err(overlap) => overlap,
+                    Ok(()) => err.into(),
+                }
+            },
+            _ => err.into(),
+        }
+    }
+
+    /// Ensures that no section of the given list other than the one with id `ignored` covers any
+    /// position in the given range
+    async fn ensure_no_overlap(
+        list: i32, ignored: Option<i32>, first_position: i16, last_position: Option<i16>, connection: &mut PgConnection,
+    ) -> Result<()> {
+        let overlapping = sqlx::query!(
+            "SELECT name FROM list_sections WHERE list = $1 AND ($2::INTEGER IS NULL OR id <> $2) AND first_position <= COALESCE($4::SMALLINT, \
+             32767) AND COALESCE(last_position, 32767) >= $3::SMALLINT LIMIT 1",
+            list,
+            ignored,
+            first_position,
+            last_position
+        )
+        .fetch_optional(connection)
+        .await?;
+
+        match overlapping {
+            Some(row) => Err(DemonlistError::SectionsOverlap { other: row.name }),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{error::Result, list::section::ListSection, player::recompute_scores_of_list};
+use log::info;
+use pointercrate_core::util::{non_nullable, nullable};
+use serde::Deserialize;
+use sqlx::{Connection, PgConnection};
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PatchListSection {
+    #[serde(default, deserialize_with = "non_nullable")]
+    name: Option<String>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    description: Option<String>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    first_position: Option<i16>,
+
+    #[serde(default, deserialize_with = "nullable")]
+    last_position: Option<Option<i16>>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    numbered: Option<bool>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    submissions_open: Option<bool>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    allows_non_100: Option<bool>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    awards_points: Option<bool>,
+}
+
+impl ListSection {
+    /// Must run inside a transaction!
+    pub async fn apply_patch(mut self, patch: PatchListSection, connection: &mut PgConnection) -> Result<Self> {
+        info!("Patching section {} with {:?}", self, patch);
+
+        let affects_scores = patch.first_position.is_some()
+            || patch.last_position.is_some()
+            || patch.allows_non_100.is_some()
+            || patch.awards_points.is_some();
+
+        if let Some(name) = patch.name {
+            self.name = name;
+        }
+
+        if let Some(description) = patch.description {
+            self.description = description;
+        }
+
+        if let Some(first_position) = patch.first_position {
+            self.first_position = first_position;
+        }
+
+        if let Some(last_position) = patch.last_position {
+            self.last_position = last_position;
+        }
+
+        if let Some(numbered) = patch.numbered {
+            self.numbered = numbered;
+        }
+
+        if let Some(submissions_open) = patch.submissions_open {
+            self.submissions_open = submissions_open;
+        }
+
+        if let Some(allows_non_100) = patch.allows_non_100 {
+            self.allows_non_100 = allows_non_100;
+        }
+
+        if let Some(awards_points) = patch.awards_points {
+            self.awards_points = awards_points;
+        }
+
+        ListSection::validate(&self.name, self.first_position, self.last_position)?;
+
+        // See ListSection::create_from
+        let mut savepoint = connection.begin().await?;
+
+        let updated = sqlx::query!(
+            "UPDATE list_sections SET name = $1, description = $2, first_position = $3, last_position = $4, numbered = $5, \
+             submissions_open = $6, allows_non_100 = $7, awards_points = $8 WHERE id = $9",
+            self.name,
+            self.description,
+            self.first_position,
+            self.last_position,
+            self.numbered,
+            self.submissions_open,
+            self.allows_non_100,
+            self.awards_points,
+            self.id
+        )
+        .execute(&mut *savepoint)
+        .await;
+
+        match updated {
+            Ok(_) => savepoint.commit().await?,
+            Err(err) => {
+                savepoint.rollback().await?;
+
+                return Err(
+                    ListSection::overlap_error(err, self.list, Some(self.id), self.first_position, self.last_position, connection).await,
+                );
+            },
+        }
+
+        if affects_scores {
+            recompute_scores_of_list(self.list, connection).await?;
+        }
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{error::Result, list::section::ListSection, player::recompute_scores_of_list};
+use log::info;
+use serde::Deserialize;
+use sqlx::{Connection, PgConnection};
+
+#[derive(Debug, Deserialize)]
+pub struct PostListSection {
+    name: String,
+
+    #[serde(default)]
+    description: String,
+
+    first_position: i16,
+
+    #[serde(default)]
+    last_position: Option<i16>,
+
+    #[serde(default = "default_true")]
+    numbered: bool,
+
+    #[serde(default = "default_true")]
+    submissions_open: bool,
+
+    #[serde(default)]
+    allows_non_100: bool,
+
+    #[serde(default = "default_true")]
+    awards_points: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ListSection {
+    /// Must run inside a transaction!
+    pub async fn create_from(data: PostListSection, list: i32, connection: &mut PgConnection) -> Result<ListSection> {
+        info!("Creating new section on list {} from {:?}", list, data);
+
+        ListSection::validate(&data.name, data.first_position, data.last_position)?;
+
+        // Overlaps are rejected by a constraint, so the insertion runs in a savepoint that lets us look up
+        // the offending section afterwards
+        let mut savepoint = connection.begin().await?;
+
+        let inserted = sqlx::query!(
+            "INSERT INTO list_sections (list, name, description, first_position, last_position, numbered, submissions_open, \
+             allows_non_100, awards_points) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) RETURNING id",
+            list,
+            data.name,
+            data.description,
+            data.first_position,
+            data.last_position,
+            data.numbered,
+            data.submissions_open,
+            data.allows_non_100,
+            data.awards_points
+        )
+        .fetch_one(&mut *savepoint)
+        .await;
+
+        let id = match inserted {
+            Ok(row) => {
+                savepoint.commit().await?;
+                row.id
+            },
+            Err(err) => {
+                savepoint.rollback().await?;
+
+                return Err(ListSection::overlap_error(err, list, None, data.first_position, data.last_position, connection).await);
+            },
+        };
+
+        // Which demons award points might have changed
+        recompute_scores_of_list(list, connection).await?;
+
+        Ok(ListSection {
+            id,
+            list,
+            name: data.name,
+            description: data.description,
+            first_position: data.first_position,
+            last_position: data.last_position,
+            numbered: data.numbered,
+            submissions_open: data.submissions_open,
+            allows_non_100: data.allows_non_100,
+            awards_points: data.awards_points,
+        })
+    }
+}
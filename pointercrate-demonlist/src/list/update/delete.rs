@@ -0,0 +1,24 @@
+use crate::{
+    error::{DemonlistError, Result},
+    list::update::ListUpdate,
+};
+use log::info;
+use sqlx::PgConnection;
+
+impl ListUpdate {
+    /// Deletes this update. Published updates cannot be deleted, as their changes have already
+    /// been applied.
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        if self.is_published() {
+            return Err(DemonlistError::ListUpdatePublished);
+        }
+
+        info!("Deleting list update {}", self);
+
+        sqlx::query!("DELETE FROM list_updates WHERE id = $1", self.id)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    demon::{DemonMove, PostDemon},
+    error::{DemonlistError, Result},
+    list::update::{ListUpdate, RequirementChange},
+};
+use sqlx::{Error, PgConnection};
+
+impl ListUpdate {
+    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<ListUpdate> {
+        let row = sqlx::query!(
+            "SELECT id, list, title, author, scheduled_for, published_at, failure FROM list_updates WHERE id = $1",
+            id
+        )
+        .fetch_one(&mut *connection)
+        .await
+        .map_err(|err| match err {
+            Error::RowNotFound => DemonlistError::ListUpdateNotFound { id },
+            _ => err.into(),
+        })?;
+
+        let additions = sqlx::query!(
+            r#"SELECT name::text AS "name!", position, requirement, verifier, publisher, creators, video FROM list_update_additions
+               WHERE list_update = $1 ORDER BY id"#,
+            id
+        )
+        .fetch_all(&mut *connection)
+        .await?
+        .into_iter()
+        .map(|row| PostDemon {
+            name: row.name,
+            position: row.position,
+            requirement: row.requirement,
+            verifier: row.verifier,
+            publisher: row.publisher,
+            creators: row.creators,
+            video: row.video,
+        })
+        .collect();
+
+        let moves = sqlx::query_as!(
+            DemonMove,
+            "SELECT demon, position FROM list_update_moves WHERE list_update = $1 ORDER BY position",
+            id
+        )
+        .fetch_all(&mut *connection)
+        .await?;
+
+        let requirements = sqlx::query_as!(
+            RequirementChange,
+            "SELECT demon, requirement FROM list_update_requirements WHERE list_update = $1 ORDER BY demon",
+            id
+        )
+        .fetch_all(&mut *connection)
+        .await?;
+
+        Ok(ListUpdate {
+            id: row.id,
+            list: row.list,
+            title: row.title,
+            author: row.author,
+            scheduled_for: row.scheduled_for,
+            published_at: row.published_at,
+            failure: row.failure,
+            additions,
+            moves,
+            requirements,
+        })
+    }
+
+    /// Gets all updates of the given list, most recently created first
+    pub async fn of_list(list: i32, connection: &mut PgConnection) -> Result<Vec<ListUpdate>> {
+        let ids = sqlx::query!("SELECT id FROM list_updates WHERE list = $1 ORDER BY id DESC", list)
+            .fetch_all(&mut *connection)
+            .await?;
+
+        let mut updates = Vec::new();
+
+        for row in ids {
+            updates.push(ListUpdate::by_id(row.id, &mut *connection).await?);
+        }
+
+        Ok(updates)
+    }
+
+    /// Gets the ids of all unpublished updates whose scheduled time has come, and which did not
+    /// already fail to be published at that time
+    pub async fn due(connection: &mut PgConnection) -> Result<Vec<i32>> {
+        Ok(sqlx::query!(
+            "SELECT id FROM list_updates WHERE published_at IS NULL AND failure IS NULL AND scheduled_for <= (NOW() AT TIME ZONE 'utc') \
+             ORDER BY scheduled_for"
+        )
+        .fetch_all(connection)
+        .await?
+        .into_iter()
+        .map(|row| row.id)
+        .collect())
+    }
+}
@@ -0,0 +1,79 @@
+//! Module for staged list updates
+//!
+//! List teams usually prepare larger changes to their list (new demons, placement changes and
+//! requirement changes) in advance and announce when they go live. A [`ListUpdate`] collects such
+//! changes. While it is unpublished it can be previewed as a hypothetical list, and once published
+//! (either manually, or automatically at its scheduled time via
+//! [`ListUpdate::publish_as_author`]) all its changes are applied at once.
+
+pub use self::{patch::PatchListUpdate, post::PostListUpdate, publish::PUBLICATION_CHECK_INTERVAL};
+use crate::{
+    demon::{DemonMove, PostDemon},
+    error::{DemonlistError, Result},
+};
+use chrono::NaiveDateTime;
+use derive_more::Display;
+use pointercrate_core::etag::Taggable;
+use serde::{Deserialize, Serialize};
+
+mod delete;
+mod get;
+mod patch;
+mod post;
+mod publish;
+
+#[derive(Debug, Deserialize, Serialize, Hash, Clone, PartialEq, Eq)]
+pub struct RequirementChange {
+    pub demon: i32,
+    pub requirement: i16,
+}
+
+#[derive(Debug, Deserialize, Serialize, Hash, Display, PartialEq, Eq)]
+#[display(fmt = "{} (ID: {})", title, id)]
+pub struct ListUpdate {
+    pub id: i32,
+
+    /// The id of the [`List`](crate::list::List) this update changes
+    pub list: i32,
+
+    pub title: String,
+
+    /// The id of the user who created this update. Changes made by publishing the update are
+    /// attributed to them in the audit logs if it is published automatically.
+    pub author: Option<i32>,
+
+    /// The (UTC) time at which this update will automatically be published. `None` means this
+    /// update is a draft
+    pub scheduled_for: Option<NaiveDateTime>,
+
+    pub published_at: Option<NaiveDateTime>,
+
+    /// Why publishing this update at its scheduled time failed, if it did. Cleared when the update
+    /// is rescheduled
+    pub failure: Option<String>,
+
+    /// Demons added by this update. They are added before any moves are applied, from top to
+    /// bottom, so their positions are positions on the list after all additions.
+    pub additions: Vec<PostDemon>,
+
+    /// Demons moved by this update, applied as a single [`Reorder`](crate::demon::Reorder)
+    pub moves: Vec<DemonMove>,
+
+    pub requirements: Vec<RequirementChange>,
+}
+
+impl Taggable for ListUpdate {}
+
+impl ListUpdate {
+    pub fn is_published(&self) -> bool {
+        self.published_at.is_some()
+    }
+
+    fn validate_title(title: &str) -> Result<()> {
+        if title.trim().is_empty() {
+            return Err(DemonlistError::ListUpdateTitleEmpty);
+        }
+
+        Ok(())
+    }
+}
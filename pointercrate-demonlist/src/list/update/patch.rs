@@ -0,0 +1,53 @@
+use crate::{
+    error::{DemonlistError, Result},
+    list::update::ListUpdate,
+};
+use chrono::NaiveDateTime;
+use log::info;
+use pointercrate_core::util::{non_nullable, nullable};
+use serde::Deserialize;
+use sqlx::PgConnection;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PatchListUpdate {
+    #[serde(default, deserialize_with = "non_nullable")]
+    title: Option<String>,
+
+    #[serde(default, deserialize_with = "nullable")]
+    scheduled_for: Option<Option<NaiveDateTime>>,
+}
+
+impl ListUpdate {
+    /// Must run inside a transaction!
+    pub async fn apply_patch(mut self, patch: PatchListUpdate, connection: &mut PgConnection) -> Result<Self> {
+        info!("Patching list update {} with {:?}", self, patch);
+
+        if self.is_published() {
+            return Err(DemonlistError::ListUpdatePublished);
+        }
+
+        if let Some(title) = patch.title {
+            ListUpdate::validate_title(&title)?;
+
+            self.title = title;
+        }
+
+        if let Some(scheduled_for) = patch.scheduled_for {
+            // Rescheduling an update that failed to be published gives it another go
+            self.scheduled_for = scheduled_for;
+            self.failure = None;
+        }
+
+        sqlx::query!(
+            "UPDATE list_updates SET title = $1, scheduled_for = $2, failure = $3 WHERE id = $4",
+            self.title,
+            self.scheduled_for,
+            self.failure,
+            self.id
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(self)
+    }
+}
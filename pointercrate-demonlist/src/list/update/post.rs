@@ -0,0 +1,123 @@
+use crate::{
+    demon::{Demon, DemonMove, PostDemon},
+    error::{DemonlistError, Result},
+    list::update::{ListUpdate, RequirementChange},
+    video::VideoHosts,
+};
+use chrono::NaiveDateTime;
+use log::info;
+use serde::Deserialize;
+use sqlx::PgConnection;
+use std::collections::HashSet;
+
+#[derive(Debug, Deserialize)]
+pub struct PostListUpdate {
+    title: String,
+
+    #[serde(default)]
+    scheduled_for: Option<NaiveDateTime>,
+
+    #[serde(default)]
+    additions: Vec<PostDemon>,
+
+    #[serde(default)]
+    moves: Vec<DemonMove>,
+
+    #[serde(default)]
+    requirements: Vec<RequirementChange>,
+}
+
+impl ListUpdate {
+    /// Must run inside a transaction!
+    pub async fn create_from(
+        data: PostListUpdate, list: i32, author: i32, hosts: &VideoHosts, connection: &mut PgConnection,
+    ) -> Result<ListUpdate> {
+        info!("Creating new update for list {} from {:?}", list, data);
+
+        ListUpdate::validate_title(&data.title)?;
+
+        // Positions can only be validated once the update is published, as the list might change in the meantime (which also
+        // means that the demons moved by this update might no longer be on the list by then, in which case publishing fails)
+        let mut additions = Vec::new();
+
+        for mut addition in data.additions {
+            Demon::validate_requirement(addition.requirement)?;
+
+            if let Some(ref video) = addition.video {
+                addition.video = Some(hosts.validate(video)?);
+            }
+
+            additions.push(addition);
+        }
+
+        let mut moved = HashSet::new();
+        let mut targets = HashSet::new();
+
+        for DemonMove { demon, position } in &data.moves {
+            if !moved.insert(*demon) || !targets.insert(*position) {
+                return Err(DemonlistError::ConflictingMoves);
+            }
+
+            Demon::validate_on_list(*demon, list, connection).await?;
+        }
+
+        for RequirementChange { demon, requirement } in &data.requirements {
+            Demon::validate_requirement(*requirement)?;
+            Demon::validate_on_list(*demon, list, connection).await?;
+        }
+
+        let id = sqlx::query!(
+            "INSERT INTO list_updates (list, title, author, scheduled_for) VALUES ($1, $2, $3, $4) RETURNING id",
+            list,
+            data.title,
+            author,
+            data.scheduled_for
+        )
+        .fetch_one(&mut *connection)
+        .await?
+        .id;
+
+        for addition in &additions {
+            sqlx::query!(
+                "INSERT INTO list_update_additions (list_update, name, position, requirement, verifier, publisher, creators, video) VALUES \
+                 ($1, $2::text, $3, $4, $5, $6, $7, $8)",
+                id,
+                addition.name,
+                addition.position,
+                addition.requirement,
+                addition.verifier,
+                addition.publisher,
+                &addition.creators,
+                addition.video
+            )
+            .execute(&mut *connection)
+            .await?;
+        }
+
+        for DemonMove { demon, position } in &data.moves {
+            sqlx::query!(
+                "INSERT INTO list_update_moves (list_update, demon, position) VALUES ($1, $2, $3)",
+                id,
+                demon,
+                position
+            )
+            .execute(&mut *connection)
+            .await?;
+        }
+
+        // If the same demon's requirement is changed multiple times, the last change wins
+        for RequirementChange { demon, requirement } in &data.requirements {
+            sqlx::query!(
+                "INSERT INTO list_update_requirements (list_update, demon, requirement) VALUES ($1, $2, $3) ON CONFLICT (list_update, demon) DO \
+                 UPDATE SET requirement = EXCLUDED.requirement",
+                id,
+                demon,
+                requirement
+            )
+            .execute(&mut *connection)
+            .await?;
+        }
+
+        ListUpdate::by_id(id, connection).await
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    demon::{current_list, Demon, FullDemon, Reorder, TimeShiftedDemon},
+    error::{DemonlistError, Result},
+    list::update::ListUpdate,
+    player::recompute_scores_of_list,
+    video::VideoHosts,
+};
+use log::info;
+use pointercrate_core::pool::audit_connection;
+use sqlx::{Connection, PgConnection};
+use std::{collections::HashMap, time::Duration};
+
+/// How often whatever background task drives scheduled publication should check for updates whose
+/// scheduled time has come
+pub const PUBLICATION_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+impl ListUpdate {
+    /// Applies all changes of this update to its list and marks it as published
+    ///
+    /// Marking the update as published happens first and only succeeds if nobody else published it
+    /// in the meantime. This also locks the update's row until the transaction ends, so concurrent
+    /// attempts to publish it wait for this one and then fail.
+    ///
+    /// Must run inside a transaction!
+    pub async fn publish(mut self, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<ListUpdate> {
+        if self.is_published() {
+            return Err(DemonlistError::ListUpdatePublished);
+        }
+
+        info!("Publishing list update {}", self);
+
+        self.published_at = sqlx::query!(
+            "UPDATE list_updates SET published_at = (NOW() AT TIME ZONE 'utc'), failure = NULL WHERE id = $1 AND published_at IS NULL \
+             RETURNING published_at",
+            self.id
+        )
+        .fetch_optional(&mut *connection)
+        .await?
+        .ok_or(DemonlistError::ListUpdatePublished)?
+        .published_at;
+        self.failure = None;
+
+        self.apply_changes(hosts, connection).await?;
+
+        Ok(self)
+    }
+
+    /// Computes what this update's list would look like if this update was published right now
+    ///
+    /// The update is applied inside a savepoint that is rolled back afterwards, so the preview
+    /// is exactly what publishing would do (and fails if publishing would fail).
+    pub async fn preview(&self, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<Vec<TimeShiftedDemon>> {
+        if self.is_published() {
+            return Err(DemonlistError::ListUpdatePublished);
+        }
+
+        let positions_now = current_list(self.list, &mut *connection)
+            .await?
+            .into_iter()
+            .map(|demon| (demon.base.id, demon.base.position))
+            .collect::<HashMap<_, _>>();
+
+        let mut savepoint = connection.begin().await?;
+
+        self.apply_changes(hosts, &mut savepoint).await?;
+
+        let demons = current_list(self.list, &mut savepoint).await?;
+
+        savepoint.rollback().await?;
+
+        Ok(demons
+            .into_iter()
+            .map(|demon| TimeShiftedDemon {
+                position_now: positions_now.get(&demon.base.id).copied(),
+                current_demon: demon,
+            })
+            .collect())
+    }
+
+    async fn apply_changes(&self, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<()> {
+        // Add from top to bottom, so that the positions of later additions already account for the earlier ones
+        let mut additions = self.additions.clone();
+        additions.sort_by_key(|addition| addition.position);
+
+        for addition in additions {
+            FullDemon::create_prevalidated(addition, self.list, hosts, &mut *connection).await?;
+        }
+
+        Reorder { moves: self.moves.clone() }.apply(self.list, &mut *connection).await?;
+
+        for change in &self.requirements {
+            Demon::by_id(change.demon, &mut *connection)
+                .await?
+                .set_requirement(change.requirement, &mut *connection)
+                .await?;
+        }
+
+        // Changing requirements might have deleted some records
+        if !self.requirements.is_empty() {
+            recompute_scores_of_list(self.list, connection).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Publishes this update on behalf of its author, as happens once its scheduled time has come
+    ///
+    /// Fails if the author no longer exists, as there would be nobody to attribute the changes to
+    /// in the audit logs.
+    ///
+    /// Must run inside a transaction!
+    pub async fn publish_as_author(self, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<ListUpdate> {
+        let author = self.author.ok_or(DemonlistError::ListUpdateAuthorDeleted)?;
+
+        audit_connection(&mut *connection, author).await?;
+
+        self.publish(hosts, connection).await
+    }
+
+    /// Records why publishing the update with the given id at its scheduled time failed. It will
+    /// not be retried until it is rescheduled.
+    pub async fn mark_failed(id: i32, failure: String, connection: &mut PgConnection) -> Result<()> {
+        sqlx::query!("UPDATE list_updates SET failure = $1 WHERE id = $2", failure, id)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
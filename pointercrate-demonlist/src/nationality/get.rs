@@ -113,24 +113,26 @@ impl Nationality {
         Ok(nationalities)
     }
 
-    pub async fn upgrade(self, connection: &mut PgConnection) -> Result<NationalityRecord> {
+    /// Fetches the records, creations, verifications and publications of this nation on the list
+    /// with the given id
+    pub async fn upgrade(self, list: i32, connection: &mut PgConnection) -> Result<NationalityRecord> {
         Ok(NationalityRecord {
-            best_records: best_records_in(&self, connection).await?,
-            created: created_in(&self, connection).await?,
-            verified: verified_in(&self, connection).await?,
-            published: published_in(&self, connection).await?,
-            unbeaten: unbeaten_in(&self, connection).await?,
+            best_records: best_records_in(&self, list, connection).await?,
+            created: created_in(&self, list, connection).await?,
+            verified: verified_in(&self, list, connection).await?,
+            published: published_in(&self, list, connection).await?,
+            unbeaten: unbeaten_in(&self, list, connection).await?,
             nation: self,
         })
     }
 }
 
-pub async fn unbeaten_in(nation: &Nationality, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
+pub async fn unbeaten_in(nation: &Nationality, list: i32, connection: &mut PgConnection) -> Result<Vec<MinimalDemon>> {
     let mut stream = sqlx::query!(
-        r#"select name::text as "name!", id as "id!", position as "position!" from demons where position <= $1 except (select demons.name, demons.id, position from records inner join players on 
-         players.id=records.player inner join demons on demons.id=records.demon where status_='APPROVED' and nationality=$2 and progress=100 union select demons.name, demons.id, demons.position from demons inner join players on players.id=verifier where players.nationality=$2)"#,
-        crate::config::extended_list_size(),
-        nation.iso_country_code
+        r#"select name::text as "name!", id as "id!", position as "position!" from demons where list = $2 and not unlisted and exists (select 1 from list_sections where list_sections.list = demons.list and submissions_open and position >= first_position and (last_position is null or position <= last_position)) except (select demons.name, demons.id, position from records inner join players on 
+         players.id=records.player inner join demons on demons.id=records.demon where status_='APPROVED' and nationality=$1 and progress=100 union select demons.name, demons.id, demons.position from demons inner join players on players.id=verifier where players.nationality=$1)"#,
+        nation.iso_country_code,
+        list
     )
     .fetch(connection);
 
@@ -149,8 +151,8 @@ pub async fn unbeaten_in(nation: &Nationality, connection: &mut PgConnection) ->
     Ok(unbeaten)
 }
 
-pub async fn created_in(nation: &Nationality, connection: &mut PgConnection) -> Result<Vec<MiniDemonWithPlayers>> {
-    let mut stream = sqlx::query!( r#"select distinct on (demon) demon, demons.name::text as "demon_name!", demons.position, players.name::text as "player_name!" from creators inner join demons on demons.id=demon inner join players on players.id=creator where nationality=$1"#, nation.iso_country_code).fetch(connection);
+pub async fn created_in(nation: &Nationality, list: i32, connection: &mut PgConnection) -> Result<Vec<MiniDemonWithPlayers>> {
+    let mut stream = sqlx::query!( r#"select distinct on (demon) demon, demons.name::text as "demon_name!", demons.position, players.name::text as "player_name!" from creators inner join demons on demons.id=demon inner join players on players.id=creator where nationality=$1 and demons.list=$2 and not demons.unlisted"#, nation.iso_country_code, list).fetch(connection);
 
     let mut creations = Vec::<MiniDemonWithPlayers>::new();
 
@@ -171,9 +173,9 @@ pub async fn created_in(nation: &Nationality, connection: &mut PgConnection) ->
     Ok(creations)
 }
 
-pub async fn verified_in(nation: &Nationality, connection: &mut PgConnection) -> Result<Vec<MiniDemon>> {
+pub async fn verified_in(nation: &Nationality, list: i32, connection: &mut PgConnection) -> Result<Vec<MiniDemon>> {
     let mut stream = sqlx::query!(
-        r#"select demons.id as demon, demons.name::text as "demon_name!", demons.position, players.name::text as "player_name!" from demons inner join players on players.id=verifier where nationality=$1"#, nation.iso_country_code).fetch(connection);
+        r#"select demons.id as demon, demons.name::text as "demon_name!", demons.position, players.name::text as "player_name!" from demons inner join players on players.id=verifier where nationality=$1 and demons.list=$2 and not demons.unlisted"#, nation.iso_country_code, list).fetch(connection);
 
     let mut demons = Vec::new();
 
@@ -191,9 +193,9 @@ pub async fn verified_in(nation: &Nationality, connection: &mut PgConnection) ->
     Ok(demons)
 }
 
-pub async fn published_in(nation: &Nationality, connection: &mut PgConnection) -> Result<Vec<MiniDemon>> {
+pub async fn published_in(nation: &Nationality, list: i32, connection: &mut PgConnection) -> Result<Vec<MiniDemon>> {
     let mut stream = sqlx::query!(
-        r#"select demons.id as demon, demons.name::text as "demon_name!", demons.position, players.name::text as "player_name!" from demons inner join players on players.id=publisher where nationality=$1"#, nation.iso_country_code).fetch(connection);
+        r#"select demons.id as demon, demons.name::text as "demon_name!", demons.position, players.name::text as "player_name!" from demons inner join players on players.id=publisher where nationality=$1 and demons.list=$2 and not demons.unlisted"#, nation.iso_country_code, list).fetch(connection);
 
     let mut demons = Vec::new();
 
@@ -211,10 +213,11 @@ pub async fn published_in(nation: &Nationality, connection: &mut PgConnection) -
     Ok(demons)
 }
 
-pub async fn best_records_in(nation: &Nationality, connection: &mut PgConnection) -> Result<Vec<BestRecord>> {
+pub async fn best_records_in(nation: &Nationality, list: i32, connection: &mut PgConnection) -> Result<Vec<BestRecord>> {
     let mut stream = sqlx::query!(
-        r#"SELECT progress as "progress!", demons.id AS "demon_id!", demons.name as "demon_name!: String", demons.position as "position!", players.name as "player_name!: String" FROM best_records_in($1) as records INNER JOIN demons ON records.demon = demons.id INNER JOIN players ON players.id = records.player"#,
-        nation.iso_country_code
+        r#"SELECT progress as "progress!", demons.id AS "demon_id!", demons.name as "demon_name!: String", demons.position as "position!", players.name as "player_name!: String" FROM best_records_in($1) as records INNER JOIN demons ON records.demon = demons.id INNER JOIN players ON players.id = records.player WHERE demons.list = $2 AND NOT demons.unlisted"#,
+        nation.iso_country_code,
+        list
     )
         .fetch(connection);
 
@@ -14,6 +14,11 @@ pub struct NationalityRankingPagination {
 
     #[serde(default, deserialize_with = "non_nullable")]
     name_contains: Option<String>,
+
+    /// The id of the list whose ranking should be retrieved. Not part of the query string, but set
+    /// based on the list the request was made for.
+    #[serde(skip)]
+    pub list: Option<i32>,
 }
 
 #[derive(Serialize, Debug)]
@@ -28,9 +33,10 @@ impl NationalityRankingPagination {
     pub async fn page(&self, connection: &mut PgConnection) -> Result<Vec<RankedNation>> {
         let mut stream = sqlx::query!(
             r#"SELECT rank as "rank!", score as "score!", nation as "nation!", iso_country_code as "iso_country_code!" FROM ranked_nations WHERE (STRPOS(nation, $1) > 
-             0 OR $1 is NULL) AND (continent::text = $2 OR $2 IS NULL)"#,
+             0 OR $1 is NULL) AND (continent::text = $2 OR $2 IS NULL) AND list = $3"#,
             self.name_contains,
-            self.continent.map(|c| c.to_sql())
+            self.continent.map(|c| c.to_sql()),
+            self.list
         )
         .fetch(connection);
 
@@ -1,6 +1,6 @@
 use crate::{
     creator::created_by,
-    demon::{published_by, verified_by},
+    demon::{published_by, verified_by, MinimalDemon},
     error::{DemonlistError, Result},
     nationality::{Nationality, Subdivision},
     player::{DatabasePlayer, FullPlayer, Player},
@@ -9,14 +9,17 @@ use crate::{
 use sqlx::{Error, PgConnection};
 
 impl Player {
-    pub async fn upgrade(self, connection: &mut PgConnection) -> Result<FullPlayer> {
-        let records = approved_records_by(&self.base, connection).await?;
-        let published = published_by(&self.base, connection).await?;
-        let verified = verified_by(&self.base, connection).await?;
-        let created = created_by(self.base.id, connection).await?;
+    /// Fetches the approved records and the created, verified and published demons of this player on
+    /// the list with the given id
+    pub async fn upgrade(self, list: i32, connection: &mut PgConnection) -> Result<FullPlayer> {
+        let records = approved_records_by(&self.base, list, connection).await?;
+        let published = published_by(&self.base, list, connection).await?;
+        let verified = verified_by(&self.base, list, connection).await?;
+        let created = created_by(self.base.id, list, connection).await?;
 
         Ok(FullPlayer {
             player: self,
+            list,
             records,
             created,
             verified,
@@ -24,11 +27,13 @@ impl Player {
         })
     }
 
-    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<Player> {
+    /// Gets the player with the given id, together with their score on the list with the given id
+    pub async fn by_id(id: i32, list: i32, connection: &mut PgConnection) -> Result<Player> {
         let result = sqlx::query!(
-            r#"SELECT id, players.name, banned, players.score, nationalities.nation::text, iso_country_code::text, iso_code::text as subdivision_code, subdivisions.name::text as subdivision_name FROM players LEFT OUTER JOIN nationalities ON 
-             players.nationality = nationalities.iso_country_code LEFT OUTER JOIN subdivisions ON players.subdivision = subdivisions.iso_code WHERE id = $1 AND (subdivisions.nation=nationalities.iso_country_code or players.subdivision is null)"#,
-            id
+            r#"SELECT id, players.name, banned, COALESCE(player_scores.score, 0) AS "score!", nationalities.nation::text, iso_country_code::text, iso_code::text as subdivision_code, subdivisions.name::text as subdivision_name FROM players LEFT OUTER JOIN player_scores ON 
+             player_scores.player = players.id AND player_scores.list = $2 LEFT OUTER JOIN nationalities ON players.nationality = nationalities.iso_country_code LEFT OUTER JOIN subdivisions ON players.subdivision = subdivisions.iso_code WHERE id = $1 AND (subdivisions.nation=nationalities.iso_country_code or players.subdivision is null)"#,
+            id,
+            list
         )
         .fetch_one(connection)
         .await;
@@ -65,6 +70,29 @@ impl Player {
             Err(err) => Err(err.into()),
         }
     }
+
+    /// Gets this player's rank on the stats viewer of the list with the given id, or `None` if they
+    /// are not ranked there (e.g. because they are banned or have no score)
+    pub async fn rank(&self, list: i32, connection: &mut PgConnection) -> Result<Option<i64>> {
+        Ok(
+            sqlx::query!("SELECT rank FROM ranked_players WHERE id = $1 AND list = $2", self.base.id, list)
+                .fetch_optional(connection)
+                .await?
+                .and_then(|row| row.rank),
+        )
+    }
+}
+
+impl FullPlayer {
+    /// The highest placed demon this player has either completed or verified
+    pub fn hardest_demon(&self) -> Option<&MinimalDemon> {
+        self.records
+            .iter()
+            .filter(|record| record.progress == 100)
+            .map(|record| &record.demon)
+            .chain(self.verified.iter())
+            .min_by_key(|demon| demon.position)
+    }
 }
 
 impl DatabasePlayer {
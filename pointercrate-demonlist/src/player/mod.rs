@@ -25,11 +25,17 @@ pub struct DatabasePlayer {
     pub banned: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Display, PartialEq, Hash)]
+#[derive(Debug, Serialize, Deserialize, Display, PartialEq)]
 #[display(fmt = "{}", player)]
 pub struct FullPlayer {
     #[serde(flatten)]
     pub player: Player,
+
+    /// The id of the [`List`](crate::list::List) this player's score, records, created, verified
+    /// and published demons were fetched for
+    #[serde(skip)]
+    pub list: i32,
+
     pub records: Vec<MinimalRecordD>,
     pub created: Vec<MinimalDemon>,
     pub verified: Vec<MinimalDemon>,
@@ -42,9 +48,9 @@ pub struct Player {
     #[serde(flatten)]
     pub base: DatabasePlayer,
 
-    /// This [`Player`]'s score on the stats viewer
+    /// This [`Player`]'s score on the stats viewer of the list this [`Player`] was retrieved for
     ///
-    /// This value is cached in the `score` column of the `players` table, and not computed on-demand!
+    /// This value is cached in the `player_scores` table, and not computed on-demand!
     /// Thus it needs to be updated on any event that can affect a player's score. These are
     /// - Record updates
     ///   * Record status updated (to approved, or from approved)
@@ -72,6 +78,17 @@ impl Hash for Player {
     }
 }
 
+// The list is not part of the serialized representation, so it must not influence the ETag either
+impl Hash for FullPlayer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.player.hash(state);
+        self.records.hash(state);
+        self.created.hash(state);
+        self.verified.hash(state);
+        self.published.hash(state);
+    }
+}
+
 impl Taggable for FullPlayer {
     fn patch_part(&self) -> u64 {
         let mut hasher = DefaultHasher::new();
@@ -81,20 +98,41 @@ impl Taggable for FullPlayer {
 }
 
 impl DatabasePlayer {
-    /// Recomputes this player's score and updates it in the database.
-    pub async fn update_score(&self, connection: &mut PgConnection) -> Result<f64, CoreError> {
-        // No need to specially handle banned players - they have no approved records, so `score_of_player` will return 0
-        let new_score = sqlx::query!(
-            "UPDATE players SET score = coalesce(score_of_player($1), 0) WHERE id = $1 RETURNING score",
+    /// Recomputes this player's scores on all lists and updates them in the database, together with
+    /// those of the player's nation and subdivision.
+    pub async fn update_score(&self, connection: &mut PgConnection) -> Result<(), CoreError> {
+        // No need to specially handle banned players - they have no approved records, so they simply won't have any scores
+        sqlx::query!("SELECT recompute_scores_of_player($1)", self.id)
+            .execute(&mut *connection)
+            .await?;
+
+        sqlx::query!(
+            "SELECT recompute_scores_of_nation(nationality) FROM players WHERE id = $1 AND nationality IS NOT NULL",
             self.id
         )
-        .fetch_one(&mut *connection)
+        .execute(&mut *connection)
         .await?;
 
-        sqlx::query!("UPDATE nationalities SET score = coalesce(score_of_nation(nationalities.iso_country_code), 0) FROM players WHERE players.id = $1 AND players.nationality = nationalities.iso_country_code", self.id).execute(&mut *connection).await?;
-        sqlx::query!("UPDATE subdivisions SET score = coalesce(score_of_subdivision(subdivisions.nation, subdivisions.iso_code), 0) FROM players WHERE players.id = $1 AND players.nationality = subdivisions.nation AND players.subdivision = subdivisions.iso_code", self.id).execute(&mut *connection).await?;
+        sqlx::query!(
+            "SELECT recompute_scores_of_subdivision(nationality, subdivision) FROM players WHERE id = $1 AND nationality IS NOT NULL AND \
+             subdivision IS NOT NULL",
+            self.id
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        Ok(())
+    }
 
-        Ok(new_score.score)
+    /// Gets this player's (cached) score on the list with the given id
+    pub async fn score_on(&self, list: i32, connection: &mut PgConnection) -> Result<f64, CoreError> {
+        Ok(
+            sqlx::query!("SELECT score FROM player_scores WHERE player = $1 AND list = $2", self.id, list)
+                .fetch_optional(connection)
+                .await?
+                .map(|row| row.score)
+                .unwrap_or(0.0),
+        )
     }
 }
 
@@ -104,3 +142,12 @@ pub async fn recompute_scores(connection: &mut PgConnection) -> Result<(), CoreE
     sqlx::query!("SELECT recompute_subdivision_scores();").execute(connection).await?;
     Ok(())
 }
+
+/// Recomputes the scores of all players, nations and subdivisions on the given list, leaving
+/// those on other lists untouched
+pub async fn recompute_scores_of_list(list: i32, connection: &mut PgConnection) -> Result<(), CoreError> {
+    sqlx::query!("SELECT recompute_scores_of_list($1)", list)
+        .execute(connection)
+        .await?;
+    Ok(())
+}
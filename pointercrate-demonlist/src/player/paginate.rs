@@ -27,6 +27,11 @@ pub struct PlayerPagination {
 
     #[serde(default, deserialize_with = "nullable")]
     nation: Option<Option<String>>,
+
+    /// The id of the list whose scores should be included. Not part of the query string, but set
+    /// based on the list the request was made for.
+    #[serde(skip)]
+    pub list: Option<i32>,
 }
 
 impl PaginationQuery for PlayerPagination {
@@ -60,6 +65,7 @@ impl Paginatable<PlayerPagination> for Player {
             .bind(&query.nation)
             .bind(query.nation == Some(None))
             .bind(query.params.limit + 1)
+            .bind(query.list)
             .fetch(connection);
 
         let mut players = Vec::new();
@@ -111,6 +117,11 @@ pub struct RankingPagination {
 
     #[serde(default, deserialize_with = "non_nullable")]
     name_contains: Option<String>,
+
+    /// The id of the list whose ranking should be paginated. Not part of the query string, but set
+    /// based on the list the request was made for.
+    #[serde(skip)]
+    pub list: Option<i32>,
 }
 
 impl PaginationQuery for RankingPagination {
@@ -128,19 +139,20 @@ impl PaginationQuery for RankingPagination {
 
 #[derive(Debug, Serialize)]
 pub struct RankedPlayer {
-    rank: i64,
+    pub rank: i64,
     #[serde(skip)]
     index: i64,
     #[serde(flatten)]
-    player: Player,
+    pub player: Player,
 }
 
 impl Paginatable<RankingPagination> for RankedPlayer {
     async fn first_and_last(connection: &mut PgConnection) -> Result<Option<(i32, i32)>, sqlx::Error> {
-        Ok(sqlx::query!("SELECT COUNT(*) FROM players WHERE NOT banned AND score > 0.0")
+        // Since this does not know which list is being paginated, we use the size of the largest ranking as upper bound
+        Ok(sqlx::query!("SELECT MAX(index) FROM ranked_players")
             .fetch_one(connection)
             .await?
-            .count
+            .max
             .map(|max| (1, max as i32)))
     }
 
@@ -158,6 +170,7 @@ impl Paginatable<RankingPagination> for RankedPlayer {
             .bind(query.continent.as_ref().map(|c| c.to_sql()))
             .bind(&query.subdivision)
             .bind(query.params.limit + 1)
+            .bind(query.list)
             .fetch(connection);
 
         let mut players = Vec::new();
@@ -72,7 +72,8 @@ impl FullPlayer {
             self.set_name(name, connection).await?;
         }
 
-        self.player.score = self.player.base.update_score(connection).await?;
+        self.player.base.update_score(connection).await?;
+        self.player.score = self.player.base.score_on(self.list, connection).await?;
 
         Ok(self)
     }
@@ -210,7 +211,7 @@ impl FullPlayer {
             record.set_player(self.player.base.clone(), &mut *connection).await?
         }
 
-        self.records = approved_records_by(&self.player.base, &mut *connection).await?;
+        self.records = approved_records_by(&self.player.base, self.list, &mut *connection).await?;
 
         // Transfer all records over, now that they're unique
         let updated = sqlx::query!("UPDATE records SET player = $1 WHERE player = $2", self.player.base.id, with.id)
@@ -265,7 +266,8 @@ impl DatabasePlayer {
     pub async fn ban(&mut self, connection: &mut PgConnection) -> Result<()> {
         // Delete all submissions for this player
         let deleted = sqlx::query!(
-            "DELETE FROM records WHERE player = $1 AND (status_ = 'SUBMITTED' OR status_ = 'UNDER_CONSIDERATION')",
+            "DELETE FROM records WHERE player = $1 AND (status_ = 'SUBMITTED' OR status_ = 'UNDER_CONSIDERATION' OR status_ = \
+             'PENDING_APPROVAL')",
             self.id
         )
         .execute(&mut *connection)
@@ -0,0 +1,93 @@
+use crate::{
+    error::{DemonlistError, Result},
+    proposal::{PlacementOpinion, PlacementProposal, ProposalStatus, ProposedDemon},
+};
+use sqlx::{Error, PgConnection};
+
+impl PlacementProposal {
+    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<PlacementProposal> {
+        let row = sqlx::query!(
+            r#"SELECT id, list, demon, position, reason, name::text, requirement, verifier, publisher, creators, video, author,
+               status::text AS "status!", created_at, resolved_by, resolved_at FROM placement_proposals WHERE id = $1"#,
+            id
+        )
+        .fetch_one(&mut *connection)
+        .await
+        .map_err(|err| match err {
+            Error::RowNotFound => DemonlistError::ProposalNotFound { id },
+            _ => err.into(),
+        })?;
+
+        // The demon columns are either all set or all NULL (enforced on creation), so looking at the non-nullable ones suffices
+        let new_demon = match (row.name, row.requirement, row.verifier, row.publisher) {
+            (Some(name), Some(requirement), Some(verifier), Some(publisher)) => Some(ProposedDemon {
+                name,
+                requirement,
+                verifier,
+                publisher,
+                creators: row.creators.unwrap_or_default(),
+                video: row.video,
+            }),
+            _ => None,
+        };
+
+        let opinions = sqlx::query_as!(
+            PlacementOpinion,
+            r#"SELECT member AS "user_id!", position, comment FROM placement_opinions WHERE proposal = $1 ORDER BY member"#,
+            id
+        )
+        .fetch_all(&mut *connection)
+        .await?;
+
+        Ok(PlacementProposal {
+            id: row.id,
+            list: row.list,
+            demon: row.demon,
+            new_demon,
+            position: row.position,
+            reason: row.reason,
+            author: row.author,
+            status: ProposalStatus::from_sql(&row.status),
+            created_at: row.created_at,
+            resolved_by: row.resolved_by,
+            resolved_at: row.resolved_at,
+            opinions,
+        })
+    }
+
+    /// Gets all proposals for the given list, most recently created first. If `open_only` is set,
+    /// proposals that have already been accepted or rejected are skipped.
+    pub async fn of_list(list: i32, open_only: bool, connection: &mut PgConnection) -> Result<Vec<PlacementProposal>> {
+        let ids = sqlx::query!(
+            "SELECT id FROM placement_proposals WHERE list = $1 AND (NOT $2 OR status = 'OPEN') ORDER BY id DESC",
+            list,
+            open_only
+        )
+        .fetch_all(&mut *connection)
+        .await?;
+
+        let mut proposals = Vec::new();
+
+        for row in ids {
+            proposals.push(PlacementProposal::by_id(row.id, &mut *connection).await?);
+        }
+
+        Ok(proposals)
+    }
+
+    /// Gets all proposals concerning the given demon, oldest first. This includes an accepted
+    /// proposal that added the demon to the list.
+    pub async fn of_demon(demon: i32, connection: &mut PgConnection) -> Result<Vec<PlacementProposal>> {
+        let ids = sqlx::query!("SELECT id FROM placement_proposals WHERE demon = $1 ORDER BY id", demon)
+            .fetch_all(&mut *connection)
+            .await?;
+
+        let mut proposals = Vec::new();
+
+        for row in ids {
+            proposals.push(PlacementProposal::by_id(row.id, &mut *connection).await?);
+        }
+
+        Ok(proposals)
+    }
+}
@@ -0,0 +1,156 @@
+//! Module for placement proposals
+//!
+//! Where a demon should be placed is usually decided by the list team as a whole. List helpers can
+//! propose a position for an existing demon, or for a new demon to be added to a list. Other team
+//! members then give their opinion on where the demon belongs, until a list moderator accepts
+//! (which moves or adds the demon) or rejects the proposal. Proposals are kept around after being
+//! resolved, so that the discussion that lead to a demon's placement stays attached to it.
+
+pub use self::{opinion::PutOpinion, post::PostPlacementProposal};
+use crate::{
+    demon::PostDemon,
+    error::{DemonlistError, Result},
+};
+use chrono::NaiveDateTime;
+use derive_more::Display;
+use pointercrate_core::etag::Taggable;
+use serde::{Deserialize, Serialize};
+
+mod get;
+mod opinion;
+mod post;
+mod resolve;
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Hash, Display)]
+pub enum ProposalStatus {
+    #[display(fmt = "open")]
+    Open,
+
+    #[display(fmt = "accepted")]
+    Accepted,
+
+    #[display(fmt = "rejected")]
+    Rejected,
+}
+
+impl ProposalStatus {
+    fn to_sql(self) -> String {
+        match self {
+            ProposalStatus::Open => "OPEN",
+            ProposalStatus::Accepted => "ACCEPTED",
+            ProposalStatus::Rejected => "REJECTED",
+        }
+        .to_owned()
+    }
+
+    fn from_sql(sql: &str) -> Self {
+        match sql {
+            "OPEN" => ProposalStatus::Open,
+            "ACCEPTED" => ProposalStatus::Accepted,
+            "REJECTED" => ProposalStatus::Rejected,
+            _ => panic!("invalid proposal status: {}", sql),
+        }
+    }
+}
+
+/// A demon that is proposed to be added to a list. The position is part of the
+/// [`PlacementProposal`] itself.
+#[derive(Debug, Serialize, Deserialize, Clone, Hash, PartialEq, Eq)]
+pub struct ProposedDemon {
+    pub name: String,
+    pub requirement: i16,
+    pub verifier: String,
+    pub publisher: String,
+
+    #[serde(default)]
+    pub creators: Vec<String>,
+
+    #[serde(default)]
+    pub video: Option<String>,
+}
+
+impl ProposedDemon {
+    fn at(self, position: i16) -> PostDemon {
+        PostDemon {
+            name: self.name,
+            position,
+            requirement: self.requirement,
+            verifier: self.verifier,
+            publisher: self.publisher,
+            creators: self.creators,
+            video: self.video,
+        }
+    }
+}
+
+/// The opinion of a single list team member on a [`PlacementProposal`]
+#[derive(Debug, Serialize, Deserialize, Clone, Hash, PartialEq, Eq)]
+pub struct PlacementOpinion {
+    /// The id of the user giving this opinion
+    pub user_id: i32,
+
+    /// The position this user thinks the demon belongs at. Equal to the proposed position if they
+    /// agree with the proposal
+    pub position: i16,
+
+    pub comment: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Hash, Display, PartialEq, Eq)]
+#[display(fmt = "proposal {} to place demon at {} ({})", id, position, status)]
+pub struct PlacementProposal {
+    pub id: i32,
+
+    /// The id of the [`List`](crate::list::List) the demon is (to be) placed on
+    pub list: i32,
+
+    /// The id of the demon whose placement is proposed. `None` for proposals to add a new demon
+    /// that have not been accepted yet, afterwards this is the newly added demon.
+    pub demon: Option<i32>,
+
+    /// The demon to add, for proposals to add a new demon
+    pub new_demon: Option<ProposedDemon>,
+
+    pub position: i16,
+
+    pub reason: String,
+
+    /// The id of the user who made this proposal
+    pub author: Option<i32>,
+
+    pub status: ProposalStatus,
+
+    pub created_at: NaiveDateTime,
+
+    /// The id of the list moderator who accepted or rejected this proposal
+    pub resolved_by: Option<i32>,
+
+    pub resolved_at: Option<NaiveDateTime>,
+
+    pub opinions: Vec<PlacementOpinion>,
+}
+
+impl Taggable for PlacementProposal {}
+
+impl PlacementProposal {
+    /// The number of team members that agree with the proposed position
+    pub fn votes_in_favour(&self) -> usize {
+        self.opinions.iter().filter(|opinion| opinion.position == self.position).count()
+    }
+
+    /// The median of all positions given by team members, if any gave their opinion
+    pub fn median_opinion(&self) -> Option<i16> {
+        let mut positions = self.opinions.iter().map(|opinion| opinion.position).collect::<Vec<_>>();
+
+        positions.sort_unstable();
+        positions.get(positions.len() / 2).copied()
+    }
+
+    fn ensure_open(&self) -> Result<()> {
+        if self.status != ProposalStatus::Open {
+            return Err(DemonlistError::ProposalResolved);
+        }
+
+        Ok(())
+    }
+}
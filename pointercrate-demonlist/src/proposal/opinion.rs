@@ -0,0 +1,71 @@
+use crate::{
+    demon::Demon,
+    error::Result,
+    proposal::{PlacementOpinion, PlacementProposal},
+};
+use log::info;
+use serde::Deserialize;
+use sqlx::PgConnection;
+
+#[derive(Debug, Deserialize)]
+pub struct PutOpinion {
+    pub position: i16,
+
+    #[serde(default)]
+    pub comment: String,
+}
+
+impl PlacementProposal {
+    /// Records the given user's opinion on this proposal, replacing any opinion they previously
+    /// gave
+    pub async fn put_opinion(mut self, user_id: i32, opinion: PutOpinion, connection: &mut PgConnection) -> Result<PlacementProposal> {
+        self.ensure_open()?;
+
+        info!(
+            "User {} places demon of {} at {} ({:?})",
+            user_id, self, opinion.position, opinion.comment
+        );
+
+        Demon::validate_position(opinion.position, self.list, connection).await?;
+
+        sqlx::query!(
+            "INSERT INTO placement_opinions (proposal, member, position, comment) VALUES ($1, $2, $3, $4) ON CONFLICT (proposal, member) \
+             DO UPDATE SET position = EXCLUDED.position, comment = EXCLUDED.comment",
+            self.id,
+            user_id,
+            opinion.position,
+            opinion.comment
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        self.opinions.retain(|existing| existing.user_id != user_id);
+        self.opinions.push(PlacementOpinion {
+            user_id,
+            position: opinion.position,
+            comment: opinion.comment,
+        });
+        self.opinions.sort_by_key(|opinion| opinion.user_id);
+
+        Ok(self)
+    }
+
+    /// Withdraws the given user's opinion on this proposal, if they gave one
+    pub async fn delete_opinion(mut self, user_id: i32, connection: &mut PgConnection) -> Result<PlacementProposal> {
+        self.ensure_open()?;
+
+        info!("User {} withdraws their opinion on {}", user_id, self);
+
+        sqlx::query!(
+            "DELETE FROM placement_opinions WHERE proposal = $1 AND member = $2",
+            self.id,
+            user_id
+        )
+        .execute(connection)
+        .await?;
+
+        self.opinions.retain(|opinion| opinion.user_id != user_id);
+
+        Ok(self)
+    }
+}
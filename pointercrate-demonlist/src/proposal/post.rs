@@ -0,0 +1,77 @@
+use crate::{
+    demon::Demon,
+    error::{DemonlistError, Result},
+    proposal::{PlacementProposal, ProposedDemon},
+    video::VideoHosts,
+};
+use log::info;
+use serde::Deserialize;
+use sqlx::PgConnection;
+
+#[derive(Debug, Deserialize)]
+pub struct PostPlacementProposal {
+    /// The id of the demon to move, for proposals concerning an already existing demon
+    #[serde(default)]
+    demon: Option<i32>,
+
+    /// The demon to add, for proposals to add a new demon
+    #[serde(default)]
+    new_demon: Option<ProposedDemon>,
+
+    position: i16,
+
+    #[serde(default)]
+    reason: String,
+}
+
+impl PlacementProposal {
+    /// Must run inside a transaction!
+    pub async fn create_from(
+        data: PostPlacementProposal, list: i32, author: i32, hosts: &VideoHosts, connection: &mut PgConnection,
+    ) -> Result<PlacementProposal> {
+        info!("Creating new placement proposal for list {} from {:?}", list, data);
+
+        // The list might change until the proposal is accepted, so this is only a sanity check. The position is validated again
+        // upon acceptance
+        Demon::validate_position(data.position, list, connection).await?;
+
+        let new_demon = match (data.demon, data.new_demon) {
+            (Some(demon), None) => {
+                Demon::validate_on_list(demon, list, connection).await?;
+
+                None
+            },
+            (None, Some(mut new_demon)) => {
+                Demon::validate_requirement(new_demon.requirement)?;
+
+                if let Some(ref video) = new_demon.video {
+                    new_demon.video = Some(hosts.validate(video)?);
+                }
+
+                Some(new_demon)
+            },
+            _ => return Err(DemonlistError::InvalidProposal),
+        };
+
+        let id = sqlx::query!(
+            "INSERT INTO placement_proposals (list, demon, position, reason, name, requirement, verifier, publisher, creators, video, \
+             author) VALUES ($1, $2, $3, $4, $5::text, $6, $7, $8, $9, $10, $11) RETURNING id",
+            list,
+            data.demon,
+            data.position,
+            data.reason,
+            new_demon.as_ref().map(|demon| demon.name.clone()),
+            new_demon.as_ref().map(|demon| demon.requirement),
+            new_demon.as_ref().map(|demon| demon.verifier.clone()),
+            new_demon.as_ref().map(|demon| demon.publisher.clone()),
+            new_demon.as_ref().map(|demon| demon.creators.clone()) as Option<Vec<String>>,
+            new_demon.as_ref().and_then(|demon| demon.video.clone()),
+            author
+        )
+        .fetch_one(&mut *connection)
+        .await?
+        .id;
+
+        PlacementProposal::by_id(id, connection).await
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{
+    demon::{Demon, FullDemon},
+    error::{DemonlistError, Result},
+    proposal::{PlacementProposal, ProposalStatus},
+    video::VideoHosts,
+};
+use log::info;
+use sqlx::PgConnection;
+
+impl PlacementProposal {
+    /// Accepts this proposal, either moving the demon it concerns to the proposed position, or
+    /// adding the proposed demon to the list
+    ///
+    /// Must run inside a transaction!
+    pub async fn accept(mut self, moderator: i32, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<PlacementProposal> {
+        self.lock_open(connection).await?;
+
+        info!("User {} accepts {}", moderator, self);
+
+        let demon_id = match (self.demon, self.new_demon.clone()) {
+            (Some(demon_id), _) => {
+                Demon::validate_on_list(demon_id, self.list, connection).await?;
+
+                Demon::by_id(demon_id, connection).await?.base.mv(self.position, connection).await?;
+
+                demon_id
+            },
+            (None, Some(new_demon)) => {
+                FullDemon::create_prevalidated(new_demon.at(self.position), self.list, hosts, connection)
+                    .await?
+                    .demon
+                    .base
+                    .id
+            },
+            (None, None) => unreachable!("proposal without demon"),
+        };
+
+        self.demon = Some(demon_id);
+        self.resolve(ProposalStatus::Accepted, moderator, connection).await
+    }
+
+    /// Rejects this proposal without changing the list
+    ///
+    /// Must run inside a transaction!
+    pub async fn reject(self, moderator: i32, connection: &mut PgConnection) -> Result<PlacementProposal> {
+        self.lock_open(connection).await?;
+
+        info!("User {} rejects {}", moderator, self);
+
+        self.resolve(ProposalStatus::Rejected, moderator, connection).await
+    }
+
+    /// Locks this proposal's row until the end of the transaction, so that concurrent attempts to
+    /// resolve it wait for each other, and checks that it is still open
+    async fn lock_open(&self, connection: &mut PgConnection) -> Result<()> {
+        self.ensure_open()?;
+
+        let status = sqlx::query!(
+            r#"SELECT status::TEXT AS "status!" FROM placement_proposals WHERE id = $1 FOR UPDATE"#,
+            self.id
+        )
+        .fetch_one(connection)
+        .await?
+        .status;
+
+        if ProposalStatus::from_sql(&status) != ProposalStatus::Open {
+            return Err(DemonlistError::ProposalResolved);
+        }
+
+        Ok(())
+    }
+
+    async fn resolve(mut self, status: ProposalStatus, moderator: i32, connection: &mut PgConnection) -> Result<PlacementProposal> {
+        self.resolved_at = sqlx::query!(
+            "UPDATE placement_proposals SET status = cast($1::text as proposal_status), demon = $2, resolved_by = $3, resolved_at = (NOW() \
+             AT TIME ZONE 'utc') WHERE id = $4 RETURNING resolved_at",
+            status.to_sql(),
+            self.demon,
+            moderator,
+            self.id
+        )
+        .fetch_one(connection)
+        .await?
+        .resolved_at;
+        self.status = status;
+        self.resolved_by = Some(moderator);
+
+        Ok(self)
+    }
+}
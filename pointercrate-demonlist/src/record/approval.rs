@@ -0,0 +1,99 @@
+//! Module containing the policy deciding how many list team members need to sign off on a record
+//! before it is approved
+//!
+//! By default, a single approval suffices. Deployments can require records on highly placed demons
+//! to be approved by two distinct list team members instead. In that case, the first approval only
+//! moves the record into the 'pending approval' state (which, being a status change, shows up in
+//! the record's audit log), and only the second one actually approves it. A `LIST_MODERATOR`
+//! approving a record always approves it right away.
+
+use crate::{
+    demon::MinimalDemon,
+    error::{DemonlistError, Result},
+    record::{FullRecord, RecordStatus},
+};
+use sqlx::PgConnection;
+use std::ops::RangeInclusive;
+
+/// The policy on how many approvals records need
+///
+/// Decides, based on a demon's position, whether records on it need a second approval. The
+/// default policy never requires one.
+#[derive(Debug, Clone, Default)]
+pub struct ApprovalPolicy {
+    two_reviewer_positions: Option<RangeInclusive<i16>>,
+}
+
+impl ApprovalPolicy {
+    /// A policy under which a single approval suffices for all records
+    pub fn single_reviewer() -> Self {
+        ApprovalPolicy::default()
+    }
+
+    /// Requires records on demons at the given positions to be approved by two distinct list team
+    /// members (or one moderator)
+    pub fn with_two_reviewers_for(mut self, positions: RangeInclusive<i16>) -> Self {
+        self.two_reviewer_positions = Some(positions);
+        self
+    }
+
+    pub fn requires_two_reviewers(&self, demon: &MinimalDemon) -> bool {
+        match self.two_reviewer_positions {
+            Some(ref positions) => positions.contains(&demon.position),
+            None => false,
+        }
+    }
+
+    /// Determines the status a new submission on the given demon should be created with, if the
+    /// submitting list team member requested the given status
+    pub fn resolve_submission(&self, demon: &MinimalDemon, status: RecordStatus, is_moderator: bool) -> Result<RecordStatus> {
+        match status {
+            RecordStatus::PendingApproval => Err(DemonlistError::PendingApprovalNotSettable),
+            RecordStatus::Approved if self.requires_two_reviewers(demon) && !is_moderator => Ok(RecordStatus::PendingApproval),
+            _ => Ok(status),
+        }
+    }
+
+    /// Determines the status the given record should be changed to, if the given list team member
+    /// requested changing it to the given status
+    ///
+    /// `demon` is the demon the record is on after the change, which differs from the record's
+    /// current demon if the record is moved at the same time.
+    ///
+    /// Fails with [`DemonlistError::SecondApprovalRequired`] if the reviewer tries to approve a
+    /// record they already gave the first approval to.
+    pub async fn resolve(
+        &self, record: &FullRecord, demon: &MinimalDemon, status: RecordStatus, reviewer: i32, is_moderator: bool,
+        connection: &mut PgConnection,
+    ) -> Result<RecordStatus> {
+        if status == RecordStatus::PendingApproval {
+            return Err(DemonlistError::PendingApprovalNotSettable);
+        }
+
+        if status != RecordStatus::Approved || record.status == RecordStatus::Approved {
+            return Ok(status);
+        }
+
+        if !self.requires_two_reviewers(demon) || is_moderator {
+            return Ok(RecordStatus::Approved);
+        }
+
+        if record.status != RecordStatus::PendingApproval {
+            return Ok(RecordStatus::PendingApproval);
+        }
+
+        match first_approver(record.id, connection).await? {
+            Some(approver) if approver == reviewer => Err(DemonlistError::SecondApprovalRequired),
+            _ => Ok(RecordStatus::Approved),
+        }
+    }
+}
+
+/// The id of the list team member who gave the first approval to the given record, if it is
+/// pending approval
+pub async fn first_approver(record_id: i32, connection: &mut PgConnection) -> Result<Option<i32>> {
+    Ok(sqlx::query!("SELECT approver FROM pending_approvals WHERE record = $1", record_id)
+        .fetch_optional(connection)
+        .await?
+        .map(|row| row.approver))
+}
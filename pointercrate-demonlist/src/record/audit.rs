@@ -1,5 +1,11 @@
-use crate::{error::Result, record::RecordStatus};
+use crate::{
+    demon::MinimalDemon,
+    error::Result,
+    player::DatabasePlayer,
+    record::{MinimalRecordPD, RecordStatus},
+};
 
+use chrono::NaiveDateTime;
 use futures::StreamExt;
 use pointercrate_core::audit::{AuditLogEntry, AuditLogEntryType, NamedId};
 use serde::Serialize;
@@ -12,6 +18,23 @@ pub struct RecordModificationData {
     status: Option<RecordStatus>,
     player: Option<NamedId>,
     demon: Option<NamedId>,
+
+    /// The key of the status reason the record had before this change. An empty string means it
+    /// previously had no reason.
+    status_reason: Option<String>,
+
+    /// Set if this entry is for a [bulk operation](crate::record::bulk) that included the record.
+    /// The changes it made to the record show up as separate entries.
+    bulk_operation: Option<BulkOperationData>,
+}
+
+#[derive(Serialize)]
+pub struct BulkOperationData {
+    operation: String,
+    detail: Option<String>,
+
+    /// How many records the operation was applied to in total
+    record_count: usize,
 }
 
 /// Gets all audit log entries for the given record, in chronological order
@@ -54,7 +77,8 @@ pub async fn audit_log_for_record(record_id: i32, connection: &mut PgConnection)
                   players.name::TEXT AS player_name,
                   player AS player_id,
                   demons.name::TEXT AS demon_name,
-                  demon AS demon_id
+                  demon AS demon_id,
+                  record_modifications.status_reason
                   FROM record_modifications 
                   LEFT OUTER JOIN members ON members.member_id = userid
                   LEFT OUTER JOIN players ON players.id = player
@@ -90,6 +114,8 @@ pub async fn audit_log_for_record(record_id: i32, connection: &mut PgConnection)
                         _ => None,
                     },
                     video: modification.video,
+                    status_reason: modification.status_reason,
+                    bulk_operation: None,
                 }),
                 user: NamedId {
                     name: modification.username,
@@ -99,6 +125,40 @@ pub async fn audit_log_for_record(record_id: i32, connection: &mut PgConnection)
         }
     }
 
+    let bulk_rows = sqlx::query!(
+        r#"SELECT time, audit_id, userid, members.name AS "name?", operation, detail, records
+                  FROM record_bulk_operations LEFT OUTER JOIN members ON members.member_id = userid
+                  WHERE $1 = ANY(records)"#,
+        record_id
+    )
+    .fetch_all(&mut *connection)
+    .await?;
+
+    for bulk in bulk_rows {
+        entries.push(AuditLogEntry {
+            time: bulk.time,
+            entry_id: bulk.audit_id,
+            id: record_id,
+            r#type: AuditLogEntryType::Modification(RecordModificationData {
+                progress: None,
+                video: None,
+                status: None,
+                player: None,
+                demon: None,
+                status_reason: None,
+                bulk_operation: Some(BulkOperationData {
+                    operation: bulk.operation,
+                    detail: bulk.detail,
+                    record_count: bulk.records.len(),
+                }),
+            }),
+            user: NamedId {
+                name: bulk.name,
+                id: bulk.userid,
+            },
+        });
+    }
+
     let deletion_row = sqlx::query!(
         r#"SELECT time, audit_id, 
                   userid,
@@ -122,5 +182,76 @@ pub async fn audit_log_for_record(record_id: i32, connection: &mut PgConnection)
         });
     }
 
+    // Interleave the bulk operations with the other entries. As they are logged after the changes they made, they end up right after them
+    entries.sort_by_key(|entry| (entry.time, entry.entry_id));
+
     Ok(entries)
 }
+
+/// An approved record, together with the time it got approved
+#[derive(Serialize, Debug)]
+pub struct ApprovedRecord {
+    pub record: MinimalRecordPD,
+    pub approved_at: NaiveDateTime,
+}
+
+/// Gets the records on the given list that were approved most recently, most recent first,
+/// optionally restricted to those of a single player or of players of a single nation
+///
+/// A record's approval time is the time its status was last changed, or the time it was added if
+/// it was approved directly upon submission. Records of banned players are never included.
+pub async fn recently_approved_records(
+    list: i32, player: Option<i32>, nation: Option<&str>, limit: i64, connection: &mut PgConnection,
+) -> Result<Vec<ApprovedRecord>> {
+    // Goes through the audit log starting from the most recent entries. A record got approved by the last entry changing its
+    // status, or by its addition if there is none (the audit log stores the old values of modified columns, so any status change
+    // shows up as a non-NULL status_)
+    let rows = sqlx::query!(
+        r#"SELECT records.id, records.progress, records.video::TEXT, players.id AS player_id, players.name::TEXT AS "player_name!",
+                  demons.id AS demon_id, demons.name::TEXT AS "demon_name!", demons.position, changes.time AS "approved_at!"
+           FROM (
+               SELECT id, time, audit_id FROM record_modifications WHERE status_ IS NOT NULL
+             UNION ALL
+               SELECT id, time, audit_id FROM record_additions
+           ) AS changes
+           INNER JOIN records ON records.id = changes.id
+           INNER JOIN players ON players.id = records.player
+           INNER JOIN demons ON demons.id = records.demon
+           WHERE records.status_ = 'APPROVED' AND NOT players.banned AND demons.list = $1
+             AND ($2::INTEGER IS NULL OR players.id = $2) AND ($3::TEXT IS NULL OR players.nationality = $3)
+             AND NOT EXISTS (
+                 SELECT 1 FROM record_modifications AS later
+                 WHERE later.id = changes.id AND later.status_ IS NOT NULL AND (later.time, later.audit_id) > (changes.time, changes.audit_id)
+             )
+           ORDER BY changes.time DESC LIMIT $4"#,
+        list,
+        player,
+        nation,
+        limit
+    )
+    .fetch_all(&mut *connection)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| ApprovedRecord {
+            approved_at: row.approved_at,
+            record: MinimalRecordPD {
+                id: row.id,
+                progress: row.progress,
+                video: row.video,
+                status: RecordStatus::Approved,
+                demon: MinimalDemon {
+                    id: row.demon_id,
+                    position: row.position,
+                    name: row.demon_name,
+                },
+                player: DatabasePlayer {
+                    id: row.player_id,
+                    name: row.player_name,
+                    banned: false,
+                },
+            },
+        })
+        .collect())
+}
@@ -0,0 +1,229 @@
+//! Module containing operations applying the same change to many records at once
+//!
+//! Cleaning up after a banned cheater or a spam wave can involve hundreds of records. Instead of
+//! patching or deleting them one by one (each time recomputing the score of the record holder), a
+//! bulk operation changes all of them inside a single transaction, only recomputes scores once at
+//! the end and groups the changes together in the audit log. The individual changes still show up
+//! in the audit logs of the affected records.
+
+use crate::{
+    error::{DemonlistError, Result},
+    player::{recompute_scores, DatabasePlayer},
+    record::{reason::StatusReasons, FullRecord, RecordPagination, RecordStatus},
+};
+use log::info;
+use pointercrate_core::error::CoreError;
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+
+/// The change to apply to every selected record
+#[derive(Deserialize, Debug)]
+#[serde(tag = "operation", rename_all = "snake_case")]
+pub enum BulkAction {
+    /// Changes the status of all selected records, optionally giving them a [status
+    /// reason](crate::record::reason::StatusReason)
+    ///
+    /// If no reason is given, the previous reasons of records whose status changes are cleared.
+    ///
+    /// Since bulk operations are reserved for list moderators, approvals never have to be signed
+    /// off by a second list team member.
+    SetStatus {
+        status: RecordStatus,
+
+        #[serde(default)]
+        reason: Option<String>,
+    },
+
+    /// Deletes all selected records
+    Delete,
+
+    /// Reassigns all selected records to the player with the given name (creating them if they do
+    /// not exist yet)
+    SetPlayer { player: String },
+}
+
+impl BulkAction {
+    fn name(&self) -> &'static str {
+        match self {
+            BulkAction::SetStatus { .. } => "set_status",
+            BulkAction::Delete => "delete",
+            BulkAction::SetPlayer { .. } => "set_player",
+        }
+    }
+
+    fn detail(&self) -> Option<String> {
+        match self {
+            BulkAction::SetStatus { status, .. } => Some(status.to_sql()),
+            BulkAction::Delete => None,
+            BulkAction::SetPlayer { player } => Some(player.clone()),
+        }
+    }
+}
+
+/// A bulk operation on records
+///
+/// The records to operate on are selected either by giving their ids, or by giving a filter (using
+/// the same fields as record pagination, minus the pagination parameters).
+#[derive(Deserialize, Debug)]
+pub struct BulkOperation {
+    #[serde(default)]
+    pub records: Option<Vec<i32>>,
+
+    #[serde(default)]
+    pub filter: Option<RecordPagination>,
+
+    #[serde(flatten)]
+    pub action: BulkAction,
+
+    /// If set, only determines which records would be affected, without changing anything
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// The records a bulk operation was (or, for dry runs, would have been) applied to
+#[derive(Serialize, Debug)]
+pub struct BulkOutcome {
+    pub records: Vec<i32>,
+    pub count: usize,
+    pub dry_run: bool,
+
+    /// The records whose status was changed by the operation, with their new status
+    #[serde(skip)]
+    pub reviewed: Vec<FullRecord>,
+}
+
+impl BulkOperation {
+    /// Applies this operation to all records it selects
+    ///
+    /// Some records might no longer exist by the time the operation gets to them, as for example
+    /// rejecting one record deletes all other records of its holder on the same demon. These
+    /// records are skipped.
+    ///
+    /// To prevent accidentally operating on every single record, filters have to restrict the
+    /// selection somehow.
+    ///
+    /// Must be run within a transaction, on a connection with an active user!
+    pub async fn apply(self, reasons: &StatusReasons, connection: &mut PgConnection) -> Result<BulkOutcome> {
+        let records = match (self.records, self.filter) {
+            (Some(_), Some(_)) => return Err(CoreError::MutuallyExclusive.into()),
+            (Some(ids), None) => existing_records(ids, connection).await?,
+            (None, Some(filter)) if !filter.is_unrestricted() => filter.matching_ids(connection).await?,
+            (None, _) => return Err(DemonlistError::NoRecordsSelected),
+        };
+
+        if let BulkAction::SetStatus { status, ref reason } = self.action {
+            if status == RecordStatus::PendingApproval {
+                return Err(DemonlistError::PendingApprovalNotSettable);
+            }
+
+            if let Some(key) = reason {
+                reasons.validate(key, status)?;
+            }
+        }
+
+        if self.dry_run || records.is_empty() {
+            return Ok(BulkOutcome {
+                count: records.len(),
+                records,
+                dry_run: self.dry_run,
+                reviewed: Vec::new(),
+            });
+        }
+
+        info!("Applying bulk operation {:?} to {} records", self.action, records.len());
+
+        let mut reviewed = Vec::new();
+
+        match self.action {
+            BulkAction::SetStatus { status, ref reason } => {
+                for &record_id in &records {
+                    let Some(mut record) = still_existing(record_id, connection).await? else {
+                        continue;
+                    };
+
+                    let status_changed = record.status != status;
+
+                    if status_changed {
+                        record.set_status(status, connection).await?;
+                    }
+
+                    // Records that already had the requested status keep their reason unless a new one was given
+                    if (status_changed || reason.is_some()) && record.reason != *reason {
+                        record.set_reason(reason.clone(), reasons, connection).await?;
+                    }
+
+                    if status_changed {
+                        reviewed.push(record);
+                    }
+                }
+            },
+            BulkAction::Delete => {
+                // Associated notes get deleted due to the ON DELETE CASCADE on record_notes.record
+                sqlx::query!("DELETE FROM records WHERE id = ANY($1)", &records)
+                    .execute(&mut *connection)
+                    .await?;
+            },
+            BulkAction::SetPlayer { ref player } => {
+                let player = DatabasePlayer::by_name_or_create(player.as_ref(), connection).await?;
+
+                for &record_id in &records {
+                    let Some(mut record) = still_existing(record_id, connection).await? else {
+                        continue;
+                    };
+
+                    if record.player.id != player.id {
+                        record.set_player(player.clone(), connection).await?;
+                    }
+                }
+            },
+        }
+
+        // Not checked at compile time, since active_user is a temporary table that only exists on connections with an active user
+        let logged = sqlx::query(
+            "INSERT INTO record_bulk_operations (userid, operation, detail, records) SELECT id, $1, $2, $3 FROM active_user LIMIT 1",
+        )
+        .bind(self.action.name())
+        .bind(self.action.detail())
+        .bind(&records)
+        .execute(&mut *connection)
+        .await?
+        .rows_affected();
+
+        if logged == 0 {
+            return Err(CoreError::internal_server_error("Bulk operation applied on a connection without an active user").into());
+        }
+
+        recompute_scores(connection).await?;
+
+        Ok(BulkOutcome {
+            count: records.len(),
+            records,
+            dry_run: false,
+            reviewed,
+        })
+    }
+}
+
+/// Checks that all records with the given ids exist, returning the ids in ascending order
+async fn existing_records(mut ids: Vec<i32>, connection: &mut PgConnection) -> Result<Vec<i32>> {
+    ids.sort_unstable();
+    ids.dedup();
+
+    let existing = sqlx::query!("SELECT id FROM records WHERE id = ANY($1) ORDER BY id", &ids)
+        .fetch_all(connection)
+        .await?;
+
+    if let Some(&record_id) = ids.iter().find(|id| !existing.iter().any(|row| row.id == **id)) {
+        return Err(DemonlistError::RecordNotFound { record_id });
+    }
+
+    Ok(ids)
+}
+
+async fn still_existing(record_id: i32, connection: &mut PgConnection) -> Result<Option<FullRecord>> {
+    match FullRecord::by_id(record_id, connection).await {
+        Ok(record) => Ok(Some(record)),
+        Err(DemonlistError::RecordNotFound { .. }) => Ok(None),
+        Err(err) => Err(err),
+    }
+}
@@ -3,9 +3,10 @@ use crate::{
     error::{DemonlistError, Result},
     nationality::Nationality,
     player::DatabasePlayer,
-    record::{FullRecord, MinimalRecordD, MinimalRecordP, RecordStatus},
+    record::{proof::proofs_on, FullRecord, MinimalRecordD, MinimalRecordP, RecordStatus},
     submitter::Submitter,
 };
+use chrono::NaiveDateTime;
 use futures::stream::StreamExt;
 use sqlx::{Error, PgConnection};
 
@@ -14,6 +15,7 @@ struct FetchedRecord {
     progress: i16,
     video: Option<String>,
     status: String,
+    status_reason: Option<String>,
     player_id: i32,
     player_name: String,
     player_banned: bool,
@@ -22,6 +24,7 @@ struct FetchedRecord {
     position: i16,
     submitter_id: i32,
     submitter_banned: bool,
+    video_unreachable_since: Option<NaiveDateTime>,
 }
 
 impl FullRecord {
@@ -36,6 +39,7 @@ impl FullRecord {
                 progress: row.progress,
                 video: row.video,
                 status: RecordStatus::from_sql(&row.status),
+                reason: row.status_reason,
                 player: DatabasePlayer {
                     id: row.player_id,
                     name: row.player_name,
@@ -50,6 +54,9 @@ impl FullRecord {
                     id: row.submitter_id,
                     banned: row.submitter_banned,
                 }),
+                proofs: proofs_on(id, false, connection).await?,
+                video_unreachable_since: row.video_unreachable_since,
+                status_token: None,
             }),
 
             Err(Error::RowNotFound) => Err(DemonlistError::RecordNotFound { record_id: id }),
@@ -58,12 +65,13 @@ impl FullRecord {
     }
 }
 
-pub async fn approved_records_by(player: &DatabasePlayer, connection: &mut PgConnection) -> Result<Vec<MinimalRecordD>> {
+pub async fn approved_records_by(player: &DatabasePlayer, list: i32, connection: &mut PgConnection) -> Result<Vec<MinimalRecordD>> {
     let mut stream = sqlx::query!(
         r#"SELECT records.id, progress, CASE WHEN players.link_banned THEN NULL ELSE records.video::text END, demons.id AS demon_id, 
          demons.name, demons.position FROM records INNER JOIN demons ON records.demon = demons.id INNER JOIN players ON players.id 
-         = $1 WHERE status_ = 'APPROVED' AND records.player = $1"#,
-        player.id
+         = $1 WHERE status_ = 'APPROVED' AND records.player = $1 AND demons.list = $2 AND NOT demons.unlisted"#,
+        player.id,
+        list
     )
     .fetch(connection);
 
@@ -0,0 +1,69 @@
+//! Module containing the status lookups submitters can do for their submissions
+//!
+//! Submitters are only identified by their IP address, so there is no account through which they
+//! could be informed about what became of their submission. Instead, each submission is given a
+//! secret token when it is made, which allows looking up its current status (including the reason
+//! for it and all public notes) later on.
+
+use crate::{
+    demon::MinimalDemon,
+    error::{DemonlistError, Result},
+    player::DatabasePlayer,
+    record::{
+        note::{notes_on, Note},
+        reason::{StatusReason, StatusReasons},
+        FullRecord, RecordStatus,
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+
+/// What a submitter gets to know about their submission
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmissionStatus {
+    pub id: i32,
+    pub progress: i16,
+    pub video: Option<String>,
+    pub status: RecordStatus,
+
+    /// The reason the record was given its status, if the list team specified one
+    ///
+    /// `None` if the reason was removed from the registry since it was given.
+    pub reason: Option<StatusReason>,
+    pub player: DatabasePlayer,
+    pub demon: MinimalDemon,
+
+    /// The notes on the submission visible to the public
+    pub notes: Vec<Note>,
+}
+
+impl SubmissionStatus {
+    /// Looks up the status of the submission with the given status token
+    ///
+    /// Fails with [`DemonlistError::SubmissionNotFound`] if no record has this token, which
+    /// includes records that were deleted (for example because they were superseded by a record
+    /// with more progress).
+    pub async fn by_token(token: &str, reasons: &StatusReasons, connection: &mut PgConnection) -> Result<SubmissionStatus> {
+        let record_id = sqlx::query!(
+            "SELECT id FROM records WHERE status_token_hash = encode(sha256(convert_to($1, 'UTF8')), 'hex')",
+            token
+        )
+        .fetch_optional(&mut *connection)
+        .await?
+        .ok_or(DemonlistError::SubmissionNotFound)?
+        .id;
+
+        let record = FullRecord::by_id(record_id, &mut *connection).await?;
+
+        Ok(SubmissionStatus {
+            id: record.id,
+            progress: record.progress,
+            video: record.video,
+            status: record.status,
+            reason: record.reason.and_then(|key| reasons.get(&key).cloned()),
+            player: record.player,
+            demon: record.demon,
+            notes: notes_on(record_id, true, connection).await?,
+        })
+    }
+}
@@ -1,7 +1,7 @@
 //! Module containing all code relating to records on the demonlist
 //!
-//! Each record can have one of four statuses, 'approved', 'rejected', 'under consideration' or
-//! 'submitted'. We will call a record of some player on some demon a (player, demon)-record.
+//! Each record can have one of five statuses, 'approved', 'rejected', 'under consideration',
+//! 'pending approval' or 'submitted'. We will call a record of some player on some demon a (player, demon)-record.
 //! We call a (player, demon)-record R _unique_ iff all other records by that player on the demon
 //! have a different status than R. We call it _globally unique_ if R is the only record, regardless
 //! of state, of player on demon.
@@ -20,14 +20,21 @@
 //! * 'under consideration' means essentially the same as 'submitted', only that all further
 //!   submissions for this (demon, player) tuple are disallowed. Note that this does not mean that
 //!   the 'under consideration' status makes. A record under consideration IS NOT UNIQUE!
+//! * 'pending approval' means that one list team member approved the record, but since the demon
+//!   is placed high enough that the [approval policy](approval::ApprovalPolicy) requires it, a
+//!   second one still has to sign off on it. Otherwise, it behaves like 'under consideration'.
 
 pub use self::{
     get::{approved_records_by, approved_records_on},
     paginate::RecordPagination,
     patch::PatchRecord,
-    post::Submission,
+    post::{NormalizedSubmission, Submission},
+    video_check::{claim_due_video_checks, VIDEO_RECHECK_BATCH_SIZE, VIDEO_RECHECK_INTERVAL},
 };
-use crate::{demon::MinimalDemon, error::Result, nationality::Nationality, player::DatabasePlayer, submitter::Submitter};
+use crate::{
+    demon::MinimalDemon, error::Result, nationality::Nationality, player::DatabasePlayer, record::proof::Proof, submitter::Submitter,
+};
+use chrono::NaiveDateTime;
 use derive_more::Display;
 use pointercrate_core::etag::Taggable;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -38,13 +45,21 @@ use std::{
     hash::{Hash, Hasher},
 };
 
+pub mod approval;
 pub mod audit;
+pub mod bulk;
 mod delete;
 mod get;
+pub mod lookup;
 pub mod note;
 mod paginate;
 mod patch;
 mod post;
+pub mod proof;
+pub mod reason;
+pub mod review;
+pub mod rules;
+mod video_check;
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
 pub enum RecordStatus {
@@ -52,6 +67,7 @@ pub enum RecordStatus {
     Approved,
     Rejected,
     UnderConsideration,
+    PendingApproval,
 }
 
 impl RecordStatus {
@@ -61,6 +77,7 @@ impl RecordStatus {
             RecordStatus::Approved => "APPROVED",
             RecordStatus::Rejected => "REJECTED",
             RecordStatus::UnderConsideration => "UNDER_CONSIDERATION",
+            RecordStatus::PendingApproval => "PENDING_APPROVAL",
         }
         .to_owned()
     }
@@ -71,6 +88,7 @@ impl RecordStatus {
             "APPROVED" => RecordStatus::Approved,
             "REJECTED" => RecordStatus::Rejected,
             "UNDER_CONSIDERATION" => RecordStatus::UnderConsideration,
+            "PENDING_APPROVAL" => RecordStatus::PendingApproval,
             _ => panic!("invalid record state: {}", sql),
         }
     }
@@ -89,6 +107,7 @@ impl Display for RecordStatus {
             RecordStatus::Approved => write!(f, "approved"),
             RecordStatus::Rejected => write!(f, "rejected"),
             RecordStatus::UnderConsideration => write!(f, "under consideration"),
+            RecordStatus::PendingApproval => write!(f, "pending approval"),
         }
     }
 }
@@ -114,9 +133,10 @@ impl<'de> Deserialize<'de> for RecordStatus {
             "submitted" => Ok(RecordStatus::Submitted),
             "rejected" => Ok(RecordStatus::Rejected),
             "under consideration" => Ok(RecordStatus::UnderConsideration),
+            "pending approval" => Ok(RecordStatus::PendingApproval),
             _ => Err(serde::de::Error::invalid_value(
                 serde::de::Unexpected::Str(&string),
-                &"'approved', 'submitted', 'under consideration' or 'rejected'",
+                &"'approved', 'submitted', 'under consideration', 'pending approval' or 'rejected'",
             )),
         }
     }
@@ -129,9 +149,26 @@ pub struct FullRecord {
     pub progress: i16,
     pub video: Option<String>,
     pub status: RecordStatus,
+
+    /// The key of the [reason](reason::StatusReason) this record was given its status for, if any
+    pub reason: Option<String>,
     pub player: DatabasePlayer,
     pub demon: MinimalDemon,
     pub submitter: Option<Submitter>,
+
+    /// Proof attached to this record in addition to its video
+    pub proofs: Vec<Proof>,
+
+    /// Set if the video of this record could not be reached the last time it was checked, to the
+    /// (UTC) time at which it was first found to be unreachable
+    pub video_unreachable_since: Option<NaiveDateTime>,
+
+    /// The secret token with which the submitter can look up the status of this record
+    ///
+    /// Only ever set on the response to the submission of a record. Only a hash of the token is
+    /// stored, so it cannot be retrieved again afterwards.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_token: Option<String>,
 }
 
 impl Taggable for FullRecord {
@@ -141,11 +178,12 @@ impl Taggable for FullRecord {
         self.progress.hash(&mut hasher);
         self.video.hash(&mut hasher);
         self.status.hash(&mut hasher);
+        self.reason.hash(&mut hasher);
         self.player.id.hash(&mut hasher);
         self.demon.id.hash(&mut hasher);
         // notes have sub-endpoint -> no hash
         // submitter cannot be patched -> no hash
-        // raw footage cannot be patched -> no hash
+        // proofs have sub-endpoint -> no hash
         hasher.finish()
     }
 }
@@ -2,6 +2,7 @@ mod delete;
 mod get;
 mod patch;
 mod post;
+pub mod template;
 
 pub use self::{get::notes_on, patch::PatchNote, post::NewNote};
 use pointercrate_core::etag::Taggable;
@@ -1,13 +1,23 @@
 use crate::{
     error::{DemonlistError, Result},
-    record::{note::Note, FullRecord},
+    record::{
+        note::{template::NoteTemplate, Note},
+        FullRecord,
+    },
 };
+use pointercrate_core::error::CoreError;
 use serde::Deserialize;
 use sqlx::PgConnection;
 
 #[derive(Deserialize, Debug)]
 pub struct NewNote {
-    content: String,
+    #[serde(default)]
+    content: Option<String>,
+
+    /// The id of a [`NoteTemplate`] to fill in with the record's details, instead of giving the
+    /// content directly
+    #[serde(default)]
+    template: Option<i32>,
 
     #[serde(default)]
     is_public: bool,
@@ -19,14 +29,21 @@ impl Note {
     /// This does **not** insert the note into the records `notes` vector! Also doesn't set the
     /// `author` field!
     pub async fn create_on(record: &FullRecord, new_note: NewNote, connection: &mut PgConnection) -> Result<Note> {
-        if new_note.content.trim().is_empty() {
+        let content = match (new_note.content, new_note.template) {
+            (Some(_), Some(_)) => return Err(CoreError::MutuallyExclusive.into()),
+            (Some(content), None) => content,
+            (None, Some(template_id)) => NoteTemplate::by_id(template_id, &mut *connection).await?.render(record)?,
+            (None, None) => return Err(DemonlistError::NoteEmpty),
+        };
+
+        if content.trim().is_empty() {
             return Err(DemonlistError::NoteEmpty);
         }
 
         let note_id = sqlx::query!(
             "INSERT INTO record_notes (record, content, is_public) VALUES ($1, $2, $3) RETURNING id",
             record.id,
-            new_note.content,
+            content,
             new_note.is_public
         )
         .fetch_one(connection)
@@ -36,7 +53,7 @@ impl Note {
         Ok(Note {
             id: note_id,
             record: record.id,
-            content: new_note.content,
+            content,
             is_public: new_note.is_public,
             transferred: false,
             author: None,
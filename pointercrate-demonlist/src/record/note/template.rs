@@ -0,0 +1,247 @@
+//! Module for canned notes the list team can put on records
+//!
+//! Templates are managed by list moderators. Their content can contain the placeholders listed in
+//! [`PLACEHOLDERS`] (written as `{player}`, etc.), which are filled in with the details of the
+//! record when a note is created from the template. The created note does not remember which
+//! template it came from, so changing or deleting a template never affects existing notes.
+
+use crate::{
+    error::{DemonlistError, Result},
+    record::FullRecord,
+};
+use futures::StreamExt;
+use log::info;
+use pointercrate_core::{etag::Taggable, util::non_nullable};
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, PgConnection};
+
+/// The placeholders that can be used in the content of note templates
+pub const PLACEHOLDERS: &[&str] = &["player", "demon", "progress"];
+
+#[derive(Debug, Serialize, Deserialize, Hash)]
+pub struct NoteTemplate {
+    pub id: i32,
+
+    /// Short name under which the list team can pick this template
+    pub name: String,
+
+    pub content: String,
+
+    /// The id of the user that created this template
+    pub created_by: Option<i32>,
+}
+
+impl Taggable for NoteTemplate {}
+
+#[derive(Debug, Deserialize)]
+pub struct NewNoteTemplate {
+    name: String,
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PatchNoteTemplate {
+    #[serde(default, deserialize_with = "non_nullable")]
+    name: Option<String>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    content: Option<String>,
+}
+
+impl NoteTemplate {
+    pub async fn by_id(template_id: i32, connection: &mut PgConnection) -> Result<NoteTemplate> {
+        sqlx::query_as!(
+            NoteTemplate,
+            "SELECT id, name, content, created_by FROM record_note_templates WHERE id = $1",
+            template_id
+        )
+        .fetch_one(connection)
+        .await
+        .map_err(|err| match err {
+            Error::RowNotFound => DemonlistError::NoteTemplateNotFound { template_id },
+            _ => err.into(),
+        })
+    }
+
+    /// Gets all note templates, ordered by name
+    pub async fn all(connection: &mut PgConnection) -> Result<Vec<NoteTemplate>> {
+        let mut stream = sqlx::query_as!(
+            NoteTemplate,
+            "SELECT id, name, content, created_by FROM record_note_templates ORDER BY name, id"
+        )
+        .fetch(connection);
+
+        let mut templates = Vec::new();
+
+        while let Some(template) = stream.next().await {
+            templates.push(template?);
+        }
+
+        Ok(templates)
+    }
+
+    pub async fn create_from(data: NewNoteTemplate, created_by: i32, connection: &mut PgConnection) -> Result<NoteTemplate> {
+        info!("Creating new note template {:?}", data);
+
+        validate_name(&data.name)?;
+        validate_content(&data.content)?;
+
+        let id = sqlx::query!(
+            "INSERT INTO record_note_templates (name, content, created_by) VALUES ($1, $2, $3) RETURNING id",
+            data.name,
+            data.content,
+            created_by
+        )
+        .fetch_one(&mut *connection)
+        .await?
+        .id;
+
+        Ok(NoteTemplate {
+            id,
+            name: data.name,
+            content: data.content,
+            created_by: Some(created_by),
+        })
+    }
+
+    pub async fn apply_patch(mut self, patch: PatchNoteTemplate, connection: &mut PgConnection) -> Result<NoteTemplate> {
+        info!("Patching note template {} with {:?}", self.id, patch);
+
+        if let Some(name) = patch.name {
+            validate_name(&name)?;
+
+            self.name = name;
+        }
+
+        if let Some(content) = patch.content {
+            validate_content(&content)?;
+
+            self.content = content;
+        }
+
+        sqlx::query!(
+            "UPDATE record_note_templates SET name = $1, content = $2 WHERE id = $3",
+            self.name,
+            self.content,
+            self.id
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(self)
+    }
+
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        info!("Deleting note template {}", self.id);
+
+        sqlx::query!("DELETE FROM record_note_templates WHERE id = $1", self.id)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Fills in the placeholders of this template with the details of the given record
+    pub fn render(&self, record: &FullRecord) -> Result<String> {
+        fill(&self.content, |placeholder| match placeholder {
+            "player" => Some(record.player.name.clone()),
+            "demon" => Some(record.demon.name.clone()),
+            "progress" => Some(record.progress.to_string()),
+            _ => None,
+        })
+    }
+}
+
+fn validate_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        return Err(DemonlistError::NoteTemplateNameEmpty);
+    }
+
+    Ok(())
+}
+
+fn validate_content(content: &str) -> Result<()> {
+    if content.trim().is_empty() {
+        return Err(DemonlistError::NoteEmpty);
+    }
+
+    fill(content, |placeholder| PLACEHOLDERS.contains(&placeholder).then(String::new)).map(|_| ())
+}
+
+/// Replaces every `{placeholder}` in the given content with the value returned for it
+///
+/// Only braces enclosing a non-empty sequence of lowercase letters and underscores form a
+/// placeholder, all other braces (e.g. in `{}` or `{ Wave }`) are left as is. Fails with
+/// [`DemonlistError::UnknownNotePlaceholder`] if no value is returned for some placeholder.
+fn fill(content: &str, value: impl Fn(&str) -> Option<String>) -> Result<String> {
+    let mut filled = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let placeholder = rest
+            .find('}')
+            .map(|end| &rest[..end])
+            .filter(|name| !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_'));
+
+        let Some(placeholder) = placeholder else {
+            filled.push('{');
+            continue;
+        };
+
+        match value(placeholder) {
+            Some(value) => filled.push_str(&value),
+            None => {
+                return Err(DemonlistError::UnknownNotePlaceholder {
+                    placeholder: placeholder.to_string(),
+                })
+            },
+        }
+
+        rest = &rest[placeholder.len() + 1..];
+    }
+
+    filled.push_str(rest);
+
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(placeholder: &str) -> Option<String> {
+        match placeholder {
+            "player" => Some("stardust1971".to_string()),
+            "demon" => Some("Bloodbath".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(
+            fill("{player} on {demon}, {player}!", values).unwrap(),
+            "stardust1971 on Bloodbath, stardust1971!"
+        );
+        assert_eq!(fill("no placeholders", values).unwrap(), "no placeholders");
+    }
+
+    #[test]
+    fn test_fill_ignores_other_braces() {
+        assert_eq!(fill("{} { player } {Demon} {1}", values).unwrap(), "{} { player } {Demon} {1}");
+        assert_eq!(fill("{{player}}", values).unwrap(), "{stardust1971}");
+        assert_eq!(fill("unclosed {player", values).unwrap(), "unclosed {player");
+        assert_eq!(fill("{player, see {demon}", values).unwrap(), "{player, see Bloodbath");
+    }
+
+    #[test]
+    fn test_fill_unknown_placeholder() {
+        assert!(matches!(
+            fill("{player} beat {level}", values),
+            Err(DemonlistError::UnknownNotePlaceholder { placeholder }) if placeholder == "level"
+        ));
+    }
+}
@@ -54,6 +54,19 @@ pub struct RecordPagination {
 
     #[serde(default, deserialize_with = "non_nullable")]
     pub submitter: Option<i32>,
+
+    /// Only include records on demons on the list with this id
+    #[serde(default, deserialize_with = "non_nullable")]
+    pub list: Option<i32>,
+
+    /// Only include records whose video was (or was not) found to be unreachable when it was last
+    /// checked
+    #[serde(default, deserialize_with = "non_nullable")]
+    pub video_unreachable: Option<bool>,
+
+    /// Only include records that have (or do not have) raw footage attached
+    #[serde(default, deserialize_with = "non_nullable")]
+    pub has_raw_footage: Option<bool>,
 }
 
 impl PaginationQuery for RecordPagination {
@@ -69,6 +82,60 @@ impl PaginationQuery for RecordPagination {
     }
 }
 
+impl RecordPagination {
+    /// Whether this filter matches every record there is
+    pub(crate) fn is_unrestricted(&self) -> bool {
+        self.progress.is_none()
+            && self.progress_lt.is_none()
+            && self.progress_gt.is_none()
+            && self.demon_position.is_none()
+            && self.demon_position_lt.is_none()
+            && self.demon_position_gt.is_none()
+            && self.status.is_none()
+            && self.player.is_none()
+            && self.demon.is_none()
+            && self.demon_id.is_none()
+            && self.video.is_none()
+            && self.submitter.is_none()
+            && self.list.is_none()
+            && self.video_unreachable.is_none()
+            && self.has_raw_footage.is_none()
+    }
+
+    /// The ids of all records matching this filter, in ascending order
+    ///
+    /// The pagination parameters (`before`, `after` and `limit`) are ignored.
+    pub(crate) async fn matching_ids(&self, connection: &mut PgConnection) -> Result<Vec<i32>, sqlx::Error> {
+        let sql_query = format!(include_str!("../../sql/paginate_records.sql"), "ASC");
+
+        // Binding NULL to the LIMIT clause means no limit at all
+        let rows = sqlx::query(&sql_query)
+            .bind(None::<i32>)
+            .bind(None::<i32>)
+            .bind(self.progress)
+            .bind(self.progress_lt)
+            .bind(self.progress_gt)
+            .bind(self.demon_position)
+            .bind(self.demon_position_lt)
+            .bind(self.demon_position_gt)
+            .bind(self.status.map(|s| s.to_sql()))
+            .bind(self.demon.as_deref())
+            .bind(self.demon_id)
+            .bind(&self.video)
+            .bind(self.video == Some(None))
+            .bind(self.player)
+            .bind(self.submitter)
+            .bind(None::<i32>)
+            .bind(self.list)
+            .bind(self.video_unreachable)
+            .bind(self.has_raw_footage)
+            .fetch_all(&mut *connection)
+            .await?;
+
+        rows.iter().map(|row| row.try_get("id")).collect()
+    }
+}
+
 impl Paginatable<RecordPagination> for MinimalRecordPD {
     first_and_last!("records");
 
@@ -94,6 +161,9 @@ impl Paginatable<RecordPagination> for MinimalRecordPD {
             .bind(query.player)
             .bind(query.submitter)
             .bind(query.params.limit + 1)
+            .bind(query.list)
+            .bind(query.video_unreachable)
+            .bind(query.has_raw_footage)
             .fetch(&mut *connection);
 
         let mut records = Vec::new();
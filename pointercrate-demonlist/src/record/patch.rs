@@ -2,7 +2,8 @@ use crate::{
     demon::MinimalDemon,
     error::{DemonlistError, Result},
     player::DatabasePlayer,
-    record::{FullRecord, RecordStatus},
+    record::{approval::ApprovalPolicy, reason::StatusReasons, FullRecord, RecordStatus},
+    video::VideoHosts,
 };
 use log::{info, warn};
 use pointercrate_core::{
@@ -23,6 +24,11 @@ pub struct PatchRecord {
     #[serde(default, deserialize_with = "non_nullable")]
     status: Option<RecordStatus>,
 
+    /// The key of the [reason](crate::record::reason::StatusReason) for the record's (new) status.
+    /// If the status changes and no reason is given, the previous reason is cleared.
+    #[serde(default, deserialize_with = "nullable")]
+    reason: Option<Option<String>>,
+
     #[serde(default, deserialize_with = "non_nullable")]
     player: Option<String>,
 
@@ -33,9 +39,42 @@ pub struct PatchRecord {
     demon_id: Option<i32>,
 }
 
+impl PatchRecord {
+    /// Whether this patch changes the status of a record
+    pub fn changes_status(&self) -> bool {
+        self.status.is_some()
+    }
+
+    /// Runs the status change requested by this patch (if any) through the given
+    /// [`ApprovalPolicy`], turning approvals that need to be signed off by a second list team member
+    /// into pending ones
+    ///
+    /// If this patch also moves the record to a different demon, the policy is applied with respect
+    /// to that demon.
+    pub async fn apply_approval_policy(
+        &mut self, record: &FullRecord, policy: &ApprovalPolicy, reviewer: i32, is_moderator: bool, connection: &mut PgConnection,
+    ) -> Result<()> {
+        if let Some(status) = self.status {
+            let new_demon = match (&self.demon, self.demon_id) {
+                (Some(demon_name), None) => Some(MinimalDemon::by_name(demon_name.as_ref(), connection).await?),
+                (None, Some(demon_id)) => Some(MinimalDemon::by_id(demon_id, connection).await?),
+                (Some(_), Some(_)) => return Err(CoreError::MutuallyExclusive.into()),
+                (None, None) => None,
+            };
+            let demon = new_demon.as_ref().unwrap_or(&record.demon);
+
+            self.status = Some(policy.resolve(record, demon, status, reviewer, is_moderator, connection).await?);
+        }
+
+        Ok(())
+    }
+}
+
 impl FullRecord {
     /// Must be called inside a transaction
-    pub async fn apply_patch(mut self, data: PatchRecord, connection: &mut PgConnection) -> Result<Self> {
+    pub async fn apply_patch(
+        mut self, data: PatchRecord, hosts: &VideoHosts, reasons: &StatusReasons, connection: &mut PgConnection,
+    ) -> Result<Self> {
         info!("Applying patch {:?} for record {}", data, self);
 
         if let Some(progress) = data.progress {
@@ -45,18 +84,29 @@ impl FullRecord {
         if let Some(video) = data.video {
             match video {
                 None => self.delete_video(connection).await?,
-                Some(video) => self.set_video(video, connection).await?,
+                Some(video) => self.set_video(video, hosts, connection).await?,
             }
         }
 
+        let old_status = self.status;
+
         if let Some(status) = data.status {
             self.set_status(status, connection).await?
         }
 
+        match data.reason {
+            Some(reason) => self.set_reason(reason, reasons, connection).await?,
+            None if self.status != old_status && self.reason.is_some() => self.set_reason(None, reasons, connection).await?,
+            None => (),
+        }
+
         if let Some(player) = data.player {
             let player = DatabasePlayer::by_name_or_create(player.as_ref(), connection).await?;
+            let old_holder = self.player.clone();
 
             self.set_player(player, connection).await?;
+
+            old_holder.update_score(connection).await?;
         }
 
         match (data.demon, data.demon_id) {
@@ -182,7 +232,7 @@ impl FullRecord {
                 );
             },
             // Nothing needed to be done here!
-            RecordStatus::Submitted | RecordStatus::UnderConsideration => {},
+            RecordStatus::Submitted | RecordStatus::UnderConsideration | RecordStatus::PendingApproval => {},
         }
 
         Ok(())
@@ -190,16 +240,17 @@ impl FullRecord {
 
     pub async fn delete_video(&mut self, connection: &mut PgConnection) -> Result<()> {
         sqlx::query!("UPDATE records SET video = NULL WHERE id = $1", self.id)
-            .execute(connection)
+            .execute(&mut *connection)
             .await?;
 
         self.video = None;
+        self.forget_video_checks(connection).await?;
 
         Ok(())
     }
 
-    pub async fn set_video(&mut self, video: String, connection: &mut PgConnection) -> Result<()> {
-        let video = crate::video::validate(&video)?;
+    pub async fn set_video(&mut self, video: String, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<()> {
+        let video = hosts.validate(&video)?;
 
         if Some(&video) == self.video.as_ref() {
             return Ok(());
@@ -213,10 +264,11 @@ impl FullRecord {
         }
 
         sqlx::query!("UPDATE records SET video = $1::text WHERE id = $2", video, self.id)
-            .execute(connection)
+            .execute(&mut *connection)
             .await?;
 
         self.video = Some(video);
+        self.forget_video_checks(connection).await?;
 
         Ok(())
     }
@@ -244,7 +296,8 @@ impl FullRecord {
     /// If the new player has a record that would stand in conflict with this one, this records
     /// takes precedence and overrides the existing one.
     ///
-    /// If this record is approved, updates the score of the old holder.
+    /// Does not update the score of the old holder, that is up to the caller (which allows
+    /// operations moving many records at once to only recompute scores at the end).
     pub async fn set_player(&mut self, player: DatabasePlayer, connection: &mut PgConnection) -> Result<()> {
         if player.banned && self.status != RecordStatus::Rejected {
             return Err(DemonlistError::PlayerBanned);
@@ -258,8 +311,6 @@ impl FullRecord {
             .execute(&mut *connection)
             .await?;
 
-        self.player.update_score(connection).await?;
-
         self.player = player;
 
         Ok(())
@@ -299,7 +350,9 @@ impl FullRecord {
             // Nothing needed here, a 'rejected' record is globally unique
             (RecordStatus::Rejected, _) => (),
 
-            (RecordStatus::Submitted, RecordStatus::Approved) | (RecordStatus::UnderConsideration, RecordStatus::Approved) => {
+            (RecordStatus::Submitted, RecordStatus::Approved)
+            | (RecordStatus::UnderConsideration, RecordStatus::Approved)
+            | (RecordStatus::PendingApproval, RecordStatus::Approved) => {
                 // Since a rejected record is globally unique, we know no other (player,
                 // demon)-record is 'rejected'. We also know that the submission has at least as
                 // much progress as an 'accepted' (player, demon)-record. We can therefore just
@@ -327,7 +380,8 @@ impl FullRecord {
                 .await?;
             },
 
-            // the other cases just convert back and forth between 'submitted' and 'under consideration', which doesn't change anything
+            // the other cases just convert back and forth between 'submitted', 'under consideration' and 'pending approval', which
+            // doesn't change anything
             _ => (),
         }
 
@@ -346,6 +400,24 @@ impl FullRecord {
         Ok(())
     }
 
+    /// Updates the reason for this record's status
+    ///
+    /// The reason has to exist in the given registry and be applicable to the record's current
+    /// status.
+    pub async fn set_reason(&mut self, reason: Option<String>, reasons: &StatusReasons, connection: &mut PgConnection) -> Result<()> {
+        if let Some(ref key) = reason {
+            reasons.validate(key, self.status)?;
+        }
+
+        sqlx::query!("UPDATE records SET status_reason = $1 WHERE id = $2", reason, self.id)
+            .execute(connection)
+            .await?;
+
+        self.reason = reason;
+
+        Ok(())
+    }
+
     /// Updates this record's progress
     ///
     /// If this record is approved, all submissions with lower progress of the same (player,
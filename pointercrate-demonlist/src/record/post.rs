@@ -1,15 +1,21 @@
 use crate::{
     demon::MinimalDemon,
-    error::{DemonlistError, Result},
+    error::Result,
     player::{claim::PlayerClaim, DatabasePlayer},
-    record::{FullRecord, RecordStatus},
+    record::{
+        approval::ApprovalPolicy,
+        proof::{normalize_proof_url, Proof, ProofKind},
+        rules::SubmissionRules,
+        FullRecord, RecordStatus,
+    },
     submitter::Submitter,
+    video::VideoHosts,
 };
 use derive_more::Display;
 use log::debug;
+use rand::{distributions::Alphanumeric, Rng};
 use serde::Deserialize;
 use sqlx::{PgConnection, Row};
-use url::Url;
 
 #[derive(Deserialize, Debug, Display)]
 #[display(fmt = "{}% on {} by {} [status: {}]", progress, demon, player, status)]
@@ -21,6 +27,10 @@ pub struct Submission {
     video: Option<String>,
     #[serde(default)]
     raw_footage: Option<String>,
+
+    /// Additional videos, e.g. showing specific parts of the run in more detail
+    #[serde(default)]
+    clips: Vec<String>,
     #[serde(default)]
     status: RecordStatus,
 
@@ -38,6 +48,7 @@ pub struct NormalizedSubmission {
 
     video: Option<String>,
     raw_footage: Option<String>,
+    clips: Vec<String>,
     note: Option<String>,
 }
 
@@ -46,6 +57,7 @@ pub struct ValidatedSubmission {
     progress: i16,
     video: Option<String>,
     raw_footage: Option<String>,
+    clips: Vec<String>,
     status: RecordStatus,
     player: DatabasePlayer,
     demon: MinimalDemon,
@@ -61,13 +73,24 @@ impl Submission {
         self.status
     }
 
-    pub async fn normalize(self, connection: &mut PgConnection) -> Result<NormalizedSubmission> {
+    pub async fn normalize(self, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<NormalizedSubmission> {
         // validate video
         let video = match self.video {
-            Some(ref video) => Some(crate::video::validate(video)?),
+            Some(ref video) => Some(hosts.validate(video)?),
+            None => None,
+        };
+
+        let raw_footage = match self.raw_footage {
+            Some(ref raw_footage) => Some(normalize_proof_url(ProofKind::RawFootage, raw_footage, hosts)?),
             None => None,
         };
 
+        let clips = self
+            .clips
+            .iter()
+            .map(|clip| normalize_proof_url(ProofKind::Clip, clip, hosts))
+            .collect::<Result<_>>()?;
+
         // Resolve player and demon name against the database
         let player = DatabasePlayer::by_name_or_create(self.player.as_ref(), connection).await?;
         let demon = MinimalDemon::by_id(self.demon, connection).await?;
@@ -78,7 +101,8 @@ impl Submission {
             demon,
             status: self.status,
             video,
-            raw_footage: self.raw_footage,
+            raw_footage,
+            clips,
             note: self.note,
         })
     }
@@ -89,75 +113,55 @@ impl NormalizedSubmission {
         PlayerClaim::verified_claim_on(self.player.id, connection).await
     }
 
-    pub async fn validate(self, connection: &mut PgConnection) -> Result<ValidatedSubmission> {
-        // Banned player can't have records on the list
-        if self.player.banned {
-            return Err(DemonlistError::PlayerBanned);
-        }
+    pub fn progress(&self) -> i16 {
+        self.progress
+    }
 
-        // Cannot submit records for the legacy list (it is possible to directly add them for list mods)
-        if self.demon.position > crate::config::extended_list_size() && self.status == RecordStatus::Submitted {
-            return Err(DemonlistError::SubmitLegacy);
-        }
+    pub fn player(&self) -> &DatabasePlayer {
+        &self.player
+    }
 
-        // Can only submit 100% records for the extended list (it is possible to directly add them for list
-        // mods)
-        if self.demon.position > crate::config::list_size() && self.progress != 100 && self.status == RecordStatus::Submitted {
-            return Err(DemonlistError::Non100Extended);
-        }
+    pub fn demon(&self) -> &MinimalDemon {
+        &self.demon
+    }
 
-        let requirement = self.demon.requirement(&mut *connection).await?;
+    pub fn status(&self) -> RecordStatus {
+        self.status
+    }
 
-        // Check if the record meets the record requirement for this demon
-        if self.progress > 100 || self.progress < requirement {
-            return Err(DemonlistError::InvalidProgress { requirement });
-        }
+    pub fn video(&self) -> Option<&String> {
+        self.video.as_ref()
+    }
 
-        debug!("Submission is valid, checking for duplicates!");
+    pub fn raw_footage(&self) -> Option<&String> {
+        self.raw_footage.as_ref()
+    }
 
-        // Search for existing records. If a video exists, we also check if a record with
-        // exactly that video exists.
+    pub fn clips(&self) -> &[String] {
+        &self.clips
+    }
 
-        if let Some(ref video) = self.video {
-            if let Some(row) = sqlx::query!(r#"SELECT id, status_::text as "status_!: String" FROM records WHERE video = $1"#, video.to_string())
-                .fetch_optional(&mut *connection) // FIXME(sqlx)
-                .await?
-            {
-                return Err(DemonlistError::SubmissionExists {
-                    existing: row.id,
-                    status: RecordStatus::from_sql(&row.status_),
-                });
-            }
-        }
+    /// Runs the status this submission is to be created with through the given [`ApprovalPolicy`],
+    /// turning approvals that need to be signed off by a second list team member into pending ones
+    pub fn apply_approval_policy(&mut self, policy: &ApprovalPolicy, is_moderator: bool) -> Result<()> {
+        self.status = policy.resolve_submission(&self.demon, self.status, is_moderator)?;
 
-        let existing = sqlx::query!(
-            r#"SELECT id, status_::text as "status_!: String" FROM records WHERE demon = $1 AND player = $2 AND (status_ = 'REJECTED' OR status_ = 
-             'UNDER_CONSIDERATION' OR (status_ = 'APPROVED' AND progress >= $3)) LIMIT 1"#,
-            self.demon.id,
-            self.player.id,
-            self.progress
-        )
-            .fetch_optional(&mut *connection)
-            .await?;
-
-        if let Some(row) = existing {
-            return Err(DemonlistError::SubmissionExists {
-                existing: row.id,
-                status: RecordStatus::from_sql(&row.status_),
-            });
-        }
+        Ok(())
+    }
 
-        match self.raw_footage {
-            Some(ref raw) => {
-                let _ = Url::parse(raw).map_err(|_| DemonlistError::MalformedRawUrl)?;
-            },
-            _ => (),
-        }
+    /// Validates this submission against the given [`SubmissionRules`]
+    pub async fn validate(
+        self, submitter: &Submitter, rules: &SubmissionRules, connection: &mut PgConnection,
+    ) -> Result<ValidatedSubmission> {
+        rules.check(&self, submitter, connection).await?;
+
+        debug!("Submission is valid!");
 
         Ok(ValidatedSubmission {
             progress: self.progress,
             video: self.video,
             raw_footage: self.raw_footage,
+            clips: self.clips,
             status: self.status,
             player: self.player,
             demon: self.demon,
@@ -168,15 +172,18 @@ impl NormalizedSubmission {
 
 impl ValidatedSubmission {
     pub async fn create(self, submitter: Submitter, connection: &mut PgConnection) -> Result<FullRecord> {
+        let status_token: String = rand::thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect();
+
         let id = sqlx::query(
-            "INSERT INTO records (progress, video, status_, player, submitter, demon) VALUES ($1, $2::TEXT, 'SUBMITTED', $3, $4,$5) \
-             RETURNING id",
+            "INSERT INTO records (progress, video, status_, player, submitter, demon, status_token_hash) VALUES ($1, $2::TEXT, \
+             'SUBMITTED', $3, $4, $5, encode(sha256(convert_to($6, 'UTF8')), 'hex')) RETURNING id",
         )
         .bind(self.progress)
         .bind(&self.video)
         .bind(self.player.id)
         .bind(submitter.id)
         .bind(self.demon.id)
+        .bind(&status_token)
         .fetch_one(&mut *connection)
         .await?
         .get("id");
@@ -186,9 +193,13 @@ impl ValidatedSubmission {
             progress: self.progress,
             video: self.video,
             status: RecordStatus::Submitted,
+            reason: None,
             player: self.player,
             demon: self.demon,
             submitter: Some(submitter),
+            proofs: Vec::new(),
+            video_unreachable_since: None,
+            status_token: Some(status_token),
         };
 
         // Dealing with different status and upholding their invariant is complicated, we should not
@@ -205,14 +216,17 @@ impl ValidatedSubmission {
             }
         }
 
+        // Submitter provided proof is only visible to the list team until someone decides otherwise
         if let Some(raw_footage) = self.raw_footage {
-            sqlx::query!(
-                "INSERT INTO record_notes (record, content) VALUES ($1, $2)",
-                record.id,
-                format!("Raw footage: {}", raw_footage)
-            )
-            .execute(&mut *connection)
-            .await?;
+            let proof = Proof::insert(record.id, ProofKind::RawFootage, raw_footage, false, &mut *connection).await?;
+
+            record.proofs.push(proof);
+        }
+
+        for clip in self.clips {
+            let proof = Proof::insert(record.id, ProofKind::Clip, clip, false, &mut *connection).await?;
+
+            record.proofs.push(proof);
         }
 
         if self.status != RecordStatus::Submitted {
@@ -229,7 +243,8 @@ mod tests {
         demon::MinimalDemon,
         error::DemonlistError,
         player::DatabasePlayer,
-        record::{post::NormalizedSubmission, RecordStatus},
+        record::{post::NormalizedSubmission, rules::SubmissionRules, RecordStatus},
+        submitter::Submitter,
     };
     use pointercrate_core::pool::PointercratePool;
     use sqlx::{Postgres, Transaction};
@@ -259,9 +274,10 @@ mod tests {
             status: RecordStatus::Submitted,
             video: None,
             raw_footage: None,
+            clips: Vec::new(),
             note: None,
         }
-        .validate(&mut conn)
+        .validate(&Submitter { id: 1, banned: false }, &SubmissionRules::default(), &mut conn)
         .await;
 
         assert!(result.is_err());
@@ -0,0 +1,12 @@
+use crate::{error::Result, record::proof::Proof};
+use sqlx::PgConnection;
+
+impl Proof {
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        sqlx::query!("DELETE FROM record_proofs WHERE id = $1", self.id)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    error::{DemonlistError, Result},
+    record::proof::{Proof, ProofKind},
+};
+use sqlx::{Error, PgConnection};
+
+impl Proof {
+    pub async fn by_id(record_id: i32, proof_id: i32, connection: &mut PgConnection) -> Result<Proof> {
+        let row = sqlx::query!(
+            r#"SELECT id, record, kind::text AS "kind!", url, is_public FROM record_proofs WHERE id = $1 AND record = $2"#,
+            proof_id,
+            record_id
+        )
+        .fetch_one(connection)
+        .await;
+
+        match row {
+            Err(Error::RowNotFound) => Err(DemonlistError::ProofNotFound { proof_id, record_id }),
+            Err(err) => Err(err.into()),
+            Ok(row) => Ok(Proof {
+                id: row.id,
+                record: row.record,
+                kind: ProofKind::from_sql(&row.kind),
+                url: row.url,
+                is_public: row.is_public,
+            }),
+        }
+    }
+}
+
+/// Gets all proof attached to the given record, in the order it was added
+pub async fn proofs_on(record_id: i32, public_only: bool, connection: &mut PgConnection) -> Result<Vec<Proof>> {
+    let rows = sqlx::query!(
+        r#"SELECT id, record, kind::text AS "kind!", url, is_public FROM record_proofs WHERE record = $1 AND (NOT $2 OR is_public) ORDER BY id"#,
+        record_id,
+        public_only
+    )
+    .fetch_all(connection)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| Proof {
+            id: row.id,
+            record: row.record,
+            kind: ProofKind::from_sql(&row.kind),
+            url: row.url,
+            is_public: row.is_public,
+        })
+        .collect())
+}
@@ -0,0 +1,76 @@
+//! Module containing proof attached to records in addition to their main video
+//!
+//! Each record has at most one main video (the one shown on the list). Everything else the list
+//! team might need to verify a record (raw footage, clips of specific parts of the run, ...) is
+//! attached to the record as a [`Proof`]. Proof is only visible to the list team unless it is
+//! explicitly made public.
+
+mod delete;
+mod get;
+mod patch;
+mod post;
+
+pub(crate) use self::post::normalize_proof_url;
+pub use self::{get::proofs_on, patch::PatchProof, post::NewProof};
+use derive_more::Display;
+use pointercrate_core::etag::Taggable;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Hash, Display)]
+pub enum ProofKind {
+    /// The unedited and untrimmed recording of the completion, usually uploaded to some file
+    /// sharing service instead of a video host
+    #[display(fmt = "raw footage")]
+    RawFootage,
+
+    /// An additional video, e.g. showing a specific part of the run in more detail
+    #[display(fmt = "clip")]
+    Clip,
+}
+
+impl ProofKind {
+    fn to_sql(self) -> String {
+        match self {
+            ProofKind::RawFootage => "RAW_FOOTAGE",
+            ProofKind::Clip => "CLIP",
+        }
+        .to_owned()
+    }
+
+    fn from_sql(sql: &str) -> Self {
+        match sql {
+            "RAW_FOOTAGE" => ProofKind::RawFootage,
+            "CLIP" => ProofKind::Clip,
+            _ => panic!("invalid proof kind: {}", sql),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, PartialEq, Eq)]
+#[display(fmt = "{} {} (ID: {})", kind, url, id)]
+pub struct Proof {
+    pub id: i32,
+
+    #[serde(skip)]
+    pub record: i32,
+
+    pub kind: ProofKind,
+
+    pub url: String,
+
+    /// Whether this proof is visible to everyone, or only to the list team
+    pub is_public: bool,
+}
+
+// The record is not part of the serialized representation, so it must not influence the ETag either
+impl Hash for Proof {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.kind.hash(state);
+        self.url.hash(state);
+        self.is_public.hash(state);
+    }
+}
+
+impl Taggable for Proof {}
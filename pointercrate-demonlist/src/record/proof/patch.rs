@@ -0,0 +1,24 @@
+use crate::{error::Result, record::proof::Proof};
+use pointercrate_core::util::non_nullable;
+use serde::Deserialize;
+use sqlx::PgConnection;
+
+#[derive(Debug, Deserialize)]
+pub struct PatchProof {
+    #[serde(default, deserialize_with = "non_nullable")]
+    pub is_public: Option<bool>,
+}
+
+impl Proof {
+    pub async fn apply_patch(mut self, patch: PatchProof, connection: &mut PgConnection) -> Result<Proof> {
+        if let Some(is_public) = patch.is_public {
+            sqlx::query!("UPDATE record_proofs SET is_public = $1 WHERE id = $2", is_public, self.id)
+                .execute(connection)
+                .await?;
+
+            self.is_public = is_public;
+        }
+
+        Ok(self)
+    }
+}
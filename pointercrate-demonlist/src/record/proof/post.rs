@@ -0,0 +1,67 @@
+use crate::{
+    error::{DemonlistError, Result},
+    record::{
+        proof::{Proof, ProofKind},
+        FullRecord,
+    },
+    video::VideoHosts,
+};
+use serde::Deserialize;
+use sqlx::PgConnection;
+use url::Url;
+
+#[derive(Deserialize, Debug)]
+pub struct NewProof {
+    kind: ProofKind,
+    url: String,
+
+    #[serde(default)]
+    is_public: bool,
+}
+
+/// Validates a link to proof of the given kind, returning it in canonical form
+///
+/// Clips need to be uploaded to one of the supported video hosts. Raw footage is usually uploaded to
+/// file sharing services we know nothing about, so any web URL is accepted for it (links to
+/// supported video hosts are still normalized).
+pub(crate) fn normalize_proof_url(kind: ProofKind, url: &str, hosts: &VideoHosts) -> Result<String> {
+    match kind {
+        ProofKind::Clip => hosts.validate(url),
+        ProofKind::RawFootage if hosts.host_of(url).is_some() => hosts.validate(url),
+        ProofKind::RawFootage => match Url::parse(url) {
+            Ok(parsed) if ["http", "https"].contains(&parsed.scheme()) => Ok(parsed.to_string()),
+            _ => Err(DemonlistError::MalformedRawUrl),
+        },
+    }
+}
+
+impl Proof {
+    /// Attaches new proof to the given record
+    pub async fn create_on(record: &FullRecord, new_proof: NewProof, hosts: &VideoHosts, connection: &mut PgConnection) -> Result<Proof> {
+        let url = normalize_proof_url(new_proof.kind, &new_proof.url, hosts)?;
+
+        Proof::insert(record.id, new_proof.kind, url, new_proof.is_public, connection).await
+    }
+
+    /// Inserts an already validated proof
+    pub(crate) async fn insert(record: i32, kind: ProofKind, url: String, is_public: bool, connection: &mut PgConnection) -> Result<Proof> {
+        let id = sqlx::query!(
+            "INSERT INTO record_proofs (record, kind, url, is_public) VALUES ($1, CAST($2::TEXT AS record_proof_kind), $3, $4) RETURNING id",
+            record,
+            kind.to_sql(),
+            url,
+            is_public
+        )
+        .fetch_one(connection)
+        .await?
+        .id;
+
+        Ok(Proof {
+            id,
+            record,
+            kind,
+            url,
+            is_public,
+        })
+    }
+}
@@ -0,0 +1,131 @@
+//! Module containing the reasons the list team can give for rejecting a record or putting it under
+//! consideration
+//!
+//! Only the key of a reason is stored with a record, its description is looked up in the
+//! [`StatusReasons`] registry configured at setup. This way, deployments can reword reasons
+//! (or add their own) without having to touch every record they were given to.
+
+use crate::{
+    error::{DemonlistError, Result},
+    record::RecordStatus,
+};
+use serde::{Deserialize, Serialize};
+
+/// A reason for a record having a specific status
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct StatusReason {
+    /// Short, unique identifier of this reason, which is what gets stored with records
+    pub key: String,
+
+    /// Human readable explanation of this reason, as displayed to submitters
+    pub description: String,
+
+    /// The statuses of records this reason can be given to
+    pub statuses: Vec<RecordStatus>,
+}
+
+impl StatusReason {
+    pub fn new(key: &str, description: &str, statuses: &[RecordStatus]) -> Self {
+        StatusReason {
+            key: key.to_string(),
+            description: description.to_string(),
+            statuses: statuses.to_vec(),
+        }
+    }
+
+    /// A reason that can be given for rejecting a record
+    pub fn rejection(key: &str, description: &str) -> Self {
+        StatusReason::new(key, description, &[RecordStatus::Rejected])
+    }
+
+    /// A reason that can be given for putting a record under consideration
+    pub fn consideration(key: &str, description: &str) -> Self {
+        StatusReason::new(key, description, &[RecordStatus::UnderConsideration])
+    }
+}
+
+/// The set of reasons the list team can choose from when rejecting records or putting them under
+/// consideration
+///
+/// A reason given alongside a status change has to be one of these, and has to apply to the new
+/// status. Submitters see the reason's description when looking up their submission's status.
+#[derive(Debug, Clone)]
+pub struct StatusReasons {
+    reasons: Vec<StatusReason>,
+}
+
+impl Default for StatusReasons {
+    fn default() -> Self {
+        StatusReasons::empty()
+            .with_reason(StatusReason::rejection(
+                "insufficient_proof",
+                "The provided proof is not sufficient to verify this record",
+            ))
+            .with_reason(StatusReason::rejection("hacked", "This record was found to be illegitimate"))
+            .with_reason(StatusReason::rejection(
+                "wrong_level",
+                "The video shows a different level (or version of the level) than the one submitted for",
+            ))
+            .with_reason(StatusReason::rejection(
+                "video_unavailable",
+                "The video of this record cannot be accessed",
+            ))
+            .with_reason(StatusReason::rejection("duplicate", "This record was already submitted before"))
+            .with_reason(StatusReason::consideration(
+                "needs_raw_footage",
+                "Raw footage is needed before a decision can be made on this record",
+            ))
+            .with_reason(StatusReason::consideration(
+                "under_investigation",
+                "The list team is looking into this record in more detail",
+            ))
+    }
+}
+
+impl StatusReasons {
+    /// A registry without any reasons, meaning records cannot be given reasons at all
+    pub fn empty() -> Self {
+        StatusReasons { reasons: Vec::new() }
+    }
+
+    /// Adds the given reason, replacing any existing reason with the same key
+    pub fn with_reason(mut self, reason: StatusReason) -> Self {
+        self.reasons.retain(|existing| existing.key != reason.key);
+        self.reasons.push(reason);
+        self
+    }
+
+    /// Removes the reason with the given key from this registry
+    ///
+    /// Records that were already given this reason keep it, but it can no longer be given to new
+    /// ones.
+    pub fn without_reason(mut self, key: &str) -> Self {
+        self.reasons.retain(|reason| reason.key != key);
+        self
+    }
+
+    pub fn get(&self, key: &str) -> Option<&StatusReason> {
+        self.reasons.iter().find(|reason| reason.key == key)
+    }
+
+    pub fn all(&self) -> &[StatusReason] {
+        &self.reasons
+    }
+
+    /// Checks that the reason with the given key exists and can be given to records of the given
+    /// status
+    pub fn validate(&self, key: &str, status: RecordStatus) -> Result<&StatusReason> {
+        let reason = self
+            .get(key)
+            .ok_or_else(|| DemonlistError::UnknownStatusReason { key: key.to_string() })?;
+
+        if !reason.statuses.contains(&status) {
+            return Err(DemonlistError::StatusReasonNotApplicable {
+                key: key.to_string(),
+                status,
+            });
+        }
+
+        Ok(reason)
+    }
+}
@@ -0,0 +1,146 @@
+//! Module containing the review queue, which stops list team members from reviewing the same
+//! submissions at the same time
+//!
+//! Before reviewing a record, a team member claims it. Claims expire after [`CLAIM_DURATION`]
+//! unless renewed, so that records do not stay locked forever if someone forgets about them, and
+//! they are released automatically (by a database trigger) once the record's status changes.
+
+use crate::{
+    error::{DemonlistError, Result},
+    record::{FullRecord, RecordStatus},
+};
+use chrono::{Duration, NaiveDateTime};
+use derive_more::Display;
+use pointercrate_core::etag::Taggable;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, PgConnection};
+
+/// How long a claim on a record stays valid unless it is renewed
+pub const CLAIM_DURATION: Duration = Duration::minutes(30);
+
+/// A list team member's claim on a record they are reviewing
+#[derive(Debug, Serialize, Deserialize, Hash, Display, PartialEq, Eq)]
+#[display(fmt = "claim of {} on record {}", reviewer_name, record)]
+pub struct ReviewClaim {
+    pub record: i32,
+
+    /// The id of the user reviewing the record
+    pub reviewer: i32,
+
+    /// The name of the user reviewing the record
+    pub reviewer_name: String,
+
+    pub claimed_at: NaiveDateTime,
+
+    /// The (UTC) time at which this claim expires, unless renewed before
+    pub expires_at: NaiveDateTime,
+}
+
+impl Taggable for ReviewClaim {}
+
+impl ReviewClaim {
+    /// Gets the unexpired claim on the given record
+    pub async fn by_record(record_id: i32, connection: &mut PgConnection) -> Result<ReviewClaim> {
+        sqlx::query_as!(
+            ReviewClaim,
+            r#"SELECT record, reviewer, members.name AS "reviewer_name: String", claimed_at, expires_at FROM review_claims
+             INNER JOIN members ON members.member_id = reviewer WHERE record = $1 AND expires_at > (NOW() AT TIME ZONE 'utc')"#,
+            record_id
+        )
+        .fetch_one(connection)
+        .await
+        .map_err(|err| match err {
+            Error::RowNotFound => DemonlistError::ReviewClaimNotFound { record_id },
+            _ => err.into(),
+        })
+    }
+
+    /// Gets all unexpired claims, oldest first
+    pub async fn all(connection: &mut PgConnection) -> Result<Vec<ReviewClaim>> {
+        Ok(sqlx::query_as!(
+            ReviewClaim,
+            r#"SELECT record, reviewer, members.name AS "reviewer_name: String", claimed_at, expires_at FROM review_claims
+             INNER JOIN members ON members.member_id = reviewer WHERE expires_at > (NOW() AT TIME ZONE 'utc') ORDER BY claimed_at"#
+        )
+        .fetch_all(connection)
+        .await?)
+    }
+
+    /// Claims the given record for review by the given user
+    ///
+    /// If the user already has a claim on the record, it is renewed. Fails if someone else holds an
+    /// unexpired claim on the record.
+    pub async fn claim(record: &FullRecord, reviewer: i32, connection: &mut PgConnection) -> Result<ReviewClaim> {
+        if !matches!(
+            record.status,
+            RecordStatus::Submitted | RecordStatus::UnderConsideration | RecordStatus::PendingApproval
+        ) {
+            return Err(DemonlistError::RecordNotReviewable);
+        }
+
+        let claimed = sqlx::query!(
+            "INSERT INTO review_claims (record, reviewer, expires_at) VALUES ($1, $2, (NOW() AT TIME ZONE 'utc') + make_interval(secs => \
+             $3)) ON CONFLICT (record) DO UPDATE SET reviewer = EXCLUDED.reviewer, claimed_at = CASE WHEN review_claims.reviewer = \
+             EXCLUDED.reviewer THEN review_claims.claimed_at ELSE EXCLUDED.claimed_at END, expires_at = EXCLUDED.expires_at WHERE \
+             review_claims.reviewer = EXCLUDED.reviewer OR review_claims.expires_at <= (NOW() AT TIME ZONE 'utc')",
+            record.id,
+            reviewer,
+            CLAIM_DURATION.num_seconds() as f64
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        let claim = ReviewClaim::by_record(record.id, connection).await?;
+
+        if claimed.rows_affected() == 0 {
+            return Err(DemonlistError::RecordClaimed {
+                reviewer: claim.reviewer_name,
+            });
+        }
+
+        Ok(claim)
+    }
+
+    /// Claims the submission that should be reviewed next for the given user
+    ///
+    /// Submissions on higher placed demons are reviewed first, and among those older submissions
+    /// before newer ones. Records pending approval are part of the queue too, except for those the
+    /// given user gave the first approval to. Must be called inside a transaction, so that two team
+    /// members asking for the next submission at the same time do not get the same one.
+    pub async fn claim_next(reviewer: i32, list: Option<i32>, connection: &mut PgConnection) -> Result<ReviewClaim> {
+        let next = sqlx::query!(
+            "SELECT records.id FROM records INNER JOIN demons ON records.demon = demons.id WHERE records.status_ IN ('SUBMITTED', \
+             'PENDING_APPROVAL') AND (demons.list = $1 OR $1 IS NULL) AND NOT EXISTS(SELECT 1 FROM review_claims WHERE record = \
+             records.id AND expires_at > (NOW() AT TIME ZONE 'utc')) AND NOT EXISTS(SELECT 1 FROM pending_approvals WHERE record = \
+             records.id AND approver = $2) ORDER BY demons.position, records.id LIMIT 1 FOR UPDATE OF records SKIP LOCKED",
+            list,
+            reviewer
+        )
+        .fetch_optional(&mut *connection)
+        .await?
+        .ok_or(DemonlistError::ReviewQueueEmpty)?;
+
+        let record = FullRecord::by_id(next.id, connection).await?;
+
+        ReviewClaim::claim(&record, reviewer, connection).await
+    }
+
+    /// Makes sure that nobody but the given user holds an unexpired claim on the given record
+    pub async fn require_unclaimed_or_held_by(record_id: i32, user_id: i32, connection: &mut PgConnection) -> Result<()> {
+        match ReviewClaim::by_record(record_id, connection).await {
+            Ok(claim) if claim.reviewer != user_id => Err(DemonlistError::RecordClaimed {
+                reviewer: claim.reviewer_name,
+            }),
+            Ok(_) | Err(DemonlistError::ReviewClaimNotFound { .. }) => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub async fn release(self, connection: &mut PgConnection) -> Result<()> {
+        sqlx::query!("DELETE FROM review_claims WHERE record = $1", self.record)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,295 @@
+//! Module containing the rules record submissions are checked against
+//!
+//! Every submission is validated against a set of [`SubmissionRule`]s before it is added to the
+//! database. The [default](SubmissionRules::default) rules are the ones pointercrate has always
+//! enforced, but deployments can register additional rules (for example requiring raw footage for
+//! the top 10 demons), optionally restricting them to certain positions, progress values or
+//! submitters, via the combinators of [`SubmissionRuleExt`].
+
+use crate::{
+    error::{DemonlistError, Result},
+    list::section::ListSection,
+    record::{post::NormalizedSubmission, RecordStatus},
+    submitter::Submitter,
+};
+use log::debug;
+use sqlx::PgConnection;
+use std::ops::RangeInclusive;
+
+#[async_trait::async_trait]
+pub trait SubmissionRule: Send + Sync {
+    /// A short, human readable name of this rule, used for logging
+    fn name(&self) -> String;
+
+    /// Checks the given submission, made by the given submitter, against this rule, returning the
+    /// error to reject it with if it violates the rule
+    async fn check(&self, submission: &NormalizedSubmission, submitter: &Submitter, connection: &mut PgConnection) -> Result<()>;
+}
+
+/// The set of rules submissions are checked against, in the order in which they are checked
+///
+/// The first rule a submission violates decides the error it is rejected with. List team members
+/// adding records directly are held to the same rules.
+pub struct SubmissionRules {
+    rules: Vec<Box<dyn SubmissionRule>>,
+}
+
+impl Default for SubmissionRules {
+    fn default() -> Self {
+        SubmissionRules::empty()
+            .with_rule(PlayerNotBanned)
+            .with_rule(SectionPolicy)
+            .with_rule(MeetsRequirement)
+            .with_rule(UniqueVideo)
+            .with_rule(NoExistingRecord)
+    }
+}
+
+impl SubmissionRules {
+    /// A rule set without any rules. Use this if you do not want to enforce all of the default
+    /// rules (you probably want to add most of them back though!)
+    pub fn empty() -> Self {
+        SubmissionRules { rules: Vec::new() }
+    }
+
+    pub fn with_rule(mut self, rule: impl SubmissionRule + 'static) -> Self {
+        self.rules.push(Box::new(rule));
+        self
+    }
+
+    /// Checks the given submission against all rules, returning the error of the first one it
+    /// violates
+    pub async fn check(&self, submission: &NormalizedSubmission, submitter: &Submitter, connection: &mut PgConnection) -> Result<()> {
+        for rule in &self.rules {
+            debug!("Checking submission against rule '{}'", rule.name());
+
+            rule.check(submission, submitter, &mut *connection).await?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Extension methods for restricting when a [`SubmissionRule`] applies
+pub trait SubmissionRuleExt: SubmissionRule + Sized {
+    /// Only applies this rule to submissions for demons at the given positions
+    fn for_positions(self, positions: RangeInclusive<i16>) -> Restricted<Self> {
+        Restricted {
+            rule: self,
+            condition: Condition::Positions(positions),
+        }
+    }
+
+    /// Only applies this rule to submissions whose progress lies in the given range
+    fn for_progress(self, progress: RangeInclusive<i16>) -> Restricted<Self> {
+        Restricted {
+            rule: self,
+            condition: Condition::Progress(progress),
+        }
+    }
+
+    /// Only applies this rule to submissions by submitters who have had at most the given amount of
+    /// their submissions approved
+    fn for_new_submitters(self, max_approved: i64) -> Restricted<Self> {
+        Restricted {
+            rule: self,
+            condition: Condition::NewSubmitter { max_approved },
+        }
+    }
+}
+
+impl<R: SubmissionRule + Sized> SubmissionRuleExt for R {}
+
+#[derive(Debug)]
+enum Condition {
+    Positions(RangeInclusive<i16>),
+    Progress(RangeInclusive<i16>),
+    NewSubmitter { max_approved: i64 },
+}
+
+impl Condition {
+    async fn holds(&self, submission: &NormalizedSubmission, submitter: &Submitter, connection: &mut PgConnection) -> Result<bool> {
+        Ok(match self {
+            Condition::Positions(positions) => positions.contains(&submission.demon().position),
+            Condition::Progress(progress) => progress.contains(&submission.progress()),
+            Condition::NewSubmitter { max_approved } => {
+                let approved = sqlx::query!(
+                    r#"SELECT COUNT(*) AS "count!" FROM records WHERE submitter = $1 AND status_ = 'APPROVED'"#,
+                    submitter.id
+                )
+                .fetch_one(connection)
+                .await?
+                .count;
+
+                approved <= *max_approved
+            },
+        })
+    }
+}
+
+/// A [`SubmissionRule`] that is only checked if some condition holds, see [`SubmissionRuleExt`]
+pub struct Restricted<R> {
+    rule: R,
+    condition: Condition,
+}
+
+#[async_trait::async_trait]
+impl<R: SubmissionRule> SubmissionRule for Restricted<R> {
+    fn name(&self) -> String {
+        format!("{} (if {:?})", self.rule.name(), self.condition)
+    }
+
+    async fn check(&self, submission: &NormalizedSubmission, submitter: &Submitter, connection: &mut PgConnection) -> Result<()> {
+        if self.condition.holds(submission, submitter, &mut *connection).await? {
+            self.rule.check(submission, submitter, connection).await
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// Banned players can't have records on the list
+pub struct PlayerNotBanned;
+
+#[async_trait::async_trait]
+impl SubmissionRule for PlayerNotBanned {
+    fn name(&self) -> String {
+        "player not banned".to_string()
+    }
+
+    async fn check(&self, submission: &NormalizedSubmission, _: &Submitter, _: &mut PgConnection) -> Result<()> {
+        if submission.player().banned {
+            return Err(DemonlistError::PlayerBanned);
+        }
+
+        Ok(())
+    }
+}
+
+/// Submissions are subject to the policies of the [`ListSection`] the demon is in (it is possible
+/// to directly add records regardless of them for list mods)
+pub struct SectionPolicy;
+
+#[async_trait::async_trait]
+impl SubmissionRule for SectionPolicy {
+    fn name(&self) -> String {
+        "section policy".to_string()
+    }
+
+    async fn check(&self, submission: &NormalizedSubmission, _: &Submitter, connection: &mut PgConnection) -> Result<()> {
+        if submission.status() != RecordStatus::Submitted {
+            return Ok(());
+        }
+
+        match ListSection::of_demon(submission.demon().id, connection).await? {
+            Some(section) if !section.submissions_open => Err(DemonlistError::SubmissionsClosed { section: section.name }),
+            Some(section) if !section.allows_non_100 && submission.progress() != 100 => {
+                Err(DemonlistError::Non100NotAccepted { section: section.name })
+            },
+            Some(_) => Ok(()),
+            None => Err(DemonlistError::SubmissionsClosed {
+                section: "unsectioned part of the list".to_string(),
+            }),
+        }
+    }
+}
+
+/// The record has to meet the record requirement of its demon
+pub struct MeetsRequirement;
+
+#[async_trait::async_trait]
+impl SubmissionRule for MeetsRequirement {
+    fn name(&self) -> String {
+        "meets requirement".to_string()
+    }
+
+    async fn check(&self, submission: &NormalizedSubmission, _: &Submitter, connection: &mut PgConnection) -> Result<()> {
+        let requirement = submission.demon().requirement(connection).await?;
+
+        if submission.progress() > 100 || submission.progress() < requirement {
+            return Err(DemonlistError::InvalidProgress { requirement });
+        }
+
+        Ok(())
+    }
+}
+
+/// No other record may use the same video
+pub struct UniqueVideo;
+
+#[async_trait::async_trait]
+impl SubmissionRule for UniqueVideo {
+    fn name(&self) -> String {
+        "unique video".to_string()
+    }
+
+    async fn check(&self, submission: &NormalizedSubmission, _: &Submitter, connection: &mut PgConnection) -> Result<()> {
+        if let Some(video) = submission.video() {
+            if let Some(row) = sqlx::query!(r#"SELECT id, status_::text as "status_!: String" FROM records WHERE video = $1"#, video.to_string())
+                .fetch_optional(connection) // FIXME(sqlx)
+                .await?
+            {
+                return Err(DemonlistError::SubmissionExists {
+                    existing: row.id,
+                    status: RecordStatus::from_sql(&row.status_),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// There may not be a rejected, under consideration or pending approval record for the same (player,
+/// demon) pair, and no approved one with at least the same progress
+pub struct NoExistingRecord;
+
+#[async_trait::async_trait]
+impl SubmissionRule for NoExistingRecord {
+    fn name(&self) -> String {
+        "no existing record".to_string()
+    }
+
+    async fn check(&self, submission: &NormalizedSubmission, _: &Submitter, connection: &mut PgConnection) -> Result<()> {
+        let existing = sqlx::query!(
+            r#"SELECT id, status_::text as "status_!: String" FROM records WHERE demon = $1 AND player = $2 AND (status_ = 'REJECTED' OR status_ =
+             'UNDER_CONSIDERATION' OR status_ = 'PENDING_APPROVAL' OR (status_ = 'APPROVED' AND progress >= $3)) LIMIT 1"#,
+            submission.demon().id,
+            submission.player().id,
+            submission.progress()
+        )
+            .fetch_optional(connection)
+            .await?;
+
+        if let Some(row) = existing {
+            return Err(DemonlistError::SubmissionExists {
+                existing: row.id,
+                status: RecordStatus::from_sql(&row.status_),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Submissions have to come with raw footage. Not part of the default rules, usually you want to
+/// restrict this to some positions, e.g. `RequireRawFootage.for_positions(1..=10)`
+///
+/// Only applies to submissions with status 'submitted', records directly added by list mods are
+/// exempt.
+pub struct RequireRawFootage;
+
+#[async_trait::async_trait]
+impl SubmissionRule for RequireRawFootage {
+    fn name(&self) -> String {
+        "require raw footage".to_string()
+    }
+
+    async fn check(&self, submission: &NormalizedSubmission, _: &Submitter, _: &mut PgConnection) -> Result<()> {
+        if submission.status() == RecordStatus::Submitted && submission.raw_footage().is_none() {
+            return Err(DemonlistError::RawRequired);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,102 @@
+//! Bookkeeping for the periodic checks of whether record videos can still be reached
+//!
+//! The checks themselves (that is, the HTTP requests to the video hosts) are done by background
+//! jobs outside of this crate. A video that cannot be reached is only ever flagged for the list
+//! team to look at, never removed automatically, since video hosts being temporarily unavailable
+//! (or rate limiting us) is far more common than videos actually disappearing.
+
+use crate::{error::Result, record::FullRecord};
+use log::info;
+use sqlx::PgConnection;
+use std::time::Duration;
+
+/// How many records' videos are queued for a recheck at once
+pub const VIDEO_RECHECK_BATCH_SIZE: i64 = 50;
+
+/// How often a new batch of videos is queued for a recheck
+pub const VIDEO_RECHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+impl FullRecord {
+    /// Notes that this record's video was reachable, clearing a previous "video unreachable" flag
+    pub async fn mark_video_reachable(&mut self, connection: &mut PgConnection) -> Result<()> {
+        sqlx::query!(
+            "INSERT INTO record_video_checks (record) VALUES ($1) ON CONFLICT (record) DO UPDATE SET checked_at = EXCLUDED.checked_at, \
+             unreachable_since = NULL",
+            self.id
+        )
+        .execute(connection)
+        .await?;
+
+        self.video_unreachable_since = None;
+
+        Ok(())
+    }
+
+    /// Flags this record's video as unreachable
+    ///
+    /// The first time a video is found to be unreachable, an internal note with the given reason
+    /// is added to the record, so that the list team sees why it was flagged.
+    pub async fn flag_video_unreachable(&mut self, reason: &str, connection: &mut PgConnection) -> Result<()> {
+        if self.video_unreachable_since.is_none() {
+            info!("Flagging video of record {} as unreachable: {}", self, reason);
+
+            sqlx::query!(
+                "INSERT INTO record_notes (record, content) VALUES ($1, $2)",
+                self.id,
+                format!("Video unreachable: {}", reason)
+            )
+            .execute(&mut *connection)
+            .await?;
+        }
+
+        let unreachable_since = sqlx::query!(
+            "INSERT INTO record_video_checks (record, unreachable_since) VALUES ($1, NOW() AT TIME ZONE 'utc') ON CONFLICT (record) DO \
+             UPDATE SET checked_at = EXCLUDED.checked_at, unreachable_since = COALESCE(record_video_checks.unreachable_since, \
+             EXCLUDED.unreachable_since) RETURNING unreachable_since AS \"unreachable_since!\"",
+            self.id
+        )
+        .fetch_one(connection)
+        .await?
+        .unreachable_since;
+
+        self.video_unreachable_since = Some(unreachable_since);
+
+        Ok(())
+    }
+
+    /// Discards the results of previous checks, to be called whenever this record's video changes
+    pub(crate) async fn forget_video_checks(&mut self, connection: &mut PgConnection) -> Result<()> {
+        sqlx::query!("DELETE FROM record_video_checks WHERE record = $1", self.id)
+            .execute(connection)
+            .await?;
+
+        self.video_unreachable_since = None;
+
+        Ok(())
+    }
+}
+
+/// Picks the approved records whose videos are most overdue for a recheck (at most
+/// [`VIDEO_RECHECK_BATCH_SIZE`]), and marks them as checked so that they are not picked again while
+/// their checks are still pending
+///
+/// A video is due for a recheck if it has never been checked, or was last checked more than 30
+/// days ago.
+pub async fn claim_due_video_checks(connection: &mut PgConnection) -> Result<Vec<i32>> {
+    let records = sqlx::query!(
+        r#"INSERT INTO record_video_checks (record)
+         SELECT records.id FROM records LEFT OUTER JOIN record_video_checks ON records.id = record_video_checks.record
+         WHERE records.status_ = 'APPROVED' AND records.video IS NOT NULL AND (record_video_checks.checked_at IS NULL OR
+         record_video_checks.checked_at < (NOW() AT TIME ZONE 'utc') - INTERVAL '30 days')
+         ORDER BY record_video_checks.checked_at NULLS FIRST, records.id LIMIT $1
+         ON CONFLICT (record) DO UPDATE SET checked_at = EXCLUDED.checked_at RETURNING record"#,
+        VIDEO_RECHECK_BATCH_SIZE
+    )
+    .fetch_all(connection)
+    .await?
+    .into_iter()
+    .map(|row| row.record)
+    .collect();
+
+    Ok(records)
+}
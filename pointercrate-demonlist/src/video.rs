@@ -1,5 +1,15 @@
+//! Module containing the video hosts videos linked on the list (as verifications or as record
+//! proof) can be uploaded to
+//!
+//! Each supported host is described by an implementation of [`VideoHost`], which knows how to
+//! bring links to its videos into a canonical form, and how to derive embed and thumbnail URLs
+//! from them. The set of supported hosts is a [`VideoHosts`] registry configured at setup, so
+//! deployments can support additional hosts (or drop hosts that no longer exist) without patching
+//! this crate.
+
 use crate::error::{DemonlistError, Result};
 use pointercrate_core::error::CoreError;
+use std::sync::Arc;
 use url::Url;
 
 const SCHEMES: [&str; 2] = ["http", "https"];
@@ -15,97 +25,296 @@ const EVERYPLAY_FORMAT: &str = "https://everyplay.com/videos/{video_id}' or'http
 const VIMEO_FORMAT: &str = "https://vimeo.com/{video_id}' or'https://www.vimeo.com/{video_id}";
 const BILIBILI_FORMAT: &str = "'https://www.bilibili.com/video/{video_id}' or'https://bilibili.com/video/{video_id}";
 
-pub fn validate(url: &str) -> Result<String> {
-    let url = Url::parse(url).map_err(|_| DemonlistError::MalformedVideoUrl)?;
+pub trait VideoHost: Send + Sync {
+    /// The name of this video host as displayed to users, e.g. "YouTube"
+    fn name(&self) -> &'static str;
+
+    /// The domains links to videos on this host can have. Links on any other domain are never
+    /// passed to this host.
+    fn domains(&self) -> &'static [&'static str];
 
-    if !SCHEMES.contains(&url.scheme()) {
-        return Err(CoreError::InvalidUrlScheme.into());
+    /// Brings the given link to a video on this host into its canonical form, which is what will be
+    /// stored in the database
+    ///
+    /// Should return [`CoreError::InvalidUrlFormat`] if the link does not point to a video.
+    fn normalize(&self, url: &Url) -> Result<String>;
+
+    /// The URL under which the given video can be embedded into a page via an `iframe`, if this
+    /// host supports embedding
+    ///
+    /// The given URL is always one previously returned by [`VideoHost::normalize`].
+    fn embed_url(&self, _video: &Url) -> Option<String> {
+        None
     }
 
-    if !url.username().is_empty() || url.password().is_some() {
-        return Err(CoreError::UrlAuthenticated.into());
+    /// The URL of a thumbnail image for the given video, if it can be derived from the video's URL
+    ///
+    /// The given URL is always one previously returned by [`VideoHost::normalize`].
+    fn thumbnail_url(&self, _video: &Url) -> Option<String> {
+        None
     }
+}
 
-    if let Some(host) = url.domain() {
-        match host {
-            "www.youtube.com" | "m.youtube.com" | "youtube.com" => {
-                if url.path() == "/watch" {
-                    if let Some(video_id) = url
-                        .query_pairs()
-                        .find_map(|(key, value)| if key == "v" { Some(value) } else { None })
-                    {
-                        return Ok(format!(
-                            "https://www.youtube.com/watch?v={}",
-                            video_id.chars().take(11).collect::<String>()
-                        ));
-                    }
-                }
+/// The set of video hosts videos can be uploaded to
+///
+/// Record videos, clips and the verification videos of demons have to be hosted on one of these,
+/// and are stored in the canonical form given by their host's [`VideoHost::normalize`]. Raw footage
+/// may be hosted elsewhere.
+#[derive(Clone)]
+pub struct VideoHosts {
+    hosts: Vec<Arc<dyn VideoHost>>,
+}
 
-                Err(CoreError::InvalidUrlFormat { expected: YOUTUBE_FORMAT }.into())
-            },
-            "youtu.be" => {
-                if let Some(path_segments) = url.path_segments() {
-                    match &path_segments.collect::<Vec<_>>()[..] {
-                        [video_id] => Ok(format!(
-                            "https://www.youtube.com/watch?v={}",
-                            video_id.chars().take(11).collect::<String>()
-                        )),
-                        _ => Err(CoreError::InvalidUrlFormat { expected: YOUTUBE_FORMAT }.into()),
-                    }
-                } else {
-                    Err(CoreError::InvalidUrlFormat { expected: YOUTUBE_FORMAT }.into())
-                }
-            },
-            "www.twitch.tv" | "twitch.tv" => {
-                if let Some(path_segments) = url.path_segments() {
-                    match &path_segments.collect::<Vec<_>>()[..] {
-                        ["videos", video_id] => Ok(format!("https://www.twitch.tv/videos/{}", video_id)),
-                        [_, "v", video_id] => Ok(format!("https://www.twitch.tv/videos/{}", video_id)),
-                        _ => Err(CoreError::InvalidUrlFormat { expected: TWITCH_FORMAT }.into()),
-                    }
-                } else {
-                    Err(CoreError::InvalidUrlFormat { expected: TWITCH_FORMAT }.into())
-                }
-            },
-            "everyplay.com" | "www.everyplay.com" => {
-                if let Some(path_segments) = url.path_segments() {
-                    match &path_segments.collect::<Vec<_>>()[..] {
-                        ["videos", video_id] => Ok(format!("https://everyplay.com/videos/{}", video_id)),
-                        _ => Err(CoreError::InvalidUrlFormat {
-                            expected: EVERYPLAY_FORMAT,
-                        }
-                        .into()),
-                    }
-                } else {
-                    Err(CoreError::InvalidUrlFormat {
-                        expected: EVERYPLAY_FORMAT,
-                    }
-                    .into())
-                }
-            },
-            "www.bilibili.com" | "bilibili.com" => {
-                if let Some(path_segments) = url.path_segments() {
-                    match &path_segments.collect::<Vec<_>>()[..] {
-                        ["video", video_id] => Ok(format!("https://www.bilibili.com/video/{}", video_id)),
-                        _ => Err(CoreError::InvalidUrlFormat { expected: BILIBILI_FORMAT }.into()),
-                    }
-                } else {
-                    Err(CoreError::InvalidUrlFormat { expected: BILIBILI_FORMAT }.into())
-                }
-            },
-            "vimeo.com" | "www.vimeo.com" => {
-                if let Some(path_segments) = url.path_segments() {
-                    match &path_segments.collect::<Vec<_>>()[..] {
-                        [video_id] => Ok(format!("https://vimeo.com/{}", video_id)),
-                        _ => Err(CoreError::InvalidUrlFormat { expected: VIMEO_FORMAT }.into()),
-                    }
-                } else {
-                    Err(CoreError::InvalidUrlFormat { expected: VIMEO_FORMAT }.into())
-                }
+impl Default for VideoHosts {
+    fn default() -> Self {
+        VideoHosts::empty()
+            .with_host(YouTube)
+            .with_host(Twitch)
+            .with_host(Everyplay)
+            .with_host(Vimeo)
+            .with_host(Bilibili)
+    }
+}
+
+impl VideoHosts {
+    /// A registry without any video hosts, meaning no videos at all will be accepted
+    pub fn empty() -> Self {
+        VideoHosts { hosts: Vec::new() }
+    }
+
+    pub fn with_host(mut self, host: impl VideoHost + 'static) -> Self {
+        self.hosts.push(Arc::new(host));
+        self
+    }
+
+    /// Removes the host with the given [name](VideoHost::name) from this registry
+    ///
+    /// Videos already stored for this host remain untouched, but no new ones will be accepted.
+    pub fn without_host(mut self, name: &str) -> Self {
+        self.hosts.retain(|host| host.name() != name);
+        self
+    }
+
+    /// Validates the given video link, returning it in the canonical form of its host
+    pub fn validate(&self, url: &str) -> Result<String> {
+        let url = Url::parse(url).map_err(|_| DemonlistError::MalformedVideoUrl)?;
+
+        if !SCHEMES.contains(&url.scheme()) {
+            return Err(CoreError::InvalidUrlScheme.into());
+        }
+
+        if !url.username().is_empty() || url.password().is_some() {
+            return Err(CoreError::UrlAuthenticated.into());
+        }
+
+        let domain = url.domain().ok_or(CoreError::UnprocessableEntity)?;
+
+        match self.by_domain(domain) {
+            Some(host) => host.normalize(&url),
+            None => Err(DemonlistError::UnsupportedVideoHost {
+                supported: self.hosts.iter().map(|host| host.name()).collect(),
+            }),
+        }
+    }
+
+    /// The host of the given (already validated) video, if it is still supported
+    pub fn host_of(&self, video: &str) -> Option<&dyn VideoHost> {
+        let url = Url::parse(video).ok()?;
+
+        self.by_domain(url.domain()?)
+    }
+
+    /// The name of the host of the given (already validated) video, for display purposes
+    ///
+    /// Falls back to the video's domain if its host is no longer supported.
+    pub fn name_of(&self, video: &str) -> String {
+        match self.host_of(video) {
+            Some(host) => host.name().to_string(),
+            None => Url::parse(video)
+                .ok()
+                .and_then(|url| url.domain().map(ToString::to_string))
+                .unwrap_or_else(|| video.to_string()),
+        }
+    }
+
+    pub fn embed_url(&self, video: &str) -> Option<String> {
+        self.host_of(video)?.embed_url(&Url::parse(video).ok()?)
+    }
+
+    pub fn thumbnail_url(&self, video: &str) -> Option<String> {
+        self.host_of(video)?.thumbnail_url(&Url::parse(video).ok()?)
+    }
+
+    fn by_domain(&self, domain: &str) -> Option<&dyn VideoHost> {
+        self.hosts
+            .iter()
+            .find(|host| host.domains().contains(&domain))
+            .map(|host| host.as_ref())
+    }
+}
+
+fn path_segments(url: &Url) -> Vec<&str> {
+    url.path_segments().map(|segments| segments.collect()).unwrap_or_default()
+}
+
+fn youtube_video_id(url: &Url) -> Option<String> {
+    url.query_pairs()
+        .find_map(|(key, value)| if key == "v" { Some(value.chars().take(11).collect()) } else { None })
+}
+
+pub struct YouTube;
+
+impl VideoHost for YouTube {
+    fn name(&self) -> &'static str {
+        "YouTube"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["www.youtube.com", "m.youtube.com", "youtube.com", "youtu.be"]
+    }
+
+    fn normalize(&self, url: &Url) -> Result<String> {
+        let video_id = match url.domain() {
+            Some("youtu.be") => match &path_segments(url)[..] {
+                [video_id] => Some(video_id.chars().take(11).collect::<String>()),
+                _ => None,
             },
-            _ => Err(DemonlistError::UnsupportedVideoHost),
+            _ if url.path() == "/watch" => youtube_video_id(url),
+            _ => None,
+        };
+
+        match video_id {
+            Some(video_id) => Ok(format!("https://www.youtube.com/watch?v={}", video_id)),
+            None => Err(CoreError::InvalidUrlFormat { expected: YOUTUBE_FORMAT }.into()),
         }
-    } else {
-        Err(CoreError::UnprocessableEntity.into())
+    }
+
+    fn embed_url(&self, video: &Url) -> Option<String> {
+        Some(format!("https://www.youtube.com/embed/{}", youtube_video_id(video)?))
+    }
+
+    fn thumbnail_url(&self, video: &Url) -> Option<String> {
+        Some(format!("https://i.ytimg.com/vi/{}/mqdefault.jpg", youtube_video_id(video)?))
+    }
+}
+
+pub struct Twitch;
+
+impl VideoHost for Twitch {
+    fn name(&self) -> &'static str {
+        "Twitch"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["www.twitch.tv", "twitch.tv"]
+    }
+
+    fn normalize(&self, url: &Url) -> Result<String> {
+        match &path_segments(url)[..] {
+            ["videos", video_id] | [_, "v", video_id] => Ok(format!("https://www.twitch.tv/videos/{}", video_id)),
+            _ => Err(CoreError::InvalidUrlFormat { expected: TWITCH_FORMAT }.into()),
+        }
+    }
+
+    fn embed_url(&self, video: &Url) -> Option<String> {
+        // per validation always of the form 'https://www.twitch.tv/videos/[video id]'
+        match &path_segments(video)[..] {
+            ["videos", video_id] => Some(format!("https://player.twitch.tv/?video={}&autoplay=false", video_id)),
+            _ => None,
+        }
+    }
+}
+
+pub struct Everyplay;
+
+impl VideoHost for Everyplay {
+    fn name(&self) -> &'static str {
+        "Everyplay"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["everyplay.com", "www.everyplay.com"]
+    }
+
+    fn normalize(&self, url: &Url) -> Result<String> {
+        match &path_segments(url)[..] {
+            ["videos", video_id] => Ok(format!("https://everyplay.com/videos/{}", video_id)),
+            _ => Err(CoreError::InvalidUrlFormat {
+                expected: EVERYPLAY_FORMAT,
+            }
+            .into()),
+        }
+    }
+}
+
+pub struct Vimeo;
+
+impl VideoHost for Vimeo {
+    fn name(&self) -> &'static str {
+        "Vimeo"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["vimeo.com", "www.vimeo.com"]
+    }
+
+    fn normalize(&self, url: &Url) -> Result<String> {
+        match &path_segments(url)[..] {
+            [video_id] => Ok(format!("https://vimeo.com/{}", video_id)),
+            _ => Err(CoreError::InvalidUrlFormat { expected: VIMEO_FORMAT }.into()),
+        }
+    }
+}
+
+pub struct Bilibili;
+
+impl VideoHost for Bilibili {
+    fn name(&self) -> &'static str {
+        "Bilibili"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["www.bilibili.com", "bilibili.com"]
+    }
+
+    fn normalize(&self, url: &Url) -> Result<String> {
+        match &path_segments(url)[..] {
+            ["video", video_id] => Ok(format!("https://www.bilibili.com/video/{}", video_id)),
+            _ => Err(CoreError::InvalidUrlFormat { expected: BILIBILI_FORMAT }.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_youtube_normalization() {
+        let hosts = VideoHosts::default();
+
+        assert_eq!(
+            hosts.validate("https://youtu.be/dQw4w9WgXcQ").unwrap(),
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+        assert_eq!(
+            hosts.validate("https://m.youtube.com/watch?v=dQw4w9WgXcQ&t=32").unwrap(),
+            "https://www.youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+        assert_eq!(
+            hosts.thumbnail_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ").unwrap(),
+            "https://i.ytimg.com/vi/dQw4w9WgXcQ/mqdefault.jpg"
+        );
+    }
+
+    #[test]
+    fn test_removed_host() {
+        let hosts = VideoHosts::default().without_host("Everyplay");
+
+        assert!(matches!(
+            hosts.validate("https://everyplay.com/videos/1234"),
+            Err(DemonlistError::UnsupportedVideoHost { .. })
+        ));
+        assert_eq!(hosts.name_of("https://everyplay.com/videos/1234"), "everyplay.com");
     }
 }
@@ -0,0 +1,16 @@
+use crate::{error::Result, webhook::Webhook};
+use log::info;
+use sqlx::PgConnection;
+
+impl Webhook {
+    /// Deletes this webhook, including its delivery log
+    pub async fn delete(self, connection: &mut PgConnection) -> Result<()> {
+        info!("Deleting {}", self);
+
+        sqlx::query!("DELETE FROM webhooks WHERE id = $1", self.id)
+            .execute(connection)
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{
+    error::{DemonlistError, Result},
+    webhook::Webhook,
+};
+use chrono::{NaiveDateTime, Utc};
+use log::info;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error, PgConnection};
+use std::collections::HashMap;
+
+/// How often a delivery should be attempted before giving up on it
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+
+#[derive(Debug, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub struct DeliveryAttempt {
+    pub attempted_at: NaiveDateTime,
+
+    /// The HTTP status code the receiver responded with. `None` if the request failed before any
+    /// response was received
+    pub status_code: Option<i16>,
+
+    /// Why this attempt failed, if it did
+    pub error: Option<String>,
+}
+
+/// A single notification of a webhook about some activity
+#[derive(Debug, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub struct WebhookDelivery {
+    pub id: i32,
+
+    /// The id of the [`Webhook`] notified by this delivery
+    pub webhook: i32,
+
+    /// The name of the activity this delivery notifies about
+    pub event: String,
+
+    /// The payload sent to the webhook, already rendered in the webhook's format
+    pub body: String,
+
+    pub created_at: NaiveDateTime,
+
+    pub delivered_at: Option<NaiveDateTime>,
+
+    /// All attempts at sending this delivery so far, oldest first
+    pub attempts: Vec<DeliveryAttempt>,
+}
+
+impl Webhook {
+    /// Creates a new delivery of the given (already rendered) body to this webhook. Sending it is up
+    /// to the caller.
+    pub async fn enqueue(&self, event: &str, body: String, connection: &mut PgConnection) -> Result<WebhookDelivery> {
+        let row = sqlx::query!(
+            "INSERT INTO webhook_deliveries (webhook, event, body) VALUES ($1, $2, $3) RETURNING id, created_at",
+            self.id,
+            event,
+            body
+        )
+        .fetch_one(connection)
+        .await?;
+
+        Ok(WebhookDelivery {
+            id: row.id,
+            webhook: self.id,
+            event: event.to_string(),
+            body,
+            created_at: row.created_at,
+            delivered_at: None,
+            attempts: Vec::new(),
+        })
+    }
+}
+
+impl WebhookDelivery {
+    /// Gets the most recent deliveries to the given webhook, most recent first
+    pub async fn of_webhook(webhook: i32, limit: i64, connection: &mut PgConnection) -> Result<Vec<WebhookDelivery>> {
+        let rows = sqlx::query!(
+            "SELECT id, webhook, event, body, created_at, delivered_at FROM webhook_deliveries WHERE webhook = $1 ORDER BY id DESC LIMIT $2",
+            webhook,
+            limit
+        )
+        .fetch_all(&mut *connection)
+        .await?;
+
+        let ids: Vec<i32> = rows.iter().map(|row| row.id).collect();
+
+        let attempt_rows = sqlx::query!(
+            "SELECT delivery, attempted_at, status_code, error FROM webhook_delivery_attempts WHERE delivery = ANY($1) ORDER BY \
+             attempted_at",
+            &ids
+        )
+        .fetch_all(&mut *connection)
+        .await?;
+
+        let mut attempts: HashMap<i32, Vec<DeliveryAttempt>> = HashMap::new();
+
+        for row in attempt_rows {
+            attempts.entry(row.delivery).or_default().push(DeliveryAttempt {
+                attempted_at: row.attempted_at,
+                status_code: row.status_code,
+                error: row.error,
+            });
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|row| WebhookDelivery {
+                id: row.id,
+                webhook: row.webhook,
+                event: row.event,
+                body: row.body,
+                created_at: row.created_at,
+                delivered_at: row.delivered_at,
+                attempts: attempts.remove(&row.id).unwrap_or_default(),
+            })
+            .collect())
+    }
+
+    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<WebhookDelivery> {
+        let row = sqlx::query!(
+            "SELECT id, webhook, event, body, created_at, delivered_at FROM webhook_deliveries WHERE id = $1",
+            id
+        )
+        .fetch_one(&mut *connection)
+        .await
+        .map_err(|err| match err {
+            Error::RowNotFound => DemonlistError::WebhookDeliveryNotFound { id },
+            _ => err.into(),
+        })?;
+
+        let attempts = sqlx::query_as!(
+            DeliveryAttempt,
+            "SELECT attempted_at, status_code, error FROM webhook_delivery_attempts WHERE delivery = $1 ORDER BY attempted_at",
+            id
+        )
+        .fetch_all(&mut *connection)
+        .await?;
+
+        Ok(WebhookDelivery {
+            id: row.id,
+            webhook: row.webhook,
+            event: row.event,
+            body: row.body,
+            created_at: row.created_at,
+            delivered_at: row.delivered_at,
+            attempts,
+        })
+    }
+
+    /// Logs an attempt at sending this delivery. `outcome` is either the HTTP status code the
+    /// receiver responded with, or a description of why no response was received. Any `2xx`
+    /// response marks this delivery as delivered.
+    ///
+    /// Must run inside a transaction!
+    pub async fn record_attempt(
+        mut self, outcome: std::result::Result<u16, String>, connection: &mut PgConnection,
+    ) -> Result<WebhookDelivery> {
+        let now = Utc::now().naive_utc();
+
+        let attempt = match outcome {
+            Ok(status_code) if (200..300).contains(&status_code) => DeliveryAttempt {
+                attempted_at: now,
+                status_code: Some(status_code as i16),
+                error: None,
+            },
+            Ok(status_code) => DeliveryAttempt {
+                attempted_at: now,
+                status_code: Some(status_code as i16),
+                error: Some(format!("Receiver responded with status code {}", status_code)),
+            },
+            Err(error) => DeliveryAttempt {
+                attempted_at: now,
+                status_code: None,
+                error: Some(error),
+            },
+        };
+
+        sqlx::query!(
+            "INSERT INTO webhook_delivery_attempts (delivery, attempted_at, status_code, error) VALUES ($1, $2, $3, $4)",
+            self.id,
+            attempt.attempted_at,
+            attempt.status_code,
+            attempt.error
+        )
+        .execute(&mut *connection)
+        .await?;
+
+        if attempt.error.is_none() {
+            info!("Successfully sent delivery {} to webhook {}", self.id, self.webhook);
+
+            sqlx::query!("UPDATE webhook_deliveries SET delivered_at = $1 WHERE id = $2", now, self.id)
+                .execute(connection)
+                .await?;
+
+            self.delivered_at = Some(now);
+        }
+
+        self.attempts.push(attempt);
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{
+    error::{DemonlistError, Result},
+    webhook::{Webhook, WebhookFormat},
+};
+use futures::StreamExt;
+use sqlx::{Error, PgConnection};
+
+impl Webhook {
+    pub async fn by_id(id: i32, connection: &mut PgConnection) -> Result<Webhook> {
+        let row = sqlx::query!(
+            r#"SELECT id, url, secret, format::text AS "format!", events, active, created_by, created_at FROM webhooks WHERE id = $1"#,
+            id
+        )
+        .fetch_one(connection)
+        .await
+        .map_err(|err| match err {
+            Error::RowNotFound => DemonlistError::WebhookNotFound { id },
+            _ => err.into(),
+        })?;
+
+        Ok(Webhook {
+            id: row.id,
+            url: row.url,
+            secret: row.secret,
+            format: WebhookFormat::from_sql(&row.format)?,
+            events: row.events,
+            active: row.active,
+            created_by: row.created_by,
+            created_at: row.created_at,
+        })
+    }
+
+    /// Gets all registered webhooks, in order of registration
+    pub async fn all(connection: &mut PgConnection) -> Result<Vec<Webhook>> {
+        let mut stream = sqlx::query!(
+            r#"SELECT id, url, secret, format::text AS "format!", events, active, created_by, created_at FROM webhooks ORDER BY id"#
+        )
+        .fetch(connection);
+
+        let mut webhooks = Vec::new();
+
+        while let Some(row) = stream.next().await {
+            let row = row?;
+
+            webhooks.push(Webhook {
+                id: row.id,
+                url: row.url,
+                secret: row.secret,
+                format: WebhookFormat::from_sql(&row.format)?,
+                events: row.events,
+                active: row.active,
+                created_by: row.created_by,
+                created_at: row.created_at,
+            })
+        }
+
+        Ok(webhooks)
+    }
+
+    /// Gets all active webhooks that want to be notified about the activity with the given name
+    pub async fn subscribed_to(event: &str, connection: &mut PgConnection) -> Result<Vec<Webhook>> {
+        let rows = sqlx::query!(
+            r#"SELECT id, url, secret, format::text AS "format!", events, active, created_by, created_at FROM webhooks WHERE active AND $1 = ANY(events) ORDER BY id"#,
+            event
+        )
+        .fetch_all(connection)
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(Webhook {
+                    id: row.id,
+                    url: row.url,
+                    secret: row.secret,
+                    format: WebhookFormat::from_sql(&row.format)?,
+                    events: row.events,
+                    active: row.active,
+                    created_by: row.created_by,
+                    created_at: row.created_at,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,138 @@
+//! Module for outbound webhooks
+//!
+//! List administrators can register any number of webhooks, each of which is notified about a
+//! chosen set of activities (records being submitted or reviewed, demons being added or moved, ...).
+//! Every notification is persisted as a [`WebhookDelivery`] before it is sent. Sending (and retrying
+//! failed deliveries, up to [`MAX_DELIVERY_ATTEMPTS`] times) is done via the background job queue.
+//! Each attempt is logged (see [`WebhookDelivery::record_attempt`]), so that administrators can see
+//! why a receiver did not get notified.
+//!
+//! The bodies of all deliveries are signed using HMAC-SHA256 with the webhook's secret. Rendering
+//! and sending the payloads is left to the API crate.
+
+pub use self::{
+    delivery::{DeliveryAttempt, WebhookDelivery, MAX_DELIVERY_ATTEMPTS},
+    patch::PatchWebhook,
+    post::{PostWebhook, RegisteredWebhook},
+};
+use crate::error::{DemonlistError, Result};
+use chrono::NaiveDateTime;
+use derive_more::Display;
+use pointercrate_core::{error::CoreError, etag::Taggable};
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
+use url::Url;
+
+mod delete;
+mod delivery;
+mod get;
+mod patch;
+mod post;
+
+/// The names of all activities webhooks can subscribe to
+pub const WEBHOOK_EVENTS: &[&str] = &[
+    "record_submitted",
+    "record_approved",
+    "record_rejected",
+    "demon_added",
+    "demon_moved",
+    "player_banned",
+    "player_renamed",
+];
+
+/// How the bodies of deliveries to a webhook are formatted
+#[derive(Debug, Serialize, Deserialize, Eq, PartialEq, Clone, Copy, Hash, Display)]
+pub enum WebhookFormat {
+    /// The activity itself, serialized as JSON
+    #[display(fmt = "json")]
+    Json,
+
+    /// A message suitable for a Discord webhook
+    #[display(fmt = "discord")]
+    Discord,
+}
+
+impl WebhookFormat {
+    fn to_sql(self) -> String {
+        match self {
+            WebhookFormat::Json => "JSON",
+            WebhookFormat::Discord => "DISCORD",
+        }
+        .to_owned()
+    }
+
+    fn from_sql(sql: &str) -> Result<Self> {
+        match sql {
+            "JSON" => Ok(WebhookFormat::Json),
+            "DISCORD" => Ok(WebhookFormat::Discord),
+            _ => Err(CoreError::internal_server_error(format!("invalid webhook format: {}", sql)).into()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Display, PartialEq, Eq)]
+#[display(fmt = "webhook {} to {}", id, url)]
+pub struct Webhook {
+    pub id: i32,
+
+    pub url: String,
+
+    /// The key used to sign the bodies of all deliveries to this webhook
+    ///
+    /// Never serialized, it is only revealed once when the webhook is registered (see
+    /// [`RegisteredWebhook`])
+    #[serde(skip_serializing, default)]
+    pub secret: String,
+
+    pub format: WebhookFormat,
+
+    /// The names of the activities this webhook is notified about. See [`WEBHOOK_EVENTS`]
+    pub events: Vec<String>,
+
+    /// Whether deliveries are created for this webhook. Deliveries still pending when a webhook is
+    /// deactivated are dropped
+    pub active: bool,
+
+    /// The id of the user who registered this webhook
+    pub created_by: Option<i32>,
+
+    pub created_at: NaiveDateTime,
+}
+
+// The secret must not influence the ETag, as the ETag would otherwise leak information about it
+impl Hash for Webhook {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        self.url.hash(state);
+        self.format.hash(state);
+        self.events.hash(state);
+        self.active.hash(state);
+        self.created_by.hash(state);
+        self.created_at.hash(state);
+    }
+}
+
+impl Taggable for Webhook {}
+
+impl Webhook {
+    pub fn validate_url(url: &str) -> Result<()> {
+        let url = Url::parse(url).map_err(|_| DemonlistError::MalformedWebhookUrl)?;
+
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(CoreError::InvalidUrlScheme.into());
+        }
+
+        Ok(())
+    }
+
+    pub fn validate_events(events: &[String]) -> Result<()> {
+        if events.is_empty() {
+            return Err(DemonlistError::NoWebhookEvents);
+        }
+
+        match events.iter().find(|event| !WEBHOOK_EVENTS.contains(&event.as_str())) {
+            Some(event) => Err(DemonlistError::UnknownWebhookEvent { event: event.clone() }),
+            None => Ok(()),
+        }
+    }
+}
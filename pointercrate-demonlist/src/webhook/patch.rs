@@ -0,0 +1,64 @@
+use crate::{
+    error::Result,
+    webhook::{Webhook, WebhookFormat},
+};
+use log::info;
+use pointercrate_core::util::non_nullable;
+use serde::Deserialize;
+use sqlx::PgConnection;
+
+#[derive(Debug, Deserialize, Default)]
+pub struct PatchWebhook {
+    #[serde(default, deserialize_with = "non_nullable")]
+    url: Option<String>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    format: Option<WebhookFormat>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    events: Option<Vec<String>>,
+
+    #[serde(default, deserialize_with = "non_nullable")]
+    active: Option<bool>,
+}
+
+impl Webhook {
+    /// Changes to the format only affect deliveries created afterwards, pending ones are retried
+    /// with the body they were created with.
+    pub async fn apply_patch(mut self, patch: PatchWebhook, connection: &mut PgConnection) -> Result<Self> {
+        info!("Patching {} with {:?}", self, patch);
+
+        if let Some(url) = patch.url {
+            Webhook::validate_url(&url)?;
+
+            self.url = url;
+        }
+
+        if let Some(events) = patch.events {
+            Webhook::validate_events(&events)?;
+
+            self.events = events;
+        }
+
+        if let Some(format) = patch.format {
+            self.format = format;
+        }
+
+        if let Some(active) = patch.active {
+            self.active = active;
+        }
+
+        sqlx::query!(
+            "UPDATE webhooks SET url = $1, format = cast($2::text as webhook_format), events = $3, active = $4 WHERE id = $5",
+            self.url,
+            self.format.to_sql(),
+            &self.events,
+            self.active,
+            self.id
+        )
+        .execute(connection)
+        .await?;
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,118 @@
+use crate::{
+    error::{DemonlistError, Result},
+    webhook::{Webhook, WebhookFormat},
+};
+use log::info;
+use pointercrate_core::etag::Taggable;
+use rand::{distributions::Alphanumeric, Rng};
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use std::hash::{Hash, Hasher};
+
+#[derive(Debug, Deserialize)]
+pub struct PostWebhook {
+    url: String,
+
+    /// The key to sign deliveries with. A random one is generated if none is given
+    #[serde(default)]
+    secret: Option<String>,
+
+    format: WebhookFormat,
+
+    events: Vec<String>,
+}
+
+/// A newly registered webhook, together with its secret
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegisteredWebhook {
+    #[serde(flatten)]
+    pub webhook: Webhook,
+
+    pub secret: String,
+}
+
+// Same ETag as the webhook itself, so that it can be used for subsequent requests
+impl Hash for RegisteredWebhook {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.webhook.hash(state);
+    }
+}
+
+impl Taggable for RegisteredWebhook {}
+
+impl From<Webhook> for RegisteredWebhook {
+    fn from(webhook: Webhook) -> Self {
+        RegisteredWebhook {
+            secret: webhook.secret.clone(),
+            webhook,
+        }
+    }
+}
+
+fn random_secret() -> String {
+    rand::thread_rng().sample_iter(&Alphanumeric).take(32).map(char::from).collect()
+}
+
+impl Webhook {
+    pub async fn create_from(data: PostWebhook, created_by: i32, connection: &mut PgConnection) -> Result<Webhook> {
+        info!("Registering new webhook to {} for {:?}", data.url, data.events);
+
+        Webhook::validate_url(&data.url)?;
+        Webhook::validate_events(&data.events)?;
+
+        let secret = match data.secret {
+            Some(secret) if secret.is_empty() => return Err(DemonlistError::WebhookSecretEmpty),
+            Some(secret) => secret,
+            None => random_secret(),
+        };
+
+        let id = sqlx::query!(
+            "INSERT INTO webhooks (url, secret, format, events, created_by) VALUES ($1, $2, cast($3::text as webhook_format), $4, $5) \
+             RETURNING id",
+            data.url,
+            secret,
+            data.format.to_sql(),
+            &data.events,
+            created_by
+        )
+        .fetch_one(&mut *connection)
+        .await?
+        .id;
+
+        Webhook::by_id(id, connection).await
+    }
+
+    /// Registers a webhook to the given url that is notified about the given activities, unless a
+    /// webhook to that url already exists
+    ///
+    /// Meant for webhooks configured by the deployment rather than by an administrator. Returns the
+    /// newly registered webhook, if any.
+    pub async fn register_if_missing(
+        url: &str, format: WebhookFormat, events: &[&str], connection: &mut PgConnection,
+    ) -> Result<Option<Webhook>> {
+        let events = events.iter().map(|event| event.to_string()).collect::<Vec<_>>();
+
+        Webhook::validate_url(url)?;
+        Webhook::validate_events(&events)?;
+
+        let id = sqlx::query!(
+            "INSERT INTO webhooks (url, secret, format, events) SELECT $1, $2, cast($3::text as webhook_format), $4 WHERE NOT EXISTS \
+             (SELECT 1 FROM webhooks WHERE url = $1) RETURNING id",
+            url,
+            random_secret(),
+            format.to_sql(),
+            &events
+        )
+        .fetch_optional(&mut *connection)
+        .await?;
+
+        match id {
+            Some(row) => {
+                info!("Registered webhook to {} for {:?}", url, events);
+
+                Ok(Some(Webhook::by_id(row.id, connection).await?))
+            },
+            None => Ok(None),
+        }
+    }
+}
@@ -1,19 +1,30 @@
 use maud::html;
+use pointercrate_core::content::ContentPagesConfig;
 use pointercrate_core::error::CoreError;
 use pointercrate_core::pool::PointercratePool;
-use pointercrate_core_api::{error::ErrorResponder, maintenance::MaintenanceFairing};
+use pointercrate_core_api::{announcement::AnnouncementFairing, error::ErrorResponder, maintenance::MaintenanceFairing};
 use pointercrate_core_pages::{
     footer::{Footer, FooterColumn, Link},
     navigation::{NavigationBar, TopLevelNavigationBarItem},
     PageConfiguration,
 };
-use pointercrate_demonlist::LIST_ADMINISTRATOR;
+use pointercrate_demonlist::{
+    error::Result,
+    list::{section::ListSection, List},
+    record::{
+        approval::ApprovalPolicy,
+        rules::{RequireRawFootage, SubmissionRuleExt, SubmissionRules},
+    },
+    video::{VideoHost, VideoHosts},
+    LIST_ADMINISTRATOR, LIST_HELPER,
+};
 use pointercrate_demonlist_pages::account::{
     demons::DemonsTab, list_integration::ListIntegrationTab, players::PlayersPage, records::RecordsPage,
 };
-use pointercrate_user::MODERATOR;
+use pointercrate_user::{ADMINISTRATOR, MODERATOR};
 use pointercrate_user_pages::account::{profile::ProfileTab, users::UsersTab, AccountPageConfig};
 use rocket::{fs::FileServer, response::Redirect, uri};
+use url::Url;
 
 /// A catcher for 404 errors (e.g. when a user tried to navigate to a URL that
 /// does not exist)
@@ -28,6 +39,36 @@ fn catch_404() -> ErrorResponder {
     CoreError::NotFound.into()
 }
 
+/// A video host not supported by pointercrate out of the box
+///
+/// Implementing [`VideoHost`] tells pointercrate how to recognize links to videos on this host, and
+/// how to embed them on demon pages.
+struct Streamable;
+
+impl VideoHost for Streamable {
+    fn name(&self) -> &'static str {
+        "Streamable"
+    }
+
+    fn domains(&self) -> &'static [&'static str] {
+        &["streamable.com", "www.streamable.com"]
+    }
+
+    fn normalize(&self, url: &Url) -> Result<String> {
+        match url.path_segments().map(|segments| segments.collect::<Vec<_>>()).as_deref() {
+            Some([video_id]) if !video_id.is_empty() => Ok(format!("https://streamable.com/{}", video_id)),
+            _ => Err(CoreError::InvalidUrlFormat {
+                expected: "https://streamable.com/{video_id}",
+            }
+            .into()),
+        }
+    }
+
+    fn embed_url(&self, video: &Url) -> Option<String> {
+        Some(format!("https://streamable.com/e{}", video.path()))
+    }
+}
+
 /// We do not have a home page, so have the website root simply redirect to the demonlist
 #[rocket::get("/")]
 fn home() -> Redirect {
@@ -43,12 +84,14 @@ async fn rocket() -> _ {
     // DATABASE_URL environment variable
     let pool = PointercratePool::init().await;
 
+    let page_configuration = page_configuration(&pool).await;
+
     // Set up the HTTP server
     let rocket = rocket::build()
         // Tell it about the connection pool to use (individual handlers can get hold of this pool by declaring an argument of type `&State<PointercratePool>`)
         .manage(pool)
         // Tell pointercrate's core components about navigation bar and footers, so that it knows how to render the website
-        .manage(page_configuration())
+        .manage(page_configuration)
         // Register our 404 catcher
         .register("/", rocket::catchers![catch_404])
         // Register our home page
@@ -87,9 +130,35 @@ async fn rocket() -> _ {
 
     let rocket = rocket.manage(account_page_config);
 
+    // Content pages (such as your guidelines or an FAQ) are Markdown documents stored in the database, which are displayed at
+    // /<slug>/ (e.g. /guidelines/) and can be managed via the /api/v1/pages/ endpoints. Here we specify which permission a user needs
+    // to have to be able to create and edit them.
+    let rocket = rocket.manage(ContentPagesConfig::new(ADMINISTRATOR));
+
     // Changing `false` to `true` here will put your website into "maintenance mode", which will disable all mutating request handlers and always return 503 SERVICE UNAVAILABLE responses for non-GET requests.
     let rocket = rocket.attach(MaintenanceFairing::new(false));
 
+    // Displays announcements (managed by administrators via the /api/v1/announcements/ endpoints) in a banner above the navigation bar of the pages they target.
+    let rocket = rocket.attach(AnnouncementFairing);
+
+    // Set up the rules record submissions are checked against. The default rules are the ones pointercrate.com uses (no banned players,
+    // the record has to meet the demon's requirement, no duplicate submissions, etc.), but you can register your own rules by implementing
+    // the [`SubmissionRule`] trait. Rules can be restricted to specific positions, progress values or to submitters without many approved
+    // records. Here, we additionally require raw footage for all submissions on top 10 demons. If you do not manage any `SubmissionRules`,
+    // the default rules are used.
+    let rocket = rocket.manage(SubmissionRules::default().with_rule(RequireRawFootage.for_positions(1..=10)));
+
+    // Set up which video hosts verifications and records can be uploaded to. The default hosts are the ones pointercrate.com supports,
+    // but you can support additional hosts by implementing the [`VideoHost`] trait, and remove hosts you do not want to accept videos
+    // from anymore (existing videos on removed hosts stay untouched). Here, we drop the long dead Everyplay and add Streamable. If you do
+    // not manage any `VideoHosts`, the default hosts are used.
+    let rocket = rocket.manage(VideoHosts::default().without_host("Everyplay").with_host(Streamable));
+
+    // Set up how many list team members need to approve a record. By default a single approval suffices, but you can require approvals
+    // of records on highly placed demons to be signed off by two distinct list team members (or one list moderator). Here, we do so for
+    // the top 10 demons. If you do not manage an `ApprovalPolicy`, a single approval suffices for all records.
+    let rocket = rocket.manage(ApprovalPolicy::single_reviewer().with_two_reviewers_for(1..=10));
+
     // Register all the endpoints related to the demonlist to our server (this is
     // optional, but without registering the demonlist related endpoint your website
     // will just be User Account Simulator 2024).
@@ -116,7 +185,7 @@ async fn rocket() -> _ {
 /// look-and-feel. It defines the navigation bar and footer layouts (e.g. what
 /// links to include) and various metadata without you needing to worry (much)
 /// about styling and layout.
-fn page_configuration() -> PageConfiguration {
+async fn page_configuration(pool: &PointercratePool) -> PageConfiguration {
     // Define a navigation bar with only two items, a link to the user account page,
     // and a link to your demonlist.
     let nav_bar = NavigationBar::new("/static/images/path/to/your/logo.png")
@@ -136,7 +205,12 @@ fn page_configuration() -> PageConfiguration {
         // Add a drop down to the demonlist item, just like on pointercrate.com
         .with_sub_item("/demonlist/statsviewer/", html! {"Stats Viewer"})
         .with_sub_item("/demonlist/?submitter=true", html! {"Record Submitter"})
-        .with_sub_item("/demonlist/?timemachine=true", html! {"Time Machine"}),
+        .with_sub_item("/demonlist/?timemachine=true", html! {"Time Machine"})
+        // Links to content pages work just like any other link. This assumes you created a content page with slug "guidelines"
+        .with_sub_item("/guidelines/", html! {"Guidelines"})
+        // Items can be restricted to users with specific permissions, e.g. to only show the list team where to manage records.
+        // Restricted footer columns work the same way, see `Footer::with_restricted_column`.
+        .with_restricted_sub_item(LIST_HELPER, "/account/", html! {"Manage Records"}),
         )
         .with_item(TopLevelNavigationBarItem::new(
             "/login/",
@@ -157,20 +231,12 @@ fn page_configuration() -> PageConfiguration {
         br;
         "<your website> and <your demonlist> are in no way affiliated with RobTopGamesAB ® or pointercrate.com"
     }, html! { "No terms of use" })
-    // Add a column with links for various list-related highlights
+    // Add a column with links for various list-related highlights: the top demon of your default list, and the first demon in each
+    // further section of it. The sections are only looked up once here, so restart your website after changing them via the
+    // `/api/v2/lists/<slug>/sections/` endpoints.
     .with_column(FooterColumn::LinkList {
         heading: "Demonlist",
-        links: vec![
-            Link::new("/demonlist/1/", "Current Top Demon"),
-            Link::new(
-                format!("/demonlist/{}/", pointercrate_demonlist::config::list_size() + 1),
-                "Extended List",
-            ),
-            Link::new(
-                format!("/demonlist/{}/", pointercrate_demonlist::config::extended_list_size() + 1),
-                "Legacy List",
-            ),
-        ],
+        links: section_links(pool).await,
     })
     // Some links to social media, for example your twitter
     .with_link("https://twitter.com/stadust1971", "Pointercrate Developer");
@@ -182,3 +248,19 @@ fn page_configuration() -> PageConfiguration {
         // Used for the HTML "keywords" meta tag
         .keywords("Your SEO keywords here")
 }
+
+/// Links to the top demon of the default list and to the first demon of each further section of it
+async fn section_links(pool: &PointercratePool) -> Vec<Link> {
+    let mut connection = pool.connection().await.unwrap();
+    let list = List::default_list(&mut connection).await.unwrap();
+    let sections = ListSection::of_list(list.id, &mut connection).await.unwrap();
+
+    std::iter::once(Link::new("/demonlist/1/", "Current Top Demon"))
+        .chain(
+            sections
+                .into_iter()
+                .filter(|section| section.first_position > 1)
+                .map(|section| Link::new(format!("/demonlist/{}/", section.first_position), section.name)),
+        )
+        .collect()
+}
@@ -43,17 +43,20 @@ pub struct CachedLevelData {
 pub type IntegrationLevel = Level<'static, CachedLevelData, Option<NewgroundsSong<'static>>>;
 
 impl GeometryDashConnector {
-    /// Attempts to pull the Geometry Dash level data for the given [`Demon`] from the database
+    /// Whether the Geometry Dash servers should be re-queried for updated data on the given [`Demon`],
+    /// which is the case if the last time this was done was over 24h ago.
     ///
-    /// If the last time the data for this demon was sought on the Geomeetry Dash servers was over 24h ago,
-    /// re-query them for updated data.
-    pub async fn load_level_for_demon(&self, demon: &Demon) -> Option<IntegrationLevel> {
-        if self.ratelimits.throttle_throttle(demon.base.id).is_ok() {
-            if self.ratelimits.throttle().is_ok() && self.ratelimits.demon_refresh(demon.base.id).is_ok() {
-                tokio::spawn(self.clone().refresh_demon_data(demon.base.name.clone(), demon.base.id));
-            }
-        }
+    /// Counts towards the rate limits on requests to the Geometry Dash servers, so a refresh should
+    /// actually be scheduled (via [`GeometryDashConnector::refresh_demon_data`]) if this returns
+    /// `true`.
+    pub fn refresh_due(&self, demon: &Demon) -> bool {
+        self.ratelimits.throttle_throttle(demon.base.id).is_ok()
+            && self.ratelimits.throttle().is_ok()
+            && self.ratelimits.demon_refresh(demon.base.id).is_ok()
+    }
 
+    /// Attempts to pull the Geometry Dash level data for the given [`Demon`] from the database
+    pub async fn load_level_for_demon(&self, demon: &Demon) -> Option<IntegrationLevel> {
         if let Some(level_id) = demon.level_id {
             let level = self
                 .lookup_level(level_id)
@@ -71,38 +74,40 @@ impl GeometryDashConnector {
         None
     }
 
-    pub async fn refresh_demon_data(self, name: String, demon_id: i32) {
+    /// Looks up the level with the given name on the Geometry Dash servers and stores its data as
+    /// the level data for the given demon. Fails if the Geometry Dash servers cannot be reached
+    pub async fn refresh_demon_data(&self, name: &str, demon_id: i32) -> Result<(), String> {
         // Lookup demon by name
         let request = LevelsRequest::default()
             // Heuristic: list level have a lot of likes
             .request_type(LevelRequestType::MostLiked)
-            .search(&name)
+            .search(name)
             // passing any `LevelRating::Demon` variant here will result in filtering by arbitrary demon difficulty
             .with_rating(LevelRating::Demon(DemonRating::Hard))
             .search_filters(SearchFilters::default().rated());
 
-        let Ok(response) = self.make_request(request.to_url(), request.to_string()).await else {
-            return;
-        };
-        let Ok(demons) = parse_get_gj_levels_response(&response) else {
-            return;
-        };
+        let response = self
+            .make_request(request.to_url(), request.to_string())
+            .await
+            .map_err(|err| format!("Failed to search for level '{}': {}", name, err))?;
+        let demons = parse_get_gj_levels_response(&response).map_err(|err| format!("Malformed level search response: {:?}", err))?;
         let Some(mut hardest) = demons
             .into_iter()
             // Geometry Dash servers only do a substring match, so we have to ensure the name is equal to what we're looking for
             .filter(|demon| demon.name.trim().eq_ignore_ascii_case(name.trim()))
             .max_by(|x, y| x.difficulty.cmp(&y.difficulty))
         else {
-            return;
+            // Nothing we can do if the level does not exist (or was not rated yet)
+            return Ok(());
         };
 
         let request = LevelRequest::new(hardest.level_id);
-        let Ok(response) = self.make_request(request.to_url(), request.to_string()).await else {
-            return;
-        };
-        let Ok(mut level) = parse_download_gj_level_response(&response) else {
-            return;
-        };
+        let response = self
+            .make_request(request.to_url(), request.to_string())
+            .await
+            .map_err(|err| format!("Failed to download level {}: {}", hardest.level_id, err))?;
+        let mut level =
+            parse_download_gj_level_response(&response).map_err(|err| format!("Malformed level download response: {:?}", err))?;
 
         if let Some(newgrounds_song) = &mut hardest.custom_song {
             self.store_newgrounds_song(newgrounds_song).await;
@@ -115,9 +120,12 @@ impl GeometryDashConnector {
         self.store_level(&mut hardest, level.creator, level.custom_song).await;
         self.store_level_data(level.level_id, &mut level.level_data).await;
 
-        let _ = sqlx::query!("UPDATE demons SET level_id = $1 WHERE id = $2", level.level_id as i64, demon_id)
+        sqlx::query!("UPDATE demons SET level_id = $1 WHERE id = $2", level.level_id as i64, demon_id)
             .execute(&self.pool)
-            .await;
+            .await
+            .map_err(|err| format!("Failed to store level id of demon {}: {}", demon_id, err))?;
+
+        Ok(())
     }
 
     async fn make_request(&self, url: String, body: String) -> Result<String, reqwest::Error> {
@@ -4,7 +4,7 @@ use pointercrate_core::{permission::PermissionsManager, pool::PointercratePool};
 use pointercrate_demonlist::demon::FullDemon;
 use pointercrate_demonlist::{
     player::{claim::PlayerClaim, FullPlayer},
-    record::RecordStatus,
+    record::{approval::ApprovalPolicy, rules::SubmissionRules, RecordStatus},
     submitter::Submitter,
     LIST_ADMINISTRATOR, LIST_HELPER, LIST_MODERATOR,
 };
@@ -15,6 +15,18 @@ use sqlx::{pool::PoolConnection, PgConnection, Pool, Postgres};
 use std::{net::IpAddr, str::FromStr};
 
 pub async fn setup_rocket(pool: Pool<Postgres>) -> (TestClient, PoolConnection<Postgres>) {
+    setup_rocket_with_rules(pool, SubmissionRules::default()).await
+}
+
+pub async fn setup_rocket_with_rules(pool: Pool<Postgres>, rules: SubmissionRules) -> (TestClient, PoolConnection<Postgres>) {
+    setup_rocket_with(pool, rules, ApprovalPolicy::default()).await
+}
+
+pub async fn setup_rocket_with_approval_policy(pool: Pool<Postgres>, policy: ApprovalPolicy) -> (TestClient, PoolConnection<Postgres>) {
+    setup_rocket_with(pool, SubmissionRules::default(), policy).await
+}
+
+async fn setup_rocket_with(pool: Pool<Postgres>, rules: SubmissionRules, policy: ApprovalPolicy) -> (TestClient, PoolConnection<Postgres>) {
     let _ = dotenv::dotenv();
 
     let mut connection = pool.acquire().await.unwrap();
@@ -24,9 +36,10 @@ pub async fn setup_rocket(pool: Pool<Postgres>) -> (TestClient, PoolConnection<P
         .implies(LIST_ADMINISTRATOR, LIST_MODERATOR)
         .implies(LIST_MODERATOR, LIST_HELPER);
 
-    let rocket = pointercrate_demonlist_api::setup(rocket::build().manage(PointercratePool::from(pool)))
+    let rocket = pointercrate_demonlist_api::setup(rocket::build().manage(PointercratePool::from(pool)).manage(rules).manage(policy))
         .manage(permissions)
-        .manage(AccountPageConfig::default());
+        .manage(AccountPageConfig::default())
+        .manage(crate::page_configuration());
 
     // generate some data
     Submitter::create_submitter(IpAddr::from_str("127.0.0.1").unwrap(), &mut *connection)
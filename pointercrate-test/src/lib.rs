@@ -1,10 +1,17 @@
 //! Utilities for pointercrate integration tests
 
-use pointercrate_user::AuthenticatedUser;
+use maud::html;
+use pointercrate_core_pages::{
+    footer::{Footer, FooterColumn},
+    navigation::{NavigationBar, TopLevelNavigationBarItem},
+    PageConfiguration,
+};
+use pointercrate_user::{AuthenticatedUser, ADMINISTRATOR};
 
 use rocket::{
     http::{Header, Status},
     local::asynchronous::{Client, LocalRequest, LocalResponse},
+    Orbit, Rocket,
 };
 use serde::{de::DeserializeOwned, Serialize};
 
@@ -13,6 +20,26 @@ use std::{collections::HashMap, fmt::Debug};
 pub mod demonlist;
 pub mod user;
 
+/// A minimal configuration for rendering HTML pages, with a navigation bar item and a footer column
+/// that only administrators get to see
+pub fn page_configuration() -> PageConfiguration {
+    PageConfiguration::new(
+        "Pointercrate",
+        NavigationBar::new("/static/images/pointercrate-icon.svg").with_item(
+            TopLevelNavigationBarItem::new("/demonlist/", html! { "Demonlist" })
+                .with_sub_item("/demonlist/statsviewer/", html! { "Stats Viewer" })
+                .with_restricted_sub_item(ADMINISTRATOR, "/admin/", html! { "Admin Panel" }),
+        ),
+        Footer::new(html! { "© Pointercrate" }, html! {}).with_restricted_column(
+            ADMINISTRATOR,
+            FooterColumn::Arbitrary {
+                heading: "Administration",
+                content: html! { "Only for administrators" },
+            },
+        ),
+    )
+}
+
 pub struct TestClient(Client);
 
 impl TestClient {
@@ -20,6 +47,10 @@ impl TestClient {
         TestClient(client)
     }
 
+    pub fn rocket(&self) -> &Rocket<Orbit> {
+        self.0.rocket()
+    }
+
     pub fn get(&self, url: impl Into<String>) -> TestRequest {
         TestRequest::new(self.0.get(url.into()))
     }
@@ -63,6 +94,17 @@ impl<'c> TestRequest<'c> {
         self
     }
 
+    pub fn json(mut self, body: &impl Serialize) -> Self {
+        self.request = self.request.json(body);
+        self
+    }
+
+    /// Requests an HTML page instead of JSON
+    pub fn accept_html(mut self) -> Self {
+        self.request.inner_mut().replace_header(Header::new("Accept", "text/html"));
+        self
+    }
+
     pub fn authorize_as(self, user: &AuthenticatedUser) -> Self {
         self.header("Authorization", format!("Bearer {}", user.generate_access_token()))
     }
@@ -1,5 +1,6 @@
 use crate::TestClient;
 use pointercrate_core::{
+    content::ContentPagesConfig,
     permission::{Permission, PermissionsManager},
     pool::PointercratePool,
 };
@@ -20,15 +21,22 @@ pub async fn setup_rocket(pool: Pool<Postgres>) -> (TestClient, PoolConnection<P
     let rocket = pointercrate_user_api::setup(rocket::build())
         .manage(PointercratePool::from(pool))
         .manage(permissions)
-        .manage(AccountPageConfig::default());
+        .manage(AccountPageConfig::default())
+        .manage(ContentPagesConfig::new(ADMINISTRATOR))
+        .manage(crate::page_configuration());
 
     (TestClient::new(Client::tracked(rocket).await.unwrap()), connection)
 }
 
 pub async fn system_user_with_perms(perm: Permission, connection: &mut PgConnection) -> AuthenticatedUser {
+    user_with_perms("Patrick", perm, connection).await
+}
+
+/// Registers a user with the given name and permissions, for tests that need multiple users
+pub async fn user_with_perms(name: &str, perm: Permission, connection: &mut PgConnection) -> AuthenticatedUser {
     let user = AuthenticatedUser::register(
         Registration {
-            name: "Patrick".to_string(),
+            name: name.to_string(),
             password: "bad password".to_string(),
         },
         &mut *connection,
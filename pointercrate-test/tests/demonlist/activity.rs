@@ -0,0 +1,129 @@
+use pointercrate_core::etag::Taggable;
+use pointercrate_demonlist::{
+    demon::FullDemon,
+    player::DatabasePlayer,
+    record::{FullRecord, RecordStatus},
+    LIST_MODERATOR,
+};
+use pointercrate_test::{demonlist::add_simple_record, TestClient};
+use pointercrate_user::AuthenticatedUser;
+use rocket::{
+    http::Status,
+    local::asynchronous::LocalResponse,
+    tokio::{io::AsyncReadExt, time::timeout},
+};
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+
+/// Reads from the given event stream until an event with the given name was received completely,
+/// returning everything read
+async fn read_until_event(stream: &mut LocalResponse<'_>, event: &str) -> String {
+    read_until(stream, &format!("event:{}", event)).await
+}
+
+/// Reads from the given event stream until an event containing the given marker was received
+/// completely, returning everything read
+async fn read_until(stream: &mut LocalResponse<'_>, marker: &str) -> String {
+    let mut received = String::new();
+    let mut buffer = [0u8; 1024];
+
+    while !received
+        .find(marker)
+        .map(|start| received[start..].contains("\n\n"))
+        .unwrap_or(false)
+    {
+        let read = timeout(Duration::from_secs(10), stream.read(&mut buffer))
+            .await
+            .expect("timed out waiting for event")
+            .unwrap();
+
+        assert_ne!(read, 0, "event stream ended");
+
+        received.push_str(std::str::from_utf8(&buffer[..read]).unwrap());
+    }
+
+    received
+}
+
+async fn set_status(clnt: &TestClient, record_id: i32, status: &str, user: &AuthenticatedUser) {
+    let record: FullRecord = clnt
+        .get(format!("/api/v1/records/{}/", record_id))
+        .authorize_as(user)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    clnt.patch(format!("/api/v1/records/{}/", record_id), &serde_json::json!({ "status": status }))
+        .authorize_as(user)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .execute()
+        .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_activity_stream_filters_non_public_events(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+    let player1 = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+    let player2 = DatabasePlayer::by_name_or_create("stardust1972", &mut connection).await.unwrap();
+    let demon = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player1.id, player1.id, &mut connection).await;
+
+    let record1 = add_simple_record(100, player1.id, demon, RecordStatus::Submitted, &mut connection).await;
+    let record2 = add_simple_record(100, player2.id, demon, RecordStatus::Submitted, &mut connection).await;
+
+    let mut public_stream = clnt.get("/api/v2/activity/").expect_status(Status::Ok).execute().await;
+    let mut team_stream = clnt
+        .get("/api/v2/activity/")
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .execute()
+        .await;
+
+    set_status(&clnt, record1, "Rejected", &moderator).await;
+    set_status(&clnt, record2, "Approved", &moderator).await;
+
+    let public = read_until_event(&mut public_stream, "record_approved").await;
+
+    assert!(!public.contains("record_rejected"), "non-public event sent to anonymous subscriber");
+    assert!(public.contains(&format!("\"id\":{}", record2)));
+
+    let team = read_until_event(&mut team_stream, "record_approved").await;
+
+    assert!(team.contains("event:record_rejected"));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_activity_stream_announces_shifted_demons(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+    let demon1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+    let demon2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut connection).await;
+    pointercrate_test::demonlist::add_demon("Slaughterhouse", 3, 50, player.id, player.id, &mut connection).await;
+
+    let mut stream = clnt.get("/api/v2/activity/").expect_status(Status::Ok).execute().await;
+
+    let demon: FullDemon = clnt
+        .get(format!("/api/v2/demons/{}/", demon1))
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    clnt.delete(format!("/api/v2/demons/{}/", demon1))
+        .authorize_as(&moderator)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::NoContent)
+        .execute()
+        .await;
+
+    // Every demon below the deleted one moved up by one, and they are announced in list order
+    let received = read_until(&mut stream, "Slaughterhouse").await;
+
+    assert_eq!(received.matches("event:demon_moved").count(), 2);
+    assert!(received.contains(&format!("{{\"id\":{},\"position\":1,", demon2)));
+    assert!(received.contains("\"position\":2,\"name\":\"Slaughterhouse\""));
+    assert!(!received.contains(&format!("\"id\":{},", demon1)));
+}
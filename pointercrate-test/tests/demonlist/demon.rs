@@ -1,9 +1,10 @@
-use pointercrate_core::pagination::PaginationParameters;
+use pointercrate_core::{etag::Taggable, pagination::PaginationParameters};
 use pointercrate_core_api::pagination::LinksBuilder;
 use pointercrate_demonlist::{
-    demon::{Demon, DemonPositionPagination},
-    player::DatabasePlayer,
-    LIST_MODERATOR,
+    demon::{Demon, DemonPositionPagination, FullDemon},
+    player::{DatabasePlayer, FullPlayer},
+    record::FullRecord,
+    LIST_ADMINISTRATOR, LIST_MODERATOR,
 };
 use rocket::http::Status;
 use sqlx::{Pool, Postgres};
@@ -107,6 +108,113 @@ async fn test_default_thumbnail_with_video(pool: Pool<Postgres>) {
     )
 }
 
+#[sqlx::test(migrations = "../migrations")]
+async fn test_thumbnail_follows_video(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+
+    let demon = serde_json::json! {{"name": "Bloodbath", "requirement": 90, "position": 1, "verifier": "Riot", "publisher": "Riot", "creators": [], "video": "https://www.youtube.com/watch?v=dQw4w9WgXcQ"}};
+
+    let demon: FullDemon = clnt
+        .post("/api/v2/demons/", &demon)
+        .authorize_as(&user)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    let url = format!("/api/v2/demons/{}/", demon.demon.base.id);
+
+    // Changing the video changes the thumbnail
+    let demon: FullDemon = clnt
+        .patch(&url, &serde_json::json!({"video": "https://youtu.be/9bZkp7q19f0"}))
+        .authorize_as(&user)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(demon.demon.thumbnail, "https://i.ytimg.com/vi/9bZkp7q19f0/mqdefault.jpg");
+
+    // ... unless the thumbnail was set manually
+    let demon: FullDemon = clnt
+        .patch(&url, &serde_json::json!({"thumbnail": "https://example.com/thumbnail.png"}))
+        .authorize_as(&user)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    let demon: FullDemon = clnt
+        .patch(&url, &serde_json::json!({"video": "https://www.youtube.com/watch?v=dQw4w9WgXcQ"}))
+        .authorize_as(&user)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(demon.demon.thumbnail, "https://example.com/thumbnail.png");
+
+    // Removing the override derives the thumbnail from the video again
+    let demon: FullDemon = clnt
+        .patch(&url, &serde_json::json!({"thumbnail": null}))
+        .authorize_as(&user)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(demon.demon.thumbnail, "https://i.ytimg.com/vi/dQw4w9WgXcQ/mqdefault.jpg");
+
+    // Removing the video also removes the thumbnail derived from it
+    let demon: FullDemon = clnt
+        .patch(&url, &serde_json::json!({"video": null}))
+        .authorize_as(&user)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(demon.demon.thumbnail, DEFAULT_THUMBNAIL);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_backfill_thumbnails(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let admin = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut *connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 100, player.id, player.id, &mut *connection).await;
+    let id2 = pointercrate_test::demonlist::add_demon("Bloodbath 2", 2, 100, player.id, player.id, &mut *connection).await;
+
+    sqlx::query!("UPDATE demons SET video = 'https://www.youtube.com/watch?v=dQw4w9WgXcQ'")
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+    sqlx::query!("UPDATE demons SET thumbnail_overridden = TRUE WHERE id = $1", id2)
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+
+    let result: serde_json::Value = clnt
+        .post("/api/v2/demons/thumbnails", &())
+        .authorize_as(&admin)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(result["changed"].as_u64(), Some(1));
+
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id1)).get_success_result().await;
+
+    assert_eq!(demon.demon.thumbnail, "https://i.ytimg.com/vi/dQw4w9WgXcQ/mqdefault.jpg");
+
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id2)).get_success_result().await;
+
+    assert_eq!(demon.demon.thumbnail, DEFAULT_THUMBNAIL);
+}
+
 #[sqlx::test(migrations = "../migrations")]
 async fn test_demon_pagination(pool: Pool<Postgres>) {
     /// The URL of the endpoint we are testing
@@ -255,3 +363,243 @@ async fn test_demon_pagination(pool: Pool<Postgres>) {
 
     assert_eq!(links, expected.generate(&base).unwrap());
 }
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_unlist_demon(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut *connection).await;
+    let id2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut *connection).await;
+
+    let submission = serde_json::json! {{"progress": 100, "demon": id1, "player": "stardust1972", "video": "https://youtube.com/watch?v=1234567890", "status": "Approved"}};
+
+    let record: FullRecord = clnt
+        .post("/api/v1/records/", &submission)
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id1)).get_success_result().await;
+
+    let demon: FullDemon = clnt
+        .patch(format!("/api/v2/demons/{}/", id1), &serde_json::json!({"unlisted": true}))
+        .authorize_as(&moderator)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert!(demon.demon.unlisted);
+
+    // The gap left behind by the unlisted demon got closed
+    let other: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id2)).get_success_result().await;
+
+    assert_eq!(other.position(), 1);
+
+    let movement_log: Vec<serde_json::Value> = clnt
+        .get(format!("/api/v2/demons/{}/audit/movement/", id2))
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(
+        movement_log.last().unwrap()["reason"]["OtherRemoved"]["other"]["id"].as_i64(),
+        Some(id1 as i64)
+    );
+
+    // The record is kept, but does not give any points anymore
+    clnt.get(format!("/api/v1/records/{}/", record.id))
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .execute()
+        .await;
+
+    let holder: FullPlayer = clnt
+        .get(format!("/api/v1/players/{}/", record.player.id))
+        .get_success_result()
+        .await;
+
+    assert_eq!(holder.player.score, 0.0f64, "Unlisting demon failed to lower player score");
+
+    // Unlisted demons cannot be moved around
+    let result: serde_json::Value = clnt
+        .patch(format!("/api/v2/demons/{}/", id1), &serde_json::json!({"position": 1}))
+        .authorize_as(&moderator)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::Conflict)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(40911));
+
+    // ... until they are relisted, which puts them at the end of the list
+    let demon: FullDemon = clnt
+        .patch(format!("/api/v2/demons/{}/", id1), &serde_json::json!({"unlisted": false}))
+        .authorize_as(&moderator)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert!(!demon.demon.unlisted);
+    assert_eq!(demon.position(), 2);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_delete_demon(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let admin = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut *connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut *connection).await;
+    let id2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut *connection).await;
+
+    let submission = serde_json::json! {{"progress": 100, "demon": id1, "player": "stardust1972", "video": "https://youtube.com/watch?v=1234567890", "status": "Approved"}};
+
+    let record: FullRecord = clnt
+        .post("/api/v1/records/", &submission)
+        .authorize_as(&admin)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id1)).get_success_result().await;
+
+    clnt.delete(format!("/api/v2/demons/{}/", id1))
+        .authorize_as(&admin)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::NoContent)
+        .execute()
+        .await;
+
+    clnt.get(format!("/api/v2/demons/{}/", id1))
+        .expect_status(Status::NotFound)
+        .execute()
+        .await;
+
+    clnt.get(format!("/api/v1/records/{}/", record.id))
+        .authorize_as(&admin)
+        .expect_status(Status::NotFound)
+        .execute()
+        .await;
+
+    let other: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id2)).get_success_result().await;
+
+    assert_eq!(other.position(), 1);
+
+    let holder: FullPlayer = clnt
+        .get(format!("/api/v1/players/{}/", record.player.id))
+        .get_success_result()
+        .await;
+
+    assert_eq!(holder.player.score, 0.0f64, "Deleting demon failed to lower player score");
+
+    let audit_log: Vec<serde_json::Value> = clnt
+        .get(format!("/api/v2/demons/{}/audit/", id1))
+        .authorize_as(&admin)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(audit_log.last().unwrap()["type"].as_str(), Some("Deletion"));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_reorder_demons(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut *connection).await;
+    let id2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut *connection).await;
+    let id3 = pointercrate_test::demonlist::add_demon("Slaughterhouse", 3, 50, player.id, player.id, &mut *connection).await;
+    let id4 = pointercrate_test::demonlist::add_demon("Acheron", 4, 50, player.id, player.id, &mut *connection).await;
+
+    let moved: Vec<serde_json::Value> = clnt
+        .post(
+            "/api/v2/demons/reorder",
+            &serde_json::json!({"moves": [{"demon": id4, "position": 1}, {"demon": id1, "position": 3}]}),
+        )
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    // Bloodlust ends up at its original position
+    assert_eq!(moved.len(), 3);
+
+    // Demons that weren't explicitly moved keep their relative order
+    for (demon_id, position) in [(id4, 1), (id2, 2), (id1, 3), (id3, 4)] {
+        let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", demon_id)).get_success_result().await;
+
+        assert_eq!(demon.position(), position);
+    }
+
+    let movement_log: Vec<serde_json::Value> = clnt
+        .get(format!("/api/v2/demons/{}/audit/movement/", id3))
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(
+        movement_log.last().unwrap()["reason"]["Reordered"]["moved"]
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+
+    let movement_log: Vec<serde_json::Value> = clnt
+        .get(format!("/api/v2/demons/{}/audit/movement/", id4))
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(movement_log.last().unwrap()["reason"], "Moved");
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_reorder_demons_conflicting_moves(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut *connection).await;
+    let id2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut *connection).await;
+
+    let result: serde_json::Value = clnt
+        .post(
+            "/api/v2/demons/reorder",
+            &serde_json::json!({"moves": [{"demon": id1, "position": 2}, {"demon": id2, "position": 2}]}),
+        )
+        .authorize_as(&moderator)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42242));
+
+    let result: serde_json::Value = clnt
+        .post(
+            "/api/v2/demons/reorder",
+            &serde_json::json!({"moves": [{"demon": id1, "position": 3}]}),
+        )
+        .authorize_as(&moderator)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42213));
+
+    // Nothing got moved
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id1)).get_success_result().await;
+
+    assert_eq!(demon.position(), 1);
+}
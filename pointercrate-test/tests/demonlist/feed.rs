@@ -0,0 +1,116 @@
+use pointercrate_demonlist::{demon::MinimalDemon, player::DatabasePlayer, record::RecordStatus, LIST_MODERATOR};
+use rocket::http::Status;
+use sqlx::{Pool, Postgres};
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_list_changes_feed(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+
+    pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+    let bloodlust = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut connection).await;
+
+    let _: Vec<MinimalDemon> = clnt
+        .post(
+            "/api/v2/demons/reorder",
+            &serde_json::json!({"moves": [{"demon": bloodlust, "position": 1}]}),
+        )
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    let feed = clnt
+        .get("/demonlist/feed.atom")
+        .expect_status(Status::Ok)
+        .expect_header("Content-Type", "application/atom+xml; charset=utf-8")
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    // Most recent changes come first, and Bloodbath being pushed down by the reorder is not a change of its own
+    let moved = feed.find("<title>Bloodlust moved to #1</title>").expect("missing movement");
+    let added = feed.find("<title>Bloodlust added to #2</title>").expect("missing addition");
+    let first_added = feed.find("<title>Bloodbath added to #1</title>").expect("missing addition");
+
+    assert!(moved < added && added < first_added);
+    assert_eq!(feed.matches("<entry>").count(), 3);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_approved_records_feeds(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+    let player1 = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+    let player2 = DatabasePlayer::by_name_or_create("stardust1972", &mut connection).await.unwrap();
+    let demon = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player1.id, player1.id, &mut connection).await;
+
+    clnt.patch_player(player2.id, &moderator, serde_json::json!({"nationality": "DE"}))
+        .await
+        .execute()
+        .await;
+
+    pointercrate_test::demonlist::add_simple_record(100, player1.id, demon, RecordStatus::Approved, &mut connection).await;
+    pointercrate_test::demonlist::add_simple_record(60, player2.id, demon, RecordStatus::Approved, &mut connection).await;
+    pointercrate_test::demonlist::add_simple_record(70, player2.id, demon, RecordStatus::Submitted, &mut connection).await;
+
+    let all = clnt
+        .get("/demonlist/records.atom")
+        .expect_status(Status::Ok)
+        .expect_header("Content-Type", "application/atom+xml; charset=utf-8")
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert!(all.contains("<title>stardust1971 beat Bloodbath</title>"));
+    assert!(all.contains("<title>stardust1972 got 60% on Bloodbath</title>"));
+    assert_eq!(all.matches("<entry>").count(), 2, "unapproved record included in feed");
+
+    let by_player = clnt
+        .get(format!("/demonlist/players/{}/records.atom", player1.id))
+        .expect_status(Status::Ok)
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert!(by_player.contains("<title>stardust1971 beat Bloodbath</title>"));
+    assert_eq!(by_player.matches("<entry>").count(), 1);
+
+    let by_nation = clnt
+        .get("/demonlist/nations/de/records.atom")
+        .expect_status(Status::Ok)
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert!(by_nation.contains("<title>stardust1972 got 60% on Bloodbath</title>"));
+    assert_eq!(by_nation.matches("<entry>").count(), 1);
+
+    // Rendered feeds are cached for a while
+    let demon2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player1.id, player1.id, &mut connection).await;
+
+    pointercrate_test::demonlist::add_simple_record(100, player1.id, demon2, RecordStatus::Approved, &mut connection).await;
+
+    let cached = clnt
+        .get("/demonlist/records.atom")
+        .expect_status(Status::Ok)
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert_eq!(cached, all);
+}
@@ -0,0 +1,781 @@
+use pointercrate_core::etag::Taggable;
+use pointercrate_demonlist::{
+    demon::{Demon, FullDemon},
+    list::{section::ListSection, update::ListUpdate, List},
+    player::{DatabasePlayer, FullPlayer},
+    proposal::{PlacementProposal, ProposalStatus},
+    video::VideoHosts,
+    LIST_ADMINISTRATOR, LIST_HELPER, LIST_MODERATOR,
+};
+use rocket::http::Status;
+use sqlx::{PgConnection, Pool, Postgres};
+
+async fn create_platformer_list(connection: &mut PgConnection) -> List {
+    let data = serde_json::from_value(serde_json::json! {{"slug": "platformer", "name": "Platformer List"}}).unwrap();
+
+    List::create_from(data, connection).await.unwrap()
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_create_list(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut connection).await;
+    let list = serde_json::json! {{"slug": "platformer", "name": "Platformer List"}};
+
+    let created: List = clnt
+        .post("/api/v2/lists/", &list)
+        .authorize_as(&user)
+        .expect_status(Status::Created)
+        .expect_header("Location", "/api/v2/lists/platformer/")
+        .get_success_result()
+        .await;
+
+    assert_eq!(created.name, "Platformer List");
+    assert!(!created.is_default());
+
+    let fetched: List = clnt
+        .get("/api/v2/lists/platformer/")
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(fetched, created);
+
+    let result: serde_json::Value = clnt
+        .post("/api/v2/lists/", &list)
+        .authorize_as(&user)
+        .expect_status(Status::Conflict)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(40909));
+
+    let lists: Vec<List> = clnt.get("/api/v2/lists/").expect_status(Status::Ok).get_result().await;
+
+    assert_eq!(lists.len(), 2);
+    assert!(lists[0].is_default());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_create_list_requires_admin(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+
+    clnt.post(
+        "/api/v2/lists/",
+        &serde_json::json! {{"slug": "platformer", "name": "Platformer List"}},
+    )
+    .authorize_as(&user)
+    .expect_status(Status::Forbidden)
+    .execute()
+    .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_create_list_invalid_slug(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut connection).await;
+
+    clnt.post(
+        "/api/v2/lists/",
+        &serde_json::json! {{"slug": "Platformer List", "name": "Platformer List"}},
+    )
+    .authorize_as(&user)
+    .expect_status(Status::UnprocessableEntity)
+    .execute()
+    .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_positions_are_scoped_to_lists(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+
+    create_platformer_list(&mut connection).await;
+
+    pointercrate_test::demonlist::add_demon("Bloodbath", 1, 87, player.id, player.id, &mut connection).await;
+
+    // Position 1 is already taken on the default list, but not on the platformer list
+    let demon: serde_json::Value = clnt
+        .post(
+            "/api/v2/lists/platformer/demons/",
+            &serde_json::json! {{"name": "Ascent", "requirement": 100, "position": 1, "verifier": "stardust1971", "publisher": "stardust1971", "creators": []}},
+        )
+        .authorize_as(&user)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(demon["position"].as_i64(), Some(1));
+
+    let (platformer_demons, _) = clnt
+        .get("/api/v2/lists/platformer/demons/listed/")
+        .expect_status(Status::Ok)
+        .get_pagination_result::<Demon>()
+        .await;
+
+    assert_eq!(platformer_demons.len(), 1);
+    assert_eq!(platformer_demons[0].base.name, "Ascent");
+
+    let (default_demons, _) = clnt
+        .get("/api/v2/demons/listed/")
+        .expect_status(Status::Ok)
+        .get_pagination_result::<Demon>()
+        .await;
+
+    assert_eq!(default_demons.len(), 1);
+    assert_eq!(default_demons[0].base.name, "Bloodbath");
+
+    clnt.get("/lists/platformer/1/").expect_status(Status::SeeOther).execute().await;
+    clnt.get("/lists/platformer/2/").expect_status(Status::NotFound).execute().await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_scores_are_scoped_to_lists(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+    let list = create_platformer_list(&mut connection).await;
+
+    sqlx::query!(
+        "INSERT INTO demons (name, position, requirement, verifier, publisher, list) VALUES ('Ascent', 1, 100, $1, $1, $2)",
+        player.id,
+        list.id
+    )
+    .execute(&mut *connection)
+    .await
+    .unwrap();
+    sqlx::query!("SELECT recompute_player_scores()")
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+
+    let (ranking, _) = clnt
+        .get("/api/v2/lists/platformer/players/ranking/")
+        .expect_status(Status::Ok)
+        .get_pagination_result::<serde_json::Value>()
+        .await;
+
+    assert_eq!(ranking.len(), 1);
+    assert_eq!(ranking[0]["id"].as_i64(), Some(player.id as i64));
+
+    let (default_ranking, _) = clnt
+        .get("/api/v1/players/ranking/")
+        .expect_status(Status::Ok)
+        .get_pagination_result::<serde_json::Value>()
+        .await;
+
+    assert!(default_ranking.is_empty());
+
+    let on_platformer: FullPlayer = clnt
+        .get(format!("/api/v2/lists/platformer/players/{}/", player.id))
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_ne!(on_platformer.player.score, 0.0f64);
+    assert_eq!(on_platformer.verified.len(), 1);
+
+    let on_default: FullPlayer = clnt
+        .get(format!("/api/v1/players/{}/", player.id))
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(on_default.player.score, 0.0f64);
+    assert!(on_default.verified.is_empty());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_unknown_list(pool: Pool<Postgres>) {
+    let (clnt, _) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let result: serde_json::Value = clnt
+        .get("/api/v2/lists/platformer/demons/")
+        .expect_status(Status::NotFound)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(40401));
+
+    clnt.get("/lists/platformer/").expect_status(Status::NotFound).execute().await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_default_sections(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let sections: Vec<ListSection> = clnt
+        .get("/api/v2/lists/demonlist/sections/")
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(sections.len(), 3);
+    assert_eq!(sections[0].first_position, 1);
+    assert!(sections[0].allows_non_100);
+    assert_eq!(sections[1].first_position, sections[0].last_position.unwrap() + 1);
+    assert!(!sections[1].allows_non_100);
+    assert_eq!(sections[2].last_position, None);
+    assert!(!sections[2].submissions_open);
+    assert!(!sections[2].numbered);
+
+    // Newly created lists start out with a single section spanning the entire list
+    let list = create_platformer_list(&mut connection).await;
+    let sections = ListSection::of_list(list.id, &mut connection).await.unwrap();
+
+    assert_eq!(sections.len(), 1);
+    assert_eq!((sections[0].first_position, sections[0].last_position), (1, None));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_create_section(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut connection).await;
+    let list = create_platformer_list(&mut connection).await;
+
+    // The default section of a new list covers everything, so it has to be shrunk first
+    let main = &ListSection::of_list(list.id, &mut connection).await.unwrap()[0];
+
+    let result: serde_json::Value = clnt
+        .post(
+            "/api/v2/lists/platformer/sections/",
+            &serde_json::json! {{"name": "Extended List", "first_position": 51}},
+        )
+        .authorize_as(&user)
+        .expect_status(Status::Conflict)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(40910));
+
+    clnt.patch(
+        format!("/api/v2/lists/platformer/sections/{}/", main.id),
+        &serde_json::json! {{"last_position": 50}},
+    )
+    .authorize_as(&user)
+    .header("If-Match", main.etag_string())
+    .expect_status(Status::Ok)
+    .execute()
+    .await;
+
+    let created: ListSection = clnt
+        .post(
+            "/api/v2/lists/platformer/sections/",
+            &serde_json::json! {{"name": "Extended List", "first_position": 51}},
+        )
+        .authorize_as(&user)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(created.list, list.id);
+    assert_eq!(created.last_position, None);
+    assert!(created.submissions_open);
+    assert!(!created.allows_non_100);
+
+    let result: serde_json::Value = clnt
+        .post(
+            "/api/v2/lists/platformer/sections/",
+            &serde_json::json! {{"name": "Broken", "first_position": 80, "last_position": 70}},
+        )
+        .authorize_as(&user)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42240));
+
+    // Sections can only be accessed through the list they belong to
+    clnt.get(format!("/api/v2/lists/demonlist/sections/{}/", created.id))
+        .expect_status(Status::NotFound)
+        .execute()
+        .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_submissions_follow_section_rules(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+    let demon = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+
+    let main = ListSection::of_list(1, &mut connection).await.unwrap().remove(0);
+
+    let main: ListSection = clnt
+        .patch(
+            format!("/api/v2/lists/demonlist/sections/{}/", main.id),
+            &serde_json::json! {{"allows_non_100": false}},
+        )
+        .authorize_as(&user)
+        .header("If-Match", main.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    let submission = serde_json::json! {{"progress": 60, "demon": demon, "player": "stardust1971", "video": "https://youtube.com/watch?v=1234567890", "raw_footage": "https://pointercrate.com"}};
+
+    let result: serde_json::Value = clnt
+        .post("/api/v1/records/", &submission)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42220));
+
+    clnt.patch(
+        format!("/api/v2/lists/demonlist/sections/{}/", main.id),
+        &serde_json::json! {{"submissions_open": false}},
+    )
+    .authorize_as(&user)
+    .header("If-Match", main.etag_string())
+    .expect_status(Status::Ok)
+    .execute()
+    .await;
+
+    let submission = serde_json::json! {{"progress": 100, "demon": demon, "player": "stardust1971", "video": "https://youtube.com/watch?v=1234567890", "raw_footage": "https://pointercrate.com"}};
+
+    let result: serde_json::Value = clnt
+        .post("/api/v1/records/", &submission)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42219));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_sections_not_awarding_points(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+
+    pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+    sqlx::query!("SELECT recompute_player_scores()")
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+
+    assert_ne!(player.score_on(1, &mut connection).await.unwrap(), 0.0f64);
+
+    let main = ListSection::of_list(1, &mut connection).await.unwrap().remove(0);
+
+    clnt.patch(
+        format!("/api/v2/lists/demonlist/sections/{}/", main.id),
+        &serde_json::json! {{"awards_points": false}},
+    )
+    .authorize_as(&user)
+    .header("If-Match", main.etag_string())
+    .expect_status(Status::Ok)
+    .execute()
+    .await;
+
+    assert_eq!(player.score_on(1, &mut connection).await.unwrap(), 0.0f64);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_list_size_follows_sections(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+    let list = create_platformer_list(&mut connection).await;
+
+    pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+    sqlx::query!(
+        "INSERT INTO demons (name, position, requirement, verifier, publisher, list) VALUES ('Ascent', 1, 100, $1, $1, $2)",
+        player.id,
+        list.id
+    )
+    .execute(&mut *connection)
+    .await
+    .unwrap();
+    sqlx::query!("SELECT recompute_player_scores()")
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+
+    // The top demon of a list awards as many points as the list is large, which is 150 for the default sections
+    assert_eq!(player.score_on(1, &mut connection).await.unwrap(), 150.0f64);
+
+    let section = ListSection::of_list(list.id, &mut connection).await.unwrap().remove(0);
+
+    clnt.patch(
+        format!("/api/v2/lists/platformer/sections/{}/", section.id),
+        &serde_json::json! {{"last_position": 50}},
+    )
+    .authorize_as(&user)
+    .header("If-Match", section.etag_string())
+    .expect_status(Status::Ok)
+    .execute()
+    .await;
+
+    assert_eq!(player.score_on(list.id, &mut connection).await.unwrap(), 50.0f64);
+    assert_eq!(player.score_on(1, &mut connection).await.unwrap(), 150.0f64);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_publish_list_update(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+    let id2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut connection).await;
+    let id3 = pointercrate_test::demonlist::add_demon("Slaughterhouse", 3, 50, player.id, player.id, &mut connection).await;
+
+    let data = serde_json::json! {{
+        "title": "Tartarus placement",
+        "additions": [{"name": "Tartarus", "position": 1, "requirement": 60, "verifier": "Dolphy", "publisher": "Riot", "creators": []}],
+        "moves": [{"demon": id3, "position": 2}],
+        "requirements": [{"demon": id1, "requirement": 70}]
+    }};
+
+    let update: ListUpdate = clnt
+        .post("/api/v2/lists/demonlist/updates/", &data)
+        .authorize_as(&moderator)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert!(!update.is_published());
+    assert_eq!(update.additions.len(), 1);
+
+    // Previewing shows the list with the update applied, without actually changing anything
+    let preview = update.preview(&VideoHosts::default(), &mut connection).await.unwrap();
+
+    let positions = preview
+        .iter()
+        .map(|demon| {
+            (
+                demon.current_demon.base.name.as_str(),
+                demon.current_demon.base.position,
+                demon.position_now,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        positions,
+        vec![
+            ("Tartarus", 1, None),
+            ("Slaughterhouse", 2, Some(3)),
+            ("Bloodbath", 3, Some(1)),
+            ("Bloodlust", 4, Some(2))
+        ]
+    );
+
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id1)).get_success_result().await;
+
+    assert_eq!(demon.position(), 1);
+    assert_eq!(demon.demon.requirement, 50);
+
+    let published: ListUpdate = clnt
+        .post(format!("/api/v2/lists/demonlist/updates/{}/publish", update.id), &())
+        .authorize_as(&moderator)
+        .header("If-Match", update.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert!(published.is_published());
+
+    for (demon_id, position) in [(id3, 2), (id1, 3), (id2, 4)] {
+        let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", demon_id)).get_success_result().await;
+
+        assert_eq!(demon.position(), position);
+    }
+
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id1)).get_success_result().await;
+
+    assert_eq!(demon.demon.requirement, 70);
+
+    let result: serde_json::Value = clnt
+        .post(format!("/api/v2/lists/demonlist/updates/{}/publish", update.id), &())
+        .authorize_as(&moderator)
+        .header("If-Match", published.etag_string())
+        .expect_status(Status::Conflict)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(40912));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_scheduled_list_update(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+    let id2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut connection).await;
+
+    let due = serde_json::json! {{"title": "Swap", "scheduled_for": "2020-01-01T00:00:00", "moves": [{"demon": id2, "position": 1}]}};
+    let not_due = serde_json::json! {{"title": "Future", "scheduled_for": "2999-01-01T00:00:00", "moves": [{"demon": id1, "position": 1}]}};
+    // Fails at publication, as the list only has two demons
+    let invalid = serde_json::json! {{
+        "title": "Invalid",
+        "scheduled_for": "2020-01-01T00:00:00",
+        "additions": [{"name": "Tartarus", "position": 10, "requirement": 60, "verifier": "Dolphy", "publisher": "Riot", "creators": []}]
+    }};
+
+    let mut updates = Vec::new();
+
+    for data in [due, not_due, invalid] {
+        let update: ListUpdate = clnt
+            .post("/api/v2/lists/demonlist/updates/", &data)
+            .authorize_as(&moderator)
+            .expect_status(Status::Created)
+            .get_success_result()
+            .await;
+
+        updates.push(update.id);
+    }
+
+    pointercrate_demonlist_api::publish_due_list_updates(clnt.rocket()).await.unwrap();
+
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", id2)).get_success_result().await;
+
+    assert_eq!(demon.position(), 1);
+
+    let due = ListUpdate::by_id(updates[0], &mut connection).await.unwrap();
+    let not_due = ListUpdate::by_id(updates[1], &mut connection).await.unwrap();
+    let invalid = ListUpdate::by_id(updates[2], &mut connection).await.unwrap();
+
+    assert!(due.is_published());
+    assert!(!not_due.is_published());
+    assert!(!invalid.is_published());
+    assert!(invalid.failure.is_some());
+
+    // Rescheduling clears the failure
+    let invalid: ListUpdate = clnt
+        .patch(
+            format!("/api/v2/lists/demonlist/updates/{}/", invalid.id),
+            &serde_json::json!({"scheduled_for": null}),
+        )
+        .authorize_as(&moderator)
+        .header("If-Match", invalid.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert!(invalid.failure.is_none());
+    assert!(invalid.scheduled_for.is_none());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_accept_placement_proposal(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+    let helper = pointercrate_test::user::user_with_perms("Bob", LIST_HELPER, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+    let id2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut connection).await;
+
+    let proposal: PlacementProposal = clnt
+        .post(
+            "/api/v2/lists/demonlist/proposals",
+            &serde_json::json! {{"demon": id2, "position": 1, "reason": "it's harder"}},
+        )
+        .authorize_as(&moderator)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(proposal.status, ProposalStatus::Open);
+    assert_eq!(proposal.author, Some(moderator.inner().id));
+
+    let proposal: PlacementProposal = clnt
+        .put(format!("/api/v2/proposals/{}/opinion", proposal.id))
+        .json(&serde_json::json! {{"position": 2, "comment": "not convinced"}})
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(proposal.opinions.len(), 1);
+    assert_eq!(proposal.votes_in_favour(), 0);
+
+    let stale = proposal.etag_string();
+
+    let proposal: PlacementProposal = clnt
+        .put(format!("/api/v2/proposals/{}/opinion", proposal.id))
+        .json(&serde_json::json! {{"position": 1, "comment": "obviously"}})
+        .authorize_as(&helper)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(proposal.opinions.len(), 2);
+    assert_eq!(proposal.votes_in_favour(), 1);
+    assert_eq!(proposal.median_opinion(), Some(2));
+
+    // Proposals can only be resolved by someone who saw all opinions given so far
+    clnt.post(format!("/api/v2/proposals/{}/accept", proposal.id), &())
+        .authorize_as(&moderator)
+        .header("If-Match", stale)
+        .expect_status(Status::PreconditionFailed)
+        .execute()
+        .await;
+
+    // Giving another opinion replaces the previous one
+    let proposal: PlacementProposal = clnt
+        .put(format!("/api/v2/proposals/{}/opinion", proposal.id))
+        .json(&serde_json::json! {{"position": 1}})
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(proposal.opinions.len(), 2);
+    assert_eq!(proposal.votes_in_favour(), 2);
+    assert_eq!(proposal.median_opinion(), Some(1));
+
+    let proposal: PlacementProposal = clnt
+        .post(format!("/api/v2/proposals/{}/accept", proposal.id), &())
+        .authorize_as(&moderator)
+        .header("If-Match", proposal.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(proposal.status, ProposalStatus::Accepted);
+    assert_eq!(proposal.resolved_by, Some(moderator.inner().id));
+
+    for (demon_id, position) in [(id2, 1), (id1, 2)] {
+        let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", demon_id)).get_success_result().await;
+
+        assert_eq!(demon.position(), position);
+    }
+
+    // Resolved proposals are closed for discussion
+    let result: serde_json::Value = clnt
+        .put(format!("/api/v2/proposals/{}/opinion", proposal.id))
+        .json(&serde_json::json! {{"position": 2}})
+        .authorize_as(&moderator)
+        .expect_status(Status::Conflict)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(40913));
+
+    clnt.post(format!("/api/v2/proposals/{}/reject", proposal.id), &())
+        .authorize_as(&moderator)
+        .header("If-Match", proposal.etag_string())
+        .expect_status(Status::Conflict)
+        .execute()
+        .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_propose_new_demon(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+
+    let id1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+
+    // A proposal needs to be about exactly one demon
+    clnt.post(
+        "/api/v2/lists/demonlist/proposals",
+        &serde_json::json! {{
+            "demon": id1,
+            "new_demon": {"name": "Tartarus", "requirement": 60, "verifier": "Dolphy", "publisher": "Riot"},
+            "position": 1
+        }},
+    )
+    .authorize_as(&moderator)
+    .expect_status(Status::UnprocessableEntity)
+    .execute()
+    .await;
+
+    let proposal: PlacementProposal = clnt
+        .post(
+            "/api/v2/lists/demonlist/proposals",
+            &serde_json::json! {{
+                "new_demon": {"name": "Tartarus", "requirement": 60, "verifier": "Dolphy", "publisher": "Riot"},
+                "position": 1,
+                "reason": "hardest level ever"
+            }},
+        )
+        .authorize_as(&moderator)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(proposal.demon, None);
+
+    let open: Vec<PlacementProposal> = clnt
+        .get("/api/v2/lists/demonlist/proposals?open=true")
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(open.len(), 1);
+
+    let proposal: PlacementProposal = clnt
+        .post(format!("/api/v2/proposals/{}/accept", proposal.id), &())
+        .authorize_as(&moderator)
+        .header("If-Match", proposal.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    let demon_id = proposal.demon.expect("accepting proposal did not add demon");
+    let demon: FullDemon = clnt.get(format!("/api/v2/demons/{}/", demon_id)).get_success_result().await;
+
+    assert_eq!(demon.demon.base.name, "Tartarus");
+    assert_eq!(demon.position(), 1);
+
+    // The discussion stays attached to the newly added demon
+    let proposals: Vec<PlacementProposal> = clnt
+        .get(format!("/api/v2/demons/{}/proposals", demon_id))
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(proposals.len(), 1);
+    assert_eq!(proposals[0].reason, "hardest level ever");
+
+    let open: Vec<PlacementProposal> = clnt
+        .get("/api/v2/lists/demonlist/proposals?open=true")
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert!(open.is_empty());
+
+    // Deleting the demon keeps the discussion around
+    clnt.delete(format!("/api/v2/demons/{}/", demon_id))
+        .authorize_as(&moderator)
+        .header("If-Match", demon.etag_string())
+        .expect_status(Status::NoContent)
+        .execute()
+        .await;
+
+    let proposal: PlacementProposal = clnt
+        .get(format!("/api/v2/proposals/{}", proposal.id))
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(proposal.demon, None);
+    assert_eq!(proposal.status, ProposalStatus::Accepted);
+}
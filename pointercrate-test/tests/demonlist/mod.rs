@@ -1,4 +1,8 @@
+mod activity;
 mod claim;
 mod demon;
+mod feed;
+mod list;
 mod player;
 mod record;
+mod webhook;
@@ -1,8 +1,10 @@
 use pointercrate_demonlist::{
     nationality::{Nationality, Subdivision},
     player::{DatabasePlayer, FullPlayer, Player},
+    record::RecordStatus,
     LIST_HELPER,
 };
+use pointercrate_test::demonlist::add_simple_record;
 use rocket::http::Status;
 use sqlx::{PgConnection, Pool, Postgres};
 
@@ -168,3 +170,71 @@ async fn test_patch_player_nationality(pool: Pool<Postgres>) {
     assert_eq!(result["data"]["nation_code"], "BE");
     assert_eq!(result["data"]["subdivision_code"], "ENG");
 }
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_player_preview_card(pool: Pool<Postgres>) {
+    let (client, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+
+    let response = client
+        .get(format!("/demonlist/statsviewer/players/{}/card.png", player.id))
+        .expect_status(Status::Ok)
+        .expect_header("Content-Type", "image/png")
+        .execute()
+        .await;
+
+    let png = response.into_bytes().await.unwrap();
+
+    assert!(png.starts_with(b"\x89PNG"));
+
+    client
+        .get("/demonlist/statsviewer/players/1000/card.png")
+        .expect_status(Status::NotFound)
+        .execute()
+        .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_stats_viewers_are_prerendered(pool: Pool<Postgres>) {
+    let (client, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let german = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+    let other = DatabasePlayer::by_name_or_create("stardust1972", &mut connection).await.unwrap();
+    let demon1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, other.id, other.id, &mut connection).await;
+    pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, german.id, german.id, &mut connection).await;
+
+    // The german player has the higher score, so they are the only one on the first page
+    add_simple_record(100, german.id, demon1, RecordStatus::Approved, &mut connection).await;
+
+    sqlx::query!("UPDATE players SET nationality = 'DE' WHERE id = $1", german.id)
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+
+    pointercrate_demonlist::player::recompute_scores(&mut connection).await.unwrap();
+
+    let stats_viewer = client
+        .get(format!("/demonlist/statsviewer?player={}&limit=1", other.id))
+        .expect_status(Status::Ok)
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert!(stats_viewer.contains(&format!(r#"data-id="{}""#, german.id)));
+    assert!(!stats_viewer.contains(&format!(r#"data-id="{}""#, other.id)));
+    // Navigating the ranking keeps the selected player
+    assert!(stats_viewer.contains(&format!(r#"&amp;player={}" rel="next""#, other.id)));
+
+    let nation_stats_viewer = client
+        .get("/demonlist/statsviewer/nations")
+        .expect_status(Status::Ok)
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert!(nation_stats_viewer.contains(r#"data-id="DE""#));
+}
@@ -9,6 +9,16 @@ use pointercrate_demonlist::{
 use rocket::http::Status;
 use sqlx::{PgConnection, Pool, Postgres};
 
+/// The number of demons in the main section of the default list, i.e. the section in which progress records give score
+async fn main_section_size(connection: &mut PgConnection) -> i16 {
+    sqlx::query!("SELECT last_position FROM list_sections WHERE list = 1 AND first_position = 1")
+        .fetch_one(connection)
+        .await
+        .unwrap()
+        .last_position
+        .unwrap()
+}
+
 #[sqlx::test(migrations = "../migrations")]
 pub async fn test_score_update_on_record_update(pool: Pool<Postgres>) {
     let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
@@ -69,24 +79,28 @@ pub async fn test_verifications_give_score(pool: Pool<Postgres>) {
     assert_ne!(player.player.score, 0.0f64);
 }
 
+// Nations/subdivisions without a score on the default list (id 1) have no row in the score tables
 async fn nationality_score(iso_country_code: &str, connection: &mut PgConnection) -> f64 {
-    sqlx::query!("SELECT score FROM nationalities WHERE iso_country_code = $1", iso_country_code)
-        .fetch_one(&mut *connection)
-        .await
-        .unwrap()
-        .score
+    sqlx::query!(
+        "SELECT score FROM nationality_scores WHERE nation = $1 AND list = 1",
+        iso_country_code
+    )
+    .fetch_optional(&mut *connection)
+    .await
+    .unwrap()
+    .map_or(0.0, |row| row.score)
 }
 
 async fn subdivision_score(nation: &str, iso_code: &str, connection: &mut PgConnection) -> f64 {
     sqlx::query!(
-        "SELECT score FROM subdivisions WHERE nation = $1 AND iso_code = $2",
+        "SELECT score FROM subdivision_scores WHERE nation = $1 AND subdivision = $2 AND list = 1",
         nation,
         iso_code
     )
-    .fetch_one(&mut *connection)
+    .fetch_optional(&mut *connection)
     .await
     .unwrap()
-    .score
+    .map_or(0.0, |row| row.score)
 }
 
 #[sqlx::test(migrations = "../migrations")]
@@ -134,7 +148,7 @@ pub async fn test_extended_progress_records_give_no_score(pool: Pool<Postgres>)
     let helper = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
     let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
 
-    let list_size = std::env::var("LIST_SIZE").unwrap().parse::<i16>().unwrap();
+    let list_size = main_section_size(&mut connection).await;
 
     let mut last_demon_id = 0;
 
@@ -174,7 +188,7 @@ pub async fn test_score_resets_if_last_record_removed(pool: Pool<Postgres>) {
     let helper = pointercrate_test::user::system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
     let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
 
-    let list_size = std::env::var("LIST_SIZE").unwrap().parse::<i16>().unwrap();
+    let list_size = main_section_size(&mut connection).await;
 
     let mut last_demon_id = 0;
 
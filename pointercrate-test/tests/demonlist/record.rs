@@ -3,8 +3,18 @@ use pointercrate_core::etag::Taggable;
 use pointercrate_demonlist::{
     error::DemonlistError,
     player::{DatabasePlayer, FullPlayer},
-    record::{note::Note, FullRecord, RecordStatus},
-    LIST_HELPER, LIST_MODERATOR,
+    record::{
+        approval::ApprovalPolicy,
+        audit::audit_log_for_record,
+        lookup::SubmissionStatus,
+        note::{template::NoteTemplate, Note},
+        proof::{Proof, ProofKind},
+        review::ReviewClaim,
+        rules::{RequireRawFootage, SubmissionRule, SubmissionRuleExt, SubmissionRules},
+        FullRecord, NormalizedSubmission, RecordStatus,
+    },
+    submitter::Submitter,
+    LIST_ADMINISTRATOR, LIST_HELPER, LIST_MODERATOR,
 };
 use pointercrate_test::{demonlist::add_simple_record, user::system_user_with_perms};
 use rocket::http::Status;
@@ -138,6 +148,73 @@ async fn submit_existing_record(pool: Pool<Postgres>) {
     assert_eq!(json["data"]["existing"].as_i64(), Some(existing as i64));
 }
 
+#[sqlx::test(migrations = "../migrations")]
+async fn submit_without_raw_footage_when_required(pool: Pool<Postgres>) {
+    let rules = SubmissionRules::default().with_rule(RequireRawFootage.for_positions(1..=1));
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket_with_rules(pool, rules).await;
+
+    let player1 = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+    let demon1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player1.id, player1.id, &mut *connection).await;
+    let demon2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player1.id, player1.id, &mut *connection).await;
+
+    let submission =
+        serde_json::json! {{"progress": 100, "demon": demon1, "player": "stardust1971", "video": "https://youtube.com/watch?v=1234567890"}};
+
+    let json: serde_json::Value = clnt
+        .post("/api/v1/records/", &submission)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(json["code"].as_i64(), Some(DemonlistError::RawRequired.error_code() as i64));
+
+    // The rule only applies to the top 1
+    let submission =
+        serde_json::json! {{"progress": 100, "demon": demon2, "player": "stardust1971", "video": "https://youtube.com/watch?v=1234567890"}};
+
+    clnt.post("/api/v1/records/", &submission).expect_status(Status::Ok).execute().await;
+}
+
+struct NoHalfwayRecords;
+
+#[rocket::async_trait]
+impl SubmissionRule for NoHalfwayRecords {
+    fn name(&self) -> String {
+        "no halfway records".to_string()
+    }
+
+    async fn check(&self, _: &NormalizedSubmission, _: &Submitter, _: &mut PgConnection) -> Result<(), DemonlistError> {
+        Err(DemonlistError::SubmissionRuleViolated {
+            reason: "We do not accept records below 90%".to_string(),
+        })
+    }
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn submit_violating_custom_rule(pool: Pool<Postgres>) {
+    let rules = SubmissionRules::default().with_rule(NoHalfwayRecords.for_progress(0..=89));
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket_with_rules(pool, rules).await;
+
+    let player1 = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+    let demon1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player1.id, player1.id, &mut *connection).await;
+
+    let submission =
+        serde_json::json! {{"progress": 60, "demon": demon1, "player": "stardust1971", "video": "https://youtube.com/watch?v=1234567890"}};
+
+    let json: serde_json::Value = clnt
+        .post("/api/v1/records/", &submission)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(json["code"].as_i64(), Some(42241i64));
+
+    let submission =
+        serde_json::json! {{"progress": 90, "demon": demon1, "player": "stardust1971", "video": "https://youtube.com/watch?v=1234567890"}};
+
+    clnt.post("/api/v1/records/", &submission).expect_status(Status::Ok).execute().await;
+}
+
 #[sqlx::test(migrations = "../migrations")]
 async fn test_no_submitter_info_on_unauthed_get(pool: Pool<Postgres>) {
     let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
@@ -224,3 +301,803 @@ async fn test_record_deletion_updates_player_score(pool: Pool<Postgres>) {
 
     assert_eq!(player.player.score, 0.0f64, "Deleting approved record failed to lower player score");
 }
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_flag_unreachable_video(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let helper = system_user_with_perms(LIST_HELPER, &mut *connection).await;
+    let (_p1, r1, r2, _r3) = setup_pagination_tests(&mut *connection).await;
+
+    let mut record = FullRecord::by_id(r1, &mut *connection).await.unwrap();
+    let mut other = FullRecord::by_id(r2, &mut *connection).await.unwrap();
+
+    record.flag_video_unreachable("404 Not Found", &mut *connection).await.unwrap();
+    record.flag_video_unreachable("404 Not Found", &mut *connection).await.unwrap();
+    other.mark_video_reachable(&mut *connection).await.unwrap();
+
+    assert!(record.video_unreachable_since.is_some());
+
+    clnt.get("/api/v1/records/?video_unreachable=true")
+        .expect_status(Status::Unauthorized)
+        .execute()
+        .await;
+
+    let json: Vec<serde_json::Value> = clnt
+        .get("/api/v1/records/?video_unreachable=true")
+        .authorize_as(&helper)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(json.len(), 1);
+    assert_eq!(json[0]["id"].as_i64(), Some(r1 as i64));
+
+    // Flagging an already flagged record does not add another note
+    let notes: Vec<Note> = clnt
+        .get(format!("/api/v1/records/{}/notes", r1))
+        .authorize_as(&helper)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(notes.len(), 1);
+    assert_eq!(notes[0].content, "Video unreachable: 404 Not Found");
+
+    record.mark_video_reachable(&mut *connection).await.unwrap();
+
+    let json: Vec<serde_json::Value> = clnt
+        .get("/api/v1/records/?video_unreachable=true")
+        .authorize_as(&helper)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert!(json.is_empty());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_submission_proofs(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let helper = system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+    let demon = clnt.add_demon(&helper, "Bloodbath", 1, 50, "stardust1972", "stardust1972").await;
+
+    let submission = serde_json::json! {{"progress": 100, "demon": demon.demon.base.id, "player": "stardust1971", "video": "https://youtube.com/watch?v=1234567890", "raw_footage": "https://pointercrate.com", "clips": ["https://youtu.be/0987654321"], "status": "approved"}};
+
+    let record: FullRecord = clnt
+        .post("/api/v1/records", &submission)
+        .authorize_as(&helper)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(record.proofs.len(), 2);
+    assert_eq!(record.proofs[0].kind, ProofKind::RawFootage);
+    assert_eq!(record.proofs[1].kind, ProofKind::Clip);
+    assert_eq!(record.proofs[1].url, "https://www.youtube.com/watch?v=0987654321");
+
+    // Clips need to be uploaded to a supported video host
+    let submission = serde_json::json! {{"progress": 100, "demon": demon.demon.base.id, "player": "stardust1972", "video": "https://youtube.com/watch?v=1234567891", "clips": ["https://pointercrate.com"], "status": "approved"}};
+
+    clnt.post("/api/v1/records", &submission)
+        .authorize_as(&helper)
+        .expect_status(Status::UnprocessableEntity)
+        .execute()
+        .await;
+
+    // Submitter provided proof is only visible to the list team
+    let unauthed: FullRecord = clnt
+        .get(format!("/api/v1/records/{}", record.id))
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert!(unauthed.proofs.is_empty());
+
+    let raw_footage = &record.proofs[0];
+
+    clnt.get(format!("/api/v1/records/{}/proofs/{}", record.id, raw_footage.id))
+        .expect_status(Status::NotFound)
+        .execute()
+        .await;
+
+    let raw_footage: Proof = clnt
+        .get(format!("/api/v1/records/{}/proofs/{}", record.id, raw_footage.id))
+        .authorize_as(&helper)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    let raw_footage: Proof = clnt
+        .patch(
+            format!("/api/v1/records/{}/proofs/{}", record.id, raw_footage.id),
+            &serde_json::json! {{"is_public": true}},
+        )
+        .authorize_as(&helper)
+        .header("If-Match", raw_footage.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert!(raw_footage.is_public);
+
+    let unauthed: FullRecord = clnt
+        .get(format!("/api/v1/records/{}", record.id))
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(unauthed.proofs.len(), 1);
+
+    // Only the list team can filter by raw footage
+    clnt.get("/api/v1/records/?has_raw_footage=true")
+        .expect_status(Status::Unauthorized)
+        .execute()
+        .await;
+
+    let json: Vec<serde_json::Value> = clnt
+        .get("/api/v1/records/?has_raw_footage=true")
+        .authorize_as(&helper)
+        .get_result()
+        .await;
+
+    assert_eq!(json.len(), 1);
+    assert_eq!(json[0]["id"].as_i64(), Some(record.id as i64));
+
+    let json: Vec<serde_json::Value> = clnt
+        .get("/api/v1/records/?has_raw_footage=false")
+        .authorize_as(&helper)
+        .get_result()
+        .await;
+
+    assert!(json.is_empty());
+
+    clnt.delete(format!("/api/v1/records/{}/proofs/{}", record.id, raw_footage.id))
+        .authorize_as(&helper)
+        .expect_status(Status::PreconditionRequired)
+        .execute()
+        .await;
+
+    clnt.delete(format!("/api/v1/records/{}/proofs/{}", record.id, raw_footage.id))
+        .authorize_as(&helper)
+        .header("If-Match", raw_footage.etag_string())
+        .expect_status(Status::NoContent)
+        .execute()
+        .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_review_queue(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let helper1 = system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+    let helper2 = pointercrate_test::user::user_with_perms("Bob", LIST_HELPER, &mut *connection).await;
+
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+    let demon1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut *connection).await;
+    let demon2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut *connection).await;
+
+    // Added first, but on the lower placed demon
+    let r2 = add_simple_record(100, player.id, demon2, RecordStatus::Submitted, &mut *connection).await;
+    let r1 = add_simple_record(100, player.id, demon1, RecordStatus::Submitted, &mut *connection).await;
+
+    let claim: ReviewClaim = clnt
+        .post("/api/v1/records/reviews/next", &())
+        .authorize_as(&helper1)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(claim.record, r1);
+
+    let claim: ReviewClaim = clnt
+        .post("/api/v1/records/reviews/next", &())
+        .authorize_as(&helper2)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(claim.record, r2);
+
+    clnt.post("/api/v1/records/reviews/next", &())
+        .authorize_as(&helper2)
+        .expect_status(Status::NotFound)
+        .execute()
+        .await;
+
+    clnt.put(format!("/api/v1/records/{}/review", r1))
+        .authorize_as(&helper2)
+        .expect_status(Status::Conflict)
+        .execute()
+        .await;
+
+    let claims: Vec<ReviewClaim> = clnt.get("/api/v1/records/reviews").authorize_as(&helper1).get_result().await;
+
+    assert_eq!(claims.len(), 2);
+
+    // Only the holder of the claim can change the status of a claimed record
+    let record: FullRecord = clnt
+        .get(format!("/api/v1/records/{}", r1))
+        .authorize_as(&helper1)
+        .get_success_result()
+        .await;
+
+    clnt.patch(format!("/api/v1/records/{}", r1), &serde_json::json! {{"status": "approved"}})
+        .authorize_as(&helper2)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Conflict)
+        .execute()
+        .await;
+
+    // Changing the status of a record releases the claim on it
+
+    clnt.patch(format!("/api/v1/records/{}", r1), &serde_json::json! {{"status": "rejected"}})
+        .authorize_as(&helper1)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .execute()
+        .await;
+
+    let claims: Vec<ReviewClaim> = clnt.get("/api/v1/records/reviews").authorize_as(&helper1).get_result().await;
+
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].record, r2);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_submission_status_lookup(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+    let demon = clnt.add_demon(&moderator, "Bloodbath", 1, 50, "stardust1972", "stardust1972").await;
+
+    let submission = serde_json::json! {{"progress": 100, "demon": demon.demon.base.id, "player": "stardust1971", "video": "https://youtube.com/watch?v=1234567890"}};
+
+    let record: FullRecord = clnt
+        .post("/api/v1/records", &submission)
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    let token = record.status_token.clone().expect("submission without status token");
+
+    let status: SubmissionStatus = clnt
+        .get(format!("/api/v1/records/status?token={}", token))
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(status.id, record.id);
+    assert_eq!(status.status, RecordStatus::Submitted);
+    assert_eq!(status.reason, None);
+
+    // Rejection reasons cannot explain why a record is under consideration
+    clnt.patch(
+        format!("/api/v1/records/{}", record.id),
+        &serde_json::json! {{"status": "under consideration", "reason": "hacked"}},
+    )
+    .authorize_as(&moderator)
+    .header("If-Match", record.etag_string())
+    .expect_status(Status::UnprocessableEntity)
+    .execute()
+    .await;
+
+    let record: FullRecord = clnt
+        .patch(
+            format!("/api/v1/records/{}", record.id),
+            &serde_json::json! {{"status": "rejected", "reason": "hacked"}},
+        )
+        .authorize_as(&moderator)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(record.reason.as_deref(), Some("hacked"));
+    assert_eq!(record.status_token, None);
+
+    clnt.post(
+        format!("/api/v1/records/{}/notes", record.id),
+        &serde_json::json! {{"content": "internal"}},
+    )
+    .authorize_as(&moderator)
+    .expect_status(Status::Created)
+    .execute()
+    .await;
+    clnt.post(
+        format!("/api/v1/records/{}/notes", record.id),
+        &serde_json::json! {{"content": "public", "is_public": true}},
+    )
+    .authorize_as(&moderator)
+    .expect_status(Status::Created)
+    .execute()
+    .await;
+
+    let status: SubmissionStatus = clnt
+        .get(format!("/api/v1/records/status?token={}", token))
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(status.status, RecordStatus::Rejected);
+    assert_eq!(status.reason.map(|reason| reason.key), Some("hacked".to_string()));
+    assert_eq!(status.notes.len(), 1);
+    assert_eq!(status.notes[0].content, "public");
+
+    // Changing the status without giving a new reason clears the old one
+    let record: FullRecord = clnt
+        .patch(
+            format!("/api/v1/records/{}", record.id),
+            &serde_json::json! {{"status": "approved"}},
+        )
+        .authorize_as(&moderator)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(record.reason, None);
+
+    // Both setting and clearing the reason show up in the audit log, with the previous reason
+    let admin = pointercrate_test::user::user_with_perms("Bob", LIST_ADMINISTRATOR, &mut *connection).await;
+    let log: Vec<serde_json::Value> = clnt
+        .get(format!("/api/v1/records/{}/audit", record.id))
+        .authorize_as(&admin)
+        .get_result()
+        .await;
+    let reasons: Vec<_> = log
+        .iter()
+        .filter_map(|entry| entry["type"]["Modification"]["status_reason"].as_str())
+        .collect();
+
+    assert_eq!(reasons, vec!["", "hacked"]);
+
+    let error: serde_json::Value = clnt
+        .get("/api/v1/records/status?token=not-a-token")
+        .expect_status(Status::NotFound)
+        .get_result()
+        .await;
+
+    assert_eq!(error["code"].as_i64(), Some(40401));
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_two_reviewer_approval(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket_with_approval_policy(
+        pool,
+        ApprovalPolicy::single_reviewer().with_two_reviewers_for(1..=1),
+    )
+    .await;
+
+    let helper1 = system_user_with_perms(LIST_HELPER, &mut *connection).await;
+    let helper2 = pointercrate_test::user::user_with_perms("Bob", LIST_HELPER, &mut *connection).await;
+    let admin = pointercrate_test::user::user_with_perms("Alice", LIST_ADMINISTRATOR, &mut *connection).await;
+
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+    let player2 = DatabasePlayer::by_name_or_create("stardust1972", &mut *connection).await.unwrap();
+    let demon1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut *connection).await;
+    let demon2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut *connection).await;
+
+    let r1 = add_simple_record(100, player.id, demon1, RecordStatus::Submitted, &mut *connection).await;
+    let r2 = add_simple_record(100, player.id, demon2, RecordStatus::Submitted, &mut *connection).await;
+    let r3 = add_simple_record(100, player2.id, demon1, RecordStatus::Submitted, &mut *connection).await;
+
+    let approve = serde_json::json! {{"status": "approved"}};
+
+    let record: FullRecord = clnt
+        .get(format!("/api/v1/records/{}", r1))
+        .authorize_as(&helper1)
+        .get_success_result()
+        .await;
+
+    // The first approval of a record on a demon covered by the policy only makes it pending
+    let record: FullRecord = clnt
+        .patch(format!("/api/v1/records/{}", r1), &approve)
+        .authorize_as(&helper1)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(record.status, RecordStatus::PendingApproval);
+
+    let result: serde_json::Value = clnt
+        .patch(format!("/api/v1/records/{}", r1), &approve)
+        .authorize_as(&helper1)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Conflict)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(40916));
+
+    // Pending records are part of the review queue, except for whoever gave the first approval
+    let claim: ReviewClaim = clnt
+        .post("/api/v1/records/reviews/next", &())
+        .authorize_as(&helper1)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(claim.record, r3);
+
+    let claim: ReviewClaim = clnt
+        .post("/api/v1/records/reviews/next", &())
+        .authorize_as(&helper2)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(claim.record, r1);
+
+    let record: FullRecord = clnt
+        .patch(format!("/api/v1/records/{}", r1), &approve)
+        .authorize_as(&helper2)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(record.status, RecordStatus::Approved);
+
+    // The pending state shows up in the audit log, attributed to the first approver
+    let log: Vec<serde_json::Value> = clnt
+        .get(format!("/api/v1/records/{}/audit", r1))
+        .authorize_as(&admin)
+        .get_result()
+        .await;
+
+    assert!(log
+        .iter()
+        .any(|entry| entry["user"]["id"].as_i64() == Some(helper1.inner().id as i64)
+            && entry["type"]["Modification"]["status"].as_str() == Some("submitted")));
+    assert!(log
+        .iter()
+        .any(|entry| entry["user"]["id"].as_i64() == Some(helper2.inner().id as i64)
+            && entry["type"]["Modification"]["status"].as_str() == Some("pending approval")));
+
+    // Demons outside the configured positions only need a single approval
+    let record: FullRecord = clnt
+        .get(format!("/api/v1/records/{}", r2))
+        .authorize_as(&helper1)
+        .get_success_result()
+        .await;
+    let record: FullRecord = clnt
+        .patch(format!("/api/v1/records/{}", r2), &approve)
+        .authorize_as(&helper1)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(record.status, RecordStatus::Approved);
+
+    // Records cannot be made pending directly
+    let record: FullRecord = clnt
+        .get(format!("/api/v1/records/{}", r3))
+        .authorize_as(&helper1)
+        .get_success_result()
+        .await;
+    let result: serde_json::Value = clnt
+        .patch(
+            format!("/api/v1/records/{}", r3),
+            &serde_json::json! {{"status": "pending approval"}},
+        )
+        .authorize_as(&helper1)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42252));
+
+    // A single moderator approval suffices
+    let record: FullRecord = clnt
+        .patch(format!("/api/v1/records/{}", r3), &approve)
+        .authorize_as(&admin)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(record.status, RecordStatus::Approved);
+
+    // Moving a record onto a demon covered by the policy while approving it still needs two approvals
+    let player3 = DatabasePlayer::by_name_or_create("stardust1973", &mut *connection).await.unwrap();
+    let r4 = add_simple_record(100, player3.id, demon2, RecordStatus::Submitted, &mut *connection).await;
+
+    let record: FullRecord = clnt
+        .get(format!("/api/v1/records/{}", r4))
+        .authorize_as(&helper1)
+        .get_success_result()
+        .await;
+    let record: FullRecord = clnt
+        .patch(
+            format!("/api/v1/records/{}", r4),
+            &serde_json::json! {{"status": "approved", "demon_id": demon1}},
+        )
+        .authorize_as(&helper1)
+        .header("If-Match", record.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(record.status, RecordStatus::PendingApproval);
+    assert_eq!(record.demon.id, demon1);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_bulk_record_operations(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+    let helper = pointercrate_test::user::user_with_perms("Bob", LIST_HELPER, &mut *connection).await;
+
+    let cheater = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+    let player = DatabasePlayer::by_name_or_create("stardust1972", &mut *connection).await.unwrap();
+    let demon1 = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut *connection).await;
+    let demon2 = pointercrate_test::demonlist::add_demon("Bloodlust", 2, 50, player.id, player.id, &mut *connection).await;
+
+    let r1 = add_simple_record(100, cheater.id, demon1, RecordStatus::Approved, &mut *connection).await;
+    let r2 = add_simple_record(100, cheater.id, demon2, RecordStatus::Approved, &mut *connection).await;
+    let r3 = add_simple_record(60, cheater.id, demon2, RecordStatus::Submitted, &mut *connection).await;
+    let r4 = add_simple_record(100, player.id, demon1, RecordStatus::Submitted, &mut *connection).await;
+
+    pointercrate_demonlist::player::recompute_scores(&mut *connection).await.unwrap();
+
+    let reject = serde_json::json! {{"filter": {"player": cheater.id}, "operation": "set_status", "status": "rejected", "reason": "hacked", "dry_run": true}};
+
+    clnt.post("/api/v1/records/bulk", &reject)
+        .authorize_as(&helper)
+        .expect_status(Status::Forbidden)
+        .execute()
+        .await;
+
+    // A dry run only reports which records would be affected
+    let outcome: serde_json::Value = clnt
+        .post("/api/v1/records/bulk", &reject)
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(outcome["count"].as_i64(), Some(3));
+    assert_eq!(outcome["records"], serde_json::json!([r1, r2, r3]));
+    assert_eq!(
+        FullRecord::by_id(r1, &mut *connection).await.unwrap().status,
+        RecordStatus::Approved
+    );
+
+    let result: serde_json::Value = clnt
+        .post("/api/v1/records/bulk", &serde_json::json! {{"operation": "delete"}})
+        .authorize_as(&moderator)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42253));
+
+    // A filter that does not filter anything would select every single record
+    let result: serde_json::Value = clnt
+        .post("/api/v1/records/bulk", &serde_json::json! {{"filter": {}, "operation": "delete"}})
+        .authorize_as(&moderator)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42253));
+
+    let player_before: FullPlayer = clnt
+        .get(format!("/api/v1/players/{}", cheater.id))
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_ne!(player_before.player.score, 0.0f64);
+
+    let outcome: serde_json::Value = clnt
+        .post(
+            "/api/v1/records/bulk",
+            &serde_json::json! {{"filter": {"player": cheater.id}, "operation": "set_status", "status": "rejected", "reason": "hacked"}},
+        )
+        .authorize_as(&moderator)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(outcome["count"].as_i64(), Some(3));
+
+    // Rejecting r2 deleted r3, as a rejected record is globally unique
+    let record = FullRecord::by_id(r1, &mut *connection).await.unwrap();
+
+    assert_eq!(record.status, RecordStatus::Rejected);
+    assert_eq!(record.reason.as_deref(), Some("hacked"));
+    assert_eq!(
+        FullRecord::by_id(r2, &mut *connection).await.unwrap().status,
+        RecordStatus::Rejected
+    );
+    assert!(FullRecord::by_id(r3, &mut *connection).await.is_err());
+
+    let player_after: FullPlayer = clnt
+        .get(format!("/api/v1/players/{}", cheater.id))
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(player_after.player.score, 0.0f64, "Bulk rejection failed to recompute scores");
+
+    // The whole operation is a single entry in the audit log
+    let entries = sqlx::query("SELECT 1 FROM record_bulk_operations WHERE operation = 'set_status'")
+        .fetch_all(&mut *connection)
+        .await
+        .unwrap();
+
+    assert_eq!(entries.len(), 1);
+
+    // It also shows up in the audit logs of the affected records, right after the changes it made
+    let log = serde_json::to_value(audit_log_for_record(r1, &mut *connection).await.unwrap()).unwrap();
+    let log = log.as_array().unwrap();
+    let bulk = &log[log.len() - 1]["type"]["Modification"];
+
+    assert!(log[..log.len() - 1]
+        .iter()
+        .any(|entry| !entry["type"]["Modification"]["status"].is_null()));
+    assert_eq!(bulk["bulk_operation"]["operation"], "set_status");
+    assert_eq!(bulk["bulk_operation"]["record_count"], 3);
+
+    // Records that already have the requested status keep their reason if no new one is given
+    clnt.post(
+        "/api/v1/records/bulk",
+        &serde_json::json! {{"records": [r1], "operation": "set_status", "status": "rejected"}},
+    )
+    .authorize_as(&moderator)
+    .expect_status(Status::Ok)
+    .execute()
+    .await;
+
+    assert_eq!(
+        FullRecord::by_id(r1, &mut *connection).await.unwrap().reason.as_deref(),
+        Some("hacked")
+    );
+
+    clnt.post(
+        "/api/v1/records/bulk",
+        &serde_json::json! {{"records": [r4], "operation": "set_player", "player": "stardust1973"}},
+    )
+    .authorize_as(&moderator)
+    .expect_status(Status::Ok)
+    .execute()
+    .await;
+
+    assert_eq!(FullRecord::by_id(r4, &mut *connection).await.unwrap().player.name, "stardust1973");
+
+    clnt.post(
+        "/api/v1/records/bulk",
+        &serde_json::json! {{"records": [r1, 0], "operation": "delete"}},
+    )
+    .authorize_as(&moderator)
+    .expect_status(Status::NotFound)
+    .execute()
+    .await;
+
+    clnt.post(
+        "/api/v1/records/bulk",
+        &serde_json::json! {{"records": [r1, r2], "operation": "delete"}},
+    )
+    .authorize_as(&moderator)
+    .expect_status(Status::Ok)
+    .execute()
+    .await;
+
+    assert!(FullRecord::by_id(r1, &mut *connection).await.is_err());
+    assert!(FullRecord::by_id(r2, &mut *connection).await.is_err());
+    assert!(FullRecord::by_id(r4, &mut *connection).await.is_ok());
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_note_templates(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let moderator = system_user_with_perms(LIST_MODERATOR, &mut *connection).await;
+    let helper = pointercrate_test::user::user_with_perms("Bob", LIST_HELPER, &mut *connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut *connection).await.unwrap();
+    let demon = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut *connection).await;
+    let record = add_simple_record(87, player.id, demon, RecordStatus::Submitted, &mut *connection).await;
+
+    let template =
+        serde_json::json! {{"name": "Raw footage", "content": "Please provide raw footage of {player}'s {progress}% on {demon}"}};
+
+    clnt.post("/api/v1/records/note-templates", &template)
+        .authorize_as(&helper)
+        .expect_status(Status::Forbidden)
+        .execute()
+        .await;
+
+    let result: serde_json::Value = clnt
+        .post(
+            "/api/v1/records/note-templates",
+            &serde_json::json! {{"name": "Broken", "content": "Cheat indicators at {timestamp}"}},
+        )
+        .authorize_as(&moderator)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42255));
+
+    let template: NoteTemplate = clnt
+        .post("/api/v1/records/note-templates", &template)
+        .authorize_as(&moderator)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    let templates: Vec<NoteTemplate> = clnt.get("/api/v1/records/note-templates").authorize_as(&helper).get_result().await;
+
+    assert_eq!(templates.len(), 1);
+    assert_eq!(templates[0].id, template.id);
+
+    // Helpers can create notes from templates, with the placeholders filled in
+    let note: Note = clnt
+        .post(
+            format!("/api/v1/records/{}/notes", record),
+            &serde_json::json! {{"template": template.id, "is_public": true}},
+        )
+        .authorize_as(&helper)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(note.content, "Please provide raw footage of stardust1971's 87% on Bloodbath");
+    assert!(note.is_public);
+
+    let result: serde_json::Value = clnt
+        .post(
+            format!("/api/v1/records/{}/notes", record),
+            &serde_json::json! {{"template": template.id, "content": "My Note"}},
+        )
+        .authorize_as(&helper)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"].as_i64(), Some(42229));
+
+    // Changing the template does not affect notes already created from it
+    let patched: NoteTemplate = clnt
+        .patch(
+            format!("/api/v1/records/note-templates/{}", template.id),
+            &serde_json::json! {{"content": "Raw footage needed for {demon}"}},
+        )
+        .authorize_as(&moderator)
+        .header("If-Match", template.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    let notes: Vec<Note> = clnt
+        .get(format!("/api/v1/records/{}/notes", record))
+        .authorize_as(&helper)
+        .get_result()
+        .await;
+
+    assert_eq!(notes[0].content, "Please provide raw footage of stardust1971's 87% on Bloodbath");
+
+    clnt.delete(format!("/api/v1/records/note-templates/{}", template.id))
+        .authorize_as(&moderator)
+        .header("If-Match", patched.etag_string())
+        .expect_status(Status::NoContent)
+        .execute()
+        .await;
+
+    clnt.post(
+        format!("/api/v1/records/{}/notes", record),
+        &serde_json::json! {{"template": template.id}},
+    )
+    .authorize_as(&helper)
+    .expect_status(Status::NotFound)
+    .execute()
+    .await;
+}
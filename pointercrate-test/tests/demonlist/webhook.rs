@@ -0,0 +1,201 @@
+use hmac::{Hmac, Mac};
+use pointercrate_core::etag::Taggable;
+use pointercrate_demonlist::{
+    player::DatabasePlayer,
+    record::{FullRecord, RecordStatus},
+    webhook::{RegisteredWebhook, WebhookDelivery},
+    LIST_ADMINISTRATOR,
+};
+use pointercrate_test::demonlist::add_simple_record;
+use rocket::{
+    http::Status,
+    tokio::{
+        self,
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        sync::oneshot,
+        time::timeout,
+    },
+};
+use sha2::Sha256;
+use sqlx::{Pool, Postgres};
+use std::time::Duration;
+
+/// Accepts a single HTTP request on the given listener, responds with `200 OK` and returns the
+/// request (headers and body)
+async fn receive_request(listener: TcpListener) -> String {
+    let (mut socket, _) = listener.accept().await.unwrap();
+    let mut received = String::new();
+    let mut buffer = [0u8; 1024];
+
+    loop {
+        let read = socket.read(&mut buffer).await.unwrap();
+
+        received.push_str(std::str::from_utf8(&buffer[..read]).unwrap());
+
+        if let Some(header_end) = received.find("\r\n\r\n") {
+            let content_length = received[..header_end]
+                .lines()
+                .find_map(|line| line.to_lowercase().strip_prefix("content-length: ").map(str::to_owned))
+                .map(|length| length.parse::<usize>().unwrap())
+                .unwrap_or(0);
+
+            if received.len() >= header_end + 4 + content_length {
+                break;
+            }
+        }
+    }
+
+    socket.write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n").await.unwrap();
+
+    received
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_webhook_unknown_event(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let admin = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut connection).await;
+
+    clnt.post(
+        "/api/v2/webhooks/",
+        &serde_json::json!({"url": "https://example.com/hook", "format": "Json", "events": ["demon_deleted"]}),
+    )
+    .authorize_as(&admin)
+    .expect_status(Status::UnprocessableEntity)
+    .execute()
+    .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+async fn test_webhook_deliveries(pool: Pool<Postgres>) {
+    let (clnt, mut connection) = pointercrate_test::demonlist::setup_rocket(pool).await;
+
+    let admin = pointercrate_test::user::system_user_with_perms(LIST_ADMINISTRATOR, &mut connection).await;
+    let player = DatabasePlayer::by_name_or_create("stardust1971", &mut connection).await.unwrap();
+    let demon = pointercrate_test::demonlist::add_demon("Bloodbath", 1, 50, player.id, player.id, &mut connection).await;
+    let record_id = add_simple_record(100, player.id, demon, RecordStatus::Submitted, &mut connection).await;
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let receiving_url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+    // Nothing listens on this port anymore once the listener is dropped
+    let failing_url = format!(
+        "http://{}/hook",
+        TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap()
+    );
+
+    let (sender, request) = oneshot::channel();
+
+    tokio::spawn(async move { sender.send(receive_request(listener).await) });
+
+    let receiving: RegisteredWebhook = clnt
+        .post(
+            "/api/v2/webhooks/",
+            &serde_json::json!({"url": receiving_url, "secret": "hunter2", "format": "Json", "events": ["record_approved"]}),
+        )
+        .authorize_as(&admin)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    let failing: RegisteredWebhook = clnt
+        .post(
+            "/api/v2/webhooks/",
+            &serde_json::json!({"url": failing_url, "format": "Discord", "events": ["record_approved"]}),
+        )
+        .authorize_as(&admin)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(failing.secret.len(), 32);
+
+    // The secret is only revealed when registering the webhook
+    let json: serde_json::Value = clnt
+        .get(format!("/api/v2/webhooks/{}/", failing.webhook.id))
+        .authorize_as(&admin)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert!(json.get("secret").is_none());
+
+    let record: FullRecord = clnt
+        .get(format!("/api/v1/records/{}/", record_id))
+        .authorize_as(&admin)
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    clnt.patch(
+        format!("/api/v1/records/{}/", record_id),
+        &serde_json::json!({"status": "Approved"}),
+    )
+    .authorize_as(&admin)
+    .header("If-Match", record.etag_string())
+    .expect_status(Status::Ok)
+    .execute()
+    .await;
+
+    let request = timeout(Duration::from_secs(10), request)
+        .await
+        .expect("timed out waiting for webhook delivery")
+        .unwrap();
+    let (headers, body) = request.split_once("\r\n\r\n").unwrap();
+    let headers = headers.to_lowercase();
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"hunter2").unwrap();
+    mac.update(body.as_bytes());
+
+    assert!(headers.contains("x-pointercrate-event: record_approved"));
+    assert!(headers.contains(&format!(
+        "x-pointercrate-signature: sha256={}",
+        hex::encode(mac.finalize().into_bytes())
+    )));
+    assert!(body.contains(&format!("\"id\":{}", record_id)));
+
+    // The failed delivery is only attempted after the successful one has been sent, and is then put back into the job queue
+    let mut retry = None;
+
+    for _ in 0..50 {
+        retry = sqlx::query!(
+            r#"SELECT attempts, run_at > (NOW() AT TIME ZONE 'utc') AS "later!" FROM jobs WHERE kind = 'deliver_webhook' AND last_error IS NOT NULL"#
+        )
+        .fetch_optional(&mut *connection)
+        .await
+        .unwrap();
+
+        if retry.is_some() {
+            break;
+        }
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let retry = retry.expect("failed delivery not scheduled for retry");
+
+    assert_eq!(retry.attempts, 1);
+    assert!(retry.later);
+
+    let failed = clnt
+        .get(format!("/api/v2/webhooks/{}/deliveries/", failing.webhook.id))
+        .authorize_as(&admin)
+        .expect_status(Status::Ok)
+        .get_result::<Vec<WebhookDelivery>>()
+        .await;
+
+    assert_eq!(failed.len(), 1);
+    assert_eq!(failed[0].attempts.len(), 1);
+    assert!(failed[0].attempts[0].status_code.is_none());
+    assert!(failed[0].delivered_at.is_none());
+
+    let delivered = clnt
+        .get(format!("/api/v2/webhooks/{}/deliveries/", receiving.webhook.id))
+        .authorize_as(&admin)
+        .expect_status(Status::Ok)
+        .get_result::<Vec<WebhookDelivery>>()
+        .await;
+
+    assert_eq!(delivered.len(), 1);
+}
@@ -0,0 +1,76 @@
+use pointercrate_core::{announcement::Announcement, etag::Taggable};
+use pointercrate_user::ADMINISTRATOR;
+use rocket::http::Status;
+use sqlx::{Pool, Postgres};
+
+#[sqlx::test(migrations = "../migrations")]
+pub async fn test_announcement_requires_administrator(pool: Pool<Postgres>) {
+    let (client, mut connection) = pointercrate_test::user::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::add_normal_user(&mut connection).await;
+
+    client
+        .post("/api/v1/announcements/", &serde_json::json!({"content": "Hello World"}))
+        .authorize_as(&user)
+        .expect_status(Status::Forbidden)
+        .execute()
+        .await;
+}
+
+#[sqlx::test(migrations = "../migrations")]
+pub async fn test_create_and_expire_announcement(pool: Pool<Postgres>) {
+    let (client, mut connection) = pointercrate_test::user::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(ADMINISTRATOR, &mut connection).await;
+
+    let announcement: serde_json::Value = client
+        .post(
+            "/api/v1/announcements/",
+            &serde_json::json!({"content": "Guidelines have changed", "severity": "warning", "target_pages": ["/demonlist/"]}),
+        )
+        .authorize_as(&user)
+        .expect_status(Status::Created)
+        .get_success_result()
+        .await;
+
+    assert_eq!(announcement["severity"], "warning");
+    assert_eq!(announcement["ends"], serde_json::Value::Null);
+
+    let created = Announcement::by_id(announcement["id"].as_i64().unwrap() as i32, &mut connection)
+        .await
+        .unwrap();
+
+    // Expiring requires the current state of the announcement
+    client
+        .post(format!("/api/v1/announcements/{}/expire", announcement["id"]), &())
+        .authorize_as(&user)
+        .expect_status(Status::PreconditionRequired)
+        .execute()
+        .await;
+
+    let expired: serde_json::Value = client
+        .post(format!("/api/v1/announcements/{}/expire", announcement["id"]), &())
+        .authorize_as(&user)
+        .header("If-Match", created.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_ne!(expired["ends"], serde_json::Value::Null);
+
+    let expired = Announcement::by_id(announcement["id"].as_i64().unwrap() as i32, &mut connection)
+        .await
+        .unwrap();
+
+    // An announcement cannot end before it starts
+    client
+        .patch(
+            format!("/api/v1/announcements/{}", announcement["id"]),
+            &serde_json::json!({"starts": "2030-01-01T00:00:00"}),
+        )
+        .authorize_as(&user)
+        .header("If-Match", expired.etag_string())
+        .expect_status(Status::UnprocessableEntity)
+        .execute()
+        .await;
+}
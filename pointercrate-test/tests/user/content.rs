@@ -0,0 +1,86 @@
+use pointercrate_core::{content::ContentPage, etag::Taggable};
+use pointercrate_user::ADMINISTRATOR;
+use rocket::http::Status;
+use sqlx::{Pool, Postgres};
+
+#[sqlx::test(migrations = "../migrations")]
+pub async fn test_edit_and_restore_content_page(pool: Pool<Postgres>) {
+    let (client, mut connection) = pointercrate_test::user::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(ADMINISTRATOR, &mut connection).await;
+
+    let page: serde_json::Value = client
+        .post(
+            "/api/v1/pages/",
+            &serde_json::json!({"slug": "guidelines", "title": "Guidelines", "content": "No *hacks*"}),
+        )
+        .authorize_as(&user)
+        .expect_status(Status::Created)
+        .expect_header("Location", "/api/v1/pages/guidelines/")
+        .get_success_result()
+        .await;
+
+    let first_revision = page["revision"].as_i64().unwrap();
+
+    client
+        .post(
+            "/api/v1/pages/",
+            &serde_json::json!({"slug": "guidelines", "title": "Other Guidelines", "content": ""}),
+        )
+        .authorize_as(&user)
+        .expect_status(Status::Conflict)
+        .execute()
+        .await;
+
+    let current = ContentPage::by_slug("guidelines", &mut connection).await.unwrap();
+
+    let patched: serde_json::Value = client
+        .patch("/api/v1/pages/guidelines", &serde_json::json!({"content": "Anything goes"}))
+        .authorize_as(&user)
+        .header("If-Match", current.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(patched["content"], "Anything goes");
+
+    let current = ContentPage::by_slug("guidelines", &mut connection).await.unwrap();
+
+    let restored: serde_json::Value = client
+        .post(format!("/api/v1/pages/guidelines/revisions/{}/restore", first_revision), &())
+        .authorize_as(&user)
+        .header("If-Match", current.etag_string())
+        .expect_status(Status::Ok)
+        .get_success_result()
+        .await;
+
+    assert_eq!(restored["content"], "No *hacks*");
+
+    let revisions: Vec<serde_json::Value> = client
+        .get("/api/v1/pages/guidelines/revisions")
+        .authorize_as(&user)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(revisions.len(), 3);
+}
+
+#[sqlx::test(migrations = "../migrations")]
+pub async fn test_content_page_invalid_slug(pool: Pool<Postgres>) {
+    let (client, mut connection) = pointercrate_test::user::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(ADMINISTRATOR, &mut connection).await;
+
+    let result: serde_json::Value = client
+        .post(
+            "/api/v1/pages/",
+            &serde_json::json!({"slug": "Not A Slug", "title": "Guidelines", "content": ""}),
+        )
+        .authorize_as(&user)
+        .expect_status(Status::UnprocessableEntity)
+        .get_result()
+        .await;
+
+    assert_eq!(result["code"], 42236);
+}
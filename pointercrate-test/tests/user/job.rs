@@ -0,0 +1,76 @@
+use pointercrate_core::job::{self, QueuedJob};
+use pointercrate_user::ADMINISTRATOR;
+use rocket::http::Status;
+use sqlx::{Pool, Postgres};
+
+#[sqlx::test(migrations = "../migrations")]
+pub async fn test_failed_job_is_dead_lettered_and_retried(pool: Pool<Postgres>) {
+    let (client, mut connection) = pointercrate_test::user::setup_rocket(pool).await;
+
+    let user = pointercrate_test::user::system_user_with_perms(ADMINISTRATOR, &mut connection).await;
+
+    let id = job::enqueue("flaky", "{}".to_string(), 2, &mut connection).await.unwrap();
+
+    // First attempt fails and the job is put back into the queue, but not immediately
+    let claimed = QueuedJob::claim(&mut connection).await.unwrap().unwrap();
+
+    assert_eq!(claimed.id, id);
+    assert_eq!(claimed.attempts, 1);
+
+    claimed.fail("first failure".to_string(), &mut connection).await.unwrap();
+
+    assert!(QueuedJob::claim(&mut connection).await.unwrap().is_none());
+
+    let queued: Vec<serde_json::Value> = client
+        .get("/api/v1/jobs/")
+        .authorize_as(&user)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(queued.len(), 1);
+    assert_eq!(queued[0]["last_error"], "first failure");
+
+    // Second (and final) attempt fails as well
+    sqlx::query!("UPDATE jobs SET run_at = (NOW() AT TIME ZONE 'utc') WHERE id = $1", id)
+        .execute(&mut *connection)
+        .await
+        .unwrap();
+
+    let claimed = QueuedJob::claim(&mut connection).await.unwrap().unwrap();
+
+    assert_eq!(claimed.attempts, 2);
+
+    claimed.fail("second failure".to_string(), &mut connection).await.unwrap();
+
+    let dead: Vec<serde_json::Value> = client
+        .get("/api/v1/jobs/dead")
+        .authorize_as(&user)
+        .expect_status(Status::Ok)
+        .get_result()
+        .await;
+
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0]["id"], id);
+    assert_eq!(dead[0]["last_error"], "second failure");
+
+    client
+        .post(format!("/api/v1/jobs/dead/{}/retry", id), &())
+        .authorize_as(&user)
+        .expect_status(Status::NoContent)
+        .execute()
+        .await;
+
+    let claimed = QueuedJob::claim(&mut connection).await.unwrap().unwrap();
+
+    assert_eq!(claimed.id, id);
+    assert_eq!(claimed.attempts, 1);
+    assert_eq!(claimed.max_attempts, 2);
+
+    client
+        .get(format!("/api/v1/jobs/dead/{}", id))
+        .authorize_as(&user)
+        .expect_status(Status::NotFound)
+        .execute()
+        .await;
+}
@@ -1,2 +1,6 @@
+mod announcement;
+mod content;
+mod job;
 mod login;
+mod navigation;
 mod register;
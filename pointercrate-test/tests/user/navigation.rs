@@ -0,0 +1,44 @@
+use pointercrate_user::{ADMINISTRATOR, MODERATOR};
+use sqlx::{Pool, Postgres};
+
+#[sqlx::test(migrations = "../migrations")]
+pub async fn test_restricted_navigation_items(pool: Pool<Postgres>) {
+    let (client, mut connection) = pointercrate_test::user::setup_rocket(pool).await;
+
+    let admin = pointercrate_test::user::system_user_with_perms(ADMINISTRATOR, &mut connection).await;
+    let moderator = pointercrate_test::user::user_with_perms("Bob", MODERATOR, &mut connection).await;
+
+    let page = client
+        .get("/account")
+        .accept_html()
+        .authorize_as(&admin)
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert!(page.contains("Stats Viewer"));
+    assert!(page.contains("Admin Panel"));
+    assert!(page.contains("Administration"));
+
+    let page = client
+        .get("/account")
+        .accept_html()
+        .authorize_as(&moderator)
+        .execute()
+        .await
+        .into_string()
+        .await
+        .unwrap();
+
+    assert!(page.contains("Stats Viewer"));
+    assert!(!page.contains("Admin Panel"));
+    assert!(!page.contains("Administration"));
+
+    let page = client.get("/login").accept_html().execute().await.into_string().await.unwrap();
+
+    assert!(page.contains("Stats Viewer"));
+    assert!(!page.contains("Admin Panel"));
+    assert!(!page.contains("Administration"));
+}
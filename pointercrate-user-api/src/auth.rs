@@ -5,14 +5,16 @@ use pointercrate_core::{
     permission::{Permission, PermissionsManager},
     pool::{audit_connection, PointercratePool},
 };
+use pointercrate_core_api::response::AuthenticatedPermissions;
 use pointercrate_user::{error::UserError, AuthenticatedUser};
 use rocket::{
+    fairing::{Fairing, Info, Kind},
     http::{Method, Status},
     request::{FromRequest, Outcome},
-    Request, State,
+    Data, Request, State,
 };
 use sqlx::{Postgres, Transaction};
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Mutex};
 
 #[allow(non_upper_case_globals)]
 pub struct Auth<const IsToken: bool> {
@@ -92,10 +94,15 @@ impl<'r> FromRequest<'r> for Auth<true> {
 
         for authorization in request.headers().get("Authorization") {
             if let ["Bearer", token] = authorization.split(' ').collect::<Vec<_>>()[..] {
-                let user = try_outcome!(AuthenticatedUser::token_auth(token, None, &mut *connection).await);
+                let user = match take_preauthenticated(request) {
+                    Some(user) => user,
+                    None => try_outcome!(AuthenticatedUser::token_auth(token, None, &mut *connection).await),
+                };
 
                 try_outcome!(audit_connection(&mut *connection, user.inner().id).await);
 
+                request.local_cache(|| AuthenticatedPermissions(user.inner().permissions));
+
                 return Outcome::Success(Auth {
                     user,
                     connection,
@@ -112,10 +119,15 @@ impl<'r> FromRequest<'r> for Auth<true> {
             if request.method() == Method::Get {
                 debug!("GET request, the cookie is enough");
 
-                let user = try_outcome!(AuthenticatedUser::token_auth(access_token, None, &mut *connection).await);
+                let user = match take_preauthenticated(request) {
+                    Some(user) => user,
+                    None => try_outcome!(AuthenticatedUser::token_auth(access_token, None, &mut *connection).await),
+                };
 
                 try_outcome!(audit_connection(&mut *connection, user.inner().id).await);
 
+                request.local_cache(|| AuthenticatedPermissions(user.inner().permissions));
+
                 return Outcome::Success(Auth {
                     user,
                     connection,
@@ -134,6 +146,8 @@ impl<'r> FromRequest<'r> for Auth<true> {
 
                 try_outcome!(audit_connection(&mut *connection, user.inner().id).await);
 
+                request.local_cache(|| AuthenticatedPermissions(user.inner().permissions));
+
                 return Outcome::Success(Auth {
                     user,
                     connection,
@@ -199,6 +213,8 @@ impl<'r> FromRequest<'r> for Auth<false> {
 
                     try_outcome!(audit_connection(&mut *connection, user.inner().id).await);
 
+                    request.local_cache(|| AuthenticatedPermissions(user.inner().permissions));
+
                     return Outcome::Success(Auth {
                         user,
                         connection,
@@ -212,3 +228,70 @@ impl<'r> FromRequest<'r> for Auth<false> {
         Outcome::Error((Status::Unauthorized, CoreError::Unauthorized.into()))
     }
 }
+
+/// A user authenticated by the [`PagePermissionsFairing`], handed over to the first [`TokenAuth`]
+/// request guard of the same request so that it does not have to authenticate them again
+struct PreauthenticatedUser(Mutex<Option<AuthenticatedUser>>);
+
+fn take_preauthenticated(request: &Request<'_>) -> Option<AuthenticatedUser> {
+    request
+        .local_cache(|| PreauthenticatedUser(Mutex::new(None)))
+        .0
+        .lock()
+        .unwrap()
+        .take()
+}
+
+/// The access token a `GET` request is authenticated with, looked up the same way as [`TokenAuth`] does
+fn access_token(request: &Request<'_>) -> Option<String> {
+    for authorization in request.headers().get("Authorization") {
+        if let ["Bearer", token] = authorization.split(' ').collect::<Vec<_>>()[..] {
+            return Some(token.to_string());
+        }
+    }
+
+    request.cookies().get("access_token").map(|cookie| cookie.value().to_string())
+}
+
+/// Rocket fairing that authenticates `GET` requests for HTML pages before they are routed.
+///
+/// Handlers of pages generally only request a [`TokenAuth`] if they themselves need to know about
+/// the user, but the navigation bar and footer rendered around every page contain items that are
+/// only visible to users with certain permissions. Authenticating the user here ensures their
+/// [`AuthenticatedPermissions`] are known to the [`Page`] responder regardless. The fairing only
+/// borrows a connection for the token check, and hands the user over to a [`TokenAuth`] guard
+/// later in the request, which then skips authentication.
+///
+/// [`Page`]: pointercrate_core_api::response::Page
+pub struct PagePermissionsFairing;
+
+#[rocket::async_trait]
+impl Fairing for PagePermissionsFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Page Permissions",
+            kind: Kind::Request,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        let wants_html = request.accept().map(|accept| accept.preferred().is_html()).unwrap_or(false);
+
+        if request.method() != Method::Get || !wants_html {
+            return;
+        }
+
+        let Some(access_token) = access_token(request) else { return };
+        let Outcome::Success(pool) = request.guard::<&State<PointercratePool>>().await else {
+            return;
+        };
+        let Ok(mut connection) = pool.connection().await else { return };
+
+        // If authentication fails, the page is rendered as if nobody was logged in, and any
+        // TokenAuth guard of the handler reports the error
+        if let Ok(user) = AuthenticatedUser::token_auth(&access_token, None, &mut connection).await {
+            request.local_cache(|| AuthenticatedPermissions(user.inner().permissions));
+            request.local_cache(|| PreauthenticatedUser(Mutex::new(Some(user))));
+        }
+    }
+}
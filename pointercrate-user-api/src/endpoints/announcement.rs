@@ -0,0 +1,98 @@
+use crate::auth::TokenAuth;
+use chrono::Utc;
+use pointercrate_core::announcement::{Announcement, PatchAnnouncement, PostAnnouncement};
+use pointercrate_core_api::{
+    announcement::AnnouncementCache,
+    error::Result,
+    etag::{Precondition, TaggableExt, Tagged},
+    response::Response2,
+};
+use pointercrate_user::ADMINISTRATOR;
+use rocket::{http::Status, serde::json::Json, State};
+
+#[rocket::get("/")]
+pub async fn list(mut auth: TokenAuth) -> Result<Json<Vec<Announcement>>> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    Ok(Json(Announcement::all(&mut auth.connection).await?))
+}
+
+#[rocket::post("/", data = "<data>")]
+pub async fn post(
+    mut auth: TokenAuth, data: Json<PostAnnouncement>, cache: &State<AnnouncementCache>,
+) -> Result<Response2<Tagged<Announcement>>> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    let announcement = Announcement::create_from(data.0, &mut auth.connection).await?;
+
+    auth.commit().await?;
+    cache.invalidate();
+
+    let announcement_id = announcement.id;
+
+    Ok(Response2::tagged(announcement)
+        .status(Status::Created)
+        .with_header("Location", format!("/api/v1/announcements/{}/", announcement_id)))
+}
+
+#[rocket::get("/<announcement_id>")]
+pub async fn get(announcement_id: i32, mut auth: TokenAuth) -> Result<Tagged<Announcement>> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    Ok(Tagged(Announcement::by_id(announcement_id, &mut auth.connection).await?))
+}
+
+#[rocket::patch("/<announcement_id>", data = "<patch>")]
+pub async fn patch(
+    announcement_id: i32, mut auth: TokenAuth, precondition: Precondition, patch: Json<PatchAnnouncement>, cache: &State<AnnouncementCache>,
+) -> Result<Tagged<Announcement>> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    let announcement = Announcement::by_id(announcement_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .apply_patch(patch.0, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+    cache.invalidate();
+
+    Ok(Tagged(announcement))
+}
+
+/// Immediately stops displaying the given announcement, without deleting it
+#[rocket::post("/<announcement_id>/expire")]
+pub async fn expire(
+    announcement_id: i32, mut auth: TokenAuth, precondition: Precondition, cache: &State<AnnouncementCache>,
+) -> Result<Tagged<Announcement>> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    let mut announcement = Announcement::by_id(announcement_id, &mut auth.connection)
+        .await?
+        .require_match(precondition)?;
+
+    announcement.expire(Utc::now().naive_utc(), &mut auth.connection).await?;
+
+    auth.commit().await?;
+    cache.invalidate();
+
+    Ok(Tagged(announcement))
+}
+
+#[rocket::delete("/<announcement_id>")]
+pub async fn delete(
+    announcement_id: i32, mut auth: TokenAuth, precondition: Precondition, cache: &State<AnnouncementCache>,
+) -> Result<Status> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    let announcement = Announcement::by_id(announcement_id, &mut auth.connection).await?;
+
+    precondition.require_etag_match(&announcement)?;
+
+    announcement.delete(&mut auth.connection).await?;
+
+    auth.commit().await?;
+    cache.invalidate();
+
+    Ok(Status::NoContent)
+}
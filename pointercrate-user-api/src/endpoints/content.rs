@@ -0,0 +1,108 @@
+use crate::auth::TokenAuth;
+use pointercrate_core::content::{ContentPage, ContentPageRevision, ContentPagesConfig, PatchContentPage, PostContentPage};
+use pointercrate_core_api::{
+    error::Result,
+    etag::{Precondition, TaggableExt, Tagged},
+    response::Response2,
+};
+use rocket::{http::Status, serde::json::Json, State};
+
+#[rocket::get("/")]
+pub async fn list(mut auth: TokenAuth, config: &State<ContentPagesConfig>) -> Result<Json<Vec<ContentPage>>> {
+    auth.require_permission(config.edit_permission)?;
+
+    Ok(Json(ContentPage::all(&mut auth.connection).await?))
+}
+
+#[rocket::post("/", data = "<data>")]
+pub async fn post(
+    mut auth: TokenAuth, config: &State<ContentPagesConfig>, data: Json<PostContentPage>,
+) -> Result<Response2<Tagged<ContentPage>>> {
+    auth.require_permission(config.edit_permission)?;
+
+    let page = ContentPage::create_from(data.0, auth.user.inner().id, &mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    let location = format!("/api/v1/pages/{}/", page.slug);
+
+    Ok(Response2::tagged(page).status(Status::Created).with_header("Location", location))
+}
+
+#[rocket::get("/<slug>")]
+pub async fn get(slug: &str, mut auth: TokenAuth, config: &State<ContentPagesConfig>) -> Result<Tagged<ContentPage>> {
+    auth.require_permission(config.edit_permission)?;
+
+    Ok(Tagged(ContentPage::by_slug(slug, &mut auth.connection).await?))
+}
+
+#[rocket::patch("/<slug>", data = "<patch>")]
+pub async fn patch(
+    slug: &str, mut auth: TokenAuth, config: &State<ContentPagesConfig>, precondition: Precondition, patch: Json<PatchContentPage>,
+) -> Result<Tagged<ContentPage>> {
+    auth.require_permission(config.edit_permission)?;
+
+    let page = ContentPage::by_slug(slug, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .apply_patch(patch.0, auth.user.inner().id, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(page))
+}
+
+#[rocket::delete("/<slug>")]
+pub async fn delete(slug: &str, mut auth: TokenAuth, config: &State<ContentPagesConfig>, precondition: Precondition) -> Result<Status> {
+    auth.require_permission(config.edit_permission)?;
+
+    let page = ContentPage::by_slug(slug, &mut auth.connection).await?;
+
+    precondition.require_etag_match(&page)?;
+
+    page.delete(&mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+#[rocket::get("/<slug>/revisions")]
+pub async fn revisions(slug: &str, mut auth: TokenAuth, config: &State<ContentPagesConfig>) -> Result<Json<Vec<ContentPageRevision>>> {
+    auth.require_permission(config.edit_permission)?;
+
+    let page = ContentPage::by_slug(slug, &mut auth.connection).await?;
+
+    Ok(Json(page.revisions(&mut auth.connection).await?))
+}
+
+#[rocket::get("/<slug>/revisions/<revision_id>")]
+pub async fn revision(
+    slug: &str, revision_id: i32, mut auth: TokenAuth, config: &State<ContentPagesConfig>,
+) -> Result<Json<ContentPageRevision>> {
+    auth.require_permission(config.edit_permission)?;
+
+    let page = ContentPage::by_slug(slug, &mut auth.connection).await?;
+
+    Ok(Json(page.revision(revision_id, &mut auth.connection).await?))
+}
+
+/// Reverts the given page to one of its previous revisions, by creating a new revision with the
+/// same contents
+#[rocket::post("/<slug>/revisions/<revision_id>/restore")]
+pub async fn restore(
+    slug: &str, revision_id: i32, mut auth: TokenAuth, config: &State<ContentPagesConfig>, precondition: Precondition,
+) -> Result<Tagged<ContentPage>> {
+    auth.require_permission(config.edit_permission)?;
+
+    let page = ContentPage::by_slug(slug, &mut auth.connection)
+        .await?
+        .require_match(precondition)?
+        .restore(revision_id, auth.user.inner().id, &mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Tagged(page))
+}
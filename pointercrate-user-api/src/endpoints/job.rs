@@ -0,0 +1,60 @@
+use crate::auth::TokenAuth;
+use pointercrate_core::job::{DeadJob, QueuedJob};
+use pointercrate_core_api::{
+    error::Result,
+    etag::{Precondition, Tagged},
+};
+use pointercrate_user::ADMINISTRATOR;
+use rocket::{http::Status, serde::json::Json};
+
+/// All jobs waiting to be run, including those currently running
+#[rocket::get("/")]
+pub async fn queued(mut auth: TokenAuth) -> Result<Json<Vec<QueuedJob>>> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    Ok(Json(QueuedJob::all(&mut auth.connection).await?))
+}
+
+#[rocket::get("/dead")]
+pub async fn dead(mut auth: TokenAuth) -> Result<Json<Vec<DeadJob>>> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    Ok(Json(DeadJob::all(&mut auth.connection).await?))
+}
+
+#[rocket::get("/dead/<job_id>")]
+pub async fn get_dead(job_id: i32, mut auth: TokenAuth) -> Result<Tagged<DeadJob>> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    Ok(Tagged(DeadJob::by_id(job_id, &mut auth.connection).await?))
+}
+
+/// Puts the given dead job back into the queue
+#[rocket::post("/dead/<job_id>/retry")]
+pub async fn retry_dead(job_id: i32, mut auth: TokenAuth) -> Result<Status> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    DeadJob::by_id(job_id, &mut auth.connection)
+        .await?
+        .retry(&mut auth.connection)
+        .await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
+}
+
+#[rocket::delete("/dead/<job_id>")]
+pub async fn delete_dead(job_id: i32, mut auth: TokenAuth, precondition: Precondition) -> Result<Status> {
+    auth.require_permission(ADMINISTRATOR)?;
+
+    let job = DeadJob::by_id(job_id, &mut auth.connection).await?;
+
+    precondition.require_etag_match(&job)?;
+
+    job.delete(&mut auth.connection).await?;
+
+    auth.commit().await?;
+
+    Ok(Status::NoContent)
+}
@@ -1,2 +1,5 @@
+pub(crate) mod announcement;
 pub(crate) mod auth;
+pub(crate) mod content;
+pub(crate) mod job;
 pub(crate) mod user;
@@ -1,5 +1,6 @@
-use crate::ratelimits::UserRatelimits;
+use crate::{auth::PagePermissionsFairing, ratelimits::UserRatelimits};
 
+use pointercrate_core_api::announcement::AnnouncementCache;
 use rocket::{Build, Rocket};
 
 pub mod auth;
@@ -12,6 +13,8 @@ pub fn setup(rocket: Rocket<Build>) -> Rocket<Build> {
 
     rocket
         .manage(ratelimits)
+        .manage(AnnouncementCache::default())
+        .attach(PagePermissionsFairing)
         .mount(
             "/api/v1/auth/",
             rocket::routes![
@@ -33,8 +36,48 @@ pub fn setup(rocket: Rocket<Build>) -> Rocket<Build> {
                 endpoints::user::delete_user
             ],
         )
+        .mount(
+            "/api/v1/announcements/",
+            rocket::routes![
+                endpoints::announcement::list,
+                endpoints::announcement::post,
+                endpoints::announcement::get,
+                endpoints::announcement::patch,
+                endpoints::announcement::expire,
+                endpoints::announcement::delete
+            ],
+        )
+        .mount(
+            "/api/v1/jobs/",
+            rocket::routes![
+                endpoints::job::queued,
+                endpoints::job::dead,
+                endpoints::job::get_dead,
+                endpoints::job::retry_dead,
+                endpoints::job::delete_dead
+            ],
+        )
+        .mount(
+            "/api/v1/pages/",
+            rocket::routes![
+                endpoints::content::list,
+                endpoints::content::post,
+                endpoints::content::get,
+                endpoints::content::patch,
+                endpoints::content::delete,
+                endpoints::content::revisions,
+                endpoints::content::revision,
+                endpoints::content::restore
+            ],
+        )
         .mount(
             "/",
-            rocket::routes![pages::login_page, pages::account_page, pages::login, pages::register],
+            rocket::routes![
+                pages::login_page,
+                pages::account_page,
+                pages::login,
+                pages::register,
+                pages::content_page
+            ],
         )
 }
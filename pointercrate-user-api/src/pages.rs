@@ -2,7 +2,7 @@ use crate::{
     auth::{BasicAuth, TokenAuth},
     ratelimits::UserRatelimits,
 };
-use pointercrate_core::{permission::PermissionsManager, pool::PointercratePool};
+use pointercrate_core::{content::ContentPage, permission::PermissionsManager, pool::PointercratePool};
 use pointercrate_core_api::response::Page;
 use pointercrate_core_pages::head::HeadLike;
 use pointercrate_user::{error::UserError, AuthenticatedUser, Registration, User};
@@ -88,3 +88,13 @@ pub async fn account_page(
         None => Err(Redirect::to(rocket::uri!(login_page))),
     }
 }
+
+/// Displays the content page with the given slug (see [`pointercrate_core::content`])
+///
+/// This route has a low priority, so that content pages can never shadow any other page.
+#[rocket::get("/<slug>", rank = 20)]
+pub async fn content_page(slug: &str, pool: &State<PointercratePool>) -> pointercrate_core_api::error::Result<Page> {
+    let mut connection = pool.connection().await?;
+
+    Ok(Page::new(ContentPage::by_slug(slug, &mut connection).await?))
+}